@@ -0,0 +1,50 @@
+//! Worker thread reporting progress to the main context via a channel.
+
+extern crate glib;
+
+use std::thread;
+use std::time::Duration;
+
+enum Message {
+    Progress(u32),
+    Finished(String),
+}
+
+fn main() {
+    let main_loop = glib::MainLoop::new(None, false);
+
+    // Attaching the receiver requires owning the main context
+    let main_context = glib::MainContext::default();
+    assert!(main_context.acquire(), "Main context owned by another thread");
+
+    let (sender, receiver) = glib::MainContext::channel(glib::PRIORITY_DEFAULT);
+
+    // The worker can run on any thread and only needs the sender
+    thread::spawn(move || {
+        for percent in (0..=100).step_by(10) {
+            thread::sleep(Duration::from_millis(20));
+            sender
+                .send(Message::Progress(percent))
+                .expect("Receiver went away");
+        }
+        sender
+            .send(Message::Finished(String::from("all done")))
+            .expect("Receiver went away");
+    });
+
+    // The closure is called from the main context for every message
+    let main_loop_clone = main_loop.clone();
+    receiver.attach(None, move |msg| match msg {
+        Message::Progress(percent) => {
+            println!("Progress: {}%", percent);
+            glib::Continue(true)
+        }
+        Message::Finished(result) => {
+            println!("Finished: {}", result);
+            main_loop_clone.quit();
+            glib::Continue(false)
+        }
+    });
+
+    main_loop.run();
+}
@@ -0,0 +1,45 @@
+//! Using the main context as executor for futures.
+
+extern crate futures;
+extern crate glib;
+
+use futures::prelude::*;
+
+fn main() {
+    let c = glib::MainContext::default();
+    let l = glib::MainLoop::new(Some(&c), false);
+
+    c.push_thread_default();
+
+    // Futures that don't have to be `Send` can be spawned from the owner thread
+    let l_clone = l.clone();
+    c.spawn_local(
+        glib::timeout_future(100)
+            .then(|_| {
+                println!("100ms passed");
+                glib::timeout_future_seconds(1)
+            })
+            .map(move |_| {
+                println!("1s passed");
+                l_clone.quit();
+            }),
+    );
+
+    // Streams can be consumed the same way
+    c.spawn_local(
+        glib::interval_stream(200)
+            .take(3)
+            .for_each(|_| {
+                println!("tick");
+                future::ready(())
+            }),
+    );
+
+    l.run();
+
+    // Or block on a single future until it resolved
+    let res = c.block_on(glib::timeout_future(10).map(|_| 42));
+    println!("block_on returned {}", res);
+
+    c.pop_thread_default();
+}
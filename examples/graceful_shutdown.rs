@@ -0,0 +1,54 @@
+//! Daemon-style main loop that shuts down cleanly on SIGINT/SIGTERM or after a timeout.
+
+extern crate glib;
+#[cfg(unix)]
+extern crate libc;
+
+#[cfg(unix)]
+use std::time::Duration;
+
+#[cfg(unix)]
+fn main() {
+    let main_loop = glib::MainLoop::new(None, false);
+
+    // Attaching the receiver requires owning the main context
+    let main_context = glib::MainContext::default();
+    assert!(main_context.acquire(), "Main context owned by another thread");
+
+    let (sender, receiver) = glib::MainContext::channel(glib::PRIORITY_DEFAULT);
+
+    // Background work that keeps running until the receiver goes away
+    let worker = std::thread::spawn(move || {
+        let mut i = 0u32;
+        while sender.send(i).is_ok() {
+            i += 1;
+            std::thread::sleep(Duration::from_millis(100));
+        }
+    });
+
+    let source_id = receiver.attach(None, |i| {
+        println!("Working... {}", i);
+        glib::Continue(true)
+    });
+
+    let signal_sources = main_loop.quit_on_signal(&[libc::SIGINT, libc::SIGTERM]);
+
+    println!("Press Ctrl+C to quit, or wait 3 seconds");
+    if main_loop.run_with_timeout(Duration::from_secs(3)) {
+        println!("Quit by signal");
+    } else {
+        println!("Timeout");
+    }
+
+    // Tear everything down: removing the receiver makes the worker's sends fail
+    glib::Source::remove(source_id).unwrap();
+    for source in signal_sources {
+        source.destroy();
+    }
+    worker.join().unwrap();
+}
+
+#[cfg(not(unix))]
+fn main() {
+    println!("This example requires UNIX signals");
+}
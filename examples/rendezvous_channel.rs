@@ -0,0 +1,34 @@
+//! Zero-capacity channel where every `send()` waits until the item was taken by the main context.
+
+extern crate glib;
+
+use std::thread;
+
+fn main() {
+    let main_loop = glib::MainLoop::new(None, false);
+
+    // Attaching the receiver requires owning the main context
+    let main_context = glib::MainContext::default();
+    assert!(main_context.acquire(), "Main context owned by another thread");
+
+    let (sender, receiver) = glib::MainContext::sync_channel(glib::PRIORITY_DEFAULT, 0);
+
+    let producer = thread::spawn(move || {
+        for i in 0..5 {
+            sender.send(i).expect("Receiver went away");
+            println!("Handed over item {}", i);
+        }
+    });
+
+    let main_loop_clone = main_loop.clone();
+    receiver.attach(None, move |item| {
+        println!("Received item {}", item);
+        if item == 4 {
+            main_loop_clone.quit();
+        }
+        glib::Continue(true)
+    });
+
+    main_loop.run();
+    producer.join().unwrap();
+}
@@ -0,0 +1,39 @@
+//! Bounded channel that blocks a fast producer until the main context caught up.
+
+extern crate glib;
+
+use std::thread;
+use std::time::{Duration, Instant};
+
+fn main() {
+    let main_loop = glib::MainLoop::new(None, false);
+
+    // Attaching the receiver requires owning the main context
+    let main_context = glib::MainContext::default();
+    assert!(main_context.acquire(), "Main context owned by another thread");
+
+    // At most 4 items can be queued before `send()` blocks
+    let (sender, receiver) = glib::MainContext::sync_channel(glib::PRIORITY_DEFAULT, 4);
+
+    let producer = thread::spawn(move || {
+        let start = Instant::now();
+        for i in 0..20 {
+            sender.send(i).expect("Receiver went away");
+        }
+        println!("Producer finished after {:?}", start.elapsed());
+    });
+
+    // A slow consumer: every item takes 10ms to process
+    let main_loop_clone = main_loop.clone();
+    receiver.attach(None, move |item| {
+        thread::sleep(Duration::from_millis(10));
+        println!("Processed item {}", item);
+        if item == 19 {
+            main_loop_clone.quit();
+        }
+        glib::Continue(true)
+    });
+
+    main_loop.run();
+    producer.join().unwrap();
+}
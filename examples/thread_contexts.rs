@@ -0,0 +1,56 @@
+//! Multiple threads, each running its own main context, talking to each other.
+
+extern crate glib;
+
+fn main() {
+    let main_context = glib::MainContext::default();
+    let main_loop = glib::MainLoop::new(Some(&main_context), false);
+
+    // Attaching the receiver requires owning the main context
+    assert!(main_context.acquire(), "Main context owned by another thread");
+
+    // Results from the worker are passed back to the default main context
+    let (result_sender, result_receiver) = glib::MainContext::channel(glib::PRIORITY_DEFAULT);
+    // Requests are sent to the worker's main context
+    let (request_sender, request_receiver) =
+        glib::MainContext::channel::<u64>(glib::PRIORITY_DEFAULT);
+
+    let worker = glib::thread::spawn_named_with_context("worker", move |context| {
+        let worker_loop = glib::MainLoop::new(Some(context), false);
+        let worker_loop_clone = worker_loop.clone();
+
+        request_receiver.attach(Some(context), move |n| {
+            let result = (1..=n).product::<u64>();
+            if result_sender.send((n, result)).is_err() {
+                worker_loop_clone.quit();
+                return glib::Continue(false);
+            }
+            glib::Continue(true)
+        });
+
+        worker_loop.run();
+    })
+    .expect("Failed to spawn worker thread");
+
+    for n in 1..=10 {
+        request_sender.send(n).unwrap();
+    }
+
+    let main_loop_clone = main_loop.clone();
+    result_receiver.attach(Some(&main_context), move |(n, result)| {
+        println!("{}! = {}", n, result);
+        if n == 10 {
+            main_loop_clone.quit();
+            // Removing the receiver makes the worker's next send fail so it shuts down
+            glib::Continue(false)
+        } else {
+            glib::Continue(true)
+        }
+    });
+
+    main_loop.run();
+
+    // Wake up the worker one last time so that it notices the receiver is gone
+    let _ = request_sender.send(0);
+    worker.join().unwrap();
+}
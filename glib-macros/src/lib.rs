@@ -0,0 +1,379 @@
+// Copyright 2019, The Gtk-rs Project Developers.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+// Licensed under the MIT license, see the LICENSE file or <http://opensource.org/licenses/MIT>
+
+//! Derive macros for the [`glib`](../glib/index.html) crate.
+//!
+//! The macros are re-exported by `glib` and should be used from there.
+
+extern crate proc_macro;
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::quote;
+use syn::{
+    parse_macro_input, parse_quote, Attribute, Data, DataEnum, DeriveInput, Fields, Ident, Index,
+    Lit, Meta, NestedMeta, Path,
+};
+
+/// Derives `StaticVariantType`, `ToVariant` and `FromVariant` for a struct or enum.
+///
+/// Structs are converted to a tuple of their fields in the order of declaration, so a struct
+/// with fields of type `String` and `u32` is converted to a variant of type `(su)`. Unit structs
+/// are converted to the empty tuple `()`.
+///
+/// Structs with named fields and the `#[variant(dict)]` attribute are converted to a dictionary
+/// of type `a{sv}` instead. Each field is stored under its name, with its value boxed into a
+/// variant of type `v`. This is the usual format of option dictionaries in D-Bus and GSettings
+/// APIs. Converting from a variant fails if any of the fields is missing or has the wrong type,
+/// additional entries are ignored.
+///
+/// Enums with only unit variants are converted to a string of type `s` containing the name of
+/// the variant, which is how GSettings stores enums. All other enums are converted to a tagged
+/// variant of type `(sv)`: the name of the variant and its value, boxed into a variant of type
+/// `v`. The value of unit variants is the empty tuple `()`, variants with a single field use the
+/// value of the field and variants with several fields a tuple of them.
+///
+/// All field types have to implement the three traits themselves. Generic types and enum
+/// variants with named fields are not supported.
+///
+/// The generated code refers to the traits via the `::glib` path. If the `glib` crate is renamed
+/// or only available via a re-export, e.g. from gtk, the path can be set with
+/// `#[variant(crate = "path::to::glib")]`.
+///
+/// ```
+/// extern crate glib;
+///
+/// use glib::prelude::*;
+///
+/// #[derive(Debug, PartialEq, glib::Variant)]
+/// struct Window {
+///     title: String,
+///     size: (i32, i32),
+/// }
+///
+/// #[derive(Debug, PartialEq, glib::Variant)]
+/// #[variant(dict)]
+/// struct Options {
+///     verbose: bool,
+///     name: String,
+/// }
+///
+/// #[derive(Debug, PartialEq, glib::Variant)]
+/// enum Shape {
+///     Circle(f64),
+///     Rectangle(f64, f64),
+///     Empty,
+/// }
+///
+/// # fn main() {
+/// let window = Window { title: String::from("Main"), size: (640, 480) };
+/// let variant = window.to_variant();
+/// assert_eq!(variant.type_().to_str(), "(s(ii))");
+/// assert_eq!(variant.get::<Window>(), Some(window));
+///
+/// let options = Options { verbose: true, name: String::from("test") };
+/// let variant = options.to_variant();
+/// assert_eq!(variant.to_string(), "{'verbose': <true>, 'name': <'test'>}");
+/// assert_eq!(variant.get::<Options>(), Some(options));
+///
+/// let variant = Shape::Circle(2.0).to_variant();
+/// assert_eq!(variant.to_string(), "('Circle', <2.0>)");
+/// assert_eq!(variant.get::<Shape>(), Some(Shape::Circle(2.0)));
+/// # }
+/// ```
+#[proc_macro_derive(Variant, attributes(variant))]
+pub fn variant_derive(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let gen = match derive_variant(&input) {
+        Ok(gen) => gen,
+        Err(err) => err.to_compile_error(),
+    };
+    gen.into()
+}
+
+fn derive_variant(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    if !input.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &input.generics,
+            "Variant can't be derived for generic types",
+        ));
+    }
+
+    let attrs = parse_attrs(&input.attrs)?;
+    let glib = &attrs.crate_path;
+    match input.data {
+        Data::Struct(ref data) if attrs.dict => match data.fields {
+            Fields::Named(_) => Ok(derive_dict_struct(glib, &input.ident, &data.fields)),
+            _ => Err(syn::Error::new_spanned(
+                &input.ident,
+                "#[variant(dict)] requires named fields",
+            )),
+        },
+        Data::Struct(ref data) => Ok(derive_tuple_struct(glib, &input.ident, &data.fields)),
+        Data::Enum(_) if attrs.dict => Err(syn::Error::new_spanned(
+            &input.ident,
+            "#[variant(dict)] is only supported for structs",
+        )),
+        Data::Enum(ref data) if data.variants.iter().all(is_unit) => {
+            Ok(derive_string_enum(glib, &input.ident, data))
+        }
+        Data::Enum(ref data) => derive_tagged_enum(glib, &input.ident, data),
+        Data::Union(_) => Err(syn::Error::new_spanned(
+            &input.ident,
+            "Variant can't be derived for unions",
+        )),
+    }
+}
+
+struct Attrs {
+    dict: bool,
+    crate_path: Path,
+}
+
+fn parse_attrs(attrs: &[Attribute]) -> syn::Result<Attrs> {
+    let mut dict = false;
+    let mut crate_path = None;
+    for attr in attrs {
+        if !attr.path.is_ident("variant") {
+            continue;
+        }
+
+        let list = match attr.parse_meta()? {
+            Meta::List(list) => list,
+            meta => return Err(syn::Error::new_spanned(meta, "expected #[variant(...)]")),
+        };
+        for nested in &list.nested {
+            match *nested {
+                NestedMeta::Meta(Meta::Path(ref path)) if path.is_ident("dict") => dict = true,
+                NestedMeta::Meta(Meta::NameValue(ref name_value))
+                    if name_value.path.is_ident("crate") =>
+                {
+                    match name_value.lit {
+                        Lit::Str(ref lit) => crate_path = Some(lit.parse()?),
+                        ref lit => {
+                            return Err(syn::Error::new_spanned(lit, "expected a path as string"));
+                        }
+                    }
+                }
+                ref nested => {
+                    return Err(syn::Error::new_spanned(nested, "unknown variant attribute"));
+                }
+            }
+        }
+    }
+
+    Ok(Attrs {
+        dict,
+        crate_path: crate_path.unwrap_or_else(|| parse_quote!(::glib)),
+    })
+}
+
+fn is_unit(variant: &syn::Variant) -> bool {
+    match variant.fields {
+        Fields::Unit => true,
+        _ => false,
+    }
+}
+
+fn field_bindings(n: usize) -> Vec<Ident> {
+    (0..n)
+        .map(|i| Ident::new(&format!("field{}", i), Span::call_site()))
+        .collect()
+}
+
+fn derive_tuple_struct(glib: &Path, name: &Ident, fields: &Fields) -> TokenStream2 {
+    let types = &fields.iter().map(|f| &f.ty).collect::<Vec<_>>();
+    let members = &fields
+        .iter()
+        .enumerate()
+        .map(|(i, f)| match f.ident {
+            Some(ref ident) => quote!(#ident),
+            None => {
+                let index = Index::from(i);
+                quote!(#index)
+            }
+        })
+        .collect::<Vec<_>>();
+    let bindings = &field_bindings(types.len());
+    let construct = match *fields {
+        Fields::Named(_) => quote!(#name { #(#members: #bindings),* }),
+        Fields::Unnamed(_) => quote!(#name(#(#bindings),*)),
+        Fields::Unit => quote!(#name),
+    };
+
+    quote! {
+        impl #glib::StaticVariantType for #name {
+            fn static_variant_type() -> ::std::borrow::Cow<'static, #glib::VariantTy> {
+                <(#(#types,)*) as #glib::StaticVariantType>::static_variant_type()
+            }
+        }
+
+        impl #glib::ToVariant for #name {
+            fn to_variant(&self) -> #glib::Variant {
+                #glib::ToVariant::to_variant(&(#(&self.#members,)*))
+            }
+        }
+
+        impl #glib::FromVariant for #name {
+            fn from_variant(variant: &#glib::Variant) -> Option<Self> {
+                let (#(#bindings,)*) = variant.get::<(#(#types,)*)>()?;
+                Some(#construct)
+            }
+        }
+    }
+}
+
+fn derive_dict_struct(glib: &Path, name: &Ident, fields: &Fields) -> TokenStream2 {
+    let types = &fields.iter().map(|f| &f.ty).collect::<Vec<_>>();
+    let idents = &fields
+        .iter()
+        .map(|f| f.ident.as_ref().unwrap())
+        .collect::<Vec<_>>();
+    let keys = &idents.iter().map(|i| i.to_string()).collect::<Vec<_>>();
+
+    quote! {
+        impl #glib::StaticVariantType for #name {
+            fn static_variant_type() -> ::std::borrow::Cow<'static, #glib::VariantTy> {
+                <::std::collections::HashMap<::std::string::String, #glib::Variant>
+                    as #glib::StaticVariantType>::static_variant_type()
+            }
+        }
+
+        impl #glib::ToVariant for #name {
+            fn to_variant(&self) -> #glib::Variant {
+                let entry_type = #glib::VariantTy::new("{sv}").unwrap();
+                #glib::Variant::new_array(entry_type, &[#(
+                    #glib::Variant::new_dict_entry(
+                        &#glib::ToVariant::to_variant(#keys),
+                        &#glib::Variant::new_variant(&#glib::ToVariant::to_variant(&self.#idents)),
+                    )
+                ),*])
+            }
+        }
+
+        impl #glib::FromVariant for #name {
+            #[allow(unused_variables, unused_mut)]
+            fn from_variant(variant: &#glib::Variant) -> Option<Self> {
+                let mut dict = variant
+                    .get::<::std::collections::HashMap<::std::string::String, #glib::Variant>>()?;
+                Some(#name {
+                    #(#idents: dict.remove(#keys)?.get::<#types>()?),*
+                })
+            }
+        }
+    }
+}
+
+fn derive_string_enum(glib: &Path, name: &Ident, data: &DataEnum) -> TokenStream2 {
+    let variants = &data.variants.iter().map(|v| &v.ident).collect::<Vec<_>>();
+    let tags = &variants.iter().map(|v| v.to_string()).collect::<Vec<_>>();
+
+    quote! {
+        impl #glib::StaticVariantType for #name {
+            fn static_variant_type() -> ::std::borrow::Cow<'static, #glib::VariantTy> {
+                <::std::string::String as #glib::StaticVariantType>::static_variant_type()
+            }
+        }
+
+        impl #glib::ToVariant for #name {
+            fn to_variant(&self) -> #glib::Variant {
+                let tag = match *self {
+                    #(#name::#variants => #tags),*
+                };
+                #glib::ToVariant::to_variant(tag)
+            }
+        }
+
+        impl #glib::FromVariant for #name {
+            fn from_variant(variant: &#glib::Variant) -> Option<Self> {
+                if !variant.is::<Self>() {
+                    return None;
+                }
+
+                match variant.get_str()? {
+                    #(#tags => Some(#name::#variants),)*
+                    _ => None,
+                }
+            }
+        }
+    }
+}
+
+fn derive_tagged_enum(glib: &Path, name: &Ident, data: &DataEnum) -> syn::Result<TokenStream2> {
+    let mut to_arms = Vec::new();
+    let mut from_arms = Vec::new();
+    for variant in &data.variants {
+        let ident = &variant.ident;
+        let tag = ident.to_string();
+        match variant.fields {
+            Fields::Unit => {
+                to_arms.push(quote! {
+                    #name::#ident => (#tag, #glib::ToVariant::to_variant(&()))
+                });
+                from_arms.push(quote! {
+                    #tag => value.get::<()>().map(|()| #name::#ident)
+                });
+            }
+            Fields::Unnamed(ref fields) if fields.unnamed.len() == 1 => {
+                let ty = &fields.unnamed[0].ty;
+                to_arms.push(quote! {
+                    #name::#ident(ref value) => (#tag, #glib::ToVariant::to_variant(value))
+                });
+                from_arms.push(quote! {
+                    #tag => value.get::<#ty>().map(#name::#ident)
+                });
+            }
+            Fields::Unnamed(ref fields) => {
+                let types = &fields.unnamed.iter().map(|f| &f.ty).collect::<Vec<_>>();
+                let bindings = &field_bindings(types.len());
+                to_arms.push(quote! {
+                    #name::#ident(#(ref #bindings),*) => {
+                        (#tag, #glib::ToVariant::to_variant(&(#(#bindings,)*)))
+                    }
+                });
+                from_arms.push(quote! {
+                    #tag => value
+                        .get::<(#(#types,)*)>()
+                        .map(|(#(#bindings,)*)| #name::#ident(#(#bindings),*))
+                });
+            }
+            Fields::Named(_) => {
+                return Err(syn::Error::new_spanned(
+                    variant,
+                    "Variant can't be derived for enum variants with named fields",
+                ));
+            }
+        }
+    }
+
+    Ok(quote! {
+        impl #glib::StaticVariantType for #name {
+            fn static_variant_type() -> ::std::borrow::Cow<'static, #glib::VariantTy> {
+                <(::std::string::String, #glib::Variant) as #glib::StaticVariantType>::static_variant_type()
+            }
+        }
+
+        impl #glib::ToVariant for #name {
+            fn to_variant(&self) -> #glib::Variant {
+                let (tag, value) = match *self {
+                    #(#to_arms),*
+                };
+                #glib::Variant::new_tuple(&[
+                    #glib::ToVariant::to_variant(tag),
+                    #glib::Variant::new_variant(&value),
+                ])
+            }
+        }
+
+        impl #glib::FromVariant for #name {
+            fn from_variant(variant: &#glib::Variant) -> Option<Self> {
+                let (tag, value) = variant.get::<(::std::string::String, #glib::Variant)>()?;
+                match tag.as_str() {
+                    #(#from_arms,)*
+                    _ => None,
+                }
+            }
+        }
+    })
+}
@@ -0,0 +1,161 @@
+// Copyright 2019, The Gtk-rs Project Developers.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+// Licensed under the MIT license, see the LICENSE file or <http://opensource.org/licenses/MIT>
+
+use glib::prelude::*;
+use glib::{Variant, VariantTy};
+
+#[derive(Debug, PartialEq, glib::Variant)]
+struct Point {
+    x: i32,
+    y: i32,
+    label: Option<String>,
+}
+
+#[derive(Debug, PartialEq, glib::Variant)]
+struct Pair(u8, String);
+
+#[derive(Debug, PartialEq, glib::Variant)]
+struct Unit;
+
+#[derive(Debug, PartialEq, glib::Variant)]
+#[variant(dict)]
+struct Options {
+    verbose: bool,
+    points: Vec<Point>,
+}
+
+#[derive(Debug, PartialEq, glib::Variant)]
+enum Mode {
+    Read,
+    Write,
+}
+
+// The derived code only uses the crate path given via the attribute
+mod reexport {
+    pub use glib::{FromVariant, StaticVariantType, ToVariant, Variant, VariantTy};
+}
+
+#[derive(Debug, PartialEq, glib::Variant)]
+#[variant(crate = "crate::reexport")]
+enum Direction {
+    Up,
+    Down,
+}
+
+#[derive(Debug, PartialEq, glib::Variant)]
+#[variant(crate = "crate::reexport")]
+struct Step(Direction, u32);
+
+#[derive(Debug, PartialEq, glib::Variant)]
+enum Event {
+    Move(Point),
+    Key(u32),
+    Resize(i32, i32),
+    Quit,
+}
+
+#[test]
+fn test_variant_struct() {
+    let point = Point {
+        x: 1,
+        y: -2,
+        label: Some(String::from("origin")),
+    };
+    let v = point.to_variant();
+    assert_eq!(v.type_().to_str(), "(iims)");
+    assert_eq!(v.get::<Point>(), Some(point));
+    assert_eq!((1, 2).to_variant().get::<Point>(), None);
+
+    let pair = Pair(7, String::from("seven"));
+    let v = pair.to_variant();
+    assert_eq!(v.type_().to_str(), "(ys)");
+    assert_eq!(v.get::<Pair>(), Some(pair));
+
+    assert_eq!(Unit.to_variant().type_().to_str(), "()");
+    assert_eq!(().to_variant().get::<Unit>(), Some(Unit));
+}
+
+#[test]
+fn test_variant_dict_struct() {
+    let options = Options {
+        verbose: true,
+        points: vec![Point {
+            x: 0,
+            y: 0,
+            label: None,
+        }],
+    };
+    let v = options.to_variant();
+    assert_eq!(v.type_().to_str(), "a{sv}");
+    assert_eq!(v.get::<Options>(), Some(options));
+
+    // Missing fields
+    assert_eq!(
+        Variant::new_array(
+            VariantTy::new("{sv}").unwrap(),
+            &[Variant::new_dict_entry(
+                &"verbose".to_variant(),
+                &Variant::new_variant(&true.to_variant())
+            )],
+        )
+        .get::<Options>(),
+        None
+    );
+}
+
+#[test]
+fn test_variant_string_enum() {
+    assert_eq!(Mode::static_variant_type().to_str(), "s");
+    assert_eq!(Mode::Read.to_variant().get_str(), Some("Read"));
+    assert_eq!(Mode::Read.to_variant().get::<Mode>(), Some(Mode::Read));
+    // Only strings are accepted, not e.g. object paths
+    assert_eq!(
+        Variant::parse(None, "objectpath '/Read'").unwrap().get::<Mode>(),
+        None
+    );
+}
+
+#[test]
+fn test_variant_crate_path() {
+    let v = Direction::Down.to_variant();
+    assert_eq!(v.get_str(), Some("Down"));
+    assert_eq!(v.get::<Direction>(), Some(Direction::Down));
+
+    let step = Step(Direction::Up, 3);
+    let v = step.to_variant();
+    assert_eq!(v.type_().to_str(), "(su)");
+    assert_eq!(v.get::<Step>(), Some(step));
+}
+
+#[test]
+fn test_variant_enum() {
+    let v = Mode::Write.to_variant();
+    assert_eq!(v.get_str(), Some("Write"));
+    assert_eq!(v.get::<Mode>(), Some(Mode::Write));
+    assert_eq!("Execute".to_variant().get::<Mode>(), None);
+
+    let event = Event::Move(Point {
+        x: 3,
+        y: 4,
+        label: None,
+    });
+    let v = event.to_variant();
+    assert_eq!(v.type_().to_str(), "(sv)");
+    assert_eq!(v.get::<Event>(), Some(event));
+    assert_eq!(
+        Event::Key(13).to_variant().get::<Event>(),
+        Some(Event::Key(13))
+    );
+    assert_eq!(
+        Event::Resize(640, 480).to_variant().to_string(),
+        "('Resize', <(640, 480)>)"
+    );
+    assert_eq!(
+        Event::Resize(640, 480).to_variant().get::<Event>(),
+        Some(Event::Resize(640, 480))
+    );
+    assert_eq!(Event::Quit.to_variant().to_string(), "('Quit', <()>)");
+    assert_eq!(Event::Quit.to_variant().get::<Event>(), Some(Event::Quit));
+    assert_eq!(Mode::Read.to_variant().get::<Event>(), None);
+}
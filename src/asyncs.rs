@@ -0,0 +1,360 @@
+// Copyright 2019, The Gtk-rs Project Developers.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+// Licensed under the MIT license, see the LICENSE file or <http://opensource.org/licenses/MIT>
+
+//! Synchronization primitives for futures.
+//!
+//! Different to the locks from `std::sync`, waiting for a [`Semaphore`] or [`Mutex`] never
+//! blocks the thread. Instead the waiting task is woken up once the permit or lock is
+//! available, which makes them usable from futures running on a `MainContext`, e.g. via
+//! `MainContext::spawn_local()`, without depending on any other runtime.
+//!
+//! Waiters are served in the order in which they started waiting.
+//!
+//! ```ignore
+//! // Run at most two subprocesses at once
+//! let semaphore = glib::asyncs::Semaphore::new(2);
+//! for cmd in commands {
+//!     c.spawn_local(semaphore.acquire().then(move |permit| {
+//!         run_subprocess(cmd).map(move |_| drop(permit))
+//!     }));
+//! }
+//! ```
+//!
+//! [`Semaphore`]: struct.Semaphore.html
+//! [`Mutex`]: struct.Mutex.html
+
+use futures::prelude::*;
+use futures::task::{Context, Poll, Waker};
+use std::cell::UnsafeCell;
+use std::collections::VecDeque;
+use std::fmt;
+use std::marker::Unpin;
+use std::mem;
+use std::ops::{Deref, DerefMut};
+use std::pin::Pin;
+use std::sync;
+use std::sync::Arc;
+
+#[derive(Debug)]
+struct SemaphoreState {
+    permits: usize,
+    waiters: VecDeque<(u64, Waker)>,
+    next_id: u64,
+}
+
+impl SemaphoreState {
+    fn wake_next(&self) {
+        if self.permits > 0 {
+            if let Some(&(_, ref waker)) = self.waiters.front() {
+                waker.wake_by_ref();
+            }
+        }
+    }
+}
+
+/// An asynchronous counting semaphore.
+///
+/// Cloning a `Semaphore` gives another handle to the same semaphore.
+#[derive(Clone, Debug)]
+pub struct Semaphore(Arc<sync::Mutex<SemaphoreState>>);
+
+impl Semaphore {
+    /// Creates a new semaphore with `permits` initially available permits.
+    pub fn new(permits: usize) -> Semaphore {
+        Semaphore(Arc::new(sync::Mutex::new(SemaphoreState {
+            permits,
+            waiters: VecDeque::new(),
+            next_id: 0,
+        })))
+    }
+
+    /// Returns the number of currently available permits.
+    pub fn available_permits(&self) -> usize {
+        self.0.lock().unwrap().permits
+    }
+
+    /// Adds `n` permits to the semaphore and wakes up waiters accordingly.
+    pub fn add_permits(&self, n: usize) {
+        let mut state = self.0.lock().unwrap();
+        state.permits += n;
+        state.wake_next();
+    }
+
+    /// Returns a future that resolves to a permit once one is available.
+    pub fn acquire(&self) -> Acquire {
+        Acquire {
+            semaphore: self.clone(),
+            id: None,
+        }
+    }
+
+    /// Takes a permit if one is available right away and nobody else is waiting for one.
+    pub fn try_acquire(&self) -> Option<SemaphorePermit> {
+        let mut state = self.0.lock().unwrap();
+        if state.permits > 0 && state.waiters.is_empty() {
+            state.permits -= 1;
+            Some(SemaphorePermit(self.clone()))
+        } else {
+            None
+        }
+    }
+}
+
+/// Future returned by [`Semaphore::acquire()`].
+///
+/// [`Semaphore::acquire()`]: struct.Semaphore.html#method.acquire
+#[derive(Debug)]
+#[must_use = "futures do nothing unless polled"]
+pub struct Acquire {
+    semaphore: Semaphore,
+    id: Option<u64>,
+}
+
+impl Unpin for Acquire {}
+
+impl Future for Acquire {
+    type Output = SemaphorePermit;
+
+    fn poll(mut self: Pin<&mut Self>, ctx: &mut Context) -> Poll<SemaphorePermit> {
+        let Acquire {
+            ref semaphore,
+            ref mut id,
+        } = *self;
+        let mut state = semaphore.0.lock().unwrap();
+
+        match *id {
+            None => {
+                if state.permits > 0 && state.waiters.is_empty() {
+                    state.permits -= 1;
+                    return Poll::Ready(SemaphorePermit(semaphore.clone()));
+                }
+
+                let new_id = state.next_id;
+                state.next_id += 1;
+                state.waiters.push_back((new_id, ctx.waker().clone()));
+                *id = Some(new_id);
+                Poll::Pending
+            }
+            Some(own_id) => {
+                if state.permits > 0 && state.waiters.front().map(|w| w.0) == Some(own_id) {
+                    state.waiters.pop_front();
+                    state.permits -= 1;
+                    *id = None;
+                    state.wake_next();
+                    return Poll::Ready(SemaphorePermit(semaphore.clone()));
+                }
+
+                if let Some(waiter) = state.waiters.iter_mut().find(|w| w.0 == own_id) {
+                    waiter.1 = ctx.waker().clone();
+                }
+                Poll::Pending
+            }
+        }
+    }
+}
+
+impl Drop for Acquire {
+    fn drop(&mut self) {
+        if let Some(id) = self.id.take() {
+            let mut state = self.semaphore.0.lock().unwrap();
+            let was_first = state.waiters.front().map(|w| w.0) == Some(id);
+            state.waiters.retain(|w| w.0 != id);
+            if was_first {
+                state.wake_next();
+            }
+        }
+    }
+}
+
+/// A permit of a [`Semaphore`], which is given back when dropped.
+///
+/// [`Semaphore`]: struct.Semaphore.html
+#[derive(Debug)]
+pub struct SemaphorePermit(Semaphore);
+
+impl SemaphorePermit {
+    /// Drops the permit without giving it back to the semaphore.
+    pub fn forget(self) {
+        mem::forget(self);
+    }
+}
+
+impl Drop for SemaphorePermit {
+    fn drop(&mut self) {
+        self.0.add_permits(1);
+    }
+}
+
+struct MutexInner<T> {
+    semaphore: Semaphore,
+    value: UnsafeCell<T>,
+}
+
+/// An asynchronous mutual exclusion lock.
+///
+/// Cloning a `Mutex` gives another handle to the same lock and value.
+pub struct Mutex<T>(Arc<MutexInner<T>>);
+
+unsafe impl<T: Send> Send for Mutex<T> {}
+unsafe impl<T: Send> Sync for Mutex<T> {}
+
+impl<T> Mutex<T> {
+    pub fn new(value: T) -> Mutex<T> {
+        Mutex(Arc::new(MutexInner {
+            semaphore: Semaphore::new(1),
+            value: UnsafeCell::new(value),
+        }))
+    }
+
+    /// Returns a future that resolves to a guard once the lock is available.
+    pub fn lock(&self) -> Lock<T> {
+        Lock {
+            mutex: self.clone(),
+            acquire: self.0.semaphore.acquire(),
+        }
+    }
+
+    /// Takes the lock if it is available right away and nobody else is waiting for it.
+    pub fn try_lock(&self) -> Option<MutexGuard<T>> {
+        self.0.semaphore.try_acquire().map(|permit| MutexGuard {
+            mutex: self.clone(),
+            _permit: permit,
+        })
+    }
+}
+
+impl<T> Clone for Mutex<T> {
+    fn clone(&self) -> Mutex<T> {
+        Mutex(self.0.clone())
+    }
+}
+
+impl<T> fmt::Debug for Mutex<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Mutex")
+            .field("locked", &(self.0.semaphore.available_permits() == 0))
+            .finish()
+    }
+}
+
+/// Future returned by [`Mutex::lock()`].
+///
+/// [`Mutex::lock()`]: struct.Mutex.html#method.lock
+#[must_use = "futures do nothing unless polled"]
+pub struct Lock<T> {
+    mutex: Mutex<T>,
+    acquire: Acquire,
+}
+
+impl<T> Unpin for Lock<T> {}
+
+impl<T> Future for Lock<T> {
+    type Output = MutexGuard<T>;
+
+    fn poll(mut self: Pin<&mut Self>, ctx: &mut Context) -> Poll<MutexGuard<T>> {
+        let permit = match Pin::new(&mut self.acquire).poll(ctx) {
+            Poll::Ready(permit) => permit,
+            Poll::Pending => return Poll::Pending,
+        };
+
+        Poll::Ready(MutexGuard {
+            mutex: self.mutex.clone(),
+            _permit: permit,
+        })
+    }
+}
+
+impl<T> fmt::Debug for Lock<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Lock").field("mutex", &self.mutex).finish()
+    }
+}
+
+/// Guard giving access to the value of a [`Mutex`], which unlocks the mutex when dropped.
+///
+/// [`Mutex`]: struct.Mutex.html
+pub struct MutexGuard<T> {
+    mutex: Mutex<T>,
+    _permit: SemaphorePermit,
+}
+
+unsafe impl<T: Send> Send for MutexGuard<T> {}
+unsafe impl<T: Send + Sync> Sync for MutexGuard<T> {}
+
+impl<T> Deref for MutexGuard<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        unsafe { &*self.mutex.0.value.get() }
+    }
+}
+
+impl<T> DerefMut for MutexGuard<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        unsafe { &mut *self.mutex.0.value.get() }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for MutexGuard<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("MutexGuard").field("value", &**self).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use MainContext;
+
+    #[test]
+    fn test_semaphore() {
+        let c = MainContext::new();
+        c.acquire();
+
+        let semaphore = Semaphore::new(1);
+        let permit = semaphore.try_acquire().unwrap();
+        assert!(semaphore.try_acquire().is_none());
+
+        let order = Rc::new(RefCell::new(Vec::new()));
+        for i in 0..3 {
+            let order = order.clone();
+            c.spawn_local(semaphore.acquire().map(move |_permit| {
+                order.borrow_mut().push(i);
+            }));
+        }
+
+        while c.iteration(false) {}
+        assert!(order.borrow().is_empty());
+
+        drop(permit);
+        while c.iteration(false) {}
+        assert_eq!(*order.borrow(), vec![0, 1, 2]);
+        assert_eq!(semaphore.available_permits(), 1);
+    }
+
+    #[test]
+    fn test_mutex() {
+        let c = MainContext::new();
+        c.acquire();
+
+        let mutex = Mutex::new(Vec::new());
+        let mut guard = mutex.try_lock().unwrap();
+        guard.push(0);
+        assert!(mutex.try_lock().is_none());
+
+        for i in 1..3 {
+            c.spawn_local(mutex.lock().map(move |mut guard| {
+                guard.push(i);
+            }));
+        }
+
+        while c.iteration(false) {}
+        drop(guard);
+        while c.iteration(false) {}
+
+        assert_eq!(*c.block_on(mutex.lock()), vec![0, 1, 2]);
+    }
+}
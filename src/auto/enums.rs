@@ -67,6 +67,97 @@ impl FromGlib<glib_sys::GChecksumType> for ChecksumType {
     }
 }
 
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[derive(Clone, Copy)]
+pub enum ConvertError {
+    NoConversion,
+    IllegalSequence,
+    Failed,
+    PartialInput,
+    BadUri,
+    NotAbsolutePath,
+    NoMemory,
+    EmbeddedNul,
+    #[doc(hidden)]
+    __Unknown(i32),
+}
+
+impl fmt::Display for ConvertError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "ConvertError::{}", match *self {
+            ConvertError::NoConversion => "NoConversion",
+            ConvertError::IllegalSequence => "IllegalSequence",
+            ConvertError::Failed => "Failed",
+            ConvertError::PartialInput => "PartialInput",
+            ConvertError::BadUri => "BadUri",
+            ConvertError::NotAbsolutePath => "NotAbsolutePath",
+            ConvertError::NoMemory => "NoMemory",
+            ConvertError::EmbeddedNul => "EmbeddedNul",
+            _ => "Unknown",
+        })
+    }
+}
+
+#[doc(hidden)]
+impl ToGlib for ConvertError {
+    type GlibType = glib_sys::GConvertError;
+
+    fn to_glib(&self) -> glib_sys::GConvertError {
+        match *self {
+            ConvertError::NoConversion => glib_sys::G_CONVERT_ERROR_NO_CONVERSION,
+            ConvertError::IllegalSequence => glib_sys::G_CONVERT_ERROR_ILLEGAL_SEQUENCE,
+            ConvertError::Failed => glib_sys::G_CONVERT_ERROR_FAILED,
+            ConvertError::PartialInput => glib_sys::G_CONVERT_ERROR_PARTIAL_INPUT,
+            ConvertError::BadUri => glib_sys::G_CONVERT_ERROR_BAD_URI,
+            ConvertError::NotAbsolutePath => glib_sys::G_CONVERT_ERROR_NOT_ABSOLUTE_PATH,
+            ConvertError::NoMemory => glib_sys::G_CONVERT_ERROR_NO_MEMORY,
+            ConvertError::EmbeddedNul => glib_sys::G_CONVERT_ERROR_EMBEDDED_NUL,
+            ConvertError::__Unknown(value) => value
+        }
+    }
+}
+
+#[doc(hidden)]
+impl FromGlib<glib_sys::GConvertError> for ConvertError {
+    fn from_glib(value: glib_sys::GConvertError) -> Self {
+        match value {
+            0 => ConvertError::NoConversion,
+            1 => ConvertError::IllegalSequence,
+            2 => ConvertError::Failed,
+            3 => ConvertError::PartialInput,
+            4 => ConvertError::BadUri,
+            5 => ConvertError::NotAbsolutePath,
+            6 => ConvertError::NoMemory,
+            7 => ConvertError::EmbeddedNul,
+            value => ConvertError::__Unknown(value),
+        }
+    }
+}
+
+impl ErrorDomain for ConvertError {
+    fn domain() -> Quark {
+        unsafe { from_glib(glib_sys::g_convert_error_quark()) }
+    }
+
+    fn code(self) -> i32 {
+        self.to_glib()
+    }
+
+    fn from(code: i32) -> Option<Self> {
+        match code {
+            0 => Some(ConvertError::NoConversion),
+            1 => Some(ConvertError::IllegalSequence),
+            2 => Some(ConvertError::Failed),
+            3 => Some(ConvertError::PartialInput),
+            4 => Some(ConvertError::BadUri),
+            5 => Some(ConvertError::NotAbsolutePath),
+            6 => Some(ConvertError::NoMemory),
+            7 => Some(ConvertError::EmbeddedNul),
+            value => Some(ConvertError::__Unknown(value)),
+        }
+    }
+}
+
 #[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
 #[derive(Clone, Copy)]
 pub enum DateMonth {
@@ -302,6 +393,143 @@ impl ErrorDomain for KeyFileError {
     }
 }
 
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[derive(Clone, Copy)]
+pub enum MarkupError {
+    BadUtf8,
+    Empty,
+    Parse,
+    UnknownElement,
+    UnknownAttribute,
+    InvalidContent,
+    MissingAttribute,
+    #[doc(hidden)]
+    __Unknown(i32),
+}
+
+impl fmt::Display for MarkupError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "MarkupError::{}", match *self {
+            MarkupError::BadUtf8 => "BadUtf8",
+            MarkupError::Empty => "Empty",
+            MarkupError::Parse => "Parse",
+            MarkupError::UnknownElement => "UnknownElement",
+            MarkupError::UnknownAttribute => "UnknownAttribute",
+            MarkupError::InvalidContent => "InvalidContent",
+            MarkupError::MissingAttribute => "MissingAttribute",
+            _ => "Unknown",
+        })
+    }
+}
+
+#[doc(hidden)]
+impl ToGlib for MarkupError {
+    type GlibType = glib_sys::GMarkupError;
+
+    fn to_glib(&self) -> glib_sys::GMarkupError {
+        match *self {
+            MarkupError::BadUtf8 => glib_sys::G_MARKUP_ERROR_BAD_UTF8,
+            MarkupError::Empty => glib_sys::G_MARKUP_ERROR_EMPTY,
+            MarkupError::Parse => glib_sys::G_MARKUP_ERROR_PARSE,
+            MarkupError::UnknownElement => glib_sys::G_MARKUP_ERROR_UNKNOWN_ELEMENT,
+            MarkupError::UnknownAttribute => glib_sys::G_MARKUP_ERROR_UNKNOWN_ATTRIBUTE,
+            MarkupError::InvalidContent => glib_sys::G_MARKUP_ERROR_INVALID_CONTENT,
+            MarkupError::MissingAttribute => glib_sys::G_MARKUP_ERROR_MISSING_ATTRIBUTE,
+            MarkupError::__Unknown(value) => value
+        }
+    }
+}
+
+#[doc(hidden)]
+impl FromGlib<glib_sys::GMarkupError> for MarkupError {
+    fn from_glib(value: glib_sys::GMarkupError) -> Self {
+        match value {
+            0 => MarkupError::BadUtf8,
+            1 => MarkupError::Empty,
+            2 => MarkupError::Parse,
+            3 => MarkupError::UnknownElement,
+            4 => MarkupError::UnknownAttribute,
+            5 => MarkupError::InvalidContent,
+            6 => MarkupError::MissingAttribute,
+            value => MarkupError::__Unknown(value),
+        }
+    }
+}
+
+impl ErrorDomain for MarkupError {
+    fn domain() -> Quark {
+        unsafe { from_glib(glib_sys::g_markup_error_quark()) }
+    }
+
+    fn code(self) -> i32 {
+        self.to_glib()
+    }
+
+    fn from(code: i32) -> Option<Self> {
+        match code {
+            0 => Some(MarkupError::BadUtf8),
+            1 => Some(MarkupError::Empty),
+            2 => Some(MarkupError::Parse),
+            3 => Some(MarkupError::UnknownElement),
+            4 => Some(MarkupError::UnknownAttribute),
+            5 => Some(MarkupError::InvalidContent),
+            6 => Some(MarkupError::MissingAttribute),
+            value => Some(MarkupError::__Unknown(value)),
+        }
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[derive(Clone, Copy)]
+pub enum NormalizeMode {
+    Default,
+    DefaultCompose,
+    All,
+    AllCompose,
+    #[doc(hidden)]
+    __Unknown(i32),
+}
+
+impl fmt::Display for NormalizeMode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "NormalizeMode::{}", match *self {
+            NormalizeMode::Default => "Default",
+            NormalizeMode::DefaultCompose => "DefaultCompose",
+            NormalizeMode::All => "All",
+            NormalizeMode::AllCompose => "AllCompose",
+            _ => "Unknown",
+        })
+    }
+}
+
+#[doc(hidden)]
+impl ToGlib for NormalizeMode {
+    type GlibType = glib_sys::GNormalizeMode;
+
+    fn to_glib(&self) -> glib_sys::GNormalizeMode {
+        match *self {
+            NormalizeMode::Default => glib_sys::G_NORMALIZE_DEFAULT,
+            NormalizeMode::DefaultCompose => glib_sys::G_NORMALIZE_DEFAULT_COMPOSE,
+            NormalizeMode::All => glib_sys::G_NORMALIZE_ALL,
+            NormalizeMode::AllCompose => glib_sys::G_NORMALIZE_ALL_COMPOSE,
+            NormalizeMode::__Unknown(value) => value
+        }
+    }
+}
+
+#[doc(hidden)]
+impl FromGlib<glib_sys::GNormalizeMode> for NormalizeMode {
+    fn from_glib(value: glib_sys::GNormalizeMode) -> Self {
+        match value {
+            0 => NormalizeMode::Default,
+            1 => NormalizeMode::DefaultCompose,
+            2 => NormalizeMode::All,
+            3 => NormalizeMode::AllCompose,
+            value => NormalizeMode::__Unknown(value),
+        }
+    }
+}
+
 #[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
 #[derive(Clone, Copy)]
 pub enum OptionArg {
@@ -467,3 +695,143 @@ impl FromGlib<glib_sys::GTimeType> for TimeType {
     }
 }
 
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[derive(Clone, Copy)]
+pub enum VariantParseError {
+    Failed,
+    BasicTypeExpected,
+    CannotInferType,
+    DefiniteTypeExpected,
+    InputNotAtEnd,
+    InvalidCharacter,
+    InvalidFormatString,
+    InvalidObjectPath,
+    InvalidSignature,
+    InvalidTypeString,
+    NoCommonType,
+    NumberOutOfRange,
+    NumberTooBig,
+    TypeError,
+    UnexpectedToken,
+    UnknownKeyword,
+    UnterminatedStringConstant,
+    ValueExpected,
+    #[doc(hidden)]
+    __Unknown(i32),
+}
+
+impl fmt::Display for VariantParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "VariantParseError::{}", match *self {
+            VariantParseError::Failed => "Failed",
+            VariantParseError::BasicTypeExpected => "BasicTypeExpected",
+            VariantParseError::CannotInferType => "CannotInferType",
+            VariantParseError::DefiniteTypeExpected => "DefiniteTypeExpected",
+            VariantParseError::InputNotAtEnd => "InputNotAtEnd",
+            VariantParseError::InvalidCharacter => "InvalidCharacter",
+            VariantParseError::InvalidFormatString => "InvalidFormatString",
+            VariantParseError::InvalidObjectPath => "InvalidObjectPath",
+            VariantParseError::InvalidSignature => "InvalidSignature",
+            VariantParseError::InvalidTypeString => "InvalidTypeString",
+            VariantParseError::NoCommonType => "NoCommonType",
+            VariantParseError::NumberOutOfRange => "NumberOutOfRange",
+            VariantParseError::NumberTooBig => "NumberTooBig",
+            VariantParseError::TypeError => "TypeError",
+            VariantParseError::UnexpectedToken => "UnexpectedToken",
+            VariantParseError::UnknownKeyword => "UnknownKeyword",
+            VariantParseError::UnterminatedStringConstant => "UnterminatedStringConstant",
+            VariantParseError::ValueExpected => "ValueExpected",
+            _ => "Unknown",
+        })
+    }
+}
+
+#[doc(hidden)]
+impl ToGlib for VariantParseError {
+    type GlibType = glib_sys::GVariantParseError;
+
+    fn to_glib(&self) -> glib_sys::GVariantParseError {
+        match *self {
+            VariantParseError::Failed => glib_sys::G_VARIANT_PARSE_ERROR_FAILED,
+            VariantParseError::BasicTypeExpected => glib_sys::G_VARIANT_PARSE_ERROR_BASIC_TYPE_EXPECTED,
+            VariantParseError::CannotInferType => glib_sys::G_VARIANT_PARSE_ERROR_CANNOT_INFER_TYPE,
+            VariantParseError::DefiniteTypeExpected => glib_sys::G_VARIANT_PARSE_ERROR_DEFINITE_TYPE_EXPECTED,
+            VariantParseError::InputNotAtEnd => glib_sys::G_VARIANT_PARSE_ERROR_INPUT_NOT_AT_END,
+            VariantParseError::InvalidCharacter => glib_sys::G_VARIANT_PARSE_ERROR_INVALID_CHARACTER,
+            VariantParseError::InvalidFormatString => glib_sys::G_VARIANT_PARSE_ERROR_INVALID_FORMAT_STRING,
+            VariantParseError::InvalidObjectPath => glib_sys::G_VARIANT_PARSE_ERROR_INVALID_OBJECT_PATH,
+            VariantParseError::InvalidSignature => glib_sys::G_VARIANT_PARSE_ERROR_INVALID_SIGNATURE,
+            VariantParseError::InvalidTypeString => glib_sys::G_VARIANT_PARSE_ERROR_INVALID_TYPE_STRING,
+            VariantParseError::NoCommonType => glib_sys::G_VARIANT_PARSE_ERROR_NO_COMMON_TYPE,
+            VariantParseError::NumberOutOfRange => glib_sys::G_VARIANT_PARSE_ERROR_NUMBER_OUT_OF_RANGE,
+            VariantParseError::NumberTooBig => glib_sys::G_VARIANT_PARSE_ERROR_NUMBER_TOO_BIG,
+            VariantParseError::TypeError => glib_sys::G_VARIANT_PARSE_ERROR_TYPE_ERROR,
+            VariantParseError::UnexpectedToken => glib_sys::G_VARIANT_PARSE_ERROR_UNEXPECTED_TOKEN,
+            VariantParseError::UnknownKeyword => glib_sys::G_VARIANT_PARSE_ERROR_UNKNOWN_KEYWORD,
+            VariantParseError::UnterminatedStringConstant => glib_sys::G_VARIANT_PARSE_ERROR_UNTERMINATED_STRING_CONSTANT,
+            VariantParseError::ValueExpected => glib_sys::G_VARIANT_PARSE_ERROR_VALUE_EXPECTED,
+            VariantParseError::__Unknown(value) => value
+        }
+    }
+}
+
+#[doc(hidden)]
+impl FromGlib<glib_sys::GVariantParseError> for VariantParseError {
+    fn from_glib(value: glib_sys::GVariantParseError) -> Self {
+        match value {
+            0 => VariantParseError::Failed,
+            1 => VariantParseError::BasicTypeExpected,
+            2 => VariantParseError::CannotInferType,
+            3 => VariantParseError::DefiniteTypeExpected,
+            4 => VariantParseError::InputNotAtEnd,
+            5 => VariantParseError::InvalidCharacter,
+            6 => VariantParseError::InvalidFormatString,
+            7 => VariantParseError::InvalidObjectPath,
+            8 => VariantParseError::InvalidSignature,
+            9 => VariantParseError::InvalidTypeString,
+            10 => VariantParseError::NoCommonType,
+            11 => VariantParseError::NumberOutOfRange,
+            12 => VariantParseError::NumberTooBig,
+            13 => VariantParseError::TypeError,
+            14 => VariantParseError::UnexpectedToken,
+            15 => VariantParseError::UnknownKeyword,
+            16 => VariantParseError::UnterminatedStringConstant,
+            17 => VariantParseError::ValueExpected,
+            value => VariantParseError::__Unknown(value),
+        }
+    }
+}
+
+impl ErrorDomain for VariantParseError {
+    fn domain() -> Quark {
+        unsafe { from_glib(glib_sys::g_variant_parse_error_quark()) }
+    }
+
+    fn code(self) -> i32 {
+        self.to_glib()
+    }
+
+    fn from(code: i32) -> Option<Self> {
+        match code {
+            0 => Some(VariantParseError::Failed),
+            1 => Some(VariantParseError::BasicTypeExpected),
+            2 => Some(VariantParseError::CannotInferType),
+            3 => Some(VariantParseError::DefiniteTypeExpected),
+            4 => Some(VariantParseError::InputNotAtEnd),
+            5 => Some(VariantParseError::InvalidCharacter),
+            6 => Some(VariantParseError::InvalidFormatString),
+            7 => Some(VariantParseError::InvalidObjectPath),
+            8 => Some(VariantParseError::InvalidSignature),
+            9 => Some(VariantParseError::InvalidTypeString),
+            10 => Some(VariantParseError::NoCommonType),
+            11 => Some(VariantParseError::NumberOutOfRange),
+            12 => Some(VariantParseError::NumberTooBig),
+            13 => Some(VariantParseError::TypeError),
+            14 => Some(VariantParseError::UnexpectedToken),
+            15 => Some(VariantParseError::UnknownKeyword),
+            16 => Some(VariantParseError::UnterminatedStringConstant),
+            17 => Some(VariantParseError::ValueExpected),
+            value => Some(VariantParseError::__Unknown(value)),
+        }
+    }
+}
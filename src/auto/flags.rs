@@ -138,6 +138,31 @@ impl FromGlib<glib_sys::GKeyFileFlags> for KeyFileFlags {
     }
 }
 
+bitflags! {
+    pub struct MarkupParseFlags: u32 {
+        const DO_NOT_USE_THIS_UNSUPPORTED_FLAG = 1;
+        const TREAT_CDATA_AS_TEXT = 2;
+        const PREFIX_ERROR_POSITION = 4;
+        const IGNORE_QUALIFIED = 8;
+    }
+}
+
+#[doc(hidden)]
+impl ToGlib for MarkupParseFlags {
+    type GlibType = glib_sys::GMarkupParseFlags;
+
+    fn to_glib(&self) -> glib_sys::GMarkupParseFlags {
+        self.bits()
+    }
+}
+
+#[doc(hidden)]
+impl FromGlib<glib_sys::GMarkupParseFlags> for MarkupParseFlags {
+    fn from_glib(value: glib_sys::GMarkupParseFlags) -> MarkupParseFlags {
+        MarkupParseFlags::from_bits_truncate(value)
+    }
+}
+
 bitflags! {
     pub struct OptionFlags: u32 {
         const NONE = 0;
@@ -167,3 +192,78 @@ impl FromGlib<glib_sys::GOptionFlags> for OptionFlags {
     }
 }
 
+
+bitflags! {
+    pub struct RegexCompileFlags: u32 {
+        const CASELESS = 1;
+        const MULTILINE = 2;
+        const DOTALL = 4;
+        const EXTENDED = 8;
+        const ANCHORED = 16;
+        const DOLLAR_ENDONLY = 32;
+        const UNGREEDY = 512;
+        const RAW = 2048;
+        const NO_AUTO_CAPTURE = 4096;
+        const OPTIMIZE = 8192;
+        const FIRSTLINE = 262144;
+        const DUPNAMES = 524288;
+        const NEWLINE_CR = 1048576;
+        const NEWLINE_LF = 2097152;
+        const NEWLINE_CRLF = 3145728;
+        const NEWLINE_ANYCRLF = 5242880;
+        const BSR_ANYCRLF = 8388608;
+        const JAVASCRIPT_COMPAT = 33554432;
+    }
+}
+
+#[doc(hidden)]
+impl ToGlib for RegexCompileFlags {
+    type GlibType = glib_sys::GRegexCompileFlags;
+
+    fn to_glib(&self) -> glib_sys::GRegexCompileFlags {
+        self.bits()
+    }
+}
+
+#[doc(hidden)]
+impl FromGlib<glib_sys::GRegexCompileFlags> for RegexCompileFlags {
+    fn from_glib(value: glib_sys::GRegexCompileFlags) -> RegexCompileFlags {
+        RegexCompileFlags::from_bits_truncate(value)
+    }
+}
+
+bitflags! {
+    pub struct RegexMatchFlags: u32 {
+        const ANCHORED = 16;
+        const NOTBOL = 128;
+        const NOTEOL = 256;
+        const NOTEMPTY = 1024;
+        const PARTIAL = 32768;
+        const NEWLINE_CR = 1048576;
+        const NEWLINE_LF = 2097152;
+        const NEWLINE_CRLF = 3145728;
+        const NEWLINE_ANY = 4194304;
+        const NEWLINE_ANYCRLF = 5242880;
+        const BSR_ANYCRLF = 8388608;
+        const BSR_ANY = 16777216;
+        const PARTIAL_SOFT = 32768;
+        const PARTIAL_HARD = 134217728;
+        const NOTEMPTY_ATSTART = 268435456;
+    }
+}
+
+#[doc(hidden)]
+impl ToGlib for RegexMatchFlags {
+    type GlibType = glib_sys::GRegexMatchFlags;
+
+    fn to_glib(&self) -> glib_sys::GRegexMatchFlags {
+        self.bits()
+    }
+}
+
+#[doc(hidden)]
+impl FromGlib<glib_sys::GRegexMatchFlags> for RegexMatchFlags {
+    fn from_glib(value: glib_sys::GRegexMatchFlags) -> RegexMatchFlags {
+        RegexMatchFlags::from_bits_truncate(value)
+    }
+}
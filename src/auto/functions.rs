@@ -59,10 +59,6 @@ pub fn base64_decode(text: &str) -> Vec<u8> {
 //    unsafe { TODO: call glib_sys:g_base64_decode_inplace() }
 //}
 
-//pub fn base64_decode_step(in_: &[u8], out: Vec<u8>, state: &mut i32, save: &mut u32) -> usize {
-//    unsafe { TODO: call glib_sys:g_base64_decode_step() }
-//}
-
 pub fn base64_encode(data: &[u8]) -> Option<GString> {
     let len = data.len() as usize;
     unsafe {
@@ -70,14 +66,6 @@ pub fn base64_encode(data: &[u8]) -> Option<GString> {
     }
 }
 
-//pub fn base64_encode_close(break_lines: bool, out: Vec<u8>, state: &mut i32, save: &mut i32) -> usize {
-//    unsafe { TODO: call glib_sys:g_base64_encode_close() }
-//}
-
-//pub fn base64_encode_step(in_: &[u8], break_lines: bool, out: Vec<u8>, state: &mut i32, save: &mut i32) -> usize {
-//    unsafe { TODO: call glib_sys:g_base64_encode_step() }
-//}
-
 pub fn bit_nth_lsf(mask: libc::c_ulong, nth_bit: i32) -> i32 {
     unsafe {
         glib_sys::g_bit_nth_lsf(mask, nth_bit)
@@ -200,10 +188,6 @@ pub fn compute_hmac_for_string(digest_type: ChecksumType, key: &[u8], str: &str)
     }
 }
 
-//pub fn convert_with_iconv(str: &[u8], converter: /*Ignored*/&IConv) -> Result<(Vec<u8>, usize), Error> {
-//    unsafe { TODO: call glib_sys:g_convert_with_iconv() }
-//}
-
 //pub fn datalist_clear(datalist: /*Ignored*/&mut Data) {
 //    unsafe { TODO: call glib_sys:g_datalist_clear() }
 //}
@@ -364,18 +348,6 @@ pub fn file_test<P: AsRef<std::path::Path>>(filename: P, test: FileTest) -> bool
     }
 }
 
-pub fn filename_display_basename<P: AsRef<std::path::Path>>(filename: P) -> Option<GString> {
-    unsafe {
-        from_glib_full(glib_sys::g_filename_display_basename(filename.as_ref().to_glib_none().0))
-    }
-}
-
-pub fn filename_display_name<P: AsRef<std::path::Path>>(filename: P) -> Option<GString> {
-    unsafe {
-        from_glib_full(glib_sys::g_filename_display_name(filename.as_ref().to_glib_none().0))
-    }
-}
-
 pub fn format_size(size: u64) -> Option<GString> {
     unsafe {
         from_glib_full(glib_sys::g_format_size(size))
@@ -511,40 +483,6 @@ pub fn get_user_special_dir(directory: UserDirectory) -> Option<std::path::PathB
     }
 }
 
-pub fn hostname_is_ascii_encoded(hostname: &str) -> bool {
-    unsafe {
-        from_glib(glib_sys::g_hostname_is_ascii_encoded(hostname.to_glib_none().0))
-    }
-}
-
-pub fn hostname_is_ip_address(hostname: &str) -> bool {
-    unsafe {
-        from_glib(glib_sys::g_hostname_is_ip_address(hostname.to_glib_none().0))
-    }
-}
-
-pub fn hostname_is_non_ascii(hostname: &str) -> bool {
-    unsafe {
-        from_glib(glib_sys::g_hostname_is_non_ascii(hostname.to_glib_none().0))
-    }
-}
-
-pub fn hostname_to_ascii(hostname: &str) -> Option<GString> {
-    unsafe {
-        from_glib_full(glib_sys::g_hostname_to_ascii(hostname.to_glib_none().0))
-    }
-}
-
-pub fn hostname_to_unicode(hostname: &str) -> Option<GString> {
-    unsafe {
-        from_glib_full(glib_sys::g_hostname_to_unicode(hostname.to_glib_none().0))
-    }
-}
-
-//pub fn iconv(converter: /*Ignored*/&IConv, inbuf: &str, inbytes_left: usize, outbuf: &str, outbytes_left: usize) -> usize {
-//    unsafe { TODO: call glib_sys:g_iconv() }
-//}
-
 //pub fn idle_remove_by_data(data: /*Unimplemented*/Option<Fundamental: Pointer>) -> bool {
 //    unsafe { TODO: call glib_sys:g_idle_remove_by_data() }
 //}
@@ -804,20 +742,12 @@ pub fn path_skip_root<P: AsRef<std::path::Path>>(file_name: P) -> Option<std::pa
     }
 }
 
-//pub fn pattern_match(pspec: /*Ignored*/&mut PatternSpec, string_length: u32, string: &str, string_reversed: Option<&str>) -> bool {
-//    unsafe { TODO: call glib_sys:g_pattern_match() }
-//}
-
 pub fn pattern_match_simple(pattern: &str, string: &str) -> bool {
     unsafe {
         from_glib(glib_sys::g_pattern_match_simple(pattern.to_glib_none().0, string.to_glib_none().0))
     }
 }
 
-//pub fn pattern_match_string(pspec: /*Ignored*/&mut PatternSpec, string: &str) -> bool {
-//    unsafe { TODO: call glib_sys:g_pattern_match_string() }
-//}
-
 //pub fn pointer_bit_lock(address: /*Unimplemented*/Fundamental: Pointer, lock_bit: i32) {
 //    unsafe { TODO: call glib_sys:g_pointer_bit_lock() }
 //}
@@ -1088,36 +1018,6 @@ pub fn unlink<P: AsRef<std::path::Path>>(filename: P) -> i32 {
     }
 }
 
-pub fn uri_escape_string(unescaped: &str, reserved_chars_allowed: Option<&str>, allow_utf8: bool) -> Option<GString> {
-    unsafe {
-        from_glib_full(glib_sys::g_uri_escape_string(unescaped.to_glib_none().0, reserved_chars_allowed.to_glib_none().0, allow_utf8.to_glib()))
-    }
-}
-
-pub fn uri_list_extract_uris(uri_list: &str) -> Vec<GString> {
-    unsafe {
-        FromGlibPtrContainer::from_glib_full(glib_sys::g_uri_list_extract_uris(uri_list.to_glib_none().0))
-    }
-}
-
-pub fn uri_parse_scheme(uri: &str) -> Option<GString> {
-    unsafe {
-        from_glib_full(glib_sys::g_uri_parse_scheme(uri.to_glib_none().0))
-    }
-}
-
-pub fn uri_unescape_segment(escaped_string: Option<&str>, escaped_string_end: Option<&str>, illegal_characters: Option<&str>) -> Option<GString> {
-    unsafe {
-        from_glib_full(glib_sys::g_uri_unescape_segment(escaped_string.to_glib_none().0, escaped_string_end.to_glib_none().0, illegal_characters.to_glib_none().0))
-    }
-}
-
-pub fn uri_unescape_string(escaped_string: &str, illegal_characters: Option<&str>) -> Option<GString> {
-    unsafe {
-        from_glib_full(glib_sys::g_uri_unescape_string(escaped_string.to_glib_none().0, illegal_characters.to_glib_none().0))
-    }
-}
-
 pub fn usleep(microseconds: libc::c_ulong) {
     unsafe {
         glib_sys::g_usleep(microseconds);
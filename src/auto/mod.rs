@@ -25,19 +25,26 @@ pub use self::time_zone::TimeZone;
 
 mod enums;
 pub use self::enums::ChecksumType;
+pub use self::enums::ConvertError;
 pub use self::enums::DateMonth;
 pub use self::enums::DateWeekday;
 pub use self::enums::KeyFileError;
+pub use self::enums::MarkupError;
+pub use self::enums::NormalizeMode;
 pub use self::enums::OptionArg;
 pub use self::enums::SeekType;
 pub use self::enums::TimeType;
+pub use self::enums::VariantParseError;
 
 mod flags;
 pub use self::flags::FileTest;
 pub use self::flags::FormatSizeFlags;
 pub use self::flags::IOCondition;
 pub use self::flags::KeyFileFlags;
+pub use self::flags::MarkupParseFlags;
 pub use self::flags::OptionFlags;
+pub use self::flags::RegexCompileFlags;
+pub use self::flags::RegexMatchFlags;
 
 mod alias;
 pub use self::alias::DateDay;
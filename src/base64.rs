@@ -0,0 +1,168 @@
+// Copyright 2019, The Gtk-rs Project Developers.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+// Licensed under the MIT license, see the LICENSE file or <http://opensource.org/licenses/MIT>
+
+use glib_sys;
+use translate::*;
+
+/// Incremental base64 encoder, for data that is not available all at once.
+///
+/// The encoded text is the same as if all data was passed to [`base64_encode()`] at once.
+///
+/// ```
+/// use glib::Base64Encoder;
+///
+/// let mut encoder = Base64Encoder::new(false);
+/// let mut text = String::new();
+/// encoder.encode_step(b"Hello ", &mut text);
+/// encoder.encode_step(b"World", &mut text);
+/// encoder.finish(&mut text);
+/// assert_eq!(text, "SGVsbG8gV29ybGQ=");
+/// ```
+///
+/// [`base64_encode()`]: fn.base64_encode.html
+#[derive(Debug, Clone)]
+pub struct Base64Encoder {
+    break_lines: bool,
+    state: i32,
+    save: i32,
+}
+
+impl Base64Encoder {
+    /// Creates a new encoder.
+    ///
+    /// If `break_lines` is `true`, a newline is inserted after every 76 characters as required
+    /// for e.g. MIME.
+    pub fn new(break_lines: bool) -> Base64Encoder {
+        Base64Encoder {
+            break_lines,
+            state: 0,
+            save: 0,
+        }
+    }
+
+    /// Encodes `data` and appends the result to `out`.
+    ///
+    /// Up to two bytes of `data` are kept back until more data is passed or the encoder is
+    /// finished.
+    pub fn encode_step(&mut self, data: &[u8], out: &mut String) {
+        let mut max_len = (data.len() / 3 + 1) * 4 + 4;
+        if self.break_lines {
+            max_len += max_len / 76 + 1;
+        }
+
+        unsafe {
+            let out = out.as_mut_vec();
+            out.reserve(max_len);
+            let len = glib_sys::g_base64_encode_step(
+                data.as_ptr(),
+                data.len(),
+                self.break_lines.to_glib(),
+                out.as_mut_ptr().add(out.len()) as *mut _,
+                &mut self.state,
+                &mut self.save,
+            );
+            let new_len = out.len() + len;
+            out.set_len(new_len);
+        }
+    }
+
+    /// Appends the encoding of the remaining bytes and the padding to `out`.
+    pub fn finish(mut self, out: &mut String) {
+        unsafe {
+            let out = out.as_mut_vec();
+            out.reserve(5);
+            let len = glib_sys::g_base64_encode_close(
+                self.break_lines.to_glib(),
+                out.as_mut_ptr().add(out.len()) as *mut _,
+                &mut self.state,
+                &mut self.save,
+            );
+            let new_len = out.len() + len;
+            out.set_len(new_len);
+        }
+    }
+}
+
+/// Incremental base64 decoder, for text that is not available all at once.
+///
+/// Invalid characters in the text are skipped.
+#[derive(Debug, Clone, Default)]
+pub struct Base64Decoder {
+    state: i32,
+    save: u32,
+}
+
+impl Base64Decoder {
+    pub fn new() -> Base64Decoder {
+        Base64Decoder::default()
+    }
+
+    /// Decodes `text` and appends the result to `out`.
+    ///
+    /// Incomplete groups of characters at the end of `text` are kept back until more text is
+    /// passed.
+    pub fn decode_step(&mut self, text: &[u8], out: &mut Vec<u8>) {
+        unsafe {
+            out.reserve((text.len() / 4 + 1) * 3);
+            let len = glib_sys::g_base64_decode_step(
+                text.as_ptr() as *const _,
+                text.len(),
+                out.as_mut_ptr().add(out.len()),
+                &mut self.state,
+                &mut self.save,
+            );
+            let new_len = out.len() + len;
+            out.set_len(new_len);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use {base64_decode, base64_encode};
+
+    #[test]
+    fn test_encoder() {
+        let data = (0..1000u32).map(|i| (i * 7) as u8).collect::<Vec<_>>();
+
+        for &break_lines in &[false, true] {
+            let mut encoder = Base64Encoder::new(break_lines);
+            let mut text = String::new();
+            for chunk in data.chunks(17) {
+                encoder.encode_step(chunk, &mut text);
+            }
+            encoder.finish(&mut text);
+
+            if break_lines {
+                assert!(text.lines().all(|line| line.len() <= 76));
+                assert_eq!(text.replace("\n", ""), base64_encode(&data).unwrap());
+            } else {
+                assert_eq!(text, base64_encode(&data).unwrap());
+            }
+            assert_eq!(base64_decode(&text), data);
+        }
+
+        let mut text = String::new();
+        Base64Encoder::new(false).finish(&mut text);
+        assert_eq!(text, "");
+    }
+
+    #[test]
+    fn test_decoder() {
+        let data = (0..1000u32).map(|i| (i * 13) as u8).collect::<Vec<_>>();
+        let text = base64_encode(&data).unwrap();
+
+        let mut decoder = Base64Decoder::new();
+        let mut decoded = Vec::new();
+        for chunk in text.as_bytes().chunks(5) {
+            decoder.decode_step(chunk, &mut decoded);
+        }
+        assert_eq!(decoded, data);
+
+        let mut decoded = Vec::new();
+        Base64Decoder::new().decode_step(b"SGVs\nbG8=", &mut decoded);
+        assert_eq!(decoded, b"Hello");
+    }
+}
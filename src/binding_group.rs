@@ -0,0 +1,182 @@
+// Copyright 2019, The Gtk-rs Project Developers.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+// Licensed under the MIT license, see the LICENSE file or <http://opensource.org/licenses/MIT>
+
+use object::{BindingGuard, Cast, IsA, ObjectExt, WeakRef};
+use BindingFlags;
+use BoolError;
+use Object;
+
+#[derive(Debug)]
+struct BindingSpec {
+    source_property: String,
+    target: WeakRef<Object>,
+    target_property: String,
+    flags: BindingFlags,
+}
+
+/// A set of property bindings from one source object to any number of targets.
+///
+/// All bindings of the group are created for the current source object, and are moved over to
+/// the new source object once the source is changed with [`set_source()`]. This allows to keep
+/// a view in sync with whatever model object it currently displays without keeping track of the
+/// individual bindings.
+///
+/// Targets are only referenced weakly, bindings to targets that were finalized are dropped. All
+/// bindings are unbound once the group is dropped.
+///
+/// ```ignore
+/// let mut group = glibBindingGroup::new();
+/// group.bind("title", &title_label, "label", glibBindingFlags::SYNC_CREATE)?;
+/// group.bind("done", &check_button, "active", glibBindingFlags::SYNC_CREATE)?;
+///
+/// // Shows the properties of `item` from now on
+/// group.set_source(Some(&item))?;
+/// ```
+///
+/// [`set_source()`]: #method.set_source
+#[derive(Debug, Default)]
+pub struct BindingGroup {
+    source: Option<Object>,
+    specs: Vec<BindingSpec>,
+    bindings: Vec<BindingGuard>,
+}
+
+impl BindingGroup {
+    pub fn new() -> BindingGroup {
+        BindingGroup::default()
+    }
+
+    /// Returns the current source object.
+    pub fn get_source(&self) -> Option<&Object> {
+        self.source.as_ref()
+    }
+
+    /// Changes the source object of all bindings.
+    ///
+    /// All bindings to the previous source object are unbound first. Returns an error if any of
+    /// the bindings can't be created for the new source, in which case all others are still
+    /// created.
+    pub fn set_source<T: IsA<Object>>(&mut self, source: Option<&T>) -> Result<(), BoolError> {
+        self.bindings.clear();
+        self.source = source.map(|source| source.upcast_ref::<Object>().clone());
+
+        let mut res = Ok(());
+        if let Some(source) = self.source.clone() {
+            self.specs.retain(|spec| spec.target.upgrade().is_some());
+
+            let bindings = self
+                .specs
+                .iter()
+                .map(|spec| BindingGroup::create_binding(&source, spec))
+                .collect::<Vec<_>>();
+            for binding in bindings {
+                match binding {
+                    Ok(Some(binding)) => self.bindings.push(binding),
+                    Ok(None) => (),
+                    Err(err) => res = Err(err),
+                }
+            }
+        }
+
+        res
+    }
+
+    /// Adds a binding from `source_property` of the source object to `target_property` of
+    /// `target`.
+    ///
+    /// If there is a source object right now, the binding is created right away and an error is
+    /// returned if that fails, in which case it is not added to the group.
+    pub fn bind<T: IsA<Object>>(
+        &mut self,
+        source_property: &str,
+        target: &T,
+        target_property: &str,
+        flags: BindingFlags,
+    ) -> Result<(), BoolError> {
+        let spec = BindingSpec {
+            source_property: source_property.to_owned(),
+            target: target.upcast_ref::<Object>().downgrade(),
+            target_property: target_property.to_owned(),
+            flags,
+        };
+
+        let binding = match self.source {
+            Some(ref source) => BindingGroup::create_binding(source, &spec)?,
+            None => None,
+        };
+        self.specs.push(spec);
+        if let Some(binding) = binding {
+            self.bindings.push(binding);
+        }
+
+        Ok(())
+    }
+
+    /// Unbinds and removes all bindings of the group.
+    pub fn clear(&mut self) {
+        self.bindings.clear();
+        self.specs.clear();
+    }
+
+    fn create_binding(source: &Object, spec: &BindingSpec) -> Result<Option<BindingGuard>, BoolError> {
+        let target = match spec.target.upgrade() {
+            Some(target) => target,
+            None => return Ok(None),
+        };
+
+        if source.find_property(spec.source_property.as_str()).is_none() {
+            return Err(glib_bool_error!("Source property not found"));
+        }
+        if target.find_property(spec.target_property.as_str()).is_none() {
+            return Err(glib_bool_error!("Target property not found"));
+        }
+
+        source
+            .bind_property(spec.source_property.as_str(), &target, spec.target_property.as_str())
+            .flags(spec.flags)
+            .build_guard()
+            .map(Some)
+            .ok_or_else(|| glib_bool_error!("Failed to create binding"))
+    }
+}
+
+#[cfg(all(test, feature = "subclassing"))]
+mod tests {
+    use super::*;
+    use subclass::object::test::{ConstructObject, SimpleObject};
+    use subclass::types::ObjectSubclass;
+
+    #[test]
+    fn test_binding_group() {
+        let first = Object::new(SimpleObject::get_type(), &[("name", &"first")]).unwrap();
+        let second = Object::new(SimpleObject::get_type(), &[("name", &"second")]).unwrap();
+        let target = Object::new(ConstructObject::get_type(), &[]).unwrap();
+
+        let mut group = BindingGroup::new();
+        group
+            .bind("name", &target, "label", BindingFlags::SYNC_CREATE)
+            .unwrap();
+        assert_eq!(target.get_property("label").unwrap().get::<&str>(), Some("default"));
+
+        group.set_source(Some(&first)).unwrap();
+        assert_eq!(target.get_property("label").unwrap().get::<&str>(), Some("first"));
+
+        group.set_source(Some(&second)).unwrap();
+        assert_eq!(target.get_property("label").unwrap().get::<&str>(), Some("second"));
+
+        // The previous source is not bound anymore
+        first.set_property("name", &"changed").unwrap();
+        assert_eq!(target.get_property("label").unwrap().get::<&str>(), Some("second"));
+        second.set_property("name", &"changed").unwrap();
+        assert_eq!(target.get_property("label").unwrap().get::<&str>(), Some("changed"));
+
+        assert!(group
+            .bind("no-such-property", &target, "label", BindingFlags::DEFAULT)
+            .is_err());
+
+        drop(group);
+        second.set_property("name", &"dropped").unwrap();
+        assert_eq!(target.get_property("label").unwrap().get::<&str>(), Some("changed"));
+    }
+}
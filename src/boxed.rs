@@ -309,6 +309,26 @@ impl<T: 'static, MM: BoxedMemoryManager<T>> Boxed<T, MM> {
             _dummy: PhantomData,
         }
     }
+
+    /// Gives up ownership of the pointer, which has to be freed by the caller.
+    ///
+    /// Values that don't own a foreign pointer are copied.
+    #[doc(hidden)]
+    #[inline]
+    pub fn into_raw(self) -> *mut T {
+        let owned = match self.inner {
+            AnyBox::ForeignOwned(ptr) => Some(ptr),
+            _ => None,
+        };
+
+        match owned {
+            Some(ptr) => {
+                mem::forget(self);
+                ptr.as_ptr()
+            }
+            None => self.to_glib_full() as *mut T,
+        }
+    }
 }
 
 impl<T: 'static, MM: BoxedMemoryManager<T>> Uninitialized for Boxed<T, MM> {
@@ -0,0 +1,206 @@
+// Copyright 2019, The Gtk-rs Project Developers.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+// Licensed under the MIT license, see the LICENSE file or <http://opensource.org/licenses/MIT>
+
+//! Weak references for closures, see the [`clone!`] macro.
+//!
+//! [`clone!`]: ../macro.clone.html
+
+use object::{ObjectExt, ObjectType, WeakRef};
+use std::rc::{self, Rc};
+use std::sync::{self, Arc};
+
+/// Trait for generalizing downgrading a strong reference to a weak reference.
+pub trait Downgrade
+where
+    Self: Sized,
+{
+    /// Weak reference type.
+    type Weak;
+
+    /// Downgrade to a weak reference.
+    fn downgrade(&self) -> Self::Weak;
+}
+
+/// Trait for generalizing upgrading a weak reference to a strong reference.
+pub trait Upgrade
+where
+    Self: Sized,
+{
+    /// Strong reference type.
+    type Strong;
+
+    /// Try upgrading a weak reference to a strong reference.
+    fn upgrade(&self) -> Option<Self::Strong>;
+}
+
+impl<T: ObjectType> Downgrade for T {
+    type Weak = WeakRef<T>;
+
+    fn downgrade(&self) -> Self::Weak {
+        ObjectExt::downgrade(self)
+    }
+}
+
+impl<T: ObjectType> Upgrade for WeakRef<T> {
+    type Strong = T;
+
+    fn upgrade(&self) -> Option<Self::Strong> {
+        WeakRef::upgrade(self)
+    }
+}
+
+impl<T> Downgrade for Rc<T> {
+    type Weak = rc::Weak<T>;
+
+    fn downgrade(&self) -> Self::Weak {
+        Rc::downgrade(self)
+    }
+}
+
+impl<T> Upgrade for rc::Weak<T> {
+    type Strong = Rc<T>;
+
+    fn upgrade(&self) -> Option<Self::Strong> {
+        rc::Weak::upgrade(self)
+    }
+}
+
+impl<T> Downgrade for Arc<T> {
+    type Weak = sync::Weak<T>;
+
+    fn downgrade(&self) -> Self::Weak {
+        Arc::downgrade(self)
+    }
+}
+
+impl<T> Upgrade for sync::Weak<T> {
+    type Strong = Arc<T>;
+
+    fn upgrade(&self) -> Option<Self::Strong> {
+        sync::Weak::upgrade(self)
+    }
+}
+
+/// Macro for passing variables as strong or weak references into a closure.
+///
+/// `@strong` variables are cloned into the closure. `@weak` variables are downgraded and only a
+/// weak reference is moved into the closure, which is upgraded again whenever the closure is
+/// called. If upgrading fails the closure returns right away, with the value given via
+/// `@default-return` or `()` otherwise. This allows connecting to signals without creating
+/// reference cycles.
+///
+/// Weak references work for all types implementing [`Downgrade`], i.e. object wrappers, `Rc` and
+/// `Arc`.
+///
+/// ```ignore
+/// let label = ...;
+/// let counter = Rc::new(Cell::new(0));
+///
+/// button.connect_clicked(clone!(@weak label, @strong counter => move |_| {
+///     counter.set(counter.get() + 1);
+///     label.set_text(&counter.get().to_string());
+/// }));
+///
+/// window.connect_delete_event(clone!(@weak app => @default-return Inhibit(false), move |_, _| {
+///     app.quit();
+///     Inhibit(false)
+/// }));
+/// ```
+///
+/// [`Downgrade`]: clone/trait.Downgrade.html
+#[macro_export]
+macro_rules! clone {
+    (@downgrade strong $name:ident) => {
+        let $name = $name.clone();
+    };
+    (@downgrade weak $name:ident) => {
+        let $name = $crate::clone::Downgrade::downgrade(&$name);
+    };
+    (@downgrade $kind:ident $name:ident) => {
+        compile_error!(concat!("Unknown clone! kind `@", stringify!($kind), "`, expected `@weak` or `@strong`"));
+    };
+    (@upgrade strong $name:ident, $default:expr) => {};
+    (@upgrade weak $name:ident, $default:expr) => {
+        let $name = match $crate::clone::Upgrade::upgrade(&$name) {
+            Some(val) => val,
+            None => return $default,
+        };
+    };
+    (@upgrade $kind:ident $name:ident, $default:expr) => {};
+
+    ($(@$kind:ident $name:ident),+ => @default-return $default:expr, move || $body:expr) => {{
+        $( clone!(@downgrade $kind $name); )+
+        move || {
+            $( clone!(@upgrade $kind $name, $default); )+
+            $body
+        }
+    }};
+    ($(@$kind:ident $name:ident),+ => @default-return $default:expr,
+        move |$($arg:tt $(: $typ:ty)*),*| $body:expr) => {{
+        $( clone!(@downgrade $kind $name); )+
+        move |$($arg $(: $typ)*),*| {
+            $( clone!(@upgrade $kind $name, $default); )+
+            $body
+        }
+    }};
+    ($(@$kind:ident $name:ident),+ => move || $body:expr) => {
+        clone!($(@$kind $name),+ => @default-return (), move || $body)
+    };
+    ($(@$kind:ident $name:ident),+ => move |$($arg:tt $(: $typ:ty)*),*| $body:expr) => {
+        clone!($(@$kind $name),+ => @default-return (), move |$($arg $(: $typ)*),*| $body)
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+    use std::rc::Rc;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn test_clone_strong() {
+        let v = Rc::new(Cell::new(1));
+        let closure = clone!(@strong v => move |x: i32| {
+            v.set(v.get() + x);
+            v.get()
+        });
+
+        assert_eq!(Rc::strong_count(&v), 2);
+        assert_eq!(closure(2), 3);
+        assert_eq!(v.get(), 3);
+    }
+
+    #[test]
+    fn test_clone_weak() {
+        let v = Rc::new(Cell::new(1));
+        let w = Arc::new(Mutex::new(0));
+        let closure = clone!(@weak v, @strong w => @default-return false, move || {
+            v.set(v.get() + 1);
+            *w.lock().unwrap() += 1;
+            true
+        });
+
+        assert_eq!(Rc::strong_count(&v), 1);
+        assert!(closure());
+        assert_eq!(v.get(), 2);
+
+        drop(v);
+        assert!(!closure());
+        assert_eq!(*w.lock().unwrap(), 1);
+    }
+
+    #[test]
+    fn test_clone_unit() {
+        let v = Rc::new(Cell::new(1));
+        let closure = clone!(@weak v => move |a: i32, b: i32| {
+            v.set(a + b);
+        });
+
+        closure(2, 3);
+        assert_eq!(v.get(), 5);
+
+        drop(v);
+        closure(4, 5);
+    }
+}
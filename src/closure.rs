@@ -12,6 +12,7 @@ use libc::{c_uint, c_void};
 
 use gobject_sys;
 use translate::{from_glib_none, mut_override, ToGlibPtr, ToGlibPtrMut, Uninitialized};
+use thread_guard::LocalCallback;
 use types::Type;
 use ToValue;
 use Value;
@@ -37,6 +38,18 @@ impl Closure {
         }
     }
 
+    /// Creates a new closure around a callback that is not `Send` and `Sync`.
+    ///
+    /// Invoking the closure from a thread other than the one it was created on only logs a
+    /// critical warning and returns `None` without calling `callback`. If the closure is
+    /// finalized on another thread, `callback` is leaked.
+    pub fn new_local<F: Fn(&[Value]) -> Option<Value> + 'static>(callback: F) -> Self {
+        let callback = LocalCallback::new(callback);
+        unsafe {
+            Closure::new_unsafe(move |values| callback.get().and_then(|callback| callback(values)))
+        }
+    }
+
     pub unsafe fn new_unsafe<F: Fn(&[Value]) -> Option<Value>>(callback: F) -> Self {
         unsafe extern "C" fn marshal<F>(_closure: *mut gobject_sys::GClosure, return_value: *mut gobject_sys::GValue,
             n_param_values: c_uint, param_values: *const gobject_sys::GValue, _invocation_hint: *mut c_void,
@@ -44,7 +57,7 @@ impl Closure {
             where F: Fn(&[Value]) -> Option<Value>
         {
             let values = slice::from_raw_parts(param_values as *const _, n_param_values as usize);
-            let callback: Box<F> = Box::from_raw(marshal_data as *mut _);
+            let callback: &F = &*(marshal_data as *const F);
             let result = callback(values);
             if !return_value.is_null() {
                 match result {
@@ -55,7 +68,6 @@ impl Closure {
                     },
                 }
             }
-            mem::forget(callback);
         }
 
         unsafe extern "C" fn finalize<F>(notify_data: *mut c_void, _closure: *mut gobject_sys::GClosure)
@@ -110,6 +122,29 @@ impl Closure {
             Some(result)
         }
     }
+
+    /// Invokes the closure with `values` as arguments and a return value of type `return_type`.
+    ///
+    /// Different to `invoke()`, this works with closures that expect a return value of a
+    /// specific type, like most closures created from C. Pass `Type::Unit` if the closure does
+    /// not return anything, in which case `None` is returned.
+    pub fn invoke_with_values(&self, return_type: Type, values: &[Value]) -> Option<Value> {
+        let mut result = if return_type == Type::Unit {
+            unsafe { Value::uninitialized() }
+        } else {
+            Value::from_type(return_type)
+        };
+
+        unsafe {
+            gobject_sys::g_closure_invoke(self.to_glib_none().0 as *mut _, result.to_glib_none_mut().0,
+                values.len() as u32, mut_override(values.as_ptr()) as *mut gobject_sys::GValue, ptr::null_mut());
+        }
+        if result.type_() == Type::Invalid {
+            None
+        } else {
+            Some(result)
+        }
+    }
 }
 
 unsafe impl Send for Closure {}
@@ -160,4 +195,22 @@ mod tests {
         let int: Option<i32> = result.and_then(|result| result.get());
         assert_eq!(int, Some(24));
     }
+
+    #[test]
+    fn test_closure_local() {
+        use std::cell::Cell;
+        use std::rc::Rc;
+
+        let call_count = Rc::new(Cell::new(0));
+        let count = call_count.clone();
+        let closure = Closure::new_local(move |values| {
+            count.set(count.get() + 1);
+            let int: Option<i32> = values[0].get();
+            int.map(|int| (int * 2).to_value())
+        });
+
+        let result = closure.invoke_with_values(::Type::I32, &[21.to_value()]);
+        assert_eq!(result.and_then(|result| result.get::<i32>()), Some(42));
+        assert_eq!(call_count.get(), 1);
+    }
 }
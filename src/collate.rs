@@ -0,0 +1,113 @@
+// Copyright 2019, The Gtk-rs Project Developers.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+// Licensed under the MIT license, see the LICENSE file or <http://opensource.org/licenses/MIT>
+
+use glib_sys;
+use libc::c_char;
+use std::cmp::Ordering;
+use std::ffi::CStr;
+use translate::*;
+use GString;
+
+/// A key for locale-aware string comparisons.
+///
+/// Comparing two keys gives the same result as comparing the original strings with
+/// `g_utf8_collate()`, but is much cheaper. This makes it useful when the same strings are
+/// compared many times, e.g. while sorting.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CollationKey(Vec<u8>);
+
+impl CollationKey {
+    /// Creates a collation key for `s`.
+    pub fn new(s: &str) -> CollationKey {
+        unsafe { CollationKey::from_glib_full(glib_sys::g_utf8_collate_key(s.to_glib_none().0, s.len() as isize)) }
+    }
+
+    /// Creates a collation key for `s` suitable for filenames.
+    ///
+    /// Different to `new()`, dots and numbers are handled specially so that e.g. `file10` is
+    /// sorted after `file2`.
+    pub fn for_filename(s: &str) -> CollationKey {
+        unsafe {
+            CollationKey::from_glib_full(glib_sys::g_utf8_collate_key_for_filename(
+                s.to_glib_none().0,
+                s.len() as isize,
+            ))
+        }
+    }
+
+    // Collation keys are not necessarily valid UTF-8, so they can't be stored as string
+    unsafe fn from_glib_full(ptr: *mut c_char) -> CollationKey {
+        assert!(!ptr.is_null());
+        let key = CStr::from_ptr(ptr).to_bytes().to_vec();
+        glib_sys::g_free(ptr as glib_sys::gpointer);
+        CollationKey(key)
+    }
+}
+
+/// Compares `a` and `b` for display to the user, according to the rules of the current locale.
+///
+/// Use [`CollationKey`] when comparing the same strings many times.
+///
+/// [`CollationKey`]: struct.CollationKey.html
+pub fn utf8_collate(a: &str, b: &str) -> Ordering {
+    unsafe { glib_sys::g_utf8_collate(a.to_glib_none().0, b.to_glib_none().0).cmp(&0) }
+}
+
+/// Sorts `vec` by the collation keys of the strings returned by `f`.
+///
+/// The keys are only computed once per item.
+pub fn sort_by_collate_key<T, F: Fn(&T) -> &str>(vec: &mut Vec<T>, f: F) {
+    sort_by_key_func(vec, |item| CollationKey::new(f(item)));
+}
+
+/// Sorts `vec` by the filename collation keys of the strings returned by `f`.
+///
+/// The keys are only computed once per item.
+pub fn sort_by_filename_collate_key<T, F: Fn(&T) -> &str>(vec: &mut Vec<T>, f: F) {
+    sort_by_key_func(vec, |item| CollationKey::for_filename(f(item)));
+}
+
+/// Sorts `filenames` the same way as file managers usually do.
+pub fn sort_filenames(filenames: &mut Vec<GString>) {
+    sort_by_filename_collate_key(filenames, |filename| filename.as_str());
+}
+
+fn sort_by_key_func<T, F: Fn(&T) -> CollationKey>(vec: &mut Vec<T>, f: F) {
+    let mut keyed = vec.drain(..).map(|item| (f(&item), item)).collect::<Vec<_>>();
+    keyed.sort_by(|a, b| a.0.cmp(&b.0));
+    vec.extend(keyed.into_iter().map(|(_, item)| item));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_collation_key() {
+        assert_eq!(CollationKey::new("abc"), CollationKey::new("abc"));
+        assert!(CollationKey::for_filename("file2") < CollationKey::for_filename("file10"));
+    }
+
+    #[test]
+    fn test_collate() {
+        assert_eq!(utf8_collate("abc", "abc"), Ordering::Equal);
+        assert_eq!(utf8_collate("abc", "abd"), Ordering::Less);
+        assert_eq!(utf8_collate("b", "a"), Ordering::Greater);
+        assert_eq!(
+            utf8_collate("a", "b"),
+            CollationKey::new("a").cmp(&CollationKey::new("b"))
+        );
+    }
+
+    #[test]
+    fn test_sort_filenames() {
+        let mut filenames = vec![GString::from("file10.txt"), GString::from("file2.txt"), GString::from("file1.txt")];
+        sort_filenames(&mut filenames);
+        assert_eq!(filenames, vec!["file1.txt", "file2.txt", "file10.txt"]);
+
+        let mut items = vec![(2, "b"), (1, "a"), (3, "c")];
+        sort_by_collate_key(&mut items, |item| item.1);
+        assert_eq!(items, vec![(1, "a"), (2, "b"), (3, "c")]);
+    }
+}
@@ -0,0 +1,260 @@
+// Copyright 2019, The Gtk-rs Project Developers.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+// Licensed under the MIT license, see the LICENSE file or <http://opensource.org/licenses/MIT>
+
+use glib_sys;
+use libc::c_char;
+use std::error;
+use std::fmt;
+use std::ptr;
+use std::slice;
+use translate::*;
+use Error;
+use GString;
+
+/// A failed charset conversion.
+///
+/// `bytes_read` is the number of input bytes that were converted successfully, e.g. the offset
+/// of the invalid sequence for `ConvertError::IllegalSequence` errors.
+#[derive(Debug)]
+pub struct CvtError {
+    pub err: Error,
+    pub bytes_read: usize,
+}
+
+impl fmt::Display for CvtError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} (after {} bytes)", self.err, self.bytes_read)
+    }
+}
+
+impl error::Error for CvtError {
+    fn description(&self) -> &str {
+        error::Error::description(&self.err)
+    }
+}
+
+// The output of conversions is not NUL-terminated in general, e.g. for UTF-16
+unsafe fn take_bytes(ptr: *mut c_char, len: usize) -> Vec<u8> {
+    let res = if len == 0 {
+        Vec::new()
+    } else {
+        slice::from_raw_parts(ptr as *const u8, len).to_vec()
+    };
+    glib_sys::g_free(ptr as glib_sys::gpointer);
+    res
+}
+
+unsafe fn cvt_result<T, F: FnOnce(*mut c_char, usize) -> T>(
+    ret: *mut c_char,
+    bytes_read: usize,
+    bytes_written: usize,
+    error: *mut glib_sys::GError,
+    f: F,
+) -> Result<(T, usize), CvtError> {
+    if error.is_null() {
+        Ok((f(ret, bytes_written), bytes_read))
+    } else {
+        Err(CvtError {
+            err: from_glib_full(error),
+            bytes_read,
+        })
+    }
+}
+
+/// Converts `str` from the charset `from_codeset` to `to_codeset`.
+///
+/// Returns the converted bytes and the number of input bytes that were converted. That can be
+/// less than the length of `str` if it ends with an incomplete character.
+///
+/// ```
+/// let (latin1, _) = glib::convert("h\u{e9}".as_bytes(), "ISO-8859-1", "UTF-8").unwrap();
+/// assert_eq!(latin1, b"h\xe9");
+/// ```
+pub fn convert(str: &[u8], to_codeset: &str, from_codeset: &str) -> Result<(Vec<u8>, usize), CvtError> {
+    unsafe {
+        let mut bytes_read = 0;
+        let mut bytes_written = 0;
+        let mut error = ptr::null_mut();
+        let ret = glib_sys::g_convert(
+            str.as_ptr() as *const _,
+            str.len() as isize,
+            to_codeset.to_glib_none().0,
+            from_codeset.to_glib_none().0,
+            &mut bytes_read,
+            &mut bytes_written,
+            &mut error,
+        );
+        cvt_result(ret, bytes_read, bytes_written, error, |ptr, len| take_bytes(ptr, len))
+    }
+}
+
+/// Same as [`convert()`], but characters that can't be represented in `to_codeset` are replaced
+/// by `fallback`.
+///
+/// Without `fallback`, such characters are replaced by their code point in the form `\uXXXX`.
+///
+/// [`convert()`]: fn.convert.html
+pub fn convert_with_fallback(
+    str: &[u8],
+    to_codeset: &str,
+    from_codeset: &str,
+    fallback: Option<&str>,
+) -> Result<(Vec<u8>, usize), CvtError> {
+    unsafe {
+        let mut bytes_read = 0;
+        let mut bytes_written = 0;
+        let mut error = ptr::null_mut();
+        let ret = glib_sys::g_convert_with_fallback(
+            str.as_ptr() as *const _,
+            str.len() as isize,
+            to_codeset.to_glib_none().0,
+            from_codeset.to_glib_none().0,
+            fallback.to_glib_none().0,
+            &mut bytes_read,
+            &mut bytes_written,
+            &mut error,
+        );
+        cvt_result(ret, bytes_read, bytes_written, error, |ptr, len| take_bytes(ptr, len))
+    }
+}
+
+/// Converts `opsysstring` from the charset of the current locale to UTF-8.
+pub fn locale_to_utf8(opsysstring: &[u8]) -> Result<(GString, usize), CvtError> {
+    unsafe {
+        let mut bytes_read = 0;
+        let mut bytes_written = 0;
+        let mut error = ptr::null_mut();
+        let ret = glib_sys::g_locale_to_utf8(
+            opsysstring.as_ptr() as *const _,
+            opsysstring.len() as isize,
+            &mut bytes_read,
+            &mut bytes_written,
+            &mut error,
+        );
+        // Embedded NUL characters are reported as errors, so the result is a valid C string
+        cvt_result(ret, bytes_read, bytes_written, error, |ptr, _| from_glib_full(ptr))
+    }
+}
+
+/// Converts `utf8string` to the charset of the current locale.
+pub fn locale_from_utf8(utf8string: &str) -> Result<(Vec<u8>, usize), CvtError> {
+    unsafe {
+        let mut bytes_read = 0;
+        let mut bytes_written = 0;
+        let mut error = ptr::null_mut();
+        let ret = glib_sys::g_locale_from_utf8(
+            utf8string.as_ptr() as *const _,
+            utf8string.len() as isize,
+            &mut bytes_read,
+            &mut bytes_written,
+            &mut error,
+        );
+        cvt_result(ret, bytes_read, bytes_written, error, |ptr, len| take_bytes(ptr, len))
+    }
+}
+
+/// A reusable converter between two charsets.
+///
+/// Opening a converter is relatively expensive, so this is more efficient than [`convert()`]
+/// when converting many strings.
+///
+/// [`convert()`]: fn.convert.html
+#[derive(Debug)]
+pub struct IConv(glib_sys::GIConv);
+
+unsafe impl Send for IConv {}
+
+impl IConv {
+    /// Opens a converter from `from_codeset` to `to_codeset`.
+    ///
+    /// Returns `None` if the conversion is not supported.
+    pub fn new(to_codeset: &str, from_codeset: &str) -> Option<IConv> {
+        let converter = unsafe {
+            glib_sys::g_iconv_open(to_codeset.to_glib_none().0, from_codeset.to_glib_none().0)
+        };
+        // Failure is signalled by (GIConv) -1 like with iconv_open()
+        if converter as isize == -1 {
+            None
+        } else {
+            Some(IConv(converter))
+        }
+    }
+
+    /// Converts `str`, see [`convert()`].
+    ///
+    /// The state of the converter is reset before each conversion.
+    ///
+    /// [`convert()`]: fn.convert.html
+    pub fn convert(&mut self, str: &[u8]) -> Result<(Vec<u8>, usize), CvtError> {
+        unsafe {
+            let mut bytes_read = 0;
+            let mut bytes_written = 0;
+            let mut error = ptr::null_mut();
+            let ret = glib_sys::g_convert_with_iconv(
+                str.as_ptr() as *const _,
+                str.len() as isize,
+                self.0,
+                &mut bytes_read,
+                &mut bytes_written,
+                &mut error,
+            );
+            cvt_result(ret, bytes_read, bytes_written, error, |ptr, len| take_bytes(ptr, len))
+        }
+    }
+}
+
+impl Drop for IConv {
+    fn drop(&mut self) {
+        unsafe {
+            glib_sys::g_iconv_close(self.0);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ConvertError;
+
+    #[test]
+    fn test_convert() {
+        let (latin1, read) = convert("a\u{e4}b".as_bytes(), "ISO-8859-1", "UTF-8").unwrap();
+        assert_eq!(latin1, b"a\xe4b");
+        assert_eq!(read, 4);
+
+        let (utf8, _) = convert(&latin1, "UTF-8", "ISO-8859-1").unwrap();
+        assert_eq!(utf8, "a\u{e4}b".as_bytes());
+
+        let (utf16, _) = convert(b"ab", "UTF-16LE", "UTF-8").unwrap();
+        assert_eq!(utf16, b"a\0b\0");
+
+        let err = convert(b"ab\xffc", "ISO-8859-1", "UTF-8").unwrap_err();
+        assert_eq!(err.err.kind::<ConvertError>(), Some(ConvertError::IllegalSequence));
+        assert_eq!(err.bytes_read, 2);
+
+        let err = convert(b"a", "does-not-exist", "UTF-8").unwrap_err();
+        assert_eq!(err.err.kind::<ConvertError>(), Some(ConvertError::NoConversion));
+    }
+
+    #[test]
+    fn test_convert_with_fallback() {
+        let s = "a\u{20ac}b".as_bytes();
+        assert!(convert(s, "ISO-8859-1", "UTF-8").is_err());
+
+        let (res, _) = convert_with_fallback(s, "ISO-8859-1", "UTF-8", Some("?")).unwrap();
+        assert_eq!(res, b"a?b");
+        let (res, _) = convert_with_fallback(s, "ISO-8859-1", "UTF-8", None).unwrap();
+        assert_eq!(res, b"a\\u20acb");
+    }
+
+    #[test]
+    fn test_iconv() {
+        assert!(IConv::new("does-not-exist", "UTF-8").is_none());
+
+        let mut converter = IConv::new("ISO-8859-1", "UTF-8").unwrap();
+        assert_eq!(converter.convert("\u{fc}".as_bytes()).unwrap().0, b"\xfc");
+        assert_eq!(converter.convert("\u{f6}".as_bytes()).unwrap().0, b"\xf6");
+        assert_eq!(converter.convert(b"\xff").unwrap_err().bytes_read, 0);
+    }
+}
@@ -9,6 +9,7 @@ use std::borrow::Cow;
 use std::error;
 use std::ffi::CStr;
 use std::fmt;
+use std::io;
 use std::str;
 use translate::*;
 use Quark;
@@ -75,6 +76,20 @@ impl Error {
         }
     }
 
+    /// Converts this error into a `std::io::Error`.
+    ///
+    /// Errors of the `FileError` domain are mapped to the closest `std::io::ErrorKind`, all
+    /// other errors are mapped to `std::io::ErrorKind::Other`. The message is preserved in
+    /// both cases.
+    pub fn to_io_error(&self) -> io::Error {
+        let kind = self
+            .kind::<::FileError>()
+            .map(::FileError::to_io_error_kind)
+            .unwrap_or(io::ErrorKind::Other);
+
+        io::Error::new(kind, self.message())
+    }
+
     fn message(&self) -> &str {
         unsafe {
             let bytes = CStr::from_ptr(self.0.message).to_bytes();
@@ -107,6 +122,21 @@ impl fmt::Debug for Error {
     }
 }
 
+impl From<io::Error> for Error {
+    /// Converts a `std::io::Error` into an error of the `FileError` domain.
+    ///
+    /// The OS error code is used for selecting the error code if available, otherwise the
+    /// closest match for the `std::io::ErrorKind` is used.
+    fn from(err: io::Error) -> Error {
+        let code = match err.raw_os_error() {
+            Some(errno) => ::FileError::from_errno(errno),
+            None => ::FileError::from_io_error_kind(err.kind()),
+        };
+
+        Error::new(code, &err.to_string())
+    }
+}
+
 /// `GLib` error domain.
 ///
 /// This trait is implemented by error enums that represent error domains (types).
@@ -258,4 +288,16 @@ mod tests {
         );
         assert!(true_dynamic_res.is_ok());
    }
+
+    #[test]
+    fn test_io_error() {
+        let err = io::Error::new(io::ErrorKind::NotFound, "file not found");
+        let glib_err = Error::from(err);
+        assert_eq!(glib_err.kind::<::FileError>(), Some(::FileError::Noent));
+        assert_eq!(glib_err.to_string(), "file not found");
+
+        let io_err = glib_err.to_io_error();
+        assert_eq!(io_err.kind(), io::ErrorKind::NotFound);
+        assert_eq!(io_err.to_string(), "file not found");
+    }
 }
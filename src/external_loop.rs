@@ -0,0 +1,148 @@
+// Copyright 2019, The Gtk-rs Project Developers.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+// Licensed under the MIT license, see the LICENSE file or <http://opensource.org/licenses/MIT>
+
+use glib_sys;
+use source::duration_to_micros;
+#[cfg(all(not(unix), feature = "dox"))]
+use libc::c_int as RawFd;
+use std::cell::RefCell;
+#[cfg(unix)]
+use std::os::unix::io::RawFd;
+use std::time::Duration;
+use translate::ToGlibPtr;
+use Continue;
+use MainContext;
+use Priority;
+use Source;
+use SourceImpl;
+
+/// An event loop that can be driven from a `GLib` main context.
+///
+/// This allows running other event loops, e.g. a tokio current-thread runtime or a mio based
+/// reactor, while the `GLib` main loop is the actual driver. Futures and I/O objects of that
+/// event loop then make progress without requiring a second thread.
+///
+/// This crate does not depend on any particular runtime, so ready-made implementations for
+/// tokio or async-std are not provided here. An implementation only needs the file descriptor
+/// of the runtime's reactor and a way to run it for a single non-blocking turn.
+///
+/// The event loop is dropped together with its source, which can happen on any thread that
+/// holds the last reference to the source, so implementations have to be `Send`.
+///
+/// See [`MainContext::attach_external_loop()`].
+///
+/// [`MainContext::attach_external_loop()`]: struct.MainContext.html#method.attach_external_loop
+pub trait ExternalLoop: Send + 'static {
+    /// Runs a single, non-blocking turn of the event loop.
+    ///
+    /// This is called whenever the file descriptor of the event loop becomes readable, or the
+    /// previously returned timeout elapsed. Returns the time after which the event loop has to be
+    /// turned again at the latest, e.g. because of a timer, or `None` if it only has to be turned
+    /// on I/O events.
+    fn turn(&mut self) -> Option<Duration>;
+}
+
+struct ExternalLoopSource<L: ExternalLoop>(RefCell<L>);
+
+// Only dispatch is implemented: GLib itself dispatches the source when the file descriptor is
+// readable, and timeouts are handled via the ready time
+impl<L: ExternalLoop> SourceImpl for ExternalLoopSource<L> {
+    fn dispatch(&self, source: &Source) -> Continue {
+        let timeout = self.0.borrow_mut().turn();
+
+        let ready_time = match timeout {
+            None => -1,
+            Some(timeout) => source.get_time().saturating_add(duration_to_micros(timeout)),
+        };
+        source.set_ready_time(ready_time);
+
+        Continue(true)
+    }
+}
+
+impl MainContext {
+    /// Drives `external_loop` from this main context.
+    ///
+    /// `fd` is polled by the main context for readability and should be the file descriptor of
+    /// the event loop's I/O backend, e.g. the epoll or kqueue file descriptor. The event loop is
+    /// turned once right away, whenever `fd` is readable and whenever the timeout returned by its
+    /// last turn elapsed.
+    ///
+    /// The returned source can be destroyed to stop driving the event loop.
+    pub fn attach_external_loop<L: ExternalLoop>(
+        &self,
+        fd: Option<RawFd>,
+        priority: Priority,
+        external_loop: L,
+    ) -> Source {
+        let source = Source::new_from_impl(ExternalLoopSource(RefCell::new(external_loop)));
+        if let Some(fd) = fd {
+            unsafe {
+                glib_sys::g_source_add_unix_fd(source.to_glib_none().0, fd, glib_sys::G_IO_IN);
+            }
+        }
+        source.set_priority(priority);
+        // Turn the event loop once right away
+        source.set_ready_time(0);
+
+        source.attach(Some(self));
+        source
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::time::Instant;
+    use MainLoop;
+
+    struct TimerLoop {
+        deadlines: Vec<Instant>,
+        fired: Arc<AtomicUsize>,
+        main_loop: MainLoop,
+    }
+
+    impl ExternalLoop for TimerLoop {
+        fn turn(&mut self) -> Option<Duration> {
+            let now = Instant::now();
+            let before = self.deadlines.len();
+            self.deadlines.retain(|deadline| *deadline > now);
+            self.fired.fetch_add(before - self.deadlines.len(), Ordering::SeqCst);
+
+            if self.deadlines.is_empty() {
+                self.main_loop.quit();
+                None
+            } else {
+                self.deadlines.iter().min().map(|deadline| *deadline - now)
+            }
+        }
+    }
+
+    #[test]
+    fn test_external_loop_timers() {
+        let c = MainContext::new();
+        let l = MainLoop::new(Some(&c), false);
+
+        c.acquire();
+
+        let now = Instant::now();
+        let fired = Arc::new(AtomicUsize::new(0));
+        let source = c.attach_external_loop(
+            None,
+            ::PRIORITY_DEFAULT,
+            TimerLoop {
+                deadlines: vec![now + Duration::from_millis(10), now + Duration::from_millis(30)],
+                fired: fired.clone(),
+                main_loop: l.clone(),
+            },
+        );
+
+        l.run();
+        source.destroy();
+
+        assert_eq!(fired.load(Ordering::SeqCst), 2);
+    }
+}
@@ -4,6 +4,7 @@
 
 use error::ErrorDomain;
 use glib_sys;
+use std::io;
 use translate::from_glib;
 use Quark;
 
@@ -36,6 +37,49 @@ pub enum FileError {
     Failed,
 }
 
+impl FileError {
+    /// Gets the `FileError` corresponding to the given `errno` value.
+    ///
+    /// Unknown values map to `FileError::Failed`.
+    pub fn from_errno(errno: i32) -> FileError {
+        unsafe {
+            <FileError as ErrorDomain>::from(glib_sys::g_file_error_from_errno(errno) as i32)
+                .unwrap_or(FileError::Failed)
+        }
+    }
+
+    /// Gets the `FileError` that best describes the given `std::io::ErrorKind`.
+    pub fn from_io_error_kind(kind: io::ErrorKind) -> FileError {
+        use self::FileError::*;
+        match kind {
+            io::ErrorKind::NotFound => Noent,
+            io::ErrorKind::PermissionDenied => Acces,
+            io::ErrorKind::AlreadyExists => Exist,
+            io::ErrorKind::BrokenPipe => Pipe,
+            io::ErrorKind::WouldBlock => Again,
+            io::ErrorKind::InvalidInput => Inval,
+            io::ErrorKind::Interrupted => Intr,
+            io::ErrorKind::UnexpectedEof => Io,
+            _ => Failed,
+        }
+    }
+
+    /// Gets the `std::io::ErrorKind` that best describes this `FileError`.
+    pub fn to_io_error_kind(self) -> io::ErrorKind {
+        use self::FileError::*;
+        match self {
+            Exist => io::ErrorKind::AlreadyExists,
+            Acces | Perm | Rofs => io::ErrorKind::PermissionDenied,
+            Noent | Nxio | Nodev => io::ErrorKind::NotFound,
+            Pipe => io::ErrorKind::BrokenPipe,
+            Again => io::ErrorKind::WouldBlock,
+            Inval | Nametoolong | Badf => io::ErrorKind::InvalidInput,
+            Intr => io::ErrorKind::Interrupted,
+            _ => io::ErrorKind::Other,
+        }
+    }
+}
+
 impl ErrorDomain for FileError {
     fn domain() -> Quark {
         unsafe { from_glib(glib_sys::g_file_error_quark()) }
@@ -105,3 +149,22 @@ impl ErrorDomain for FileError {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_io_error_kind() {
+        assert_eq!(FileError::from_io_error_kind(io::ErrorKind::NotFound), FileError::Noent);
+        assert_eq!(FileError::Noent.to_io_error_kind(), io::ErrorKind::NotFound);
+        assert_eq!(FileError::Failed.to_io_error_kind(), io::ErrorKind::Other);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_from_errno() {
+        assert_eq!(FileError::from_errno(::libc::ENOENT), FileError::Noent);
+        assert_eq!(FileError::from_errno(::libc::EEXIST), FileError::Exist);
+    }
+}
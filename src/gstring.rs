@@ -8,10 +8,13 @@ use std::cmp::Ordering;
 use std::ffi::{CStr, CString, OsStr};
 use std::fmt;
 use std::hash;
+use std::hash::Hash;
 use std::ops::Deref;
 use std::os::raw::c_char;
+use std::path::Path;
 use std::ptr;
 use std::slice;
+use std::str;
 use std::string::String;
 use translate::*;
 use types::{StaticType, Type};
@@ -20,73 +23,135 @@ use glib_sys;
 use gobject_sys;
 use value::{FromValueOptional, SetValue, SetValueOptional, Value};
 
-#[derive(Debug)]
-pub enum GString {
-    ForeignOwned(Option<CString>),
+/// An owned UTF-8 string that is either allocated by GLib or by Rust.
+///
+/// Strings returned from C functions are converted into this without copying: the allocation of
+/// a string returned with full ownership is taken over and freed with `g_free()` once the
+/// `GString` is dropped. It dereferences to `str` and is only copied into a `String` on demand.
+///
+/// The contents are checked to be valid UTF-8 once when the `GString` is created.
+pub struct GString(Inner);
+
+enum Inner {
+    Native(Option<CString>),
+    Foreign(*mut c_char, usize),
     Borrowed(*const c_char, usize),
-    Owned(*mut c_char, usize),
 }
 
 impl GString {
+    /// Takes ownership of the nul-terminated string at `ptr`, which is freed with `g_free()`.
+    ///
+    /// Use [`try_new()`] for strings that are not guaranteed to be valid UTF-8.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the string is not valid UTF-8. The string is freed before panicking.
+    ///
+    /// [`try_new()`]: #method.try_new
     pub unsafe fn new(ptr: *mut c_char) -> Self {
+        GString::try_new(ptr).expect("Invalid UTF-8")
+    }
+
+    /// Takes ownership of the nul-terminated string at `ptr`, which is freed with `g_free()`.
+    ///
+    /// If the string is not valid UTF-8 it is freed right away and the error is returned.
+    pub unsafe fn try_new(ptr: *mut c_char) -> Result<Self, str::Utf8Error> {
         assert!(!ptr.is_null());
-        GString::Owned(ptr, libc::strlen(ptr))
+        match GString::validated_len(ptr) {
+            Ok(len) => Ok(GString(Inner::Foreign(ptr, len))),
+            Err(err) => {
+                glib_sys::g_free(ptr as *mut _);
+                Err(err)
+            }
+        }
     }
 
+    /// Borrows the nul-terminated string at `ptr`, which must stay valid as long as the
+    /// `GString` is alive.
+    ///
+    /// Use [`try_new_borrowed()`] for strings that are not guaranteed to be valid UTF-8.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the string is not valid UTF-8.
+    ///
+    /// [`try_new_borrowed()`]: #method.try_new_borrowed
     pub unsafe fn new_borrowed(ptr: *const c_char) -> Self {
+        GString::try_new_borrowed(ptr).expect("Invalid UTF-8")
+    }
+
+    /// Borrows the nul-terminated string at `ptr`, which must stay valid as long as the
+    /// `GString` is alive.
+    ///
+    /// Returns an error if the string is not valid UTF-8.
+    pub unsafe fn try_new_borrowed(ptr: *const c_char) -> Result<Self, str::Utf8Error> {
         assert!(!ptr.is_null());
-        GString::Borrowed(ptr, libc::strlen(ptr))
+        GString::validated_len(ptr).map(|len| GString(Inner::Borrowed(ptr, len)))
+    }
+
+    unsafe fn validated_len(ptr: *const c_char) -> Result<usize, str::Utf8Error> {
+        let len = libc::strlen(ptr);
+        str::from_utf8(slice::from_raw_parts(ptr as *const u8, len)).map(|_| len)
     }
 
     pub fn as_str(&self) -> &str {
-        let cstr = match self {
-            GString::Borrowed(ptr, length) => unsafe {
-                let bytes = slice::from_raw_parts(*ptr as *const u8, length + 1);
-                CStr::from_bytes_with_nul_unchecked(bytes)
-            }
-            GString::Owned(ptr, length) => unsafe {
-                let bytes = slice::from_raw_parts(*ptr as *const u8, length + 1);
-                CStr::from_bytes_with_nul_unchecked(bytes)
-            }
-            GString::ForeignOwned(cstring) => {
-                cstring.as_ref().expect("ForeignOwned shouldn't be empty").as_c_str()
+        // The contents were validated on creation
+        unsafe { str::from_utf8_unchecked(self.as_c_str().to_bytes()) }
+    }
+
+    /// Returns the contents including the terminating nul.
+    pub fn as_c_str(&self) -> &CStr {
+        match self.0 {
+            Inner::Native(ref cstring) => {
+                cstring.as_ref().expect("Native shouldn't be empty").as_c_str()
             }
-        };
-        cstr.to_str().unwrap()
+            Inner::Foreign(ptr, len) => unsafe {
+                CStr::from_bytes_with_nul_unchecked(slice::from_raw_parts(ptr as *const u8, len + 1))
+            },
+            Inner::Borrowed(ptr, len) => unsafe {
+                CStr::from_bytes_with_nul_unchecked(slice::from_raw_parts(ptr as *const u8, len + 1))
+            },
+        }
     }
 }
 
+// The contents are never modified, and owned strings can be freed from any thread. Borrowed
+// strings have to stay valid while the `GString` is alive anyway, see `new_borrowed()`.
+unsafe impl Send for GString {}
+unsafe impl Sync for GString {}
+
 impl Drop for GString {
     fn drop(&mut self) {
-        if let GString::Owned(ptr, _len) = self {
+        if let Inner::Foreign(ptr, _len) = self.0 {
             unsafe {
-                glib_sys::g_free(*ptr as *mut _);
+                glib_sys::g_free(ptr as *mut _);
             }
         }
     }
 }
 
+impl Clone for GString {
+    fn clone(&self) -> GString {
+        GString(Inner::Native(Some(self.as_c_str().to_owned())))
+    }
+}
+
+impl fmt::Debug for GString {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        <str as fmt::Debug>::fmt(self.as_str(), f)
+    }
+}
+
 impl fmt::Display for GString {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}", self.as_str())
+        f.write_str(self.as_str())
     }
 }
 
 impl hash::Hash for GString {
     fn hash<H: hash::Hasher>(&self, state: &mut H) {
-        let bytes = match self {
-            GString::Borrowed(ptr, length) => unsafe {
-                slice::from_raw_parts(*ptr as *const u8, length + 1)
-            },
-            GString::Owned(ptr, length) => unsafe {
-                slice::from_raw_parts(*ptr as *const u8, length + 1)
-            },
-            GString::ForeignOwned(cstring) => cstring
-                .as_ref()
-                .expect("ForeignOwned shouldn't be empty")
-                .as_bytes(),
-        };
-        state.write(bytes);
+        // Has to be the same as for `str` because of the `Borrow<str>` impl
+        self.as_str().hash(state)
     }
 }
 
@@ -152,7 +217,7 @@ impl PartialEq<GString> for str {
 
 impl PartialOrd<GString> for String {
     fn partial_cmp(&self, other: &GString) -> Option<Ordering> {
-        Some(self.cmp(&String::from(other.as_str())))
+        Some(self.as_str().cmp(other.as_str()))
     }
 }
 
@@ -188,6 +253,18 @@ impl AsRef<OsStr> for GString {
     }
 }
 
+impl AsRef<Path> for GString {
+    fn as_ref(&self) -> &Path {
+        Path::new(self.as_str())
+    }
+}
+
+impl AsRef<CStr> for GString {
+    fn as_ref(&self) -> &CStr {
+        self.as_c_str()
+    }
+}
+
 impl Deref for GString {
     type Target = str;
 
@@ -199,10 +276,10 @@ impl Deref for GString {
 impl From<GString> for String {
     #[inline]
     fn from(mut s: GString) -> Self {
-        if let GString::ForeignOwned(ref mut cstring) = s {
-            if let Ok(s) = cstring.take().expect("ForeignOwned shouldn't be empty").into_string() {
-                return s;
-            }
+        // Strings allocated by Rust can be reused, others have to be copied
+        if let Inner::Native(ref mut cstring) = s.0 {
+            let bytes = cstring.take().expect("Native shouldn't be empty").into_bytes();
+            return unsafe { String::from_utf8_unchecked(bytes) };
         }
         String::from(s.as_str())
     }
@@ -219,36 +296,36 @@ impl From<GString> for Box<str> {
 impl From<String> for GString {
     #[inline]
     fn from(s: String) -> Self {
-        s.into_bytes().into()
+        GString(Inner::Native(Some(CString::new(s).expect("CString::new failed"))))
     }
 }
 
 impl From<Box<str>> for GString {
     #[inline]
     fn from(s: Box<str>) -> Self {
-        s.as_bytes().to_vec().into()
+        String::from(s).into()
     }
 }
 
 impl<'a> From<&'a str> for GString {
     #[inline]
     fn from(s: &'a str) -> Self {
-        s.as_bytes().to_vec().into()
+        String::from(s).into()
     }
 }
 
 impl From<Vec<u8>> for GString {
     #[inline]
     fn from(s: Vec<u8>) -> Self {
-        let cstring = CString::new(s).expect("CString::new failed");
-        cstring.into()
+        String::from_utf8(s).expect("Invalid UTF-8").into()
     }
 }
 
 impl From<CString> for GString {
     #[inline]
     fn from(s: CString) -> Self {
-        GString::ForeignOwned(Some(s))
+        s.to_str().expect("Invalid UTF-8");
+        GString(Inner::Native(Some(s)))
     }
 }
 
@@ -340,7 +417,7 @@ impl<'a> ToGlibPtr<'a, *const c_char> for GString {
 
     #[inline]
     fn to_glib_none(&'a self) -> Stash<'a, *const c_char, Self> {
-        Stash(self.as_ptr() as *const _, self)
+        Stash(self.as_c_str().as_ptr(), self)
     }
 
     #[inline]
@@ -358,7 +435,7 @@ impl<'a> ToGlibPtr<'a, *mut c_char> for GString {
 
     #[inline]
     fn to_glib_none(&'a self) -> Stash<'a, *mut c_char, Self> {
-        Stash(self.as_ptr() as *mut _, self)
+        Stash(self.as_c_str().as_ptr() as *mut _, self)
     }
 
     #[inline]
@@ -394,8 +471,13 @@ impl<'a> FromValueOptional<'a> for GString {
         if val.is_null() {
             None
         } else {
+            // Values can be set from any C code, so don't rely on them being UTF-8 as with `&str`
             let ptr = gobject_sys::g_value_dup_string(val);
-            Some(GString::new(ptr))
+            if ptr.is_null() {
+                None
+            } else {
+                GString::try_new(ptr).ok()
+            }
         }
     }
 }
@@ -419,7 +501,12 @@ impl_from_glib_container_as_vec_string!(GString, *mut c_char);
 mod tests {
     use gstring::GString;
     use std::ffi::CString;
+    use std::os::raw::c_char;
+    use std::path::Path;
     use glib_sys;
+    use gobject_sys;
+    use translate::*;
+    use types::StaticType;
 
     #[test]
     fn test_gstring() {
@@ -479,6 +566,38 @@ mod tests {
         assert_eq!(s.as_str(), "foo");
     }
 
+    #[test]
+    fn test_from_glib() {
+        unsafe {
+            let ptr = glib_sys::g_strdup(b"foo\0".as_ptr() as *const _);
+            let gstring: GString = from_glib_full(ptr);
+            // Takes over the allocation instead of copying
+            assert_eq!(gstring.as_ptr(), ptr as *const u8);
+            assert_eq!(gstring, "foo");
+
+            let gstring: GString = from_glib_none(b"bar\0".as_ptr() as *const c_char);
+            assert_eq!(gstring, "bar");
+            assert_eq!(gstring.as_c_str().to_bytes_with_nul(), b"bar\0");
+            assert_eq!(String::from(gstring), "bar");
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_invalid_utf8() {
+        let _: GString = vec![0x66, 0xff].into();
+    }
+
+    #[test]
+    fn test_conversions() {
+        let gstring: GString = "foo/bar".into();
+        let path: &Path = gstring.as_ref();
+        assert_eq!(path, Path::new("foo/bar"));
+        assert_eq!(gstring.len(), 7);
+        assert_eq!(gstring.clone(), gstring);
+        assert_eq!(format!("{:?}", gstring), "\"foo/bar\"");
+    }
+
     #[test]
     fn test_hashmap() {
         use std::collections::HashMap;
@@ -490,5 +609,35 @@ mod tests {
         h.insert(gstring, 42);
         let gstring: GString = "foo".into();
         assert!(h.contains_key(&gstring));
+        assert!(h.contains_key("foo"));
+
+        unsafe {
+            let gstring = GString::new(glib_sys::g_strdup(b"foo\0".as_ptr() as *const _));
+            assert!(h.contains_key(&gstring));
+        }
+    }
+    #[test]
+    fn test_invalid_utf8() {
+        use value::{ToValue, Value};
+
+        unsafe {
+            let invalid = b"fo\xffo\0";
+            assert!(GString::try_new_borrowed(invalid.as_ptr() as *const c_char).is_err());
+            // The string is freed on errors
+            assert!(GString::try_new(glib_sys::g_strdup(invalid.as_ptr() as *const c_char)).is_err());
+
+            let gstring = GString::try_new(glib_sys::g_strdup(b"foo\0".as_ptr() as *const c_char)).unwrap();
+            assert_eq!(gstring, "foo");
+
+            let mut value = Value::from_type(String::static_type());
+            gobject_sys::g_value_set_string(value.to_glib_none_mut().0, invalid.as_ptr() as *const c_char);
+            assert_eq!(value.get::<GString>(), None);
+            assert_eq!("foo".to_value().get::<GString>().as_ref().map(|s| s.as_str()), Some("foo"));
+        }
+    }
+    #[test]
+    fn test_send_sync() {
+        fn is_send_sync<T: Send + Sync>() {}
+        is_send_sync::<GString>();
     }
 }
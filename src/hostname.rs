@@ -0,0 +1,94 @@
+// Copyright 2019, The Gtk-rs Project Developers.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+// Licensed under the MIT license, see the LICENSE file or <http://opensource.org/licenses/MIT>
+
+// Hostnames can come from untrusted sources, so instead of panicking on NUL characters like
+// other string conversions these functions treat such hostnames as invalid.
+
+use glib_sys;
+use translate::*;
+use GString;
+
+/// Returns `true` if `hostname` contains ASCII-encoded segments of an internationalized domain
+/// name, i.e. segments starting with `xn--`.
+pub fn hostname_is_ascii_encoded(hostname: &str) -> bool {
+    !hostname.contains('\0')
+        && unsafe { from_glib(glib_sys::g_hostname_is_ascii_encoded(hostname.to_glib_none().0)) }
+}
+
+/// Returns `true` if `hostname` is an IPv4 or IPv6 address.
+pub fn hostname_is_ip_address(hostname: &str) -> bool {
+    !hostname.contains('\0')
+        && unsafe { from_glib(glib_sys::g_hostname_is_ip_address(hostname.to_glib_none().0)) }
+}
+
+/// Returns `true` if `hostname` contains non-ASCII characters, i.e. if it has to be converted
+/// with [`hostname_to_ascii()`] before use with protocols that require ASCII.
+///
+/// [`hostname_to_ascii()`]: fn.hostname_to_ascii.html
+pub fn hostname_is_non_ascii(hostname: &str) -> bool {
+    !hostname.contains('\0')
+        && unsafe { from_glib(glib_sys::g_hostname_is_non_ascii(hostname.to_glib_none().0)) }
+}
+
+/// Converts `hostname` into its canonical ASCII form, encoding internationalized domain names
+/// with punycode.
+///
+/// Returns `None` if `hostname` is not valid.
+pub fn hostname_to_ascii(hostname: &str) -> Option<GString> {
+    if hostname.contains('\0') {
+        return None;
+    }
+
+    unsafe { from_glib_full(glib_sys::g_hostname_to_ascii(hostname.to_glib_none().0)) }
+}
+
+/// Converts `hostname` into its canonical Unicode form, decoding punycode segments.
+///
+/// Returns `None` if `hostname` is not valid.
+pub fn hostname_to_unicode(hostname: &str) -> Option<GString> {
+    if hostname.contains('\0') {
+        return None;
+    }
+
+    unsafe { from_glib_full(glib_sys::g_hostname_to_unicode(hostname.to_glib_none().0)) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_idn() {
+        let unicode = "b\u{fc}cher.example";
+        let ascii = "xn--bcher-kva.example";
+
+        assert!(hostname_is_non_ascii(unicode));
+        assert!(!hostname_is_non_ascii(ascii));
+        assert!(hostname_is_ascii_encoded(ascii));
+        assert!(!hostname_is_ascii_encoded(unicode));
+        assert!(!hostname_is_ascii_encoded("example.com"));
+
+        assert_eq!(hostname_to_ascii(unicode).unwrap(), ascii);
+        assert_eq!(hostname_to_unicode(ascii).unwrap(), unicode);
+        assert_eq!(hostname_to_ascii("example.com").unwrap(), "example.com");
+    }
+
+    #[test]
+    fn test_ip_address() {
+        assert!(hostname_is_ip_address("127.0.0.1"));
+        assert!(hostname_is_ip_address("::1"));
+        assert!(hostname_is_ip_address("fe80::1"));
+        assert!(!hostname_is_ip_address("localhost"));
+        assert!(!hostname_is_ip_address("256.0.0.1"));
+    }
+
+    #[test]
+    fn test_nul() {
+        assert!(!hostname_is_ip_address("127.0.0.1\0"));
+        assert!(!hostname_is_ascii_encoded("xn--bcher-kva\0"));
+        assert!(!hostname_is_non_ascii("b\u{fc}cher\0"));
+        assert!(hostname_to_ascii("a\0b").is_none());
+        assert!(hostname_to_unicode("a\0b").is_none());
+    }
+}
@@ -37,8 +37,8 @@ impl KeyFile {
                                                         &mut full_path,
                                                         flags.to_glib(), &mut error);
             if error.is_null() {
-                let path: GString = from_glib_full(full_path);
-                Ok(path::PathBuf::from(&path))
+                // Paths are not necessarily UTF-8
+                Ok(from_glib_full(full_path))
             } else {
                 Err(from_glib_full(error))
             }
@@ -57,8 +57,8 @@ impl KeyFile {
                                                    &mut full_path,
                                                    flags.to_glib(), &mut error);
             if error.is_null() {
-                let path: GString = from_glib_full(full_path);
-                Ok(path::PathBuf::from(&path))
+                // Paths are not necessarily UTF-8
+                Ok(from_glib_full(full_path))
             } else {
                 Err(from_glib_full(error))
             }
@@ -83,6 +83,7 @@ extern crate bitflags;
 #[macro_use]
 extern crate lazy_static;
 extern crate libc;
+extern crate glib_macros;
 
 #[doc(hidden)]
 pub extern crate glib_sys;
@@ -92,6 +93,13 @@ pub extern crate gobject_sys;
 #[cfg(feature = "futures")]
 pub extern crate futures;
 
+#[cfg(feature = "serde")]
+#[macro_use]
+extern crate serde;
+#[cfg(all(test, feature = "serde"))]
+#[macro_use]
+extern crate serde_derive;
+
 use std::ffi::CStr;
 pub use bytes::Bytes;
 pub use string::String;
@@ -100,10 +108,13 @@ pub use error::{Error, BoolError};
 pub use file_error::FileError;
 pub use object::{
     Cast,
+    DataKey,
     IsA,
     IsClassFor,
+    IsDerivable,
     Object,
     ObjectExt,
+    ObjectBuilder,
     ObjectClass,
     ObjectType,
     InitiallyUnowned,
@@ -112,10 +123,17 @@ pub use object::{
     SendWeakRef,
 };
 pub use signal::{
+    EmissionHookId,
+    SignalBlockGuard,
+    SignalHandlerGuard,
     SignalHandlerId,
+    SignalQuery,
+    signal_add_emission_hook,
+    signal_arg,
     signal_handler_block,
     signal_handler_disconnect,
     signal_handler_unblock,
+    signal_remove_emission_hook,
     signal_stop_emission_by_name
 };
 
@@ -124,6 +142,7 @@ pub use types::{
     Type,
 };
 pub use value::{
+    GetError,
     ToValue,
     ToSendValue,
     TypedValue,
@@ -131,10 +150,13 @@ pub use value::{
     Value,
 };
 pub use variant::{
+    FixedSizeVariantType,
+    FromVariant,
     StaticVariantType,
     ToVariant,
     Variant,
 };
+pub use glib_macros::Variant;
 pub use variant_type::{
     VariantTy,
     VariantType,
@@ -152,6 +174,8 @@ pub use enums::{
     FlagsBuilder,
 };
 
+#[macro_use]
+mod log;
 #[macro_use]
 pub mod wrapper;
 #[macro_use]
@@ -174,19 +198,63 @@ mod auto;
 pub use gobject::*;
 mod gobject;
 
+mod base64;
+pub use base64::{Base64Decoder, Base64Encoder};
 mod bytes;
 mod string;
 pub mod char;
 pub use char::*;
 mod checksum;
+#[macro_use]
+pub mod clone;
+pub use clone::{Downgrade, Upgrade};
+mod collate;
+pub use collate::{sort_by_collate_key, sort_by_filename_collate_key, sort_filenames, utf8_collate, CollationKey};
+mod convert;
+pub use convert::{convert, convert_with_fallback, locale_from_utf8, locale_to_utf8, CvtError, IConv};
+mod strfuncs;
+pub use strfuncs::{
+    ascii_digit_value, ascii_dtostr, ascii_formatd, ascii_strcasecmp, ascii_strdown, ascii_strncasecmp,
+    ascii_strtod, ascii_strtoll, ascii_strtoull, ascii_strup, ascii_xdigit_value, str_match_string, str_to_ascii,
+    str_tokenize_and_fold, strcompress, strescape,
+};
+mod strv;
+pub use strv::StrV;
+mod unichar;
+pub use unichar::{
+    unichar_digit_value, unichar_isalnum, unichar_isalpha, unichar_iscntrl, unichar_isdefined,
+    unichar_isdigit, unichar_isgraph, unichar_islower, unichar_ismark, unichar_isprint, unichar_ispunct,
+    unichar_isspace, unichar_istitle, unichar_isupper, unichar_iswide, unichar_iswide_cjk,
+    unichar_isxdigit, unichar_iszerowidth, unichar_to_utf8, unichar_tolower, unichar_totitle,
+    unichar_toupper, unichar_validate, unichar_xdigit_value,
+};
+mod utf8;
+pub use utf8::{c_str_to_str_lossy, utf8_casefold, utf8_normalize, utf8_validate};
+#[cfg(any(feature = "v2_52", feature = "dox"))]
+pub use utf8::utf8_make_valid;
+mod hostname;
+pub use hostname::{
+    hostname_is_ascii_encoded, hostname_is_ip_address, hostname_is_non_ascii, hostname_to_ascii,
+    hostname_to_unicode,
+};
+mod uri;
+pub use uri::{uri_escape_string, uri_list_extract_uris, uri_parse_scheme, uri_unescape_segment, uri_unescape_string};
 pub mod closure;
 mod enums;
 mod file_error;
 mod key_file;
+pub use log::*;
 pub mod prelude;
+pub mod profiler;
 pub mod signal;
+pub mod sync;
 pub mod source;
 pub use source::*;
+pub mod thread;
+mod task_graph;
+pub use task_graph::{TaskEvent, TaskGraph, TaskId};
+mod tween;
+pub use tween::{Easing, Tween};
 mod time_val;
 #[macro_use]
 pub mod translate;
@@ -197,24 +265,67 @@ mod utils;
 pub use utils::*;
 pub mod value;
 pub mod variant;
+mod variant_dict;
+pub use variant_dict::VariantDict;
+mod variant_iter;
+pub use variant_iter::{VariantIter, VariantTypedIter};
+#[cfg(feature = "serde")]
+pub mod variant_serde;
 mod variant_type;
 mod main_context;
+mod main_loop;
+#[cfg(any(unix, feature = "dox"))]
+mod external_loop;
+#[cfg(any(unix, feature = "dox"))]
+pub use external_loop::ExternalLoop;
+#[cfg(any(windows, feature = "dox"))]
+mod win32_message_source;
+mod source_impl;
+pub use source_impl::SourceImpl;
+mod starvation_monitor;
+pub use starvation_monitor::{PriorityBand, StarvationMonitor, StarvationWarning};
+mod watchdog;
+pub use watchdog::Watchdog;
+mod main_context_setup;
+pub use main_context_setup::{MainContextSetup, SourceGroup};
 mod main_context_channel;
-pub use main_context_channel::{Sender, SyncSender, Receiver};
+pub use main_context_channel::{
+    BridgeReceiver,
+    ChannelStats,
+    Receiver,
+    SendFailurePolicy,
+    Sender,
+    SyncSender,
+    bridge_receiver,
+    get_send_failure_policy,
+    set_send_failure_policy,
+};
 mod date;
 pub use date::Date;
 mod value_array;
 pub use value_array::ValueArray;
 mod param_spec;
 pub use param_spec::ParamSpec;
+mod markup;
+pub use markup::{MarkupContext, MarkupParseContext, MarkupParser};
+mod pattern_spec;
+pub use pattern_spec::PatternSpec;
 mod quark;
 pub use quark::Quark;
+mod regex;
+pub use regex::{Captures, MatchInfo, Matches, Regex};
+mod type_module;
+pub use type_module::{TypeModule, TypeModuleClass};
+mod binding_group;
+pub use binding_group::BindingGroup;
 
 pub mod send_unique;
 pub use send_unique::{
     SendUniqueCell,
     SendUnique,
 };
+mod thread_guard;
+pub use thread_guard::{is_main_thread, FragileSend, MainThreadOnly, ThreadGuard, ThreadSafe};
 
 #[cfg(feature="futures")]
 mod main_context_futures;
@@ -222,6 +333,8 @@ mod main_context_futures;
 mod source_futures;
 #[cfg(feature="futures")]
 pub use source_futures::*;
+#[cfg(feature="futures")]
+pub mod asyncs;
 
 // Actual thread IDs can be reused by the OS once the old thread finished.
 // This works around it by using our own counter for threads.
@@ -0,0 +1,230 @@
+// Copyright 2019, The Gtk-rs Project Developers.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+// Licensed under the MIT license, see the LICENSE file or <http://opensource.org/licenses/MIT>
+
+//! Structured logging via `GLib`'s log writers.
+
+use glib_sys;
+#[cfg(any(feature = "v2_50", feature = "dox"))]
+use std::ffi::CString;
+use translate::*;
+
+/// Log level of a log message.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum LogLevel {
+    Error,
+    Critical,
+    Warning,
+    Message,
+    Info,
+    Debug,
+}
+
+impl LogLevel {
+    /// Returns the syslog priority corresponding to the log level, as used for the `PRIORITY`
+    /// field of structured log messages.
+    pub fn priority(self) -> &'static str {
+        match self {
+            LogLevel::Error => "3",
+            LogLevel::Critical | LogLevel::Warning => "4",
+            LogLevel::Message => "5",
+            LogLevel::Info => "6",
+            LogLevel::Debug => "7",
+        }
+    }
+}
+
+#[doc(hidden)]
+impl ToGlib for LogLevel {
+    type GlibType = glib_sys::GLogLevelFlags;
+
+    fn to_glib(&self) -> glib_sys::GLogLevelFlags {
+        match *self {
+            LogLevel::Error => glib_sys::G_LOG_LEVEL_ERROR,
+            LogLevel::Critical => glib_sys::G_LOG_LEVEL_CRITICAL,
+            LogLevel::Warning => glib_sys::G_LOG_LEVEL_WARNING,
+            LogLevel::Message => glib_sys::G_LOG_LEVEL_MESSAGE,
+            LogLevel::Info => glib_sys::G_LOG_LEVEL_INFO,
+            LogLevel::Debug => glib_sys::G_LOG_LEVEL_DEBUG,
+        }
+    }
+}
+
+/// A key-value pair of a structured log message.
+///
+/// Keys should be uppercase ASCII, see the `GLib` documentation of `g_log_structured()` for the
+/// keys that have a special meaning.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LogField<'a> {
+    key: &'a str,
+    value: &'a [u8],
+}
+
+impl<'a> LogField<'a> {
+    /// Creates a field with a string value.
+    pub fn new(key: &'a str, value: &'a str) -> LogField<'a> {
+        LogField {
+            key,
+            value: value.as_bytes(),
+        }
+    }
+
+    /// Creates a field with a binary value.
+    pub fn new_binary(key: &'a str, value: &'a [u8]) -> LogField<'a> {
+        LogField { key, value }
+    }
+
+    /// Returns the key of the field.
+    pub fn key(&self) -> &str {
+        self.key
+    }
+
+    /// Returns the value of the field, which is not necessarily valid UTF-8.
+    pub fn value(&self) -> &[u8] {
+        self.value
+    }
+}
+
+/// Logs a structured message with the given `fields` via the installed log writer.
+///
+/// A `PRIORITY` field based on `log_level` is added if `fields` does not contain one already.
+/// Messages should contain at least a `MESSAGE` field.
+///
+/// See also the [`glib_log!`] macro, which also fills in the code location fields.
+///
+/// [`glib_log!`]: macro.glib_log.html
+#[cfg(any(feature = "v2_50", feature = "dox"))]
+pub fn log_structured_array(log_level: LogLevel, fields: &[LogField]) {
+    let keys = fields
+        .iter()
+        .map(|field| CString::new(field.key).expect("Log field key with NUL byte"))
+        .collect::<Vec<_>>();
+    let priority_key = CString::new("PRIORITY").unwrap();
+
+    let mut glib_fields = keys
+        .iter()
+        .zip(fields)
+        .map(|(key, field)| glib_sys::GLogField {
+            key: key.as_ptr(),
+            value: field.value.as_ptr() as glib_sys::gconstpointer,
+            length: field.value.len() as isize,
+        })
+        .collect::<Vec<_>>();
+
+    if !fields.iter().any(|field| field.key == "PRIORITY") {
+        let priority = log_level.priority();
+        glib_fields.push(glib_sys::GLogField {
+            key: priority_key.as_ptr(),
+            value: priority.as_ptr() as glib_sys::gconstpointer,
+            length: priority.len() as isize,
+        });
+    }
+
+    unsafe {
+        glib_sys::g_log_structured_array(log_level.to_glib(), glib_fields.as_ptr(), glib_fields.len());
+    }
+}
+
+/// Logs a structured message with code location fields.
+///
+/// The `GLIB_DOMAIN`, `MESSAGE`, `CODE_FILE`, `CODE_LINE` and `CODE_FUNC` fields are filled in
+/// automatically. Additional fields can be passed as `KEY = value` pairs, where the value can be
+/// anything implementing `Display`.
+///
+/// ```ignore
+/// glib_log!("my-app", glib::LogLevel::Warning, "Failed to load config", CONFIG_PATH = path);
+/// glib_log!("my-app", glib::LogLevel::Info, format!("Loaded {} items", n));
+/// ```
+#[cfg(any(feature = "v2_50", feature = "dox"))]
+#[macro_export]
+macro_rules! glib_log {
+    ($domain:expr, $level:expr, $msg:expr $(, $key:ident = $value:expr)* $(,)*) => {{
+        let message: &str = &$msg;
+        let line = line!().to_string();
+        $(
+            #[allow(non_snake_case)]
+            let $key = $value.to_string();
+        )*
+        $crate::log_structured_array(
+            $level,
+            &[
+                $crate::LogField::new("GLIB_DOMAIN", $domain),
+                $crate::LogField::new("MESSAGE", message),
+                $crate::LogField::new("CODE_FILE", file!()),
+                $crate::LogField::new("CODE_LINE", &line),
+                $crate::LogField::new("CODE_FUNC", module_path!()),
+                $(
+                    $crate::LogField::new(stringify!($key), &$key),
+                )*
+            ],
+        );
+    }};
+}
+
+/// Logs `message` in `log_domain` with the classic, unstructured `g_log()` API.
+///
+/// Different to [`log_structured_array()`] this is available with all `GLib` versions. See also
+/// the [`g_critical!`] macro.
+///
+/// [`log_structured_array()`]: fn.log_structured_array.html
+/// [`g_critical!`]: macro.g_critical.html
+pub fn log_message(log_domain: Option<&str>, log_level: LogLevel, message: &str) {
+    unsafe {
+        glib_sys::g_log(
+            log_domain.to_glib_none().0,
+            log_level.to_glib(),
+            b"%s\0".as_ptr() as *const _,
+            message.to_glib_none().0,
+        );
+    }
+}
+
+/// Logs a critical warning, formatting the message like `format!`.
+///
+/// Critical warnings report programming errors that can be recovered from, e.g. in callbacks
+/// that are called from C code and must not panic. The first argument is the log domain.
+///
+/// ```
+/// # #[macro_use] extern crate glib;
+/// # fn main() {
+/// g_critical!("my-app", "Invalid value {}", 5);
+/// # }
+/// ```
+#[macro_export]
+macro_rules! g_critical {
+    ($domain:expr, $($arg:tt)+) => {
+        $crate::log_message(Some($domain), $crate::LogLevel::Critical, &format!($($arg)+))
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_log_level() {
+        assert_eq!(LogLevel::Warning.to_glib(), glib_sys::G_LOG_LEVEL_WARNING);
+        assert_eq!(LogLevel::Debug.priority(), "7");
+        assert!(LogLevel::Error < LogLevel::Debug);
+    }
+
+    #[test]
+    fn test_log_message() {
+        log_message(Some("glib-rs-test"), LogLevel::Debug, "test message with %s");
+        log_message(None, LogLevel::Debug, "test message");
+    }
+
+    #[cfg(feature = "v2_50")]
+    #[test]
+    fn test_log_macro() {
+        let count = 3;
+        glib_log!("glib-rs-test", LogLevel::Debug, "test message");
+        glib_log!(
+            "glib-rs-test",
+            LogLevel::Debug,
+            format!("{} items", count),
+            ITEM_COUNT = count,
+            TEST_NAME = "test_log_macro",
+        );
+    }
+}
@@ -2,20 +2,23 @@
 // See the COPYRIGHT file at the top-level directory of this distribution.
 // Licensed under the MIT license, see the LICENSE file or <http://opensource.org/licenses/MIT>
 
-use get_thread_id;
 use glib_sys;
 use std::cell::RefCell;
 use std::collections::VecDeque;
-use std::mem;
-use std::ptr;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::mpsc;
 use std::sync::{Arc, Condvar, Mutex};
-use translate::{mut_override, FromGlibPtrFull, FromGlibPtrNone, ToGlib, ToGlibPtr};
+use std::thread;
+use std::time::{Duration, Instant};
+use translate::{FromGlibPtrNone, ToGlibPtr};
 use Continue;
 use MainContext;
 use Priority;
 use Source;
 use SourceId;
+use SourceImpl;
+use ThreadGuard;
+use ThreadSafe;
 
 #[derive(Debug)]
 enum ChannelSourceState {
@@ -31,6 +34,11 @@ unsafe impl Sync for ChannelSourceState {}
 struct ChannelInner<T> {
     queue: VecDeque<T>,
     source: ChannelSourceState,
+    // Tickets of blocking senders of bounded channels, used to let them
+    // enqueue their items in the order in which they started waiting
+    next_ticket: u64,
+    serving_ticket: u64,
+    stats: Option<ChannelStats>,
 }
 
 impl<T> ChannelInner<T> {
@@ -58,6 +66,30 @@ impl<T> ChannelInner<T> {
         }
     }
 
+    fn push(&mut self, t: T) {
+        self.queue.push_back(t);
+
+        if let Some(ref stats) = self.stats {
+            let mut stats = stats.0.lock().unwrap();
+            if self.queue.len() > stats.max_queue_depth {
+                stats.max_queue_depth = self.queue.len();
+            }
+        }
+    }
+
+    fn set_destroyed(&mut self) {
+        self.source = ChannelSourceState::Destroyed;
+
+        // All items that are still queued now will never be dispatched
+        if let Some(ref stats) = self.stats {
+            stats.0.lock().unwrap().dropped += self.queue.len() as u64;
+        }
+    }
+
+    fn has_waiting_senders(&self) -> bool {
+        self.next_ticket != self.serving_ticket
+    }
+
     fn source(&self) -> Option<Source> {
         match self.source {
             // Receiver exists and is not destroyed yet
@@ -92,6 +124,9 @@ impl<T> Channel<T> {
             Mutex::new(ChannelInner {
                 queue: VecDeque::new(),
                 source: ChannelSourceState::NotAttached,
+                next_ticket: 0,
+                serving_ticket: 0,
+                stats: None,
             }),
             bound.map(|bound| ChannelBound {
                 bound,
@@ -100,6 +135,10 @@ impl<T> Channel<T> {
         )))
     }
 
+    fn receiver_disconnected(&self) -> bool {
+        (self.0).0.lock().unwrap().receiver_disconnected()
+    }
+
     fn send(&self, t: T) -> Result<(), mpsc::SendError<T>> {
         let mut inner = (self.0).0.lock().unwrap();
 
@@ -109,13 +148,27 @@ impl<T> Channel<T> {
         // A special case here is a bound of 0: the queue must be empty for accepting
         // new data and then we will again wait later for the data to be actually taken
         // out
+        //
+        // Waiting senders are served in FIFO order: each one draws a ticket and only the
+        // sender whose ticket is currently served may store its item. As the condition
+        // variable can't wake up a specific thread, all waiters are notified and the ones
+        // that are not next in line go back to sleep.
         if let Some(ChannelBound { bound, ref cond }) = (self.0).1 {
-            while inner.queue.len() >= bound
-                && !inner.queue.is_empty()
+            let ticket = inner.next_ticket;
+            inner.next_ticket = inner.next_ticket.wrapping_add(1);
+
+            while (inner.serving_ticket != ticket
+                || (inner.queue.len() >= bound && !inner.queue.is_empty()))
                 && !inner.receiver_disconnected()
             {
                 inner = cond.wait(inner).unwrap();
             }
+
+            // Let the next sender in line check for free space
+            inner.serving_ticket = inner.serving_ticket.wrapping_add(1);
+            if inner.has_waiting_senders() {
+                cond.notify_all();
+            }
         }
 
         // Error out directly if the receiver is disconnected
@@ -124,7 +177,7 @@ impl<T> Channel<T> {
         }
 
         // Store the item on our queue
-        inner.queue.push_back(t);
+        inner.push(t);
 
         // and then wake up the GSource
         inner.set_ready_time(0);
@@ -157,8 +210,9 @@ impl<T> Channel<T> {
             .as_ref()
             .expect("called try_send() on an unbounded channel");
 
-        // Check if the queue is full and handle the special case of a 0 bound
-        if inner.queue.len() >= *bound && !inner.queue.is_empty() {
+        // Check if the queue is full and handle the special case of a 0 bound. Blocked
+        // senders are first in line for any free space
+        if (inner.queue.len() >= *bound && !inner.queue.is_empty()) || inner.has_waiting_senders() {
             return Err(mpsc::TrySendError::Full(t));
         }
 
@@ -168,7 +222,7 @@ impl<T> Channel<T> {
         }
 
         // Store the item on our queue
-        inner.queue.push_back(t);
+        inner.push(t);
 
         // and then wake up the GSource
         inner.set_ready_time(0);
@@ -198,9 +252,16 @@ impl<T> Channel<T> {
 
         // Pop item if we have any
         if let Some(item) = inner.queue.pop_front() {
-            // Wake up a sender that is currently waiting, if any
+            if let Some(ref stats) = inner.stats {
+                let mut stats = stats.0.lock().unwrap();
+                stats.dispatched += 1;
+                stats.last_dispatch = Some(Instant::now());
+            }
+
+            // Wake up the senders that are currently waiting, if any. Only the one that is
+            // next in line will continue
             if let Some(ChannelBound { ref cond, .. }) = (self.0).1 {
-                cond.notify_one();
+                cond.notify_all();
             }
             return Ok(item);
         }
@@ -215,103 +276,101 @@ impl<T> Channel<T> {
     }
 }
 
-#[repr(C)]
-struct ChannelSource<T, F: FnMut(T) -> Continue + 'static> {
-    source: glib_sys::GSource,
-    thread_id: usize,
-    source_funcs: Option<Box<glib_sys::GSourceFuncs>>,
-    channel: Option<Channel<T>>,
-    callback: Option<RefCell<F>>,
+#[derive(Debug, Default)]
+struct ChannelStatsData {
+    dispatched: u64,
+    dropped: u64,
+    max_queue_depth: usize,
+    last_dispatch: Option<Instant>,
 }
 
-unsafe extern "C" fn prepare<T>(
-    source: *mut glib_sys::GSource,
-    timeout: *mut i32,
-) -> glib_sys::gboolean {
-    *timeout = -1;
+/// Statistics of a main context channel.
+///
+/// See [`Receiver::attach_with_stats()`].
+///
+/// [`Receiver::attach_with_stats()`]: struct.Receiver.html#method.attach_with_stats
+#[derive(Clone, Debug)]
+pub struct ChannelStats(Arc<Mutex<ChannelStatsData>>);
 
-    // We're always ready when the ready time was set to 0. There
-    // will be at least one item or the senders are disconnected now
-    if glib_sys::g_source_get_ready_time(source) == 0 {
-        glib_sys::GTRUE
-    } else {
-        glib_sys::GFALSE
+impl ChannelStats {
+    /// Number of items that were passed to the receiver's closure.
+    pub fn dispatched(&self) -> u64 {
+        self.0.lock().unwrap().dispatched
     }
-}
 
-unsafe extern "C" fn check<T>(source: *mut glib_sys::GSource) -> glib_sys::gboolean {
-    // We're always ready when the ready time was set to 0. There
-    // will be at least one item or the senders are disconnected now
-    if glib_sys::g_source_get_ready_time(source) == 0 {
-        glib_sys::GTRUE
-    } else {
-        glib_sys::GFALSE
+    /// Number of items that were dropped without being dispatched because the receiver was
+    /// destroyed.
+    pub fn dropped(&self) -> u64 {
+        self.0.lock().unwrap().dropped
     }
-}
 
-unsafe extern "C" fn dispatch<T, F: FnMut(T) -> Continue + 'static>(
-    source: *mut glib_sys::GSource,
-    callback: glib_sys::GSourceFunc,
-    _user_data: glib_sys::gpointer,
-) -> glib_sys::gboolean {
-    let source = &mut *(source as *mut ChannelSource<T, F>);
-    assert!(callback.is_none());
-
-    glib_sys::g_source_set_ready_time(&mut source.source, -1);
-
-    // Check the thread to ensure we're only ever called from the same thread
-    assert_eq!(
-        get_thread_id(),
-        source.thread_id,
-        "Source dispatched on a different thread than before"
-    );
+    /// Maximum number of items that were queued in the channel at once.
+    pub fn max_queue_depth(&self) -> usize {
+        self.0.lock().unwrap().max_queue_depth
+    }
 
-    // Now iterate over all items that we currently have in the channel until it is
-    // empty again. If all senders are disconnected at some point we remove the GSource
-    // from the main context it was attached to as it will never ever be called again.
-    let channel = source
-        .channel
-        .as_ref()
-        .expect("ChannelSource without Channel");
-    loop {
-        match channel.try_recv() {
-            Err(mpsc::TryRecvError::Empty) => break,
-            Err(mpsc::TryRecvError::Disconnected) => return glib_sys::G_SOURCE_REMOVE,
-            Ok(item) => {
-                let callback = source
-                    .callback
-                    .as_mut()
-                    .expect("ChannelSource called before Receiver was attached");
-                if (&mut *callback.borrow_mut())(item) == Continue(false) {
-                    return glib_sys::G_SOURCE_REMOVE;
-                }
-            }
-        }
+    /// Time of the last dispatched item, if any.
+    pub fn last_dispatch(&self) -> Option<Instant> {
+        self.0.lock().unwrap().last_dispatch
     }
+}
 
-    glib_sys::G_SOURCE_CONTINUE
+struct ChannelSource<T, F: FnMut(T) -> Continue + 'static> {
+    channel: Channel<T>,
+    callback: ThreadGuard<RefCell<F>>,
 }
 
-unsafe extern "C" fn finalize<T, F: FnMut(T) -> Continue + 'static>(
-    source: *mut glib_sys::GSource,
-) {
-    let source = &mut *(source as *mut ChannelSource<T, F>);
+// The callback is only ever called from the thread the receiver was attached on, which is
+// ensured by its ThreadGuard. SourceImpl requires Send because the source can be finalized
+// from any thread, which was always the case for channel receivers, and the items are only
+// ever taken out of the channel in dispatch().
+unsafe impl<T, F: FnMut(T) -> Continue + 'static> Send for ChannelSource<T, F> {}
+
+impl<T: 'static, F: FnMut(T) -> Continue + 'static> SourceImpl for ChannelSource<T, F> {
+    fn prepare(&self, source: &Source) -> (bool, Option<u32>) {
+        // We're always ready when the ready time was set to 0. There
+        // will be at least one item or the senders are disconnected now
+        (source.get_ready_time() == 0, None)
+    }
 
-    // Drop all memory we own by taking it out of the Options
-    let channel = source.channel.take().expect("Receiver without channel");
+    fn check(&self, source: &Source) -> bool {
+        source.get_ready_time() == 0
+    }
 
-    {
+    fn dispatch(&self, source: &Source) -> Continue {
+        source.set_ready_time(-1);
+
+        // Panics if we're not called from the thread the receiver was attached on
+        let callback = self.callback.get_ref();
+
+        // Now iterate over all items that we currently have in the channel until it is
+        // empty again. If all senders are disconnected at some point we remove the GSource
+        // from the main context it was attached to as it will never ever be called again.
+        loop {
+            match self.channel.try_recv() {
+                Err(mpsc::TryRecvError::Empty) => break,
+                Err(mpsc::TryRecvError::Disconnected) => return Continue(false),
+                Ok(item) => {
+                    let mut callback = callback.borrow_mut();
+                    if (&mut *callback)(item) == Continue(false) {
+                        return Continue(false);
+                    }
+                }
+            }
+        }
+
+        Continue(true)
+    }
+
+    fn finalize(&self) {
         // Set the source inside the channel to None so that all senders know that there
         // is no receiver left and wake up the condition variable if any
-        let mut inner = (channel.0).0.lock().unwrap();
-        inner.source = ChannelSourceState::Destroyed;
-        if let Some(ChannelBound { ref cond, .. }) = (channel.0).1 {
+        let mut inner = (self.channel.0).0.lock().unwrap();
+        inner.set_destroyed();
+        if let Some(ChannelBound { ref cond, .. }) = (self.channel.0).1 {
             cond.notify_all();
         }
     }
-
-    let _ = source.callback.take();
-    let _ = source.source_funcs.take();
 }
 
 /// A `Sender` that can be used to send items to the corresponding main context receiver.
@@ -324,11 +383,108 @@ unsafe extern "C" fn finalize<T, F: FnMut(T) -> Continue + 'static>(
 #[derive(Clone, Debug)]
 pub struct Sender<T>(Option<Channel<T>>);
 
+unsafe impl<T: Send> ThreadSafe for Sender<T> {}
+
+/// What `send_or_warn()` does when the receiver of a channel is gone.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SendFailurePolicy {
+    /// Log a warning.
+    Warn,
+    /// Log a warning, and panic in debug builds.
+    PanicInDebug,
+    /// Panic.
+    Panic,
+}
+
+static SEND_FAILURE_POLICY: AtomicUsize = AtomicUsize::new(0);
+
+/// Sets the policy for failed `send_or_warn()` calls of all channels.
+///
+/// The default is `SendFailurePolicy::Warn`.
+pub fn set_send_failure_policy(policy: SendFailurePolicy) {
+    let policy = match policy {
+        SendFailurePolicy::Warn => 0,
+        SendFailurePolicy::PanicInDebug => 1,
+        SendFailurePolicy::Panic => 2,
+    };
+    SEND_FAILURE_POLICY.store(policy, Ordering::SeqCst);
+}
+
+/// Returns the policy for failed `send_or_warn()` calls of all channels.
+///
+/// See [`set_send_failure_policy()`].
+///
+/// [`set_send_failure_policy()`]: fn.set_send_failure_policy.html
+pub fn get_send_failure_policy() -> SendFailurePolicy {
+    match SEND_FAILURE_POLICY.load(Ordering::SeqCst) {
+        0 => SendFailurePolicy::Warn,
+        1 => SendFailurePolicy::PanicInDebug,
+        _ => SendFailurePolicy::Panic,
+    }
+}
+
+const SEND_FAILED_MESSAGE: &str = "Failed to send item to channel: receiver was dropped or its source destroyed";
+
+fn send_failed(domain: &str) {
+    handle_send_failure(get_send_failure_policy(), domain);
+}
+
+fn handle_send_failure(policy: SendFailurePolicy, domain: &str) {
+    if policy == SendFailurePolicy::Panic || (policy == SendFailurePolicy::PanicInDebug && cfg!(debug_assertions))
+    {
+        panic!("{}: {}", domain, SEND_FAILED_MESSAGE);
+    }
+
+    log_send_failure(domain);
+}
+
+#[cfg(any(feature = "v2_50", feature = "dox"))]
+fn log_send_failure(domain: &str) {
+    ::log_structured_array(
+        ::LogLevel::Warning,
+        &[
+            ::LogField::new("GLIB_DOMAIN", domain),
+            ::LogField::new("MESSAGE", SEND_FAILED_MESSAGE),
+            ::LogField::new("CODE_FUNC", "glib::Sender::send_or_warn"),
+        ],
+    );
+}
+
+#[cfg(not(any(feature = "v2_50", feature = "dox")))]
+fn log_send_failure(domain: &str) {
+    use translate::ToGlib;
+
+    unsafe {
+        glib_sys::g_log(
+            domain.to_glib_none().0,
+            ::LogLevel::Warning.to_glib(),
+            b"%s\0".as_ptr() as *const _,
+            SEND_FAILED_MESSAGE.to_glib_none().0,
+        );
+    }
+}
+
 impl<T> Sender<T> {
     /// Sends a value to the channel.
     pub fn send(&self, t: T) -> Result<(), mpsc::SendError<T>> {
         self.0.as_ref().expect("Sender with no channel").send(t)
     }
+
+    /// Sends a value to the channel and logs a warning in `domain` if the receiver is gone.
+    ///
+    /// Returns `true` if the value was sent. What happens on failure can be configured via
+    /// [`set_send_failure_policy()`].
+    ///
+    /// [`set_send_failure_policy()`]: fn.set_send_failure_policy.html
+    pub fn send_or_warn(&self, t: T, domain: &str) -> bool {
+        match self.send(t) {
+            Ok(()) => true,
+            Err(_) => {
+                send_failed(domain);
+                false
+            }
+        }
+    }
 }
 
 impl<T> Drop for Sender<T> {
@@ -366,6 +522,8 @@ impl<T> Drop for Sender<T> {
 #[derive(Clone, Debug)]
 pub struct SyncSender<T>(Option<Channel<T>>);
 
+unsafe impl<T: Send> ThreadSafe for SyncSender<T> {}
+
 impl<T> SyncSender<T> {
     /// Sends a value to the channel and blocks if the channel is full.
     pub fn send(&self, t: T) -> Result<(), mpsc::SendError<T>> {
@@ -376,6 +534,23 @@ impl<T> SyncSender<T> {
     pub fn try_send(&self, t: T) -> Result<(), mpsc::TrySendError<T>> {
         self.0.as_ref().expect("Sender with no channel").try_send(t)
     }
+
+    /// Sends a value to the channel, blocking if it is full, and logs a warning in `domain` if
+    /// the receiver is gone.
+    ///
+    /// Returns `true` if the value was sent. What happens on failure can be configured via
+    /// [`set_send_failure_policy()`].
+    ///
+    /// [`set_send_failure_policy()`]: fn.set_send_failure_policy.html
+    pub fn send_or_warn(&self, t: T, domain: &str) -> bool {
+        match self.send(t) {
+            Ok(()) => true,
+            Err(_) => {
+                send_failed(domain);
+                false
+            }
+        }
+    }
 }
 
 impl<T> Drop for SyncSender<T> {
@@ -423,7 +598,7 @@ impl<T> Drop for Receiver<T> {
         // If the receiver was never attached to a main context we need to let all the senders know
         if let Some(channel) = self.0.take() {
             let mut inner = (channel.0).0.lock().unwrap();
-            inner.source = ChannelSourceState::Destroyed;
+            inner.set_destroyed();
             if let Some(ChannelBound { ref cond, .. }) = (channel.0).1 {
                 cond.notify_all();
             }
@@ -431,7 +606,7 @@ impl<T> Drop for Receiver<T> {
     }
 }
 
-impl<T> Receiver<T> {
+impl<T: 'static> Receiver<T> {
     /// Attaches the receiver to the given `context` and calls `func` whenever an item is
     /// available on the channel.
     ///
@@ -446,63 +621,93 @@ impl<T> Receiver<T> {
         context: Option<&MainContext>,
         func: F,
     ) -> SourceId {
-        unsafe {
-            let channel = self.0.take().expect("Receiver without channel");
-
-            let source_funcs = Box::new(glib_sys::GSourceFuncs {
-                check: Some(check::<T>),
-                prepare: Some(prepare::<T>),
-                dispatch: Some(dispatch::<T, F>),
-                finalize: Some(finalize::<T, F>),
-                closure_callback: None,
-                closure_marshal: None,
-            });
+        let channel = self.0.take().expect("Receiver without channel");
 
-            let source = glib_sys::g_source_new(
-                mut_override(&*source_funcs),
-                mem::size_of::<ChannelSource<T, F>>() as u32,
-            ) as *mut ChannelSource<T, F>;
-            assert!(!source.is_null());
+        let source = Source::new_from_impl(ChannelSource {
+            channel: channel.clone(),
+            callback: ThreadGuard::new(RefCell::new(func)),
+        });
 
-            // Set up the GSource
-            {
-                let source = &mut *source;
-                let mut inner = (channel.0).0.lock().unwrap();
-
-                glib_sys::g_source_set_priority(mut_override(&source.source), self.1.to_glib());
-
-                // We're immediately ready if the queue is not empty or if no sender is left at this point
-                glib_sys::g_source_set_ready_time(
-                    mut_override(&source.source),
-                    if !inner.queue.is_empty() || Arc::strong_count(&channel.0) == 1 {
-                        0
-                    } else {
-                        -1
-                    },
-                );
-                inner.source = ChannelSourceState::Attached(&mut source.source);
-            }
+        // Set up the GSource
+        {
+            let mut inner = (channel.0).0.lock().unwrap();
 
-            // Store all our data inside our part of the GSource
-            {
-                let source = &mut *source;
-                source.thread_id = get_thread_id();
-                ptr::write(&mut source.channel, Some(channel));
-                ptr::write(&mut source.callback, Some(RefCell::new(func)));
-                ptr::write(&mut source.source_funcs, Some(source_funcs));
-            }
+            source.set_priority(self.1);
 
-            let source = Source::from_glib_full(mut_override(&(*source).source));
-            if let Some(context) = context {
-                assert!(context.is_owner());
-                source.attach(Some(context))
+            // We're immediately ready if the queue is not empty or if no sender is left at this point.
+            // The receiver's and the source's references are the only ones in that case.
+            source.set_ready_time(if !inner.queue.is_empty() || Arc::strong_count(&channel.0) == 2 {
+                0
             } else {
-                let context = MainContext::ref_thread_default();
-                assert!(context.is_owner());
-                source.attach(Some(&context))
-            }
+                -1
+            });
+            inner.source = ChannelSourceState::Attached(source.to_glib_none().0);
+        }
+        drop(channel);
+
+        if let Some(context) = context {
+            assert!(context.is_owner());
+            source.attach(Some(context))
+        } else {
+            let context = MainContext::ref_thread_default();
+            assert!(context.is_owner());
+            source.attach(Some(&context))
         }
     }
+
+    /// Same as `attach()` but additionally returns a handle for querying statistics of the
+    /// channel, e.g. for monitoring the health of long-lived pipelines.
+    ///
+    /// The statistics stay available after the channel is destroyed.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if called from a thread that is not the owner of the provided
+    /// `context`, or, if `None` is provided, of the thread default main context.
+    pub fn attach_with_stats<F: FnMut(T) -> Continue + 'static>(
+        self,
+        context: Option<&MainContext>,
+        func: F,
+    ) -> (SourceId, ChannelStats) {
+        let stats = ChannelStats(Arc::new(Mutex::new(ChannelStatsData::default())));
+
+        {
+            let channel = self.0.as_ref().expect("Receiver without channel");
+            let mut inner = (channel.0).0.lock().unwrap();
+            stats.0.lock().unwrap().max_queue_depth = inner.queue.len();
+            inner.stats = Some(stats.clone());
+        }
+
+        (self.attach(context, func), stats)
+    }
+
+    /// Same as `attach()` but for fallible callbacks.
+    ///
+    /// Whenever `func` returns an error, it is passed to `error_func`, which decides whether the
+    /// receiver stays attached by returning `Continue(true)` or is removed from the main context
+    /// by returning `Continue(false)`. This allows handling failures, e.g. by logging them or
+    /// triggering cleanup, instead of silently dropping them inside the callback.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if called from a thread that is not the owner of the provided
+    /// `context`, or, if `None` is provided, of the thread default main context.
+    pub fn attach_fallible<E, F, G>(
+        self,
+        context: Option<&MainContext>,
+        mut func: F,
+        mut error_func: G,
+    ) -> SourceId
+    where
+        E: 'static,
+        F: FnMut(T) -> Result<Continue, E> + 'static,
+        G: FnMut(E) -> Continue + 'static,
+    {
+        self.attach(context, move |item| match func(item) {
+            Ok(cont) => cont,
+            Err(err) => error_func(err),
+        })
+    }
 }
 
 impl MainContext {
@@ -519,6 +724,20 @@ impl MainContext {
     /// will fail.
     ///
     /// The returned `Sender` behaves the same as `std::sync::mpsc::Sender`.
+    ///
+    /// # Delivery guarantees
+    ///
+    /// * A successful `send()` happens-before the invocation of the closure for that item, i.e.
+    ///   all memory writes done by the sending thread before `send()` are visible inside the
+    ///   closure.
+    /// * Items sent from the same `Sender` are passed to the closure in the order they were sent.
+    ///   There is no ordering between items sent from different `Sender`s or threads.
+    /// * Every item for which `send()` returned `Ok` is passed to the closure unless the
+    ///   `Receiver` is dropped, the source is destroyed or the closure returns `Continue(false)`
+    ///   before the item is dispatched. In these cases the remaining items are dropped together
+    ///   with the channel.
+    /// * Once the `Receiver` is dropped or its source is destroyed, `send()` fails and returns
+    ///   the item back to the caller.
     pub fn channel<T>(priority: Priority) -> (Sender<T>, Receiver<T>) {
         let channel = Channel::new(None);
         let receiver = Receiver(Some(channel.clone()), priority);
@@ -541,6 +760,16 @@ impl MainContext {
     /// will fail.
     ///
     /// The returned `SyncSender` behaves the same as `std::sync::mpsc::SyncSender`.
+    ///
+    /// The same delivery guarantees as for [`MainContext::channel()`] apply. In addition, a
+    /// `SyncSender` blocked in `send()` is woken up once there is space in the channel or once the
+    /// `Receiver` is dropped or its source is destroyed.
+    ///
+    /// Blocked senders are served in the order in which they started waiting, so that a burst of
+    /// items from one producer can't starve others. `try_send()` reports the channel as full as
+    /// long as any sender is blocked.
+    ///
+    /// [`MainContext::channel()`]: struct.MainContext.html#method.channel
     pub fn sync_channel<T>(priority: Priority, bound: usize) -> (SyncSender<T>, Receiver<T>) {
         let channel = Channel::new(Some(bound));
         let receiver = Receiver(Some(channel.clone()), priority);
@@ -548,6 +777,110 @@ impl MainContext {
 
         (sender, receiver)
     }
+
+    /// Same as [`MainContext::channel()`] but uses the default priority for channels, see
+    /// [`set_default_source_priority()`].
+    ///
+    /// [`MainContext::channel()`]: struct.MainContext.html#method.channel
+    /// [`set_default_source_priority()`]: fn.set_default_source_priority.html
+    pub fn default_priority_channel<T>() -> (Sender<T>, Receiver<T>) {
+        MainContext::channel(::get_default_source_priority(::SourceKind::Channel))
+    }
+
+    /// Same as [`MainContext::sync_channel()`] but uses the default priority for channels, see
+    /// [`set_default_source_priority()`].
+    ///
+    /// [`MainContext::sync_channel()`]: struct.MainContext.html#method.sync_channel
+    /// [`set_default_source_priority()`]: fn.set_default_source_priority.html
+    pub fn default_priority_sync_channel<T>(bound: usize) -> (SyncSender<T>, Receiver<T>) {
+        MainContext::sync_channel(::get_default_source_priority(::SourceKind::Channel), bound)
+    }
+}
+
+// How often, in milliseconds, the thread of bridge_receiver() checks whether its source was removed
+const BRIDGE_POLL_INTERVAL_MS: u64 = 100;
+
+/// A blocking channel receiver that can be bridged into a main context with
+/// [`bridge_receiver()`].
+///
+/// This is implemented for `std::sync::mpsc::Receiver`. Other channel types, e.g.
+/// `crossbeam_channel::Receiver`, can be bridged by implementing it for a wrapper type.
+///
+/// [`bridge_receiver()`]: fn.bridge_receiver.html
+pub trait BridgeReceiver: Send + 'static {
+    type Item: Send + 'static;
+
+    /// Waits for the next item for at most `timeout`.
+    ///
+    /// Returns `RecvTimeoutError::Disconnected` once all senders are gone and all items were
+    /// received.
+    fn recv_timeout(&self, timeout: Duration) -> Result<Self::Item, mpsc::RecvTimeoutError>;
+}
+
+impl<T: Send + 'static> BridgeReceiver for mpsc::Receiver<T> {
+    type Item = T;
+
+    fn recv_timeout(&self, timeout: Duration) -> Result<T, mpsc::RecvTimeoutError> {
+        mpsc::Receiver::recv_timeout(self, timeout)
+    }
+}
+
+/// Bridges an existing channel receiver into a main context.
+///
+/// This spawns a thread that forwards every item received from `receiver` to a new main
+/// context channel with the given `priority`, and attaches that channel to `context` with
+/// `func` as callback. This allows existing threaded code to stay unchanged while delivering
+/// its items to the main context.
+///
+/// Once all senders of the original channel are dropped the forwarding thread stops and the
+/// source is removed from the main context after all pending items were dispatched. If the
+/// source is removed first, e.g. by returning `Continue(false)` from `func`, the forwarding
+/// thread stops and drops `receiver` within 100ms, so the senders of the original channel
+/// notice that it is gone.
+///
+/// Passing `None` for the context will attach it to the thread default main context.
+///
+/// # Panics
+///
+/// This function panics if called from a thread that is not the owner of the provided
+/// `context`, or, if `None` is provided, of the thread default main context.
+pub fn bridge_receiver<R, F>(
+    receiver: R,
+    context: Option<&MainContext>,
+    priority: Priority,
+    func: F,
+) -> SourceId
+where
+    R: BridgeReceiver,
+    F: FnMut(R::Item) -> Continue + 'static,
+{
+    let (sender, glib_receiver) = MainContext::channel(priority);
+    let source_id = glib_receiver.attach(context, func);
+
+    thread::Builder::new()
+        .name(String::from("glib-bridge-receiver"))
+        .spawn(move || {
+            let channel = sender.0.as_ref().expect("Sender with no channel");
+            let poll_interval = Duration::from_millis(BRIDGE_POLL_INTERVAL_MS);
+            loop {
+                match receiver.recv_timeout(poll_interval) {
+                    Ok(item) => {
+                        if channel.send(item).is_err() {
+                            break;
+                        }
+                    }
+                    Err(mpsc::RecvTimeoutError::Timeout) => {
+                        if channel.receiver_disconnected() {
+                            break;
+                        }
+                    }
+                    Err(mpsc::RecvTimeoutError::Disconnected) => break,
+                }
+            }
+        })
+        .expect("Failed to spawn bridge thread");
+
+    source_id
 }
 
 #[cfg(test)]
@@ -555,7 +888,6 @@ mod tests {
     use super::*;
     use std::cell::RefCell;
     use std::rc::Rc;
-    use std::thread;
     use std::time;
     use MainLoop;
 
@@ -867,4 +1199,461 @@ mod tests {
 
         assert_eq!(*sum.borrow(), 6);
     }
+
+    #[test]
+    fn test_sync_channel_fairness() {
+        let c = MainContext::new();
+        let l = MainLoop::new(Some(&c), false);
+
+        c.acquire();
+
+        let (sender, receiver) = MainContext::sync_channel(Priority::default(), 1);
+
+        // Fill the channel so that all following senders block
+        sender.try_send(0).unwrap();
+
+        // Start the senders one after another, giving each one enough time
+        // to block before the next one is started
+        let mut threads = Vec::new();
+        for i in 1..5 {
+            let sender = sender.clone();
+            threads.push(thread::spawn(move || {
+                sender.send(i).unwrap();
+            }));
+            thread::sleep(time::Duration::from_millis(50));
+        }
+        drop(sender);
+
+        let items = Rc::new(RefCell::new(Vec::new()));
+        let items_clone = items.clone();
+        let l_clone = l.clone();
+        receiver.attach(Some(&c), move |item| {
+            items_clone.borrow_mut().push(item);
+            if items_clone.borrow().len() == 5 {
+                l_clone.quit();
+                Continue(false)
+            } else {
+                Continue(true)
+            }
+        });
+        l.run();
+
+        for thread in threads {
+            thread.join().unwrap();
+        }
+
+        // The blocked senders must have been served in the order they started waiting
+        assert_eq!(*items.borrow(), vec![0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_channel_fallible() {
+        let c = MainContext::new();
+        let l = MainLoop::new(Some(&c), false);
+
+        c.acquire();
+
+        let (sender, receiver) = MainContext::channel(Priority::default());
+
+        let sum = Rc::new(RefCell::new(0));
+        let errors = Rc::new(RefCell::new(Vec::new()));
+        let sum_clone = sum.clone();
+        let errors_clone = errors.clone();
+        let l_clone = l.clone();
+        receiver.attach_fallible(
+            Some(&c),
+            move |item: i32| {
+                if item % 2 == 0 {
+                    return Err(format!("even item {}", item));
+                }
+                *sum_clone.borrow_mut() += item;
+                Ok(Continue(true))
+            },
+            move |err| {
+                errors_clone.borrow_mut().push(err);
+                if errors_clone.borrow().len() == 2 {
+                    l_clone.quit();
+                    Continue(false)
+                } else {
+                    Continue(true)
+                }
+            },
+        );
+
+        for i in 1..6 {
+            sender.send(i).unwrap();
+        }
+
+        l.run();
+
+        assert_eq!(*sum.borrow(), 4);
+        assert_eq!(*errors.borrow(), vec![String::from("even item 2"), String::from("even item 4")]);
+        assert!(sender.send(6).is_err());
+    }
+
+    #[test]
+    fn test_channel_stats() {
+        let c = MainContext::new();
+        let l = MainLoop::new(Some(&c), false);
+
+        c.acquire();
+
+        let (sender, receiver) = MainContext::channel(Priority::default());
+
+        for i in 0..5 {
+            sender.send(i).unwrap();
+        }
+
+        let l_clone = l.clone();
+        let (_, stats) = receiver.attach_with_stats(Some(&c), move |item| {
+            if item == 2 {
+                l_clone.quit();
+                Continue(false)
+            } else {
+                Continue(true)
+            }
+        });
+
+        assert_eq!(stats.dispatched(), 0);
+        assert_eq!(stats.last_dispatch(), None);
+
+        l.run();
+
+        assert_eq!(stats.dispatched(), 3);
+        assert_eq!(stats.dropped(), 2);
+        assert_eq!(stats.max_queue_depth(), 5);
+        assert!(stats.last_dispatch().is_some());
+    }
+
+    #[test]
+    fn test_bridge_receiver() {
+        let c = MainContext::new();
+        let l = MainLoop::new(Some(&c), false);
+
+        c.acquire();
+
+        let (sender, receiver) = mpsc::channel();
+
+        let sum = Rc::new(RefCell::new(0));
+        let sum_clone = sum.clone();
+        let l_clone = l.clone();
+        bridge_receiver(receiver, Some(&c), Priority::default(), move |item| {
+            *sum_clone.borrow_mut() += item;
+            if *sum_clone.borrow() == 6 {
+                l_clone.quit();
+                Continue(false)
+            } else {
+                Continue(true)
+            }
+        });
+
+        thread::spawn(move || {
+            sender.send(1).unwrap();
+            sender.send(2).unwrap();
+            sender.send(3).unwrap();
+        });
+
+        l.run();
+
+        assert_eq!(*sum.borrow(), 6);
+    }
+
+    #[test]
+    fn test_bridge_receiver_source_removed() {
+        use std::sync::atomic::AtomicBool;
+
+        struct DropReceiver(mpsc::Receiver<i32>, Arc<AtomicBool>);
+
+        impl BridgeReceiver for DropReceiver {
+            type Item = i32;
+
+            fn recv_timeout(&self, timeout: Duration) -> Result<i32, mpsc::RecvTimeoutError> {
+                self.0.recv_timeout(timeout)
+            }
+        }
+
+        impl Drop for DropReceiver {
+            fn drop(&mut self) {
+                self.1.store(true, Ordering::SeqCst);
+            }
+        }
+
+        let c = MainContext::new();
+        let l = MainLoop::new(Some(&c), false);
+
+        c.acquire();
+
+        let (sender, receiver) = mpsc::channel();
+        let dropped = Arc::new(AtomicBool::new(false));
+
+        let l_clone = l.clone();
+        bridge_receiver(
+            DropReceiver(receiver, dropped.clone()),
+            Some(&c),
+            Priority::default(),
+            move |_| {
+                l_clone.quit();
+                Continue(false)
+            },
+        );
+
+        sender.send(1).unwrap();
+        l.run();
+
+        // The thread stops although the original sender is still alive and sends nothing else
+        let start = Instant::now();
+        while !dropped.load(Ordering::SeqCst) {
+            assert!(start.elapsed() < time::Duration::from_secs(5), "Bridge thread did not stop");
+            thread::sleep(time::Duration::from_millis(10));
+        }
+        assert!(sender.send(2).is_err());
+    }
+
+    #[test]
+    fn test_send_or_warn() {
+        let (sender, receiver) = MainContext::channel::<i32>(Priority::default());
+        assert!(sender.send_or_warn(1, "glib-rs-test"));
+        drop(receiver);
+        assert!(!sender.send_or_warn(2, "glib-rs-test"));
+
+        let (sender, receiver) = MainContext::sync_channel::<i32>(Priority::default(), 1);
+        drop(receiver);
+        assert!(!sender.send_or_warn(1, "glib-rs-test"));
+
+        // The policy is global, so only test the panic without changing it as other tests
+        // running in parallel would be affected
+        assert_eq!(get_send_failure_policy(), SendFailurePolicy::Warn);
+        handle_send_failure(SendFailurePolicy::Warn, "glib-rs-test");
+        let res = ::std::panic::catch_unwind(|| handle_send_failure(SendFailurePolicy::Panic, "glib-rs-test"));
+        assert!(res.is_err());
+    }
+}
+
+// Long running tests that hammer the channel from many threads with randomized
+// interleavings of send/try_send/drop/attach/destroy and check that the documented
+// delivery guarantees hold. Run with `cargo test --features stress-tests`.
+#[cfg(all(test, feature = "stress-tests"))]
+mod stress_tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time;
+    use MainLoop;
+
+    const N_THREADS: usize = 16;
+    const N_ITEMS: usize = 1000;
+    const N_ITERATIONS: usize = 20;
+
+    // Simple xorshift generator so that the tests don't need an additional dependency
+    struct Rng(u32);
+
+    impl Rng {
+        fn new(seed: usize) -> Rng {
+            Rng(seed as u32 | 1)
+        }
+
+        fn next(&mut self) -> u32 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 17;
+            self.0 ^= self.0 << 5;
+            self.0
+        }
+
+        fn maybe_sleep(&mut self) {
+            match self.next() % 64 {
+                0 => thread::sleep(time::Duration::from_micros(u64::from(self.next() % 500))),
+                1 => thread::yield_now(),
+                _ => (),
+            }
+        }
+    }
+
+    fn run_senders<S, F>(sender: S, seed: usize, send: F) -> Vec<thread::JoinHandle<()>>
+    where
+        S: Clone + Send + 'static,
+        F: Fn(&S, (usize, usize)) + Clone + Send + 'static,
+    {
+        (0..N_THREADS)
+            .map(|id| {
+                let sender = sender.clone();
+                let send = send.clone();
+                thread::spawn(move || {
+                    let mut rng = Rng::new(seed * N_THREADS + id + 1);
+                    for i in 0..N_ITEMS {
+                        rng.maybe_sleep();
+                        send(&sender, (id, i));
+                    }
+                })
+            })
+            .collect()
+    }
+
+    fn check_order_and_count(received: &[Vec<usize>]) {
+        assert_eq!(received.len(), N_THREADS);
+        for items in received {
+            assert_eq!(items.len(), N_ITEMS);
+            for (expected, item) in items.iter().enumerate() {
+                assert_eq!(*item, expected);
+            }
+        }
+    }
+
+    fn receive_all(c: &MainContext, receiver: Receiver<(usize, usize)>) -> Vec<Vec<usize>> {
+        let l = MainLoop::new(Some(c), false);
+        let received = Rc::new(RefCell::new(vec![Vec::new(); N_THREADS]));
+        let received_clone = received.clone();
+        let l_clone = l.clone();
+        let mut count = 0;
+        receiver.attach(Some(c), move |(id, i)| {
+            received_clone.borrow_mut()[id].push(i);
+            count += 1;
+            if count == N_THREADS * N_ITEMS {
+                l_clone.quit();
+            }
+            Continue(true)
+        });
+
+        l.run();
+
+        let received = received.borrow().clone();
+        received
+    }
+
+    #[test]
+    fn stress_channel_ordering() {
+        for iteration in 0..N_ITERATIONS {
+            let c = MainContext::new();
+            c.acquire();
+
+            let (sender, receiver) = MainContext::channel(Priority::default());
+            let threads = run_senders(sender, iteration, |sender: &Sender<_>, item| {
+                sender.send(item).unwrap();
+            });
+
+            check_order_and_count(&receive_all(&c, receiver));
+
+            for thread in threads {
+                thread.join().unwrap();
+            }
+        }
+    }
+
+    #[test]
+    fn stress_sync_channel_ordering() {
+        for iteration in 0..N_ITERATIONS {
+            let c = MainContext::new();
+            c.acquire();
+
+            let (sender, receiver) =
+                MainContext::sync_channel(Priority::default(), iteration % 4);
+            let threads = run_senders(sender, iteration, |sender: &SyncSender<_>, mut item| {
+                // Mix blocking and non-blocking sends
+                if item.1 % 2 == 0 {
+                    sender.send(item).unwrap();
+                } else {
+                    loop {
+                        match sender.try_send(item) {
+                            Ok(()) => break,
+                            Err(mpsc::TrySendError::Full(i)) => {
+                                item = i;
+                                thread::yield_now();
+                            }
+                            Err(mpsc::TrySendError::Disconnected(_)) => {
+                                panic!("Receiver disconnected")
+                            }
+                        }
+                    }
+                }
+            });
+
+            check_order_and_count(&receive_all(&c, receiver));
+
+            for thread in threads {
+                thread.join().unwrap();
+            }
+        }
+    }
+
+    #[test]
+    fn stress_send_happens_before_dispatch() {
+        for iteration in 0..N_ITERATIONS {
+            let c = MainContext::new();
+            c.acquire();
+
+            let counters = Arc::new((0..N_THREADS).map(|_| AtomicUsize::new(0)).collect::<Vec<_>>());
+            let (sender, receiver) = MainContext::channel(Priority::default());
+
+            let counters_clone = counters.clone();
+            let threads = run_senders(sender, iteration, move |sender: &Sender<_>, item| {
+                // Relaxed store before sending, must be visible in the callback
+                counters_clone[item.0].store(item.1 + 1, Ordering::Relaxed);
+                sender.send(item).unwrap();
+            });
+
+            let l = MainLoop::new(Some(&c), false);
+            let l_clone = l.clone();
+            let mut count = 0;
+            receiver.attach(Some(&c), move |(id, i)| {
+                assert!(counters[id].load(Ordering::Relaxed) > i);
+                count += 1;
+                if count == N_THREADS * N_ITEMS {
+                    l_clone.quit();
+                }
+                Continue(true)
+            });
+            l.run();
+
+            for thread in threads {
+                thread.join().unwrap();
+            }
+        }
+    }
+
+    #[test]
+    fn stress_drop_and_destroy_receiver() {
+        for iteration in 0..N_ITERATIONS {
+            let c = MainContext::new();
+            c.acquire();
+
+            let (sender, receiver) =
+                MainContext::sync_channel::<(usize, usize)>(Priority::default(), iteration % 2);
+            let failed = Arc::new(AtomicUsize::new(0));
+            let delivered = Rc::new(RefCell::new(0));
+
+            let last_sender = sender.clone();
+            let failed_clone = failed.clone();
+            let threads = run_senders(sender, iteration, move |sender: &SyncSender<_>, item| {
+                // Must never block forever once the receiver is gone
+                if sender.send(item).is_err() {
+                    failed_clone.fetch_add(1, Ordering::SeqCst);
+                }
+            });
+
+            let mut rng = Rng::new(iteration + 1);
+            if rng.next() % 2 == 0 {
+                drop(receiver);
+            } else {
+                let delivered_clone = delivered.clone();
+                let source_id = receiver.attach(Some(&c), move |_| {
+                    *delivered_clone.borrow_mut() += 1;
+                    Continue(true)
+                });
+                for _ in 0..(rng.next() % 100) {
+                    c.iteration(false);
+                }
+                Source::remove(source_id).unwrap();
+            }
+
+            // After the receiver is gone every further send has to fail
+            assert!(last_sender.send((0, 0)).is_err());
+
+            for thread in threads {
+                thread.join().unwrap();
+            }
+
+            // No item can be both delivered and returned to the sender
+            assert!(failed.load(Ordering::SeqCst) + *delivered.borrow() <= N_THREADS * N_ITEMS);
+        }
+    }
 }
@@ -128,7 +128,7 @@ unsafe extern "C" fn dispatch(
         .state
         .compare_and_swap(READY, NOT_READY, Ordering::SeqCst);
     if cur == READY {
-        if let Poll::Ready(()) = source.poll() {
+        if let Poll::Ready(()) = ::profiler::profile(|| source.poll()) {
             source.state.store(DONE, Ordering::SeqCst);
             cur = DONE;
         } else {
@@ -176,6 +176,8 @@ impl TaskSource {
             }
 
             glib_sys::g_source_set_priority(source, priority.to_glib());
+            // Name used by the profiler
+            glib_sys::g_source_set_name(source, b"glib::MainContext::spawn\0".as_ptr() as *const _);
 
             from_glib_full(source)
         }
@@ -0,0 +1,215 @@
+// Copyright 2019, The Gtk-rs Project Developers.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+// Licensed under the MIT license, see the LICENSE file or <http://opensource.org/licenses/MIT>
+
+use std::fmt;
+use Continue;
+use MainContext;
+use Receiver;
+use Source;
+use SourceKind;
+
+/// Owning handle for a set of sources.
+///
+/// All sources of the group are destroyed once the group is dropped, which makes the teardown
+/// of a component as simple as dropping the group that was returned by
+/// [`MainContextSetup::build()`].
+///
+/// [`MainContextSetup::build()`]: struct.MainContextSetup.html#method.build
+#[derive(Debug, Default)]
+pub struct SourceGroup(Vec<Source>);
+
+impl SourceGroup {
+    pub fn new() -> SourceGroup {
+        SourceGroup(Vec::new())
+    }
+
+    /// Adds an already attached source to the group.
+    pub fn add(&mut self, source: Source) {
+        self.0.push(source);
+    }
+
+    pub fn sources(&self) -> &[Source] {
+        &self.0
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Destroys all sources of the group.
+    pub fn destroy(self) {}
+}
+
+impl Drop for SourceGroup {
+    fn drop(&mut self) {
+        for source in self.0.drain(..) {
+            source.destroy();
+        }
+    }
+}
+
+/// Builder for attaching a set of sources to a main context at once.
+///
+/// Nothing is attached until [`build()`] is called, which attaches all sources in the order in
+/// which they were added and returns a [`SourceGroup`] owning them.
+///
+/// ```ignore
+/// let sources = glib::MainContextSetup::new(&ctx)
+///     .timeout(1000, move || { refresh(); glib::Continue(true) })
+///     .idle(move || { load_more() })
+///     .channel(receiver, move |msg| { handle(msg); glib::Continue(true) })
+///     .unix_signal(libc::SIGHUP, move || { reload(); glib::Continue(true) })
+///     .build();
+///
+/// // Later, removes all of the above from the main context
+/// drop(sources);
+/// ```
+///
+/// [`build()`]: #method.build
+/// [`SourceGroup`]: struct.SourceGroup.html
+pub struct MainContextSetup {
+    context: MainContext,
+    pending: Vec<Box<FnMut(&MainContext) -> Source>>,
+}
+
+impl MainContextSetup {
+    pub fn new(context: &MainContext) -> MainContextSetup {
+        MainContextSetup {
+            context: context.clone(),
+            pending: Vec::new(),
+        }
+    }
+
+    /// Adds a source that is created by the caller, e.g. with `timeout_source_new()` or
+    /// `Source::new_from_impl()`.
+    pub fn source(mut self, source: Source) -> Self {
+        self.pending.push(Box::new(move |context| {
+            source.attach(Some(context));
+            source.clone()
+        }));
+        self
+    }
+
+    /// Adds a timeout calling `func` every `interval` milliseconds, see `timeout_add()`.
+    pub fn timeout<F>(self, interval: u32, func: F) -> Self
+    where F: FnMut() -> Continue + Send + 'static {
+        let priority = ::get_default_source_priority(SourceKind::Timeout);
+        self.source(::timeout_source_new(interval, None, priority, func))
+    }
+
+    /// Adds a timeout calling `func` every `interval` seconds, see `timeout_add_seconds()`.
+    pub fn timeout_seconds<F>(self, interval: u32, func: F) -> Self
+    where F: FnMut() -> Continue + Send + 'static {
+        let priority = ::get_default_source_priority(SourceKind::Timeout);
+        self.source(::timeout_source_new_seconds(interval, None, priority, func))
+    }
+
+    /// Adds an idle source calling `func`, see `idle_add()`.
+    pub fn idle<F>(self, func: F) -> Self
+    where F: FnMut() -> Continue + Send + 'static {
+        let priority = ::get_default_source_priority(SourceKind::Idle);
+        self.source(::idle_source_new(None, priority, func))
+    }
+
+    #[cfg(any(unix, feature = "dox"))]
+    /// Adds a source calling `func` whenever the UNIX signal `signum` is raised, see
+    /// `unix_signal_add()`.
+    pub fn unix_signal<F>(self, signum: i32, func: F) -> Self
+    where F: FnMut() -> Continue + Send + 'static {
+        self.source(::unix_signal_source_new(signum, None, ::PRIORITY_DEFAULT, func))
+    }
+
+    /// Adds a source calling `func` for every item received on `receiver`, see
+    /// `Receiver::attach()`.
+    ///
+    /// # Panics
+    ///
+    /// `build()` panics if called from a thread that is not the owner of the main context if
+    /// any channel was added.
+    pub fn channel<T: 'static, F: FnMut(T) -> Continue + 'static>(mut self, receiver: Receiver<T>, func: F) -> Self {
+        let mut receiver = Some(receiver);
+        let mut func = Some(func);
+        self.pending.push(Box::new(move |context| {
+            let receiver = receiver.take().expect("Channel attached twice");
+            let func = func.take().expect("Channel attached twice");
+            let id = receiver.attach(Some(context), func);
+            context.find_source_by_id(&id).expect("Channel source not found")
+        }));
+        self
+    }
+
+    /// Attaches all sources to the main context and returns a group owning them.
+    pub fn build(self) -> SourceGroup {
+        let MainContextSetup { context, pending } = self;
+
+        let mut group = SourceGroup::new();
+        for mut attach in pending {
+            group.add(attach(&context));
+        }
+        group
+    }
+}
+
+impl fmt::Debug for MainContextSetup {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("MainContextSetup")
+            .field("context", &self.context)
+            .field("pending", &self.pending.len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use MainLoop;
+
+    #[test]
+    fn test_main_context_setup() {
+        let c = MainContext::new();
+        let l = MainLoop::new(Some(&c), false);
+
+        c.acquire();
+
+        let count = Arc::new(AtomicUsize::new(0));
+        let (sender, receiver) = MainContext::channel(::PRIORITY_DEFAULT);
+
+        let count_clone = count.clone();
+        let count_clone_2 = count.clone();
+        let l_clone = l.clone();
+        let setup = MainContextSetup::new(&c)
+            .idle(move || {
+                count_clone.fetch_add(1, Ordering::SeqCst);
+                Continue(false)
+            })
+            .timeout(10, move || {
+                sender.send(()).unwrap();
+                Continue(true)
+            })
+            .channel(receiver, move |()| {
+                count_clone_2.fetch_add(1, Ordering::SeqCst);
+                l_clone.quit();
+                Continue(true)
+            });
+
+        // Nothing is attached before building
+        assert!(!c.iteration(false));
+
+        let group = setup.build();
+        assert_eq!(group.len(), 3);
+
+        l.run();
+        assert_eq!(count.load(Ordering::SeqCst), 2);
+
+        let sources = group.sources().to_vec();
+        drop(group);
+        assert!(sources.iter().all(|source| source.is_destroyed()));
+    }
+}
@@ -0,0 +1,107 @@
+// Copyright 2019, The Gtk-rs Project Developers.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+// Licensed under the MIT license, see the LICENSE file or <http://opensource.org/licenses/MIT>
+
+use source::duration_to_millis;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use Continue;
+use MainLoop;
+#[cfg(any(unix, feature = "dox"))]
+use Source;
+
+impl MainLoop {
+    /// Runs the main loop until `quit()` is called or `timeout` has elapsed, whichever comes first.
+    ///
+    /// Returns `true` if the main loop was quit before the timeout and `false` otherwise.
+    pub fn run_with_timeout(&self, timeout: Duration) -> bool {
+        let timed_out = Arc::new(AtomicBool::new(false));
+
+        let timed_out_clone = timed_out.clone();
+        let main_loop = self.clone();
+        let source = ::timeout_source_new(duration_to_millis(timeout), Some("MainLoop::run_with_timeout"), ::PRIORITY_DEFAULT, move || {
+            timed_out_clone.store(true, Ordering::SeqCst);
+            main_loop.quit();
+            Continue(false)
+        });
+        source.attach(Some(&self.get_context()));
+
+        self.run();
+
+        source.destroy();
+
+        !timed_out.load(Ordering::SeqCst)
+    }
+
+    /// Quits the main loop whenever one of the given UNIX signals is raised.
+    ///
+    /// The returned sources are attached to the context of the main loop and can be destroyed
+    /// to stop handling the signals.
+    #[cfg(any(unix, feature = "dox"))]
+    pub fn quit_on_signal(&self, signums: &[i32]) -> Vec<Source> {
+        let context = self.get_context();
+
+        signums
+            .iter()
+            .map(|&signum| {
+                let main_loop = self.clone();
+                let source = ::unix_signal_source_new(signum, Some("MainLoop::quit_on_signal"), ::PRIORITY_DEFAULT, move || {
+                    main_loop.quit();
+                    Continue(true)
+                });
+                source.attach(Some(&context));
+                source
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use MainContext;
+
+    #[test]
+    fn test_run_with_timeout() {
+        let c = MainContext::new();
+        let l = MainLoop::new(Some(&c), false);
+
+        c.acquire();
+
+        assert!(!l.run_with_timeout(Duration::from_millis(10)));
+
+        let l_clone = l.clone();
+        ::idle_source_new(None, ::PRIORITY_DEFAULT, move || {
+            l_clone.quit();
+            Continue(false)
+        })
+        .attach(Some(&c));
+        assert!(l.run_with_timeout(Duration::from_secs(10)));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_quit_on_signal() {
+        let c = MainContext::new();
+        let l = MainLoop::new(Some(&c), false);
+
+        c.acquire();
+
+        let sources = l.quit_on_signal(&[::libc::SIGUSR1]);
+        assert_eq!(sources.len(), 1);
+
+        ::idle_source_new(None, ::PRIORITY_DEFAULT, || {
+            unsafe {
+                ::libc::raise(::libc::SIGUSR1);
+            }
+            Continue(false)
+        })
+        .attach(Some(&c));
+        assert!(l.run_with_timeout(Duration::from_secs(10)));
+
+        for source in sources {
+            source.destroy();
+        }
+    }
+}
@@ -0,0 +1,443 @@
+// Copyright 2019, The Gtk-rs Project Developers.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+// Licensed under the MIT license, see the LICENSE file or <http://opensource.org/licenses/MIT>
+
+use glib_sys;
+use libc::{c_char, size_t};
+use std::ffi::CStr;
+use std::marker::PhantomData;
+use std::ptr;
+use std::slice;
+use std::str;
+use translate::*;
+use Error;
+use MarkupError;
+use MarkupParseFlags;
+
+/// Callbacks for the elements of a document parsed by a [`MarkupParseContext`].
+///
+/// All callbacks do nothing by default. Returning an error from a callback stops parsing, the
+/// error is then returned by [`MarkupParseContext::parse()`]. Use `MarkupError` for errors
+/// about the document's content.
+///
+/// [`MarkupParseContext`]: struct.MarkupParseContext.html
+/// [`MarkupParseContext::parse()`]: struct.MarkupParseContext.html#method.parse
+pub trait MarkupParser {
+    /// Called for each opening tag, with the attributes in document order.
+    fn start_element(
+        &mut self,
+        _context: &MarkupContext,
+        _element_name: &str,
+        _attributes: &[(&str, &str)],
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// Called for each closing tag, and right after `start_element()` for empty elements.
+    fn end_element(&mut self, _context: &MarkupContext, _element_name: &str) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// Called for text between elements, with entities already expanded.
+    ///
+    /// Text may be split over several calls and includes whitespace.
+    fn text(&mut self, _context: &MarkupContext, _text: &str) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// Called for comments, processing instructions and doctype declarations, and for CDATA
+    /// sections unless `MarkupParseFlags::TREAT_CDATA_AS_TEXT` is set.
+    fn passthrough(&mut self, _context: &MarkupContext, _passthrough_text: &str) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// Called once parsing failed, including failures because of an error returned by one of the
+    /// other callbacks.
+    fn error(&mut self, _context: &MarkupContext, _error: &Error) {}
+}
+
+/// The state of a [`MarkupParseContext`] passed to the [`MarkupParser`] callbacks.
+///
+/// [`MarkupParseContext`]: struct.MarkupParseContext.html
+/// [`MarkupParser`]: trait.MarkupParser.html
+#[derive(Debug)]
+pub struct MarkupContext<'a> {
+    ptr: *mut glib_sys::GMarkupParseContext,
+    phantom: PhantomData<&'a glib_sys::GMarkupParseContext>,
+}
+
+impl<'a> MarkupContext<'a> {
+    /// Returns the name of the element that is currently being parsed.
+    pub fn get_element(&self) -> Option<&'a str> {
+        unsafe {
+            let ptr = glib_sys::g_markup_parse_context_get_element(self.ptr);
+            if ptr.is_null() {
+                None
+            } else {
+                Some(c_to_str(ptr))
+            }
+        }
+    }
+
+    /// Returns the names of all open elements, starting with the innermost one.
+    pub fn get_element_stack(&self) -> Vec<&'a str> {
+        let mut stack = Vec::new();
+        unsafe {
+            let mut list = glib_sys::g_markup_parse_context_get_element_stack(self.ptr);
+            while !list.is_null() {
+                stack.push(c_to_str((*list).data as *const c_char));
+                list = (*list).next;
+            }
+        }
+        stack
+    }
+
+    /// Returns the current line and character in the line, both counting from 1.
+    pub fn get_position(&self) -> (usize, usize) {
+        let mut line = 0;
+        let mut char_ = 0;
+        unsafe {
+            glib_sys::g_markup_parse_context_get_position(self.ptr, &mut line, &mut char_);
+        }
+        (line as usize, char_ as usize)
+    }
+}
+
+/// A parser for a simple subset of XML.
+///
+/// The supported subset is enough for GtkBuilder files, Pango markup and similar documents, but
+/// there are no DTDs, no external entities and no namespaces. See the GLib documentation of
+/// `GMarkupParseContext` for the details.
+///
+/// The document can be passed in pieces, e.g. as it is read from a file.
+///
+/// ```
+/// use glib::{Error, MarkupContext, MarkupParseContext, MarkupParseFlags, MarkupParser};
+///
+/// #[derive(Default)]
+/// struct Collector {
+///     names: Vec<String>,
+/// }
+///
+/// impl MarkupParser for Collector {
+///     fn start_element(
+///         &mut self,
+///         _context: &MarkupContext,
+///         element_name: &str,
+///         attributes: &[(&str, &str)],
+///     ) -> Result<(), Error> {
+///         if element_name == "item" {
+///             if let Some(&(_, name)) = attributes.iter().find(|&&(key, _)| key == "name") {
+///                 self.names.push(name.to_owned());
+///             }
+///         }
+///         Ok(())
+///     }
+/// }
+///
+/// let mut context = MarkupParseContext::new(Collector::default(), MarkupParseFlags::empty());
+/// context.parse("<list><item name='a'/>").unwrap();
+/// context.parse("<item name='b'/></list>").unwrap();
+/// context.end_parse().unwrap();
+/// assert_eq!(context.into_parser().names, vec!["a", "b"]);
+/// ```
+pub struct MarkupParseContext<P: MarkupParser> {
+    ptr: ptr::NonNull<glib_sys::GMarkupParseContext>,
+    // Only a pointer to the vtable is stored by GLib, and the parser is passed as user data. Both
+    // are boxed to keep their addresses stable.
+    _vtable: Box<glib_sys::GMarkupParser>,
+    parser: Option<Box<P>>,
+    // GLib refuses to continue after an error without setting another one
+    failed: bool,
+}
+
+impl<P: MarkupParser> MarkupParseContext<P> {
+    pub fn new(parser: P, flags: MarkupParseFlags) -> MarkupParseContext<P> {
+        let vtable = Box::new(glib_sys::GMarkupParser {
+            start_element: Some(start_element_trampoline::<P>),
+            end_element: Some(end_element_trampoline::<P>),
+            text: Some(text_trampoline::<P>),
+            passthrough: Some(passthrough_trampoline::<P>),
+            error: Some(error_trampoline::<P>),
+        });
+        let mut parser = Box::new(parser);
+
+        unsafe {
+            let ptr = glib_sys::g_markup_parse_context_new(
+                &*vtable,
+                flags.to_glib(),
+                &mut *parser as *mut P as glib_sys::gpointer,
+                None,
+            );
+
+            MarkupParseContext {
+                ptr: ptr::NonNull::new(ptr).expect("Failed to create markup parse context"),
+                _vtable: vtable,
+                parser: Some(parser),
+                failed: false,
+            }
+        }
+    }
+
+    /// Parses the next piece of the document.
+    ///
+    /// Once an error was returned the context can't be used for parsing anymore, and all
+    /// further calls return an error.
+    pub fn parse(&mut self, text: &str) -> Result<(), Error> {
+        self.check_failed()?;
+        unsafe {
+            let mut error = ptr::null_mut();
+            let ret = glib_sys::g_markup_parse_context_parse(
+                self.ptr.as_ptr(),
+                text.as_ptr() as *const _,
+                text.len() as isize,
+                &mut error,
+            );
+            self.result(ret, error)
+        }
+    }
+
+    /// Signals the end of the document.
+    ///
+    /// Returns an error if the document is incomplete, e.g. if elements were left open.
+    pub fn end_parse(&mut self) -> Result<(), Error> {
+        self.check_failed()?;
+        unsafe {
+            let mut error = ptr::null_mut();
+            let ret = glib_sys::g_markup_parse_context_end_parse(self.ptr.as_ptr(), &mut error);
+            self.result(ret, error)
+        }
+    }
+
+    fn check_failed(&self) -> Result<(), Error> {
+        if self.failed {
+            Err(Error::new(MarkupError::Parse, "Parsing already failed"))
+        } else {
+            Ok(())
+        }
+    }
+
+    unsafe fn result(&mut self, ret: glib_sys::gboolean, error: *mut glib_sys::GError) -> Result<(), Error> {
+        if ret != glib_sys::GFALSE {
+            return Ok(());
+        }
+
+        self.failed = true;
+        if error.is_null() {
+            Err(Error::new(MarkupError::Parse, "Parsing failed"))
+        } else {
+            Err(from_glib_full(error))
+        }
+    }
+
+    /// Returns the current line and character in the line, both counting from 1.
+    pub fn get_position(&self) -> (usize, usize) {
+        self.context().get_position()
+    }
+
+    pub fn get_parser(&self) -> &P {
+        self.parser.as_ref().unwrap()
+    }
+
+    pub fn get_parser_mut(&mut self) -> &mut P {
+        self.parser.as_mut().unwrap()
+    }
+
+    /// Frees the context and returns the parser.
+    pub fn into_parser(mut self) -> P {
+        // The context is freed when dropped and does not access the parser anymore
+        *self.parser.take().unwrap()
+    }
+
+    fn context(&self) -> MarkupContext {
+        MarkupContext {
+            ptr: self.ptr.as_ptr(),
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<P: MarkupParser> Drop for MarkupParseContext<P> {
+    fn drop(&mut self) {
+        unsafe { glib_sys::g_markup_parse_context_free(self.ptr.as_ptr()) }
+    }
+}
+
+// GMarkup validates the document as UTF-8 before calling any callbacks
+unsafe fn c_to_str<'a>(ptr: *const c_char) -> &'a str {
+    str::from_utf8_unchecked(CStr::from_ptr(ptr).to_bytes())
+}
+
+unsafe fn c_to_str_len<'a>(ptr: *const c_char, len: size_t) -> &'a str {
+    if len == 0 {
+        ""
+    } else {
+        str::from_utf8_unchecked(slice::from_raw_parts(ptr as *const u8, len))
+    }
+}
+
+unsafe fn set_error(res: Result<(), Error>, error: *mut *mut glib_sys::GError) {
+    if let Err(err) = res {
+        *error = err.to_glib_full() as *mut _;
+    }
+}
+
+unsafe extern "C" fn start_element_trampoline<P: MarkupParser>(
+    context: *mut glib_sys::GMarkupParseContext,
+    element_name: *const c_char,
+    attribute_names: *mut *const c_char,
+    attribute_values: *mut *const c_char,
+    user_data: glib_sys::gpointer,
+    error: *mut *mut glib_sys::GError,
+) {
+    let parser = &mut *(user_data as *mut P);
+    let context = MarkupContext { ptr: context, phantom: PhantomData };
+
+    let mut attributes = Vec::new();
+    let mut i = 0;
+    while !(*attribute_names.add(i)).is_null() {
+        attributes.push((c_to_str(*attribute_names.add(i)), c_to_str(*attribute_values.add(i))));
+        i += 1;
+    }
+
+    set_error(parser.start_element(&context, c_to_str(element_name), &attributes), error);
+}
+
+unsafe extern "C" fn end_element_trampoline<P: MarkupParser>(
+    context: *mut glib_sys::GMarkupParseContext,
+    element_name: *const c_char,
+    user_data: glib_sys::gpointer,
+    error: *mut *mut glib_sys::GError,
+) {
+    let parser = &mut *(user_data as *mut P);
+    let context = MarkupContext { ptr: context, phantom: PhantomData };
+
+    set_error(parser.end_element(&context, c_to_str(element_name)), error);
+}
+
+unsafe extern "C" fn text_trampoline<P: MarkupParser>(
+    context: *mut glib_sys::GMarkupParseContext,
+    text: *const c_char,
+    text_len: size_t,
+    user_data: glib_sys::gpointer,
+    error: *mut *mut glib_sys::GError,
+) {
+    let parser = &mut *(user_data as *mut P);
+    let context = MarkupContext { ptr: context, phantom: PhantomData };
+
+    set_error(parser.text(&context, c_to_str_len(text, text_len)), error);
+}
+
+unsafe extern "C" fn passthrough_trampoline<P: MarkupParser>(
+    context: *mut glib_sys::GMarkupParseContext,
+    passthrough_text: *const c_char,
+    text_len: size_t,
+    user_data: glib_sys::gpointer,
+    error: *mut *mut glib_sys::GError,
+) {
+    let parser = &mut *(user_data as *mut P);
+    let context = MarkupContext { ptr: context, phantom: PhantomData };
+
+    set_error(parser.passthrough(&context, c_to_str_len(passthrough_text, text_len)), error);
+}
+
+unsafe extern "C" fn error_trampoline<P: MarkupParser>(
+    context: *mut glib_sys::GMarkupParseContext,
+    error: *mut glib_sys::GError,
+    user_data: glib_sys::gpointer,
+) {
+    let parser = &mut *(user_data as *mut P);
+    let context = MarkupContext { ptr: context, phantom: PhantomData };
+    let error: Error = from_glib_borrow(error);
+
+    parser.error(&context, &error);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Default)]
+    struct Recorder {
+        events: Vec<String>,
+        errors: usize,
+    }
+
+    impl MarkupParser for Recorder {
+        fn start_element(
+            &mut self,
+            context: &MarkupContext,
+            element_name: &str,
+            attributes: &[(&str, &str)],
+        ) -> Result<(), Error> {
+            if element_name == "invalid" {
+                return Err(Error::new(MarkupError::UnknownElement, "Invalid element"));
+            }
+
+            let attributes = attributes
+                .iter()
+                .map(|&(key, value)| format!(" {}={}", key, value))
+                .collect::<String>();
+            self.events.push(format!("<{}{}>", element_name, attributes));
+            assert_eq!(context.get_element(), Some(element_name));
+            Ok(())
+        }
+
+        fn end_element(&mut self, context: &MarkupContext, element_name: &str) -> Result<(), Error> {
+            self.events.push(format!("</{}>", element_name));
+            assert_eq!(context.get_element_stack()[0], element_name);
+            Ok(())
+        }
+
+        fn text(&mut self, _context: &MarkupContext, text: &str) -> Result<(), Error> {
+            self.events.push(text.to_owned());
+            Ok(())
+        }
+
+        fn passthrough(&mut self, _context: &MarkupContext, passthrough_text: &str) -> Result<(), Error> {
+            self.events.push(passthrough_text.to_owned());
+            Ok(())
+        }
+
+        fn error(&mut self, _context: &MarkupContext, _error: &Error) {
+            self.errors += 1;
+        }
+    }
+
+    #[test]
+    fn test_parse() {
+        let mut context = MarkupParseContext::new(Recorder::default(), MarkupParseFlags::empty());
+        context.parse("<a x='1' y=\"&lt;\"><!-- c -->t&amp;").unwrap();
+        context.parse("t<b/></a>").unwrap();
+        context.end_parse().unwrap();
+
+        let recorder = context.into_parser();
+        assert_eq!(recorder.errors, 0);
+        assert_eq!(
+            recorder.events.concat(),
+            "<a x=1 y=<><!-- c -->t&t<b></b></a>"
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        let mut context = MarkupParseContext::new(Recorder::default(), MarkupParseFlags::empty());
+        let err = context.parse("<a>\n<invalid/>").unwrap_err();
+        assert_eq!(err.kind::<MarkupError>(), Some(MarkupError::UnknownElement));
+        assert_eq!(context.get_parser().errors, 1);
+        // The context is unusable after an error
+        let err = context.parse("</a>").unwrap_err();
+        assert_eq!(err.kind::<MarkupError>(), Some(MarkupError::Parse));
+        assert!(context.end_parse().is_err());
+
+        let mut context = MarkupParseContext::new(Recorder::default(), MarkupParseFlags::empty());
+        context.parse("<a>\n<b>").unwrap();
+        assert_eq!(context.get_position().0, 2);
+        let err = context.end_parse().unwrap_err();
+        assert_eq!(err.kind::<MarkupError>(), Some(MarkupError::Parse));
+
+        let mut context = MarkupParseContext::new(Recorder::default(), MarkupParseFlags::empty());
+        let err = context.parse("<a></b>").unwrap_err();
+        assert_eq!(err.kind::<MarkupError>(), Some(MarkupError::Parse));
+    }
+}
@@ -6,18 +6,21 @@
 
 use glib_sys;
 use gobject_sys;
+use std::any::Any;
 use std::fmt;
 use std::hash;
 use std::marker::PhantomData;
 use std::mem;
 use std::ops;
 use std::ptr;
+use thread_guard::LocalCallback;
 use translate::*;
 use types::StaticType;
 
-use value::ToValue;
+use value::{FromValueOptional, ToValue};
 use BoolError;
 use Closure;
+use Quark;
 use SignalHandlerId;
 use Type;
 use Value;
@@ -65,6 +68,13 @@ pub trait UnsafeFrom<T> {
 /// implementations exist.
 pub unsafe trait IsA<T: ObjectType>: ObjectType + AsRef<T> + 'static { }
 
+/// Marker trait for object types that can be subclassed.
+///
+/// This is implemented by `glib_wrapper!` for classes that opt in with `@derivable`, which
+/// requires specifying their class struct. Other classes can't be used as the parent type of an
+/// `ObjectSubclass`.
+pub unsafe trait IsDerivable: ObjectType { }
+
 /// Trait for mapping a class struct type to its corresponding instance type.
 pub unsafe trait IsClassFor: Sized + 'static {
     /// Corresponding Rust instance type for this class.
@@ -555,6 +565,11 @@ macro_rules! glib_object_wrapper {
             #[allow(clippy::cast_ptr_alignment)]
             unsafe fn from_glib_full(ptr: *mut $ffi_name) -> Self {
                 debug_assert!($crate::types::instance_of::<Self>(ptr as *const _));
+                // Taking ownership of a floating reference sinks it, otherwise whoever sinks it
+                // next would take over our reference
+                if $crate::gobject_sys::g_object_is_floating(ptr as *mut _) != $crate::glib_sys::GFALSE {
+                    $crate::gobject_sys::g_object_ref_sink(ptr as *mut _);
+                }
                 $name($crate::translate::from_glib_full(ptr as *mut _), ::std::marker::PhantomData)
             }
         }
@@ -796,6 +811,11 @@ macro_rules! glib_object_wrapper {
         glib_object_wrapper!(@munch_impls $name, $($implements)*);
     };
 
+    (@derivable $name:ident) => {
+        #[doc(hidden)]
+        unsafe impl $crate::object::IsDerivable for $name { }
+    };
+
     (@class_impl $name:ident, $ffi_class_name:path, $rust_class_name:ident) => {
         #[repr(C)]
         pub struct $rust_class_name($ffi_class_name);
@@ -856,30 +876,120 @@ glib_object_wrapper!(@object
     [doc = "The base class in the object hierarchy."]
     Object, GObject, GObjectClass, ObjectClass, @get_type gobject_sys::g_object_get_type()
 );
+glib_object_wrapper!(@derivable Object);
 
 impl Object {
-    pub fn new(type_: Type, properties: &[(&str, &ToValue)]) -> Result<Object, BoolError> {
-        use std::ffi::CString;
+    // Checks the properties against the class of `type_` before construction, as GObject would
+    // only emit critical warnings for invalid properties
+    fn validate_construct_properties(type_: Type, params: &mut [(::std::ffi::CString, Value)]) -> Result<(), BoolError> {
+        unsafe {
+            let klass = gobject_sys::g_type_class_ref(type_.to_glib());
+            let res = {
+                let klass = &*(klass as *const ObjectClass);
+                params.iter_mut().try_for_each(|&mut (ref name, ref mut value)| {
+                    let name = name.to_str().unwrap();
+                    let pspec = match klass.find_property(name) {
+                        Some(pspec) => pspec,
+                        None => return Err(glib_bool_error!("property not found")),
+                    };
+
+                    if !pspec.get_flags().contains(::ParamFlags::WRITABLE) {
+                        return Err(glib_bool_error!("property is not writable"));
+                    }
 
-        if !type_.is_a(&Object::static_type()) {
-            return Err(glib_bool_error!("Can't instantiate non-GObject objects"));
+                    let valid_type: bool = from_glib(gobject_sys::g_type_check_value_holds(
+                            mut_override(value.to_glib_none().0),
+                            pspec.get_value_type().to_glib()));
+
+                    // Same as in ObjectExt::set_property(), allow objects with a more generic
+                    // type in the value if the object itself is compatible
+                    if !valid_type && value.type_().is_a(&Object::static_type()) {
+                        match value.get::<Object>() {
+                            Some(ref obj) if !obj.get_type().is_a(&pspec.get_value_type()) => {
+                                return Err(glib_bool_error!("property can't be set from the given object type"));
+                            }
+                            _ => value.0.g_type = pspec.get_value_type().to_glib(),
+                        }
+                    } else if !valid_type {
+                        return Err(glib_bool_error!("property can't be set from the given type"));
+                    }
+
+                    Ok(())
+                })
+            };
+            gobject_sys::g_type_class_unref(klass);
+
+            res
         }
+    }
 
+    pub fn new(type_: Type, properties: &[(&str, &ToValue)]) -> Result<Object, BoolError> {
         let params = properties.iter()
                                .map(|&(name, value)|
-                                    (CString::new(name).unwrap(), value.to_value()))
-                               .collect::<Vec<_>>();
+                                    Object::property_name(name).map(|name| (name, value.to_value())))
+                               .collect::<Result<Vec<_>, _>>()?;
+
+        Object::new_internal(type_, params)
+    }
+
+    /// Returns a builder for constructing an object of type `type_` with a list of properties.
+    ///
+    /// ```ignore
+    /// let window = glib::Object::builder(gtk::Window::static_type())
+    ///     .property("title", "Hello")
+    ///     .property("default-width", &800)
+    ///     .build()?;
+    /// ```
+    pub fn builder(type_: Type) -> ObjectBuilder {
+        ObjectBuilder::new(type_)
+    }
 
+    fn property_name(name: &str) -> Result<::std::ffi::CString, BoolError> {
+        ::std::ffi::CString::new(name).map_err(|_| glib_bool_error!("property name contains a NUL byte"))
+    }
+
+    #[cfg(any(feature = "v2_54", feature = "dox"))]
+    unsafe fn new_with_params(type_: Type, params: &[(::std::ffi::CString, Value)]) -> *mut gobject_sys::GObject {
+        let names = params.iter()
+                          .map(|&(ref name, _)| name.as_ptr())
+                          .collect::<Vec<_>>();
+        let values = params.iter()
+                           .map(|&(_, ref value)| *value.to_glib_none().0)
+                           .collect::<Vec<_>>();
+
+        gobject_sys::g_object_new_with_properties(type_.to_glib(), params.len() as u32,
+                                                  mut_override(names.as_ptr()), values.as_ptr())
+    }
+
+    // g_object_newv() is deprecated since 2.54 but the only option before
+    #[cfg(not(any(feature = "v2_54", feature = "dox")))]
+    unsafe fn new_with_params(type_: Type, params: &[(::std::ffi::CString, Value)]) -> *mut gobject_sys::GObject {
         let params_c = params.iter()
                              .map(|&(ref name, ref value)|
                                   gobject_sys::GParameter {
                                       name: name.as_ptr(),
-                                      value: unsafe { *value.to_glib_none().0 }
+                                      value: *value.to_glib_none().0
                                   })
                              .collect::<Vec<_>>();
 
+        gobject_sys::g_object_newv(type_.to_glib(), params_c.len() as u32, mut_override(params_c.as_ptr()))
+    }
+
+    fn new_internal(type_: Type, mut params: Vec<(::std::ffi::CString, Value)>) -> Result<Object, BoolError> {
+        if !type_.is_a(&Object::static_type()) {
+            return Err(glib_bool_error!("Can't instantiate non-GObject objects"));
+        }
+
         unsafe {
-            let ptr = gobject_sys::g_object_newv(type_.to_glib(), params_c.len() as u32, mut_override(params_c.as_ptr()));
+            if gobject_sys::g_type_test_flags(type_.to_glib(), gobject_sys::G_TYPE_FLAG_ABSTRACT) != glib_sys::GFALSE {
+                return Err(glib_bool_error!("Can't instantiate abstract type"));
+            }
+        }
+
+        Object::validate_construct_properties(type_, &mut params)?;
+
+        unsafe {
+            let ptr = Object::new_with_params(type_, &params);
             if ptr.is_null() {
                 Err(glib_bool_error!("Can't instantiate object"))
             } else if gobject_sys::g_object_is_floating(ptr) != glib_sys::GFALSE {
@@ -891,6 +1001,32 @@ impl Object {
     }
 }
 
+/// Returns the signal ID, detail quark and return type of `signal_name` on `type_`.
+fn lookup_signal(type_: Type, signal_name: &str) -> Option<(u32, glib_sys::GQuark, Type)> {
+    unsafe {
+        let mut signal_id = 0;
+        let mut signal_detail = 0;
+
+        let found: bool = from_glib(gobject_sys::g_signal_parse_name(signal_name.to_glib_none().0,
+                                                                     type_.to_glib(), &mut signal_id,
+                                                                     &mut signal_detail, true.to_glib()));
+        if !found {
+            return None;
+        }
+
+        let mut details = mem::zeroed();
+        gobject_sys::g_signal_query(signal_id, &mut details);
+        if details.signal_id != signal_id {
+            return None;
+        }
+
+        // This is actually G_SIGNAL_TYPE_STATIC_SCOPE
+        let return_type = from_glib(details.return_type & (!gobject_sys::G_TYPE_FLAG_RESERVED_ID_BIT));
+
+        Some((signal_id, signal_detail, return_type))
+    }
+}
+
 pub trait ObjectExt: ObjectType {
     /// Returns `true` if the object is an instance of (can be cast to) `T`.
     fn is<T: StaticType>(&self) -> bool;
@@ -900,6 +1036,11 @@ pub trait ObjectExt: ObjectType {
 
     fn set_property<'a, N: Into<&'a str>>(&self, property_name: N, value: &ToValue) -> Result<(), BoolError>;
     fn get_property<'a, N: Into<&'a str>>(&self, property_name: N) -> Result<Value, BoolError>;
+    /// Gets the value of the property `property_name` as `T`.
+    ///
+    /// Returns `Ok(None)` for properties that are currently unset, e.g. a `None` string, and an
+    /// error if the property does not exist, is not readable or its type does not match `T`.
+    fn property<'a, T: for<'b> FromValueOptional<'b>, N: Into<&'a str>>(&self, property_name: N) -> Result<Option<T>, BoolError>;
     fn has_property<'a, N: Into<&'a str>>(&self, property_name: N, type_: Option<Type>) -> Result<(), BoolError>;
     fn get_property_type<'a, N: Into<&'a str>>(&self, property_name: N) -> Option<Type>;
     fn find_property<'a, N: Into<&'a str>>(&self, property_name: N) -> Option<::ParamSpec>;
@@ -909,23 +1050,101 @@ pub trait ObjectExt: ObjectType {
     fn unblock_signal(&self, handler_id: &SignalHandlerId);
     fn stop_signal_emission(&self, signal_name: &str);
 
+    /// Connects `callback` to the signal `signal_name`.
+    ///
+    /// The callback gets the instance and the signal arguments as values and has to return a
+    /// value of the signal's return type, or `None` if the signal has no return value. Returning
+    /// a value of the wrong type panics during emission, see `connect_returning()` for checking
+    /// the type when connecting instead. Returning `None` for a signal with a return value logs a
+    /// critical warning and returns the default value of the return type.
+    ///
+    /// For signals with an accumulator the return values of all handlers are passed to the
+    /// accumulator, which decides whether the emission continues and what `emit()` returns.
     fn connect<'a, N, F>(&self, signal_name: N, after: bool, callback: F) -> Result<SignalHandlerId, BoolError>
         where N: Into<&'a str>, F: Fn(&[Value]) -> Option<Value> + Send + Sync + 'static;
     unsafe fn connect_unsafe<'a, N, F>(&self, signal_name: N, after: bool, callback: F) -> Result<SignalHandlerId, BoolError>
         where N: Into<&'a str>, F: Fn(&[Value]) -> Option<Value>;
+    /// Like `connect()` but for callbacks that are not `Send` and `Sync`.
+    ///
+    /// Emitting the signal from a thread other than the one the callback was connected on only
+    /// logs a critical warning and the callback is not called, the default value of the return
+    /// type is returned instead. If the handler is disconnected on
+    /// another thread, the callback is leaked.
+    fn connect_local<'a, N, F>(&self, signal_name: N, after: bool, callback: F) -> Result<SignalHandlerId, BoolError>
+        where N: Into<&'a str>, F: Fn(&[Value]) -> Option<Value> + 'static;
+    /// Connects `callback` returning a typed value to the signal `signal_name`.
+    ///
+    /// Returns an error if the signal does not exist or if `R` is not compatible with the
+    /// return type of the signal, e.g. `bool` for signals like `"handled"` that use the
+    /// `signal_accumulator_true_handled` accumulator, where returning `true` stops the emission.
+    fn connect_returning<'a, N, R, F>(&self, signal_name: N, after: bool, callback: F) -> Result<SignalHandlerId, BoolError>
+        where N: Into<&'a str>, R: StaticType + ToValue, F: Fn(&[Value]) -> R + Send + Sync + 'static;
     fn emit<'a, N: Into<&'a str>>(&self, signal_name: N, args: &[&ToValue]) -> Result<Option<Value>, BoolError>;
     fn disconnect(&self, handler_id: SignalHandlerId);
 
     fn connect_notify<F: Fn(&Self, &::ParamSpec) + Send + Sync + 'static>(&self, name: Option<&str>, f: F) -> SignalHandlerId;
     unsafe fn connect_notify_unsafe<F: Fn(&Self, &::ParamSpec)>(&self, name: Option<&str>, f: F) -> SignalHandlerId;
+    /// Like `connect_notify()` but for callbacks that are not `Send` and `Sync`.
+    ///
+    /// Notifying from a thread other than the one the callback was connected on only logs a
+    /// critical warning and the callback is not called. If the handler is disconnected on
+    /// another thread, the callback is leaked.
+    fn connect_notify_local<F: Fn(&Self, &::ParamSpec) + 'static>(&self, name: Option<&str>, f: F) -> SignalHandlerId;
     fn notify<'a, N: Into<&'a str>>(&self, property_name: N);
     fn notify_by_pspec(&self, pspec: &::ParamSpec);
 
+    /// Freezes emission of `notify` signals until the returned guard is dropped.
+    ///
+    /// All notifications for property changes while the guard is alive are queued and emitted
+    /// once when the guard is dropped, with only one emission per changed property. This also
+    /// happens if a panic unwinds through the code that holds the guard.
+    fn freeze_notify(&self) -> PropertyNotificationFreezeGuard;
+
     fn downgrade(&self) -> WeakRef<Self>;
 
     fn bind_property<'a, O: ObjectType, N: Into<&'a str>, M: Into<&'a str>>(&'a self, source_property: N, target: &'a O, target_property: M) -> BindingBuilder<'a>;
 
     fn ref_count(&self) -> u32;
+
+    /// Returns whether the object has a floating reference.
+    ///
+    /// Objects are never floating once they are owned by a Rust wrapper, as taking a reference to
+    /// a floating object always sinks it. This is mostly useful for checking raw pointers or the
+    /// objects passed from C code.
+    fn is_floating(&self) -> bool;
+
+    /// Stores `value` on the object under `key`, replacing and dropping any previous value.
+    ///
+    /// The value is dropped once it is replaced, stolen or the object is finalized, which can
+    /// happen on any thread and is why it must be `Send` and `Sync`.
+    ///
+    /// # Safety
+    ///
+    /// `key` must not be used by other code, e.g. C code, for storing other data on the object,
+    /// as these functions assume that all data stored under `key` was stored by `set_qdata()`.
+    /// Use `set_data()` with a `DataKey` instead where possible.
+    unsafe fn set_qdata<QD: Any + Send + Sync>(&self, key: Quark, value: QD);
+    /// Returns a clone of the value stored under `key`, or `None` if there is no value or it is
+    /// not of type `QD`.
+    ///
+    /// # Safety
+    ///
+    /// See `set_qdata()`.
+    unsafe fn get_qdata<QD: Any + Clone + Send + Sync>(&self, key: Quark) -> Option<QD>;
+    /// Removes the value stored under `key` from the object and returns it, or `None` if there is
+    /// no value or it is not of type `QD`, in which case it is left on the object.
+    ///
+    /// # Safety
+    ///
+    /// See `set_qdata()`.
+    unsafe fn steal_qdata<QD: Any + Send + Sync>(&self, key: Quark) -> Option<QD>;
+
+    /// Like `set_qdata()` but with a typed key.
+    fn set_data<QD: Any + Send + Sync>(&self, key: &DataKey<QD>, value: QD);
+    /// Like `get_qdata()` but with a typed key.
+    fn get_data<QD: Any + Clone + Send + Sync>(&self, key: &DataKey<QD>) -> Option<QD>;
+    /// Like `steal_qdata()` but with a typed key.
+    fn steal_data<QD: Any + Send + Sync>(&self, key: &DataKey<QD>) -> Option<QD>;
 }
 
 impl<T: ObjectType> ObjectExt for T {
@@ -1032,6 +1251,21 @@ impl<T: ObjectType> ObjectExt for T {
         }
     }
 
+    fn property<'a, U: for<'b> FromValueOptional<'b>, N: Into<&'a str>>(&self, property_name: N) -> Result<Option<U>, BoolError> {
+        let value = self.get_property(property_name)?;
+
+        unsafe {
+            let valid_type: bool = from_glib(gobject_sys::g_type_check_value_holds(
+                    mut_override(value.to_glib_none().0),
+                    U::static_type().to_glib()));
+            if !valid_type {
+                return Err(glib_bool_error!("property type doesn't match the requested type"));
+            }
+
+            Ok(U::from_value_optional(&value))
+        }
+    }
+
     fn block_signal(&self, handler_id: &SignalHandlerId) {
         unsafe {
             gobject_sys::g_signal_handler_block(self.as_object_ref().to_glib_none().0, handler_id.to_glib());
@@ -1080,6 +1314,18 @@ impl<T: ObjectType> ObjectExt for T {
             Some(mem::transmute(notify_trampoline::<Self, F> as usize)), Box::into_raw(f))
     }
 
+    fn connect_notify_local<F: Fn(&Self, &::ParamSpec) + 'static>(&self, name: Option<&str>, f: F) -> SignalHandlerId {
+        let f = LocalCallback::new(f);
+
+        unsafe {
+            self.connect_notify_unsafe(name, move |s, pspec| {
+                if let Some(f) = f.get() {
+                    f(s, pspec)
+                }
+            })
+        }
+    }
+
     fn notify<'a, N: Into<&'a str>>(&self, property_name: N) {
         let property_name = property_name.into();
 
@@ -1094,6 +1340,14 @@ impl<T: ObjectType> ObjectExt for T {
         }
     }
 
+    fn freeze_notify(&self) -> PropertyNotificationFreezeGuard {
+        unsafe {
+            let ptr: *mut gobject_sys::GObject = self.as_object_ref().to_glib_none().0;
+            gobject_sys::g_object_freeze_notify(ptr);
+            PropertyNotificationFreezeGuard(from_glib_none(ptr))
+        }
+    }
+
     fn has_property<'a, N: Into<&'a str>>(&self, property_name: N, type_: Option<Type>) -> Result<(), BoolError> {
         self.get_object_class().has_property(property_name, type_)
     }
@@ -1121,27 +1375,11 @@ impl<T: ObjectType> ObjectExt for T {
         where N: Into<&'a str>, F: Fn(&[Value]) -> Option<Value> {
         let signal_name: &str = signal_name.into();
 
-        let type_ = self.get_type();
-
-        let mut signal_id = 0;
-        let mut signal_detail = 0;
-
-        let found: bool = from_glib(gobject_sys::g_signal_parse_name(signal_name.to_glib_none().0,
-                                                                     type_.to_glib(), &mut signal_id,
-                                                                     &mut signal_detail, true.to_glib()));
-
-        if !found {
-            return Err(glib_bool_error!("Signal not found"));
-        }
-
-        let mut details = mem::zeroed();
-        gobject_sys::g_signal_query(signal_id, &mut details);
-        if details.signal_id != signal_id {
-            return Err(glib_bool_error!("Signal not found"));
-        }
+        let (signal_id, signal_detail, return_type) = match lookup_signal(self.get_type(), signal_name) {
+            Some(signal) => signal,
+            None => return Err(glib_bool_error!("Signal not found")),
+        };
 
-        // This is actually G_SIGNAL_TYPE_STATIC_SCOPE
-        let return_type: Type = from_glib(details.return_type & (!gobject_sys::G_TYPE_FLAG_RESERVED_ID_BIT));
         let closure = Closure::new_unsafe(move |values| {
             let ret = callback(values);
 
@@ -1180,7 +1418,10 @@ impl<T: ObjectType> ObjectExt for T {
                         Some(ret)
                     }
                     None => {
-                        panic!("Signal required return value of type {} but got None", return_type.name());
+                        // Panicking here would unwind into the C code emitting the signal
+                        g_critical!("glib-rs", "Signal required return value of type {} but got None",
+                                    return_type.name());
+                        Some(Value::from_type(return_type))
                     }
                 }
             }
@@ -1195,6 +1436,32 @@ impl<T: ObjectType> ObjectExt for T {
         }
     }
 
+    fn connect_local<'a, N, F>(&self, signal_name: N, after: bool, callback: F) -> Result<SignalHandlerId, BoolError>
+        where N: Into<&'a str>, F: Fn(&[Value]) -> Option<Value> + 'static {
+        let callback = LocalCallback::new(callback);
+
+        unsafe {
+            self.connect_unsafe(signal_name, after, move |values| {
+                callback.get().and_then(|callback| callback(values))
+            })
+        }
+    }
+
+    fn connect_returning<'a, N, R, F>(&self, signal_name: N, after: bool, callback: F) -> Result<SignalHandlerId, BoolError>
+        where N: Into<&'a str>, R: StaticType + ToValue, F: Fn(&[Value]) -> R + Send + Sync + 'static {
+        let signal_name: &str = signal_name.into();
+
+        let return_type = match lookup_signal(self.get_type(), signal_name) {
+            Some((_, _, return_type)) => return_type,
+            None => return Err(glib_bool_error!("Signal not found")),
+        };
+        if !R::static_type().is_a(&return_type) {
+            return Err(glib_bool_error!("Incompatible signal return type"));
+        }
+
+        self.connect(signal_name, after, move |values| Some(callback(values).to_value()))
+    }
+
     fn emit<'a, N: Into<&'a str>>(&self, signal_name: N, args: &[&ToValue]) -> Result<Option<Value>, BoolError> {
         let signal_name: &str = signal_name.into();
         unsafe {
@@ -1222,8 +1489,11 @@ impl<T: ObjectType> ObjectExt for T {
             }
 
             for (i, item) in args.iter().enumerate() {
+                // Subtypes of the parameter type are accepted, e.g. a specific object type for a
+                // parameter of a more generic object or interface type
                 let arg_type = *(details.param_types.add(i)) & (!gobject_sys::G_TYPE_FLAG_RESERVED_ID_BIT);
-                if arg_type != item.to_value_type().to_glib() {
+                let valid_type: bool = from_glib(gobject_sys::g_type_is_a(item.to_value_type().to_glib(), arg_type));
+                if !valid_type {
                     return Err(glib_bool_error!("Incompatible argument types"));
                 }
             }
@@ -1288,6 +1558,101 @@ impl<T: ObjectType> ObjectExt for T {
 
         unsafe { glib_sys::g_atomic_int_get(&(*ptr).ref_count as *const u32 as *const i32) as u32 }
     }
+
+    fn is_floating(&self) -> bool {
+        unsafe {
+            from_glib(gobject_sys::g_object_is_floating(self.as_object_ref().to_glib_none().0 as *mut _))
+        }
+    }
+
+    unsafe fn set_qdata<QD: Any + Send + Sync>(&self, key: Quark, value: QD) {
+        unsafe extern "C" fn drop_value(ptr: glib_sys::gpointer) {
+            let _ = Box::from_raw(ptr as *mut Box<Any + Send + Sync>);
+        }
+
+        let value: Box<Box<Any + Send + Sync>> = Box::new(Box::new(value));
+        gobject_sys::g_object_set_qdata_full(
+            self.as_object_ref().to_glib_none().0,
+            key.to_glib(),
+            Box::into_raw(value) as glib_sys::gpointer,
+            Some(drop_value),
+        );
+    }
+
+    unsafe fn get_qdata<QD: Any + Clone + Send + Sync>(&self, key: Quark) -> Option<QD> {
+        let ptr = gobject_sys::g_object_get_qdata(self.as_object_ref().to_glib_none().0, key.to_glib());
+        if ptr.is_null() {
+            return None;
+        }
+
+        let value = &*(ptr as *const Box<Any + Send + Sync>);
+        value.downcast_ref::<QD>().cloned()
+    }
+
+    unsafe fn steal_qdata<QD: Any + Send + Sync>(&self, key: Quark) -> Option<QD> {
+        let obj = self.as_object_ref().to_glib_none().0;
+        let ptr = gobject_sys::g_object_get_qdata(obj, key.to_glib());
+        if ptr.is_null() || !(*(ptr as *const Box<Any + Send + Sync>)).is::<QD>() {
+            return None;
+        }
+
+        let ptr = gobject_sys::g_object_steal_qdata(obj, key.to_glib());
+        let value = Box::from_raw(ptr as *mut Box<Any + Send + Sync>);
+        value.downcast::<QD>().ok().map(|value| *value)
+    }
+
+    fn set_data<QD: Any + Send + Sync>(&self, key: &DataKey<QD>, value: QD) {
+        // DataKeys are only used with these functions
+        unsafe { self.set_qdata(key.quark(), value) }
+    }
+
+    fn get_data<QD: Any + Clone + Send + Sync>(&self, key: &DataKey<QD>) -> Option<QD> {
+        unsafe { self.get_qdata(key.quark()) }
+    }
+
+    fn steal_data<QD: Any + Send + Sync>(&self, key: &DataKey<QD>) -> Option<QD> {
+        unsafe { self.steal_qdata(key.quark()) }
+    }
+}
+
+/// Typed key for storing data on objects via `ObjectExt::set_data()`.
+///
+/// Keys are usually declared once as a `static` so that all users agree on the type of the
+/// stored value. The name must not be used as a quark for other data on the same objects, e.g.
+/// by C code:
+///
+/// ```ignore
+/// static CACHE: glib::DataKey<Arc<Mutex<Cache>>> = glib::DataKey::new("my-app-cache");
+///
+/// obj.set_data(&CACHE, Arc::new(Mutex::new(Cache::default())));
+/// let cache = obj.get_data(&CACHE).unwrap();
+/// ```
+pub struct DataKey<QD> {
+    name: &'static str,
+    phantom: PhantomData<QD>,
+}
+
+impl<QD> DataKey<QD> {
+    pub const fn new(name: &'static str) -> DataKey<QD> {
+        DataKey {
+            name,
+            phantom: PhantomData,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    pub fn quark(&self) -> Quark {
+        unsafe { from_glib(glib_sys::g_quark_from_string(self.name.to_glib_none().0)) }
+    }
+}
+
+impl<QD> fmt::Debug for DataKey<QD> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("DataKey").field(&self.name).finish()
+    }
 }
 
 impl ObjectClass {
@@ -1334,7 +1699,15 @@ impl ObjectClass {
 }
 
 glib_wrapper! {
-    pub struct InitiallyUnowned(Object<gobject_sys::GInitiallyUnowned, gobject_sys::GInitiallyUnownedClass, InitiallyUnownedClass>);
+    /// Base class for objects that are created with a floating reference.
+    ///
+    /// The floating reference is meant to be taken over ("sunk") by the first owner of the
+    /// object, e.g. the container a widget is added to. On the Rust side the wrapper is always
+    /// the owner: constructing an object with `Object::new()` and converting pointers with
+    /// `from_glib_none()` or `from_glib_full()` sink floating references, so wrappers never
+    /// hold a floating reference and dropping them never frees an object that is still owned
+    /// elsewhere.
+    pub struct InitiallyUnowned(Object<gobject_sys::GInitiallyUnowned, gobject_sys::GInitiallyUnownedClass, InitiallyUnownedClass>) @derivable;
 
     match fn {
         get_type => || gobject_sys::g_initially_unowned_get_type(),
@@ -1363,6 +1736,21 @@ impl<T: ObjectType> WeakRef<T> {
             }
         }
     }
+
+    /// Makes the weak reference point to `obj`, or to nothing if `None` is passed.
+    pub fn set(&self, obj: Option<&T>) {
+        unsafe {
+            gobject_sys::g_weak_ref_set(
+                mut_override(&*self.0),
+                obj.map_or(ptr::null_mut(), |obj| obj.as_object_ref().to_glib_none().0));
+        }
+    }
+}
+
+impl<T: ObjectType> fmt::Debug for WeakRef<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("WeakRef").field(&self.upgrade()).finish()
+    }
 }
 
 impl<T: ObjectType> Drop for WeakRef<T> {
@@ -1454,6 +1842,49 @@ impl<T: ObjectType> From<WeakRef<T>> for SendWeakRef<T> {
 unsafe impl<T: ObjectType> Sync for SendWeakRef<T> {}
 unsafe impl<T: ObjectType> Send for SendWeakRef<T> {}
 
+/// Builder for constructing objects with a list of properties, see [`Object::builder()`].
+///
+/// All properties are set in one go during construction, after checking that they exist on the
+/// type, are writable and have a compatible type.
+///
+/// [`Object::builder()`]: struct.Object.html#method.builder
+#[derive(Debug)]
+pub struct ObjectBuilder {
+    type_: Type,
+    properties: Vec<(String, Value)>,
+}
+
+impl ObjectBuilder {
+    pub fn new(type_: Type) -> Self {
+        ObjectBuilder {
+            type_,
+            properties: Vec::new(),
+        }
+    }
+
+    /// Sets the property `name` to `value`, replacing a previously set value.
+    pub fn property<T: ToValue + ?Sized>(mut self, name: &str, value: &T) -> Self {
+        let value = value.to_value();
+        match self.properties.iter_mut().find(|&&mut (ref n, _)| n == name) {
+            Some(property) => property.1 = value,
+            None => self.properties.push((name.to_owned(), value)),
+        }
+        self
+    }
+
+    /// Constructs the object.
+    ///
+    /// Fails if the type is not instantiable or any of the properties can't be set.
+    pub fn build(self) -> Result<Object, BoolError> {
+        let params = self.properties
+            .into_iter()
+            .map(|(name, value)| Object::property_name(&name).map(|name| (name, value)))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Object::new_internal(self.type_, params)
+    }
+}
+
 pub struct BindingBuilder<'a> {
     source: &'a ObjectRef,
     source_property: &'a str,
@@ -1513,6 +1944,30 @@ impl<'a> BindingBuilder<'a> {
         }
     }
 
+    /// Updates the source property from the target property as well.
+    pub fn bidirectional(self) -> Self {
+        Self {
+            flags: self.flags | ::BindingFlags::BIDIRECTIONAL,
+            ..self
+        }
+    }
+
+    /// Sets the target property to the value of the source property right away.
+    pub fn sync_create(self) -> Self {
+        Self {
+            flags: self.flags | ::BindingFlags::SYNC_CREATE,
+            ..self
+        }
+    }
+
+    /// Inverts boolean values when updating the properties.
+    pub fn invert_boolean(self) -> Self {
+        Self {
+            flags: self.flags | ::BindingFlags::INVERT_BOOLEAN,
+            ..self
+        }
+    }
+
     pub fn build(self) -> Option<::Binding> {
         unsafe {
             from_glib_none(
@@ -1528,4 +1983,361 @@ impl<'a> BindingBuilder<'a> {
             )
         }
     }
+
+    /// Same as `build()` but returns a guard that unbinds the binding when dropped.
+    pub fn build_guard(self) -> Option<BindingGuard> {
+        self.build().map(|binding| BindingGuard(Some(binding)))
+    }
+}
+
+/// Unbinds a `Binding` when dropped.
+///
+/// See [`BindingBuilder::build_guard()`].
+///
+/// [`BindingBuilder::build_guard()`]: struct.BindingBuilder.html#method.build_guard
+#[derive(Debug)]
+#[must_use]
+pub struct BindingGuard(Option<::Binding>);
+
+impl BindingGuard {
+    pub fn binding(&self) -> &::Binding {
+        self.0.as_ref().unwrap()
+    }
+
+    /// Unbinds the binding right away.
+    pub fn unbind(self) {
+        // Unbinding happens when dropping
+    }
+
+    /// Returns the binding without unbinding it.
+    pub fn into_binding(mut self) -> ::Binding {
+        self.0.take().unwrap()
+    }
+}
+
+impl Drop for BindingGuard {
+    fn drop(&mut self) {
+        if let Some(binding) = self.0.take() {
+            binding.unbind();
+        }
+    }
+}
+
+/// Thaws `notify` signal emission of an object when dropped.
+///
+/// See [`ObjectExt::freeze_notify()`].
+///
+/// [`ObjectExt::freeze_notify()`]: trait.ObjectExt.html#tymethod.freeze_notify
+#[derive(Debug)]
+#[must_use]
+pub struct PropertyNotificationFreezeGuard(Object);
+
+impl Drop for PropertyNotificationFreezeGuard {
+    fn drop(&mut self) {
+        unsafe {
+            gobject_sys::g_object_thaw_notify(self.0.to_glib_none().0);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "subclassing")]
+    use subclass::object::test::{ConstructObject, DummyInterface, SimpleObject};
+    #[cfg(feature = "subclassing")]
+    use subclass::types::ObjectSubclass;
+
+    #[cfg(feature = "subclassing")]
+    #[test]
+    fn test_connect_notify() {
+        use std::sync::{Arc, Mutex};
+
+        let type_ = SimpleObject::get_type();
+        let obj = Object::new(type_, &[]).unwrap();
+
+        let notified = Arc::new(Mutex::new(Vec::new()));
+        let notified_clone = notified.clone();
+        obj.connect_notify(None, move |_, pspec| {
+            notified_clone.lock().unwrap().push(pspec.get_name());
+        });
+
+        let name_notified = Arc::new(Mutex::new(0));
+        let name_notified_clone = name_notified.clone();
+        obj.connect_notify(Some("name"), move |obj, _| {
+            assert_eq!(obj.get_property("name").unwrap().get::<&str>(), Some("test"));
+            *name_notified_clone.lock().unwrap() += 1;
+        });
+
+        obj.set_property("name", &"test").unwrap();
+        obj.notify("constructed");
+
+        assert_eq!(*name_notified.lock().unwrap(), 1);
+        assert_eq!(
+            *notified.lock().unwrap(),
+            vec![String::from("name"), String::from("constructed")]
+        );
+    }
+
+    #[cfg(feature = "subclassing")]
+    #[test]
+    fn test_freeze_notify() {
+        use std::collections::HashSet;
+        use std::panic;
+        use std::sync::{Arc, Mutex};
+
+        let obj = Object::new(SimpleObject::get_type(), &[]).unwrap();
+
+        let notified = Arc::new(Mutex::new(Vec::new()));
+        let notified_clone = notified.clone();
+        obj.connect_notify(None, move |_, pspec| {
+            notified_clone.lock().unwrap().push(pspec.get_name());
+        });
+
+        {
+            let _guard = obj.freeze_notify();
+            obj.set_property("name", &"first").unwrap();
+            obj.set_property("name", &"second").unwrap();
+            obj.notify("constructed");
+            assert!(notified.lock().unwrap().is_empty());
+        }
+        // Queued notifications are emitted once per property, in no particular order
+        let notified_names = notified.lock().unwrap().iter().cloned().collect::<HashSet<_>>();
+        assert_eq!(notified.lock().unwrap().len(), 2);
+        assert_eq!(
+            notified_names,
+            [String::from("name"), String::from("constructed")].iter().cloned().collect()
+        );
+
+        // Notifications are thawed while unwinding, too
+        notified.lock().unwrap().clear();
+        let res = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+            let _guard = obj.freeze_notify();
+            obj.set_property("name", &"third").unwrap();
+            panic!("failed to update");
+        }));
+        assert!(res.is_err());
+        assert_eq!(*notified.lock().unwrap(), vec![String::from("name")]);
+
+        obj.set_property("name", &"fourth").unwrap();
+        assert_eq!(notified.lock().unwrap().len(), 2);
+    }
+
+    #[cfg(feature = "subclassing")]
+    #[test]
+    fn test_create_invalid_properties() {
+        let type_ = SimpleObject::get_type();
+
+        assert!(Object::new(type_, &[("does-not-exist", &"test")]).is_err());
+        assert!(Object::new(type_, &[("name", &1i32)]).is_err());
+        assert!(Object::new(type_, &[("constructed", &false)]).is_err());
+        assert!(Object::new(type_, &[("na\0me", &"test")]).is_err());
+
+        let obj = Object::new(type_, &[("name", &"test")]).unwrap();
+        assert_eq!(obj.get_property("name").unwrap().get::<&str>(), Some("test"));
+    }
+
+    #[test]
+    fn test_weak_ref_set() {
+        let obj = Object::new(Object::static_type(), &[]).unwrap();
+
+        let weak = ::WeakRef::new();
+        assert!(weak.upgrade().is_none());
+
+        weak.set(Some(&obj));
+        assert_eq!(weak.upgrade(), Some(obj.clone()));
+
+        weak.set(None);
+        assert!(weak.upgrade().is_none());
+
+        weak.set(Some(&obj));
+        drop(obj);
+        assert!(weak.upgrade().is_none());
+    }
+
+    #[cfg(feature = "subclassing")]
+    #[test]
+    fn test_bind_property() {
+        let type_ = SimpleObject::get_type();
+        let source = Object::new(type_, &[("name", &"source")]).unwrap();
+        let target = Object::new(type_, &[]).unwrap();
+
+        let binding = source
+            .bind_property("name", &target, "name")
+            .flags(::BindingFlags::SYNC_CREATE)
+            .transform_to(|_, value| {
+                let name = value.get::<&str>()?;
+                Some(name.to_uppercase().to_value())
+            })
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            target.get_property("name").unwrap().get::<&str>(),
+            Some("SOURCE")
+        );
+
+        source.set_property("name", &"changed").unwrap();
+        assert_eq!(
+            target.get_property("name").unwrap().get::<&str>(),
+            Some("CHANGED")
+        );
+
+        binding.unbind();
+        source.set_property("name", &"unbound").unwrap();
+        assert_eq!(
+            target.get_property("name").unwrap().get::<&str>(),
+            Some("CHANGED")
+        );
+    }
+
+    #[cfg(feature = "subclassing")]
+    #[test]
+    fn test_binding_guard() {
+        let type_ = SimpleObject::get_type();
+        let source = Object::new(type_, &[("name", &"source")]).unwrap();
+        let target = Object::new(type_, &[]).unwrap();
+
+        let guard = source
+            .bind_property("name", &target, "name")
+            .sync_create()
+            .bidirectional()
+            .build_guard()
+            .unwrap();
+        assert!(guard
+            .binding()
+            .get_flags()
+            .contains(::BindingFlags::SYNC_CREATE | ::BindingFlags::BIDIRECTIONAL));
+
+        assert_eq!(
+            target.get_property("name").unwrap().get::<&str>(),
+            Some("source")
+        );
+
+        target.set_property("name", &"target").unwrap();
+        assert_eq!(
+            source.get_property("name").unwrap().get::<&str>(),
+            Some("target")
+        );
+
+        drop(guard);
+        source.set_property("name", &"unbound").unwrap();
+        assert_eq!(
+            target.get_property("name").unwrap().get::<&str>(),
+            Some("target")
+        );
+    }
+
+    #[cfg(feature = "subclassing")]
+    #[test]
+    fn test_property_introspection() {
+        let type_ = SimpleObject::get_type();
+        let obj = Object::new(type_, &[]).unwrap();
+
+        let mut names = obj
+            .list_properties()
+            .iter()
+            .map(|pspec| pspec.get_name())
+            .collect::<Vec<_>>();
+        names.sort();
+        assert_eq!(names, vec!["constructed", "name"]);
+
+        let pspec = obj.find_property("constructed").unwrap();
+        assert_eq!(pspec.get_nick(), "Constructed");
+        assert_eq!(
+            pspec.get_blurb(),
+            "True if the constructed() virtual method was called"
+        );
+        assert_eq!(pspec.get_flags(), ::ParamFlags::READABLE);
+        assert_eq!(pspec.get_value_type(), bool::static_type());
+        assert_eq!(pspec.get_owner_type(), type_);
+        assert_eq!(
+            pspec.get_default_value().and_then(|value| value.get::<bool>()),
+            Some(false)
+        );
+
+        assert!(obj.find_property("does-not-exist").is_none());
+        assert_eq!(obj.get_property_type("name"), Some(String::static_type()));
+    }
+
+    #[cfg(feature = "subclassing")]
+    #[test]
+    fn test_cast() {
+        use Binding;
+
+        let type_ = SimpleObject::get_type();
+        let source = Object::new(type_, &[]).unwrap();
+        let target = Object::new(type_, &[]).unwrap();
+
+        let binding = source
+            .bind_property("name", &target, "name")
+            .build()
+            .unwrap();
+
+        let obj = binding.clone().upcast::<Object>();
+        assert!(obj.is::<Binding>());
+        assert_eq!(obj.downcast_ref::<Binding>(), Some(&binding));
+        assert_eq!(obj.clone().downcast::<Binding>(), Ok(binding.clone()));
+        assert_eq!(obj.clone().dynamic_cast::<Binding>(), Ok(binding));
+
+        assert!(source.is::<DummyInterface>());
+        assert!(source.clone().downcast::<Binding>().is_err());
+        assert_eq!(source.clone().dynamic_cast::<Binding>(), Err(source));
+    }
+
+    #[test]
+    fn test_qdata() {
+        use std::sync::{Arc, Mutex};
+
+        static COUNTER: DataKey<Arc<Mutex<u32>>> = DataKey::new("glib-rs-test-counter");
+
+        let obj = Object::new(Object::static_type(), &[]).unwrap();
+
+        assert!(obj.get_data(&COUNTER).is_none());
+
+        let counter = Arc::new(Mutex::new(1));
+        obj.set_data(&COUNTER, counter.clone());
+        *obj.get_data(&COUNTER).unwrap().lock().unwrap() += 1;
+        assert_eq!(*counter.lock().unwrap(), 2);
+
+        // Values of the wrong type are not returned
+        let key = Quark::from_string("glib-rs-test-counter");
+        unsafe {
+            assert!(obj.get_qdata::<String>(key).is_none());
+            assert!(obj.steal_qdata::<String>(key).is_none());
+        }
+        assert!(obj.get_data(&COUNTER).is_some());
+
+        assert!(obj.steal_data(&COUNTER).is_some());
+        assert!(obj.get_data(&COUNTER).is_none());
+
+        // Stored values are dropped with the object
+        unsafe {
+            obj.set_qdata(key, counter.clone());
+        }
+        assert_eq!(Arc::strong_count(&counter), 2);
+        drop(obj);
+        assert_eq!(Arc::strong_count(&counter), 1);
+    }
+
+    #[cfg(feature = "subclassing")]
+    #[test]
+    fn test_object_builder() {
+        let type_ = ConstructObject::get_type();
+
+        let obj = Object::builder(type_)
+            .property("label", "first")
+            .property("id", &7u32)
+            .property("label", "second")
+            .build()
+            .unwrap();
+        assert_eq!(obj.get_property("label").unwrap().get::<&str>(), Some("second"));
+        assert_eq!(obj.get_property("id").unwrap().get::<u32>(), Some(7));
+
+        // Names and types are validated before constructing
+        assert!(Object::builder(type_).property("no-such-property", &1u32).build().is_err());
+        assert!(Object::builder(type_).property("id", "abc").build().is_err());
+        assert!(Object::builder(type_).property("i\0d", &1u32).build().is_err());
+    }
 }
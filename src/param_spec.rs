@@ -6,7 +6,10 @@
 // default/min/max values and similar
 use gobject_sys;
 use libc;
+use std::fmt;
 use translate::*;
+use EnumClass;
+use FlagsClass;
 use ParamFlags;
 use Value;
 
@@ -95,96 +98,123 @@ impl ParamSpec {
     //}
 
     pub fn boolean(name: &str, nick: &str, blurb: &str, default_value: bool, flags: ParamFlags) -> ParamSpec {
+        validate_name(name);
         unsafe {
             from_glib_full(gobject_sys::g_param_spec_boolean(name.to_glib_none().0, nick.to_glib_none().0, blurb.to_glib_none().0, default_value.to_glib(), flags.to_glib()))
         }
     }
 
     pub fn boxed(name: &str, nick: &str, blurb: &str, boxed_type: ::Type, flags: ParamFlags) -> ParamSpec {
+        validate_name(name);
+        validate_type(name, boxed_type, ::Type::BaseBoxed);
         unsafe {
             from_glib_full(gobject_sys::g_param_spec_boxed(name.to_glib_none().0, nick.to_glib_none().0, blurb.to_glib_none().0, boxed_type.to_glib(), flags.to_glib()))
         }
     }
 
     pub fn char(name: &str, nick: &str, blurb: &str, minimum: i8, maximum: i8, default_value: i8, flags: ParamFlags) -> ParamSpec {
+        validate_name(name);
+        validate_range(name, minimum, maximum, default_value);
         unsafe {
             from_glib_full(gobject_sys::g_param_spec_char(name.to_glib_none().0, nick.to_glib_none().0, blurb.to_glib_none().0, minimum, maximum, default_value, flags.to_glib()))
         }
     }
 
     pub fn double(name: &str, nick: &str, blurb: &str, minimum: f64, maximum: f64, default_value: f64, flags: ParamFlags) -> ParamSpec {
+        validate_name(name);
+        validate_range(name, minimum, maximum, default_value);
         unsafe {
             from_glib_full(gobject_sys::g_param_spec_double(name.to_glib_none().0, nick.to_glib_none().0, blurb.to_glib_none().0, minimum, maximum, default_value, flags.to_glib()))
         }
     }
 
     pub fn enum_(name: &str, nick: &str, blurb: &str, enum_type: ::Type, default_value: i32, flags: ParamFlags) -> ParamSpec {
+        validate_name(name);
+        validate_enum_default(name, enum_type, default_value);
         unsafe {
             from_glib_full(gobject_sys::g_param_spec_enum(name.to_glib_none().0, nick.to_glib_none().0, blurb.to_glib_none().0, enum_type.to_glib(), default_value, flags.to_glib()))
         }
     }
 
     pub fn flags(name: &str, nick: &str, blurb: &str, flags_type: ::Type, default_value: u32, flags: ParamFlags) -> ParamSpec {
+        validate_name(name);
+        validate_flags_default(name, flags_type, default_value);
         unsafe {
             from_glib_full(gobject_sys::g_param_spec_flags(name.to_glib_none().0, nick.to_glib_none().0, blurb.to_glib_none().0, flags_type.to_glib(), default_value, flags.to_glib()))
         }
     }
 
     pub fn float(name: &str, nick: &str, blurb: &str, minimum: f32, maximum: f32, default_value: f32, flags: ParamFlags) -> ParamSpec {
+        validate_name(name);
+        validate_range(name, minimum, maximum, default_value);
         unsafe {
             from_glib_full(gobject_sys::g_param_spec_float(name.to_glib_none().0, nick.to_glib_none().0, blurb.to_glib_none().0, minimum, maximum, default_value, flags.to_glib()))
         }
     }
 
     pub fn gtype(name: &str, nick: &str, blurb: &str, is_a_type: ::Type, flags: ParamFlags) -> ParamSpec {
+        validate_name(name);
         unsafe {
             from_glib_full(gobject_sys::g_param_spec_gtype(name.to_glib_none().0, nick.to_glib_none().0, blurb.to_glib_none().0, is_a_type.to_glib(), flags.to_glib()))
         }
     }
 
     pub fn int(name: &str, nick: &str, blurb: &str, minimum: i32, maximum: i32, default_value: i32, flags: ParamFlags) -> ParamSpec {
+        validate_name(name);
+        validate_range(name, minimum, maximum, default_value);
         unsafe {
             from_glib_full(gobject_sys::g_param_spec_int(name.to_glib_none().0, nick.to_glib_none().0, blurb.to_glib_none().0, minimum, maximum, default_value, flags.to_glib()))
         }
     }
 
     pub fn int64(name: &str, nick: &str, blurb: &str, minimum: i64, maximum: i64, default_value: i64, flags: ParamFlags) -> ParamSpec {
+        validate_name(name);
+        validate_range(name, minimum, maximum, default_value);
         unsafe {
             from_glib_full(gobject_sys::g_param_spec_int64(name.to_glib_none().0, nick.to_glib_none().0, blurb.to_glib_none().0, minimum, maximum, default_value, flags.to_glib()))
         }
     }
 
     pub fn long(name: &str, nick: &str, blurb: &str, minimum: libc::c_long, maximum: libc::c_long, default_value: libc::c_long, flags: ParamFlags) -> ParamSpec {
+        validate_name(name);
+        validate_range(name, minimum, maximum, default_value);
         unsafe {
             from_glib_full(gobject_sys::g_param_spec_long(name.to_glib_none().0, nick.to_glib_none().0, blurb.to_glib_none().0, minimum, maximum, default_value, flags.to_glib()))
         }
     }
 
     pub fn object(name: &str, nick: &str, blurb: &str, object_type: ::Type, flags: ParamFlags) -> ParamSpec {
+        validate_name(name);
+        validate_type(name, object_type, ::Type::BaseObject);
         unsafe {
             from_glib_full(gobject_sys::g_param_spec_object(name.to_glib_none().0, nick.to_glib_none().0, blurb.to_glib_none().0, object_type.to_glib(), flags.to_glib()))
         }
     }
 
     pub fn override_(name: &str, overridden: &ParamSpec) -> ParamSpec {
+        validate_name(name);
         unsafe {
             from_glib_none(gobject_sys::g_param_spec_override(name.to_glib_none().0, overridden.to_glib_none().0))
         }
     }
 
     pub fn param(name: &str, nick: &str, blurb: &str, param_type: ::Type, flags: ParamFlags) -> ParamSpec {
+        validate_name(name);
+        validate_type(name, param_type, ::Type::BaseParamSpec);
         unsafe {
             from_glib_full(gobject_sys::g_param_spec_param(name.to_glib_none().0, nick.to_glib_none().0, blurb.to_glib_none().0, param_type.to_glib(), flags.to_glib()))
         }
     }
 
     pub fn pointer(name: &str, nick: &str, blurb: &str, flags: ParamFlags) -> ParamSpec {
+        validate_name(name);
         unsafe {
             from_glib_full(gobject_sys::g_param_spec_pointer(name.to_glib_none().0, nick.to_glib_none().0, blurb.to_glib_none().0, flags.to_glib()))
         }
     }
 
     pub fn string(name: &str, nick: &str, blurb: &str, default_value: Option<&str>, flags: ParamFlags) -> ParamSpec {
+        validate_name(name);
         let default_value = default_value.to_glib_none();
         unsafe {
             from_glib_full(gobject_sys::g_param_spec_string(name.to_glib_none().0, nick.to_glib_none().0, blurb.to_glib_none().0, default_value.0, flags.to_glib()))
@@ -192,44 +222,144 @@ impl ParamSpec {
     }
 
     pub fn uchar(name: &str, nick: &str, blurb: &str, minimum: u8, maximum: u8, default_value: u8, flags: ParamFlags) -> ParamSpec {
+        validate_name(name);
+        validate_range(name, minimum, maximum, default_value);
         unsafe {
             from_glib_full(gobject_sys::g_param_spec_uchar(name.to_glib_none().0, nick.to_glib_none().0, blurb.to_glib_none().0, minimum, maximum, default_value, flags.to_glib()))
         }
     }
 
     pub fn uint(name: &str, nick: &str, blurb: &str, minimum: u32, maximum: u32, default_value: u32, flags: ParamFlags) -> ParamSpec {
+        validate_name(name);
+        validate_range(name, minimum, maximum, default_value);
         unsafe {
             from_glib_full(gobject_sys::g_param_spec_uint(name.to_glib_none().0, nick.to_glib_none().0, blurb.to_glib_none().0, minimum, maximum, default_value, flags.to_glib()))
         }
     }
 
     pub fn uint64(name: &str, nick: &str, blurb: &str, minimum: u64, maximum: u64, default_value: u64, flags: ParamFlags) -> ParamSpec {
+        validate_name(name);
+        validate_range(name, minimum, maximum, default_value);
         unsafe {
             from_glib_full(gobject_sys::g_param_spec_uint64(name.to_glib_none().0, nick.to_glib_none().0, blurb.to_glib_none().0, minimum, maximum, default_value, flags.to_glib()))
         }
     }
 
     pub fn ulong(name: &str, nick: &str, blurb: &str, minimum: libc::c_ulong, maximum: libc::c_ulong, default_value: libc::c_ulong, flags: ParamFlags) -> ParamSpec {
+        validate_name(name);
+        validate_range(name, minimum, maximum, default_value);
         unsafe {
             from_glib_full(gobject_sys::g_param_spec_ulong(name.to_glib_none().0, nick.to_glib_none().0, blurb.to_glib_none().0, minimum, maximum, default_value, flags.to_glib()))
         }
     }
 
     pub fn unichar(name: &str, nick: &str, blurb: &str, default_value: char, flags: ParamFlags) -> ParamSpec {
+        validate_name(name);
         unsafe {
             from_glib_full(gobject_sys::g_param_spec_unichar(name.to_glib_none().0, nick.to_glib_none().0, blurb.to_glib_none().0, default_value.to_glib(), flags.to_glib()))
         }
     }
 
     pub fn value_array(name: &str, nick: &str, blurb: &str, element_spec: &ParamSpec, flags: ParamFlags) -> ParamSpec {
+        validate_name(name);
         unsafe {
             from_glib_none(gobject_sys::g_param_spec_value_array(name.to_glib_none().0, nick.to_glib_none().0, blurb.to_glib_none().0, element_spec.to_glib_none().0, flags.to_glib()))
         }
     }
 
     pub fn variant(name: &str, nick: &str, blurb: &str, type_: &::VariantTy, default_value: Option<&::Variant>, flags: ParamFlags) -> ParamSpec {
+        validate_name(name);
         unsafe {
             from_glib_none(gobject_sys::g_param_spec_variant(name.to_glib_none().0, nick.to_glib_none().0, blurb.to_glib_none().0, type_.to_glib_none().0, default_value.to_glib_none().0, flags.to_glib()))
         }
     }
 }
+
+/// Panics if `name` is not a valid property name.
+///
+/// Valid names start with a letter and only contain letters, digits, `-` and `_`.
+fn validate_name(name: &str) {
+    let mut chars = name.chars();
+    let valid = match chars.next() {
+        Some(c) if c.is_ascii_alphabetic() => {
+            chars.all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        }
+        _ => false,
+    };
+    assert!(valid, "Invalid property name '{}'", name);
+}
+
+fn validate_range<T: PartialOrd + fmt::Debug>(name: &str, minimum: T, maximum: T, default_value: T) {
+    assert!(
+        minimum <= default_value && default_value <= maximum,
+        "Default value {:?} of property '{}' is not in the range {:?}..={:?}",
+        default_value, name, minimum, maximum
+    );
+}
+
+fn validate_type(name: &str, type_: ::Type, base: ::Type) {
+    assert!(
+        type_.is_a(&base),
+        "Value type {} of property '{}' is not a {}",
+        type_, name, base
+    );
+}
+
+fn validate_enum_default(name: &str, enum_type: ::Type, default_value: i32) {
+    let class = EnumClass::new(enum_type)
+        .unwrap_or_else(|| panic!("Value type {} of property '{}' is not an enum", enum_type, name));
+    assert!(
+        class.get_value(default_value).is_some(),
+        "Default value {} of property '{}' is not a value of {}",
+        default_value, name, enum_type
+    );
+}
+
+fn validate_flags_default(name: &str, flags_type: ::Type, default_value: u32) {
+    let class = FlagsClass::new(flags_type)
+        .unwrap_or_else(|| panic!("Value type {} of property '{}' is not a flags type", flags_type, name));
+    let mask = class.get_values().iter().fold(0, |mask, v| mask | v.get_value());
+    assert_eq!(
+        default_value & !mask, 0,
+        "Default value {:#x} of property '{}' contains bits not defined by {}",
+        default_value, name, flags_type
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use StaticType;
+
+    #[test]
+    fn test_new() {
+        let pspec = ParamSpec::int("some-int", "Some int", "Some integer", -5, 5, 1, ParamFlags::READWRITE);
+        assert_eq!(pspec.get_name(), "some-int");
+        assert_eq!(pspec.get_value_type(), ::Type::I32);
+        assert_eq!(pspec.get_default_value().unwrap().get::<i32>(), Some(1));
+
+        let pspec = ParamSpec::string("some_string", "Some string", "Some string", Some("abc"), ParamFlags::READABLE);
+        assert_eq!(pspec.get_default_value().unwrap().get::<String>(), Some(String::from("abc")));
+
+        let pspec = ParamSpec::object("obj", "Object", "Some object", ::Object::static_type(), ParamFlags::READWRITE);
+        assert_eq!(pspec.get_value_type(), ::Object::static_type());
+    }
+
+    #[test]
+    #[should_panic(expected = "is not in the range")]
+    fn test_invalid_default() {
+        ParamSpec::uint("count", "Count", "Some count", 1, 10, 0, ParamFlags::READWRITE);
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid property name")]
+    fn test_invalid_name() {
+        ParamSpec::boolean("1st", "First", "Invalid name", false, ParamFlags::READWRITE);
+    }
+
+    #[test]
+    #[should_panic(expected = "is not a")]
+    fn test_invalid_type() {
+        ParamSpec::object("obj", "Object", "Not an object", ::Type::String, ParamFlags::READWRITE);
+    }
+}
@@ -0,0 +1,160 @@
+// Copyright 2019, The Gtk-rs Project Developers.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+// Licensed under the MIT license, see the LICENSE file or <http://opensource.org/licenses/MIT>
+
+use glib_sys;
+use std::fmt;
+use std::ptr;
+use translate::*;
+
+/// A compiled glob-style pattern.
+///
+/// Patterns can contain `*` for any number of characters and `?` for a single character. There
+/// is no way to escape them, and unlike shell globs `*` and `?` also match `/`. This is the same
+/// matching as used by file filters in GTK.
+///
+/// ```
+/// use glib::PatternSpec;
+///
+/// let pattern = PatternSpec::new("*.txt");
+/// assert!(pattern.matches("notes.txt"));
+/// assert!(pattern.matches("dir/notes.txt"));
+/// assert!(!pattern.matches("notes.txt.bak"));
+/// ```
+pub struct PatternSpec {
+    ptr: ptr::NonNull<glib_sys::GPatternSpec>,
+    pattern: String,
+}
+
+// The compiled pattern is never modified after creation
+unsafe impl Send for PatternSpec {}
+unsafe impl Sync for PatternSpec {}
+
+impl PatternSpec {
+    pub fn new(pattern: &str) -> PatternSpec {
+        unsafe {
+            let ptr = glib_sys::g_pattern_spec_new(pattern.to_glib_none().0);
+            PatternSpec {
+                ptr: ptr::NonNull::new(ptr).expect("Failed to compile pattern"),
+                pattern: pattern.to_owned(),
+            }
+        }
+    }
+
+    /// Returns the pattern this was created from.
+    pub fn as_str(&self) -> &str {
+        &self.pattern
+    }
+
+    /// Returns `true` if the whole of `string` matches the pattern.
+    pub fn matches(&self, string: &str) -> bool {
+        unsafe {
+            from_glib(glib_sys::g_pattern_match_string(
+                self.ptr.as_ptr(),
+                string.to_glib_none().0,
+            ))
+        }
+    }
+
+    /// Same as [`matches()`], but with `string_reversed` being the characters of `string` in
+    /// reverse order.
+    ///
+    /// Depending on the pattern, GLib matches against the reversed string, which it otherwise
+    /// has to create each time. When matching the same string against many patterns it is
+    /// cheaper to reverse it only once:
+    ///
+    /// ```
+    /// use glib::PatternSpec;
+    ///
+    /// let patterns = [PatternSpec::new("*.txt"), PatternSpec::new("*.md")];
+    /// let name = "README.md";
+    /// let reversed = name.chars().rev().collect::<String>();
+    /// assert!(patterns.iter().any(|p| p.matches_with_reversed(name, &reversed)));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `string` and `string_reversed` differ in length.
+    ///
+    /// [`matches()`]: #method.matches
+    pub fn matches_with_reversed(&self, string: &str, string_reversed: &str) -> bool {
+        assert_eq!(string.len(), string_reversed.len(), "Reversed string of different length");
+        unsafe {
+            from_glib(glib_sys::g_pattern_match(
+                self.ptr.as_ptr(),
+                string.len() as u32,
+                string.to_glib_none().0,
+                string_reversed.to_glib_none().0,
+            ))
+        }
+    }
+}
+
+impl Drop for PatternSpec {
+    fn drop(&mut self) {
+        unsafe { glib_sys::g_pattern_spec_free(self.ptr.as_ptr()) }
+    }
+}
+
+impl Clone for PatternSpec {
+    fn clone(&self) -> PatternSpec {
+        PatternSpec::new(&self.pattern)
+    }
+}
+
+impl PartialEq for PatternSpec {
+    fn eq(&self, other: &PatternSpec) -> bool {
+        unsafe { from_glib(glib_sys::g_pattern_spec_equal(self.ptr.as_ptr(), other.ptr.as_ptr())) }
+    }
+}
+
+impl Eq for PatternSpec {}
+
+impl fmt::Debug for PatternSpec {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("PatternSpec").field(&self.pattern).finish()
+    }
+}
+
+impl<'a> From<&'a str> for PatternSpec {
+    fn from(pattern: &'a str) -> PatternSpec {
+        PatternSpec::new(pattern)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matches(pattern: &str, string: &str) -> bool {
+        let pattern = PatternSpec::new(pattern);
+        let reversed = string.chars().rev().collect::<String>();
+        let res = pattern.matches(string);
+        assert_eq!(res, pattern.matches_with_reversed(string, &reversed));
+        assert_eq!(res, ::pattern_match_simple(pattern.as_str(), string));
+        res
+    }
+
+    #[test]
+    fn test_matches() {
+        assert!(matches("*.txt", "a.txt"));
+        assert!(matches("*.txt", ".txt"));
+        assert!(matches("*.txt", "dir/a.txt"));
+        assert!(!matches("*.txt", "a.txt~"));
+        assert!(matches("a?c", "abc"));
+        assert!(matches("a?c", "a\u{e4}c"));
+        assert!(!matches("a?c", "ac"));
+        assert!(matches("*b*", "abc"));
+        assert!(matches("f\u{f6}*", "f\u{f6}\u{f6}"));
+        assert!(matches("", ""));
+        assert!(!matches("", "a"));
+    }
+
+    #[test]
+    fn test_equal() {
+        assert_eq!(PatternSpec::new("*.txt"), PatternSpec::new("*.txt"));
+        assert_eq!(PatternSpec::new("a**b"), PatternSpec::new("a*b"));
+        assert_ne!(PatternSpec::new("*.txt"), PatternSpec::new("*.md"));
+        assert_eq!(PatternSpec::new("*.txt").clone(), PatternSpec::new("*.txt"));
+    }
+}
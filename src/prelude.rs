@@ -3,6 +3,7 @@
 pub use {
     Cast,
     Continue,
+    FromVariant,
     IsA,
     IsClassFor,
     ObjectExt,
@@ -0,0 +1,247 @@
+// Copyright 2019, The Gtk-rs Project Developers.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+// Licensed under the MIT license, see the LICENSE file or <http://opensource.org/licenses/MIT>
+
+//! Opt-in profiling of source dispatch times.
+//!
+//! Once enabled with [`set_enabled()`], the dispatch time of every source created through these
+//! bindings, e.g. via `timeout_add()`, `idle_source_new()`, `MainContext::channel()`,
+//! `Source::new_from_impl()` or futures spawned on a `MainContext`, is recorded per source
+//! name. This helps finding the callbacks that take too long and block the
+//! main loop.
+//!
+//! ```ignore
+//! glib::profiler::set_enabled(true);
+//! // ... run the main loop for a while ...
+//! print!("{}", glib::profiler::report());
+//! ```
+//!
+//! [`set_enabled()`]: fn.set_enabled.html
+
+use glib_sys;
+use std::collections::HashMap;
+use std::ffi::CStr;
+use std::fmt::Write;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+lazy_static! {
+    static ref STATS: Mutex<HashMap<String, DispatchStats>> = Mutex::new(HashMap::new());
+}
+
+/// Dispatch time statistics of all sources with the same name.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DispatchStats {
+    count: u64,
+    min: Duration,
+    max: Duration,
+    total: Duration,
+}
+
+impl DispatchStats {
+    fn new(duration: Duration) -> DispatchStats {
+        DispatchStats {
+            count: 1,
+            min: duration,
+            max: duration,
+            total: duration,
+        }
+    }
+
+    fn add(&mut self, duration: Duration) {
+        self.count += 1;
+        if duration < self.min {
+            self.min = duration;
+        }
+        if duration > self.max {
+            self.max = duration;
+        }
+        self.total += duration;
+    }
+
+    /// Number of recorded dispatches.
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Shortest recorded dispatch time.
+    pub fn min(&self) -> Duration {
+        self.min
+    }
+
+    /// Longest recorded dispatch time.
+    pub fn max(&self) -> Duration {
+        self.max
+    }
+
+    /// Sum of all recorded dispatch times.
+    pub fn total(&self) -> Duration {
+        self.total
+    }
+
+    /// Average recorded dispatch time.
+    pub fn mean(&self) -> Duration {
+        let nanos = (self.total.as_secs() as u128 * 1_000_000_000 + u128::from(self.total.subsec_nanos()))
+            / u128::from(self.count);
+        Duration::new((nanos / 1_000_000_000) as u64, (nanos % 1_000_000_000) as u32)
+    }
+}
+
+/// Enables or disables recording of dispatch times.
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::SeqCst);
+}
+
+/// Returns `true` if recording of dispatch times is enabled.
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Removes all recorded dispatch times.
+pub fn reset() {
+    STATS.lock().unwrap().clear();
+}
+
+/// Returns the recorded statistics per source name, sorted by descending maximum dispatch time.
+///
+/// Sources without a name are recorded as `<unnamed>`.
+pub fn stats() -> Vec<(String, DispatchStats)> {
+    let mut stats = STATS
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|(name, stats)| (name.clone(), *stats))
+        .collect::<Vec<_>>();
+    stats.sort_by(|a, b| b.1.max.cmp(&a.1.max).then_with(|| a.0.cmp(&b.0)));
+    stats
+}
+
+/// Returns a human-readable report of the recorded statistics.
+pub fn report() -> String {
+    fn as_micros(d: Duration) -> u64 {
+        d.as_secs() * 1_000_000 + u64::from(d.subsec_micros())
+    }
+
+    let mut report = format!(
+        "{:<40} {:>10} {:>12} {:>12} {:>12}\n",
+        "source", "count", "min (µs)", "mean (µs)", "max (µs)"
+    );
+    for (name, stats) in stats() {
+        let _ = writeln!(
+            report,
+            "{:<40} {:>10} {:>12} {:>12} {:>12}",
+            name,
+            stats.count,
+            as_micros(stats.min),
+            as_micros(stats.mean()),
+            as_micros(stats.max)
+        );
+    }
+    report
+}
+
+fn record(name: String, duration: Duration) {
+    let mut stats = STATS.lock().unwrap();
+    if let Some(stats) = stats.get_mut(&name) {
+        stats.add(duration);
+        return;
+    }
+    stats.insert(name, DispatchStats::new(duration));
+}
+
+unsafe fn current_source_name() -> String {
+    let source = glib_sys::g_main_current_source();
+    if source.is_null() {
+        return String::from("<unnamed>");
+    }
+
+    let name = glib_sys::g_source_get_name(source);
+    if name.is_null() {
+        String::from("<unnamed>")
+    } else {
+        CStr::from_ptr(name).to_string_lossy().into_owned()
+    }
+}
+
+/// Calls `func` and records its duration for the currently dispatched source if enabled.
+pub(crate) fn profile<R, F: FnOnce() -> R>(func: F) -> R {
+    if !is_enabled() {
+        return func();
+    }
+
+    let start = Instant::now();
+    let res = func();
+    let duration = start.elapsed();
+
+    record(unsafe { current_source_name() }, duration);
+
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+    use Continue;
+    use MainContext;
+    use MainLoop;
+
+    #[test]
+    fn test_profiler() {
+        let c = MainContext::new();
+        let l = MainLoop::new(Some(&c), false);
+
+        c.acquire();
+
+        set_enabled(true);
+
+        let mut count = 0;
+        let l_clone = l.clone();
+        let source = ::timeout_source_new(1, Some("glib-rs-profiler-test"), ::PRIORITY_DEFAULT, move || {
+            thread::sleep(Duration::from_millis(2));
+            count += 1;
+            if count == 3 {
+                l_clone.quit();
+                Continue(false)
+            } else {
+                Continue(true)
+            }
+        });
+        source.attach(Some(&c));
+
+        // Sources implemented in Rust are recorded too
+        struct OnceSource;
+        impl ::SourceImpl for OnceSource {
+            fn dispatch(&self, _source: &::Source) -> Continue {
+                Continue(false)
+            }
+        }
+        let once = ::Source::new_from_impl(OnceSource);
+        once.set_name("glib-rs-profiler-test-impl");
+        once.set_ready_time(0);
+        once.attach(Some(&c));
+
+        l.run();
+
+        // Profiling is global, don't leave it enabled for other tests
+        set_enabled(false);
+
+        let (_, stats) = stats()
+            .into_iter()
+            .find(|&(ref name, _)| name == "glib-rs-profiler-test")
+            .unwrap();
+        assert_eq!(stats.count(), 3);
+        assert!(stats.min() >= Duration::from_millis(2));
+        assert!(stats.min() <= stats.mean() && stats.mean() <= stats.max());
+        assert!(report().contains("glib-rs-profiler-test"));
+
+        let (_, stats) = stats()
+            .into_iter()
+            .find(|&(ref name, _)| name == "glib-rs-profiler-test-impl")
+            .unwrap();
+        assert_eq!(stats.count(), 1);
+    }
+}
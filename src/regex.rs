@@ -0,0 +1,639 @@
+// Copyright 2019, The Gtk-rs Project Developers.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+// Licensed under the MIT license, see the LICENSE file or <http://opensource.org/licenses/MIT>
+
+use glib_sys;
+use std::ptr;
+use translate::*;
+use Continue;
+use Error;
+use GString;
+use RegexCompileFlags;
+use RegexMatchFlags;
+
+glib_wrapper! {
+    /// A compiled Perl-compatible regular expression.
+    ///
+    /// Matching behaves exactly like `GRegex` in C, which is useful when patterns are shared
+    /// with C libraries or have to behave the same as in other GNOME applications.
+    ///
+    /// ```
+    /// use glib::{Regex, RegexCompileFlags, RegexMatchFlags};
+    ///
+    /// let regex = Regex::new(
+    ///     r"(?<key>\w+)=(?<value>\w+)",
+    ///     RegexCompileFlags::empty(),
+    ///     RegexMatchFlags::empty(),
+    /// ).unwrap();
+    ///
+    /// let pairs = regex
+    ///     .matches("a=1, b=2", RegexMatchFlags::empty())
+    ///     .map(|m| (m.name("key").unwrap(), m.name("value").unwrap()))
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(pairs, vec![("a", "1"), ("b", "2")]);
+    /// ```
+    #[derive(Debug)]
+    pub struct Regex(Shared<glib_sys::GRegex>);
+
+    match fn {
+        ref => |ptr| glib_sys::g_regex_ref(ptr),
+        unref => |ptr| glib_sys::g_regex_unref(ptr),
+        get_type => || glib_sys::g_regex_get_type(),
+    }
+}
+
+// A compiled regex is immutable and GRegex is documented to be usable from multiple threads
+unsafe impl Send for Regex {}
+unsafe impl Sync for Regex {}
+
+impl Regex {
+    /// Compiles `pattern`.
+    ///
+    /// `match_options` are added to the options of every match done with this regex.
+    pub fn new(
+        pattern: &str,
+        compile_options: RegexCompileFlags,
+        match_options: RegexMatchFlags,
+    ) -> Result<Regex, Error> {
+        unsafe {
+            let mut error = ptr::null_mut();
+            let ret = glib_sys::g_regex_new(
+                pattern.to_glib_none().0,
+                compile_options.to_glib(),
+                match_options.to_glib(),
+                &mut error,
+            );
+            if error.is_null() { Ok(from_glib_full(ret)) } else { Err(from_glib_full(error)) }
+        }
+    }
+
+    pub fn get_pattern(&self) -> GString {
+        unsafe { from_glib_none(glib_sys::g_regex_get_pattern(self.to_glib_none().0)) }
+    }
+
+    pub fn get_compile_flags(&self) -> RegexCompileFlags {
+        unsafe { from_glib(glib_sys::g_regex_get_compile_flags(self.to_glib_none().0)) }
+    }
+
+    pub fn get_match_flags(&self) -> RegexMatchFlags {
+        unsafe { from_glib(glib_sys::g_regex_get_match_flags(self.to_glib_none().0)) }
+    }
+
+    /// Returns the number of capturing groups of the pattern.
+    pub fn get_capture_count(&self) -> usize {
+        unsafe { glib_sys::g_regex_get_capture_count(self.to_glib_none().0) as usize }
+    }
+
+    /// Returns the number of the highest back reference in the pattern.
+    pub fn get_max_backref(&self) -> usize {
+        unsafe { glib_sys::g_regex_get_max_backref(self.to_glib_none().0) as usize }
+    }
+
+    /// Returns the number of the capturing group called `name`.
+    pub fn get_string_number(&self, name: &str) -> Option<usize> {
+        let n = unsafe { glib_sys::g_regex_get_string_number(self.to_glib_none().0, name.to_glib_none().0) };
+        if n < 0 { None } else { Some(n as usize) }
+    }
+
+    /// Returns `true` if the pattern matches anywhere in `string`.
+    pub fn is_match(&self, string: &str, match_options: RegexMatchFlags) -> bool {
+        self.match_(string, match_options).is_some()
+    }
+
+    /// Returns the first match in `string`, or `None` if there is none or matching failed.
+    ///
+    /// Use [`MatchInfo::next()`] to continue with the following matches.
+    ///
+    /// [`MatchInfo::next()`]: struct.MatchInfo.html#method.next
+    pub fn match_<'a>(&self, string: &'a str, match_options: RegexMatchFlags) -> Option<MatchInfo<'a>> {
+        self.match_full(string, 0, match_options).ok().and_then(|info| info)
+    }
+
+    /// Returns the first match in `string`, starting at the byte offset `start_position`.
+    ///
+    /// Different to matching against `&string[start_position..]`, lookbehind assertions and
+    /// `^` take the text before `start_position` into account.
+    pub fn match_full<'a>(
+        &self,
+        string: &'a str,
+        start_position: usize,
+        match_options: RegexMatchFlags,
+    ) -> Result<Option<MatchInfo<'a>>, Error> {
+        assert!(start_position <= string.len(), "Start position out of bounds");
+        unsafe {
+            let mut match_info = ptr::null_mut();
+            let mut error = ptr::null_mut();
+            let matched: bool = from_glib(glib_sys::g_regex_match_full(
+                self.to_glib_none().0,
+                string.as_ptr() as *const _,
+                string.len() as isize,
+                start_position as i32,
+                match_options.to_glib(),
+                &mut match_info,
+                &mut error,
+            ));
+            let match_info = MatchInfo::from_glib_full(match_info, string);
+            if !error.is_null() {
+                Err(from_glib_full(error))
+            } else if matched {
+                Ok(Some(match_info))
+            } else {
+                Ok(None)
+            }
+        }
+    }
+
+    /// Returns an iterator over all non-overlapping matches in `string`.
+    ///
+    /// The iterator ends early if matching fails, e.g. because the backtracking limit was
+    /// reached.
+    pub fn matches<'a>(&self, string: &'a str, match_options: RegexMatchFlags) -> Matches<'a> {
+        Matches {
+            match_info: self.match_(string, match_options),
+        }
+    }
+
+    /// Replaces all matches in `string` with `replacement`.
+    ///
+    /// `replacement` can refer to the groups of the match, e.g. `\0` or `\g<name>`, see
+    /// [`check_replacement()`]. Use [`replace_literal()`] to insert `replacement` as is.
+    ///
+    /// [`check_replacement()`]: #method.check_replacement
+    /// [`replace_literal()`]: #method.replace_literal
+    pub fn replace(
+        &self,
+        string: &str,
+        start_position: usize,
+        replacement: &str,
+        match_options: RegexMatchFlags,
+    ) -> Result<GString, Error> {
+        assert!(start_position <= string.len(), "Start position out of bounds");
+        unsafe {
+            let mut error = ptr::null_mut();
+            let ret = glib_sys::g_regex_replace(
+                self.to_glib_none().0,
+                string.as_ptr() as *const _,
+                string.len() as isize,
+                start_position as i32,
+                replacement.to_glib_none().0,
+                match_options.to_glib(),
+                &mut error,
+            );
+            if error.is_null() { Ok(from_glib_full(ret)) } else { Err(from_glib_full(error)) }
+        }
+    }
+
+    /// Replaces all matches in `string` with `replacement`, without expanding references.
+    pub fn replace_literal(
+        &self,
+        string: &str,
+        start_position: usize,
+        replacement: &str,
+        match_options: RegexMatchFlags,
+    ) -> Result<GString, Error> {
+        assert!(start_position <= string.len(), "Start position out of bounds");
+        unsafe {
+            let mut error = ptr::null_mut();
+            let ret = glib_sys::g_regex_replace_literal(
+                self.to_glib_none().0,
+                string.as_ptr() as *const _,
+                string.len() as isize,
+                start_position as i32,
+                replacement.to_glib_none().0,
+                match_options.to_glib(),
+                &mut error,
+            );
+            if error.is_null() { Ok(from_glib_full(ret)) } else { Err(from_glib_full(error)) }
+        }
+    }
+
+    /// Replaces all matches in `string` with the text `eval` appends to the `String` it is
+    /// passed.
+    ///
+    /// Once `eval` returns `Continue(false)` the remaining matches are left unchanged.
+    ///
+    /// ```
+    /// use glib::{Continue, Regex, RegexCompileFlags, RegexMatchFlags};
+    ///
+    /// let regex = Regex::new(r"\d+", RegexCompileFlags::empty(), RegexMatchFlags::empty()).unwrap();
+    /// let doubled = regex.replace_eval("1 2 3", 0, RegexMatchFlags::empty(), |m, res| {
+    ///     let n = m.fetch(0).unwrap().parse::<u32>().unwrap();
+    ///     res.push_str(&(n * 2).to_string());
+    ///     Continue(true)
+    /// }).unwrap();
+    /// assert_eq!(doubled, "2 4 6");
+    /// ```
+    pub fn replace_eval<F: FnMut(&MatchInfo, &mut String) -> Continue>(
+        &self,
+        string: &str,
+        start_position: usize,
+        match_options: RegexMatchFlags,
+        eval: F,
+    ) -> Result<GString, Error> {
+        unsafe extern "C" fn eval_trampoline<F: FnMut(&MatchInfo, &mut String) -> Continue>(
+            match_info: *const glib_sys::GMatchInfo,
+            result: *mut glib_sys::GString,
+            user_data: glib_sys::gpointer,
+        ) -> glib_sys::gboolean {
+            let data = &mut *(user_data as *mut (F, String, &str));
+            let match_info = MatchInfo::from_glib_none(match_info, data.2);
+
+            data.1.clear();
+            let Continue(cont) = (data.0)(&match_info, &mut data.1);
+            glib_sys::g_string_append_len(result, data.1.as_ptr() as *const _, data.1.len() as isize);
+
+            // Returning TRUE stops the replacement
+            (!cont).to_glib()
+        }
+
+        assert!(start_position <= string.len(), "Start position out of bounds");
+        let mut data = (eval, String::new(), string);
+        unsafe {
+            let mut error = ptr::null_mut();
+            let ret = glib_sys::g_regex_replace_eval(
+                self.to_glib_none().0,
+                string.as_ptr() as *const _,
+                string.len() as isize,
+                start_position as i32,
+                match_options.to_glib(),
+                Some(eval_trampoline::<F>),
+                &mut data as *mut (F, String, &str) as glib_sys::gpointer,
+                &mut error,
+            );
+            if error.is_null() { Ok(from_glib_full(ret)) } else { Err(from_glib_full(error)) }
+        }
+    }
+
+    /// Splits `string` at the matches.
+    ///
+    /// The text of capturing groups is included in the result as well.
+    pub fn split(&self, string: &str, match_options: RegexMatchFlags) -> Vec<GString> {
+        self.split_full(string, 0, match_options, None).unwrap_or_default()
+    }
+
+    /// Splits `string` at the matches after `start_position` into at most `max_tokens` pieces.
+    pub fn split_full(
+        &self,
+        string: &str,
+        start_position: usize,
+        match_options: RegexMatchFlags,
+        max_tokens: Option<usize>,
+    ) -> Result<Vec<GString>, Error> {
+        assert!(start_position <= string.len(), "Start position out of bounds");
+        unsafe {
+            let mut error = ptr::null_mut();
+            let ret = glib_sys::g_regex_split_full(
+                self.to_glib_none().0,
+                string.as_ptr() as *const _,
+                string.len() as isize,
+                start_position as i32,
+                match_options.to_glib(),
+                max_tokens.map_or(0, |max_tokens| max_tokens as i32),
+                &mut error,
+            );
+            if error.is_null() {
+                Ok(FromGlibPtrContainer::from_glib_full(ret))
+            } else {
+                Err(from_glib_full(error))
+            }
+        }
+    }
+
+    /// Escapes all characters of `string` that have a special meaning in patterns.
+    pub fn escape_string(string: &str) -> GString {
+        unsafe {
+            from_glib_full(glib_sys::g_regex_escape_string(
+                string.as_ptr() as *const _,
+                string.len() as i32,
+            ))
+        }
+    }
+
+    /// Checks whether `replacement` is valid for [`replace()`].
+    ///
+    /// Returns whether `replacement` contains references to groups of the match.
+    ///
+    /// [`replace()`]: #method.replace
+    pub fn check_replacement(replacement: &str) -> Result<bool, Error> {
+        unsafe {
+            let mut has_references = glib_sys::GFALSE;
+            let mut error = ptr::null_mut();
+            let _ = glib_sys::g_regex_check_replacement(
+                replacement.to_glib_none().0,
+                &mut has_references,
+                &mut error,
+            );
+            if error.is_null() { Ok(from_glib(has_references)) } else { Err(from_glib_full(error)) }
+        }
+    }
+}
+
+/// The current match of a [`Regex`] in a string.
+///
+/// Group numbers count from 1, group 0 is the whole match. Byte offsets are relative to the
+/// start of the whole string.
+///
+/// [`Regex`]: struct.Regex.html
+#[derive(Debug)]
+pub struct MatchInfo<'a> {
+    ptr: ptr::NonNull<glib_sys::GMatchInfo>,
+    // The match info only points to the string that was matched, which is also not necessarily
+    // NUL-terminated
+    string: &'a str,
+}
+
+impl<'a> MatchInfo<'a> {
+    unsafe fn from_glib_full(ptr: *mut glib_sys::GMatchInfo, string: &'a str) -> MatchInfo<'a> {
+        MatchInfo {
+            ptr: ptr::NonNull::new(ptr).expect("No match info"),
+            string,
+        }
+    }
+
+    unsafe fn from_glib_none(ptr: *const glib_sys::GMatchInfo, string: &'a str) -> MatchInfo<'a> {
+        MatchInfo::from_glib_full(glib_sys::g_match_info_ref(ptr as *mut _), string)
+    }
+
+    pub fn get_regex(&self) -> Regex {
+        unsafe { from_glib_none(glib_sys::g_match_info_get_regex(self.ptr.as_ptr())) }
+    }
+
+    /// Returns `true` if the current state is a match.
+    pub fn matches(&self) -> bool {
+        unsafe { from_glib(glib_sys::g_match_info_matches(self.ptr.as_ptr())) }
+    }
+
+    /// Returns `true` if the match is only partial, see `RegexMatchFlags::PARTIAL`.
+    pub fn is_partial_match(&self) -> bool {
+        unsafe { from_glib(glib_sys::g_match_info_is_partial_match(self.ptr.as_ptr())) }
+    }
+
+    /// Returns the number of matched groups, including group 0.
+    pub fn get_match_count(&self) -> usize {
+        let count = unsafe { glib_sys::g_match_info_get_match_count(self.ptr.as_ptr()) };
+        if count < 0 { 0 } else { count as usize }
+    }
+
+    /// Advances to the next match.
+    ///
+    /// Returns `Ok(false)` once there are no further matches.
+    pub fn next(&mut self) -> Result<bool, Error> {
+        unsafe {
+            let mut error = ptr::null_mut();
+            let ret = glib_sys::g_match_info_next(self.ptr.as_ptr(), &mut error);
+            if error.is_null() { Ok(from_glib(ret)) } else { Err(from_glib_full(error)) }
+        }
+    }
+
+    /// Returns the start and end byte offset of group `match_num`.
+    ///
+    /// Returns `None` if the group did not take part in the match.
+    pub fn fetch_pos(&self, match_num: usize) -> Option<(usize, usize)> {
+        unsafe {
+            let mut start = 0;
+            let mut end = 0;
+            let ret: bool = from_glib(glib_sys::g_match_info_fetch_pos(
+                self.ptr.as_ptr(),
+                match_num as i32,
+                &mut start,
+                &mut end,
+            ));
+            if ret && start >= 0 { Some((start as usize, end as usize)) } else { None }
+        }
+    }
+
+    /// Returns the start and end byte offset of the group called `name`.
+    pub fn fetch_named_pos(&self, name: &str) -> Option<(usize, usize)> {
+        unsafe {
+            let mut start = 0;
+            let mut end = 0;
+            let ret: bool = from_glib(glib_sys::g_match_info_fetch_named_pos(
+                self.ptr.as_ptr(),
+                name.to_glib_none().0,
+                &mut start,
+                &mut end,
+            ));
+            if ret && start >= 0 { Some((start as usize, end as usize)) } else { None }
+        }
+    }
+
+    /// Returns the text of group `match_num`.
+    pub fn fetch(&self, match_num: usize) -> Option<&'a str> {
+        let string = self.string;
+        self.fetch_pos(match_num).and_then(|(start, end)| string.get(start..end))
+    }
+
+    /// Returns the text of the group called `name`.
+    pub fn fetch_named(&self, name: &str) -> Option<&'a str> {
+        let string = self.string;
+        self.fetch_named_pos(name).and_then(|(start, end)| string.get(start..end))
+    }
+
+    /// Returns the text of all groups of the current match, `None` for groups that did not
+    /// take part in the match.
+    pub fn fetch_all(&self) -> Vec<Option<&'a str>> {
+        (0..self.get_match_count()).map(|n| self.fetch(n)).collect()
+    }
+
+    /// Expands the references to groups in `string_to_expand` like [`Regex::replace()`].
+    ///
+    /// [`Regex::replace()`]: struct.Regex.html#method.replace
+    pub fn expand_references(&self, string_to_expand: &str) -> Result<GString, Error> {
+        unsafe {
+            let mut error = ptr::null_mut();
+            let ret = glib_sys::g_match_info_expand_references(
+                self.ptr.as_ptr(),
+                string_to_expand.to_glib_none().0,
+                &mut error,
+            );
+            if error.is_null() { Ok(from_glib_full(ret)) } else { Err(from_glib_full(error)) }
+        }
+    }
+
+    /// Returns a copy of the groups of the current match, which stays valid after advancing.
+    pub fn captures(&self) -> Captures<'a> {
+        let regex = self.get_regex();
+        let positions = (0..regex.get_capture_count() + 1)
+            .map(|n| self.fetch_pos(n))
+            .collect();
+
+        Captures {
+            string: self.string,
+            regex,
+            positions,
+        }
+    }
+}
+
+impl<'a> Drop for MatchInfo<'a> {
+    fn drop(&mut self) {
+        unsafe { glib_sys::g_match_info_unref(self.ptr.as_ptr()) }
+    }
+}
+
+/// The groups of a single match, as returned by [`Regex::matches()`].
+///
+/// [`Regex::matches()`]: struct.Regex.html#method.matches
+#[derive(Debug, Clone)]
+pub struct Captures<'a> {
+    string: &'a str,
+    regex: Regex,
+    positions: Vec<Option<(usize, usize)>>,
+}
+
+impl<'a> Captures<'a> {
+    /// Returns the number of groups, including group 0.
+    pub fn len(&self) -> usize {
+        self.positions.len()
+    }
+
+    /// Returns the start and end byte offset of group `n`.
+    pub fn pos(&self, n: usize) -> Option<(usize, usize)> {
+        self.positions.get(n).and_then(|pos| *pos)
+    }
+
+    /// Returns the text of group `n`.
+    pub fn get(&self, n: usize) -> Option<&'a str> {
+        let string = self.string;
+        self.pos(n).and_then(|(start, end)| string.get(start..end))
+    }
+
+    /// Returns the text of the group called `name`.
+    pub fn name(&self, name: &str) -> Option<&'a str> {
+        self.regex.get_string_number(name).and_then(|n| self.get(n))
+    }
+}
+
+/// Iterator over the matches of a [`Regex`] in a string.
+///
+/// See [`Regex::matches()`].
+///
+/// [`Regex`]: struct.Regex.html
+/// [`Regex::matches()`]: struct.Regex.html#method.matches
+#[derive(Debug)]
+pub struct Matches<'a> {
+    match_info: Option<MatchInfo<'a>>,
+}
+
+impl<'a> Iterator for Matches<'a> {
+    type Item = Captures<'a>;
+
+    fn next(&mut self) -> Option<Captures<'a>> {
+        let (captures, more) = match self.match_info {
+            Some(ref mut match_info) => {
+                if !match_info.matches() {
+                    return None;
+                }
+                let captures = match_info.captures();
+                (captures, match_info.next().unwrap_or(false))
+            }
+            None => return None,
+        };
+
+        if !more {
+            self.match_info = None;
+        }
+        Some(captures)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn regex(pattern: &str) -> Regex {
+        Regex::new(pattern, RegexCompileFlags::empty(), RegexMatchFlags::empty()).unwrap()
+    }
+
+    #[test]
+    fn test_new() {
+        let re = Regex::new("a(b)?", RegexCompileFlags::CASELESS, RegexMatchFlags::NOTEMPTY).unwrap();
+        assert_eq!(re.get_pattern(), "a(b)?");
+        assert!(re.get_compile_flags().contains(RegexCompileFlags::CASELESS));
+        assert_eq!(re.get_match_flags(), RegexMatchFlags::NOTEMPTY);
+        assert_eq!(re.get_capture_count(), 1);
+        assert!(re.is_match("xAB", RegexMatchFlags::empty()));
+        assert!(!re.is_match("xyz", RegexMatchFlags::empty()));
+
+        assert!(Regex::new("a(b", RegexCompileFlags::empty(), RegexMatchFlags::empty()).is_err());
+    }
+
+    #[test]
+    fn test_match_info() {
+        let re = regex(r"(?<word>[a-z]+)(\d)?");
+        assert_eq!(re.get_string_number("word"), Some(1));
+        assert_eq!(re.get_string_number("nope"), None);
+
+        let string = String::from("ab1 cd");
+        let mut info = re.match_(&string, RegexMatchFlags::empty()).unwrap();
+        assert!(info.matches());
+        assert_eq!(info.fetch(0), Some("ab1"));
+        assert_eq!(info.fetch_named("word"), Some("ab"));
+        assert_eq!(info.fetch_pos(2), Some((2, 3)));
+        assert_eq!(info.fetch_all(), vec![Some("ab1"), Some("ab"), Some("1")]);
+        assert_eq!(info.expand_references(r"\2-\g<word>").unwrap(), "1-ab");
+
+        assert!(info.next().unwrap());
+        assert_eq!(info.fetch(0), Some("cd"));
+        assert_eq!(info.fetch(2), None);
+        assert!(!info.next().unwrap());
+        assert!(!info.matches());
+
+        let info = re.match_full(&string, 1, RegexMatchFlags::empty()).unwrap().unwrap();
+        assert_eq!(info.fetch(0), Some("b1"));
+        assert!(re.match_("123", RegexMatchFlags::empty()).is_none());
+    }
+
+    #[test]
+    fn test_matches() {
+        let re = regex(r"(?<key>\w+)=(?<value>\w*)");
+        let captures = re.matches("a=1 b= c=3", RegexMatchFlags::empty()).collect::<Vec<_>>();
+        assert_eq!(captures.len(), 3);
+        assert_eq!(captures[0].len(), 3);
+        assert_eq!(captures[0].name("key"), Some("a"));
+        assert_eq!(captures[1].name("value"), Some(""));
+        assert_eq!(captures[2].get(0), Some("c=3"));
+        assert_eq!(captures[2].pos(2), Some((9, 10)));
+
+        // Empty matches don't loop forever
+        assert_eq!(regex("x*").matches("ab", RegexMatchFlags::empty()).count(), 3);
+        assert_eq!(regex("z").matches("ab", RegexMatchFlags::empty()).count(), 0);
+    }
+
+    #[test]
+    fn test_replace() {
+        let re = regex(r"(\w+)@(\w+)");
+        assert_eq!(re.replace("me@host", 0, r"\2 at \1", RegexMatchFlags::empty()).unwrap(), "host at me");
+        assert_eq!(re.replace_literal("me@host", 0, r"\2", RegexMatchFlags::empty()).unwrap(), r"\2");
+        assert!(re.replace("me@host", 0, r"\g<", RegexMatchFlags::empty()).is_err());
+
+        let re = regex(r"\d");
+        let mut count = 0;
+        let res = re.replace_eval("1 2 3", 0, RegexMatchFlags::empty(), |m, res| {
+            count += 1;
+            res.push_str(&m.fetch(0).unwrap().repeat(2));
+            Continue(count < 2)
+        }).unwrap();
+        assert_eq!(res, "11 22 3");
+
+        assert!(Regex::check_replacement(r"\0").unwrap());
+        assert!(!Regex::check_replacement("x").unwrap());
+        assert!(Regex::check_replacement(r"\g<").is_err());
+    }
+
+    #[test]
+    fn test_split() {
+        let re = regex(r"\s*,\s*");
+        assert_eq!(re.split("a , b,c", RegexMatchFlags::empty()), vec!["a", "b", "c"]);
+        assert_eq!(re.split_full("a,b,c", 0, RegexMatchFlags::empty(), Some(2)).unwrap(), vec!["a", "b,c"]);
+        assert_eq!(regex(r"(-)").split("a-b", RegexMatchFlags::empty()), vec!["a", "-", "b"]);
+    }
+
+    #[test]
+    fn test_escape() {
+        assert_eq!(Regex::escape_string("a.b*c"), r"a\.b\*c");
+        let re = regex(&Regex::escape_string("1+1=2"));
+        assert!(re.is_match("1+1=2", RegexMatchFlags::empty()));
+        assert!(!re.is_match("11=2", RegexMatchFlags::empty()));
+    }
+}
@@ -12,6 +12,27 @@ use std::ptr;
 use translate::*;
 
 /// Wrapper implementations for shared types. See `glib_wrapper!`.
+///
+/// This wraps C types with custom reference counting functions, i.e. types that are neither
+/// objects nor copied like boxed types. Cloning the wrapper increases the reference count and
+/// dropping it decreases the reference count again.
+///
+/// Each invocation defines a hidden `MemoryManager` type, so every wrapper has to be defined in
+/// its own module.
+///
+/// ```ignore
+/// mod frame_timings {
+///     glib_wrapper! {
+///         pub struct FrameTimings(Shared<ffi::GdkFrameTimings>);
+///
+///         match fn {
+///             ref => |ptr| ffi::gdk_frame_timings_ref(ptr),
+///             unref => |ptr| ffi::gdk_frame_timings_unref(ptr),
+///         }
+///     }
+/// }
+/// pub use frame_timings::FrameTimings;
+/// ```
 #[macro_export]
 macro_rules! glib_shared_wrapper {
     ([$($attr:meta)*] $name:ident, $ffi_name:path, @ref $ref_arg:ident $ref_expr:expr,
@@ -408,3 +429,96 @@ impl<T: 'static, MM: SharedMemoryManager<T>> FromGlibPtrBorrow<*mut T> for Share
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::ptr;
+    use translate::*;
+
+    pub struct Counted {
+        refcount: usize,
+    }
+
+    unsafe fn counted_ref(ptr: *mut Counted) {
+        (*ptr).refcount += 1;
+    }
+
+    unsafe fn counted_unref(ptr: *mut Counted) {
+        (*ptr).refcount -= 1;
+        if (*ptr).refcount == 0 {
+            let _ = Box::from_raw(ptr);
+        }
+    }
+
+    glib_wrapper! {
+        pub struct CountedWrapper(Shared<Counted>);
+
+        match fn {
+            ref => |ptr| counted_ref(ptr),
+            unref => |ptr| counted_unref(ptr),
+        }
+    }
+
+    #[test]
+    fn test_refcount() {
+        let ptr = Box::into_raw(Box::new(Counted { refcount: 1 }));
+
+        unsafe {
+            let w: CountedWrapper = from_glib_none(ptr);
+            assert_eq!((*ptr).refcount, 2);
+
+            let w2 = w.clone();
+            assert_eq!((*ptr).refcount, 3);
+            assert_eq!(w2.to_glib_none().0, ptr);
+
+            {
+                let borrowed: CountedWrapper = from_glib_borrow(ptr);
+                assert_eq!((*ptr).refcount, 3);
+                drop(borrowed);
+            }
+            assert_eq!((*ptr).refcount, 3);
+
+            drop(w);
+            drop(w2);
+            assert_eq!((*ptr).refcount, 1);
+
+            let full: *mut Counted = {
+                let w: CountedWrapper = from_glib_none(ptr);
+                w.to_glib_full()
+            };
+            assert_eq!((*ptr).refcount, 2);
+            let _: CountedWrapper = from_glib_full(full);
+            assert_eq!((*ptr).refcount, 1);
+
+            counted_unref(ptr);
+        }
+    }
+
+    #[test]
+    fn test_vec() {
+        let ptr = Box::into_raw(Box::new(Counted { refcount: 1 }));
+
+        unsafe {
+            let v: Vec<CountedWrapper> = vec![from_glib_none(ptr), from_glib_none(ptr)];
+            assert_eq!((*ptr).refcount, 3);
+
+            let (arr, stash) =
+                <CountedWrapper as ToGlibContainerFromSlice<*mut *mut Counted>>::to_glib_none_from_slice(&v);
+            assert_eq!(*arr, ptr);
+            assert_eq!(*arr.add(1), ptr);
+            assert_eq!(*arr.add(2), ptr::null_mut());
+            assert_eq!((*ptr).refcount, 3);
+
+            let v2: Vec<CountedWrapper> = FromGlibPtrArrayContainerAsVec::from_glib_none_as_vec(arr);
+            assert_eq!(v2.len(), 2);
+            assert_eq!((*ptr).refcount, 5);
+
+            drop(stash);
+            drop(v2);
+            drop(v);
+            assert_eq!((*ptr).refcount, 1);
+
+            counted_unref(ptr);
+        }
+    }
+}
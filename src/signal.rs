@@ -4,12 +4,20 @@
 
 //! `IMPL` Low level signal support.
 
-use glib_sys::{gboolean, gpointer};
+use glib_sys::{self, gboolean, gpointer};
 use gobject_sys::{self, GCallback};
-use libc::{c_char, c_ulong, c_void};
-use object::ObjectType;
+use libc::{c_char, c_uint, c_ulong, c_void};
+use object::{ObjectExt, ObjectType, WeakRef};
+use std::fmt;
 use std::mem;
-use translate::{from_glib, FromGlib, ToGlib, ToGlibPtr};
+use std::ptr;
+use std::slice;
+use translate::{from_glib, from_glib_none, FromGlib, ToGlib, ToGlibPtr};
+use value::FromValueOptional;
+use BoolError;
+use SignalFlags;
+use Type;
+use Value;
 
 /// The id of a signal that is returned by `connect`.
 #[derive(Debug, Eq, PartialEq)]
@@ -83,7 +91,522 @@ pub fn signal_stop_emission_by_name<T: ObjectType>(instance: &T, signal_name: &s
     }
 }
 
+/// The id of an emission hook that is returned by `signal_add_emission_hook`.
+#[derive(Debug, Eq, PartialEq)]
+pub struct EmissionHookId {
+    signal_id: u32,
+    hook_id: c_ulong,
+}
+
+/// Adds an emission hook for the signal `signal_name` of `type_`.
+///
+/// The hook is called for every emission of the signal on any instance of `type_` and its
+/// subtypes, before any handlers run, with the instance and the signal arguments. Emissions
+/// can happen on any thread. The hook stays installed as long as it returns `true`, or until it
+/// is removed with [`signal_remove_emission_hook()`].
+///
+/// `signal_name` can contain a detail, in which case the hook is only called for emissions with
+/// that detail. Returns an error if the signal does not exist or was registered with
+/// `SignalFlags::NO_HOOKS`.
+///
+/// ```ignore
+/// glib::signal_add_emission_hook(gtk::Button::static_type(), "clicked", |args| {
+///     println!("clicked {:?}", args[0]);
+///     true
+/// })?;
+/// ```
+///
+/// [`signal_remove_emission_hook()`]: fn.signal_remove_emission_hook.html
+pub fn signal_add_emission_hook<F>(type_: Type, signal_name: &str, f: F) -> Result<EmissionHookId, BoolError>
+where F: Fn(&[Value]) -> bool + Send + Sync + 'static {
+    unsafe extern "C" fn hook_trampoline<F: Fn(&[Value]) -> bool + Send + Sync + 'static>(
+        _ihint: *mut gobject_sys::GSignalInvocationHint,
+        n_param_values: c_uint,
+        param_values: *const gobject_sys::GValue,
+        data: gpointer,
+    ) -> gboolean {
+        let f: &F = &*(data as *const F);
+        let values = slice::from_raw_parts(param_values as *const Value, n_param_values as usize);
+        f(values).to_glib()
+    }
+
+    unsafe {
+        let mut signal_id = 0;
+        let mut detail = 0;
+
+        let found: bool = from_glib(gobject_sys::g_signal_parse_name(signal_name.to_glib_none().0,
+                                                                     type_.to_glib(), &mut signal_id,
+                                                                     &mut detail, false.to_glib()));
+        let query = match SignalQuery::new(signal_id) {
+            Some(ref query) if found => query.clone(),
+            _ => return Err(glib_bool_error!("Signal not found")),
+        };
+        if query.flags().contains(SignalFlags::NO_HOOKS) {
+            return Err(glib_bool_error!("Signal does not support emission hooks"));
+        }
+
+        let f: Box<F> = Box::new(f);
+        let hook_id = gobject_sys::g_signal_add_emission_hook(
+            signal_id,
+            detail,
+            Some(hook_trampoline::<F>),
+            Box::into_raw(f) as gpointer,
+            Some(destroy_hook::<F>),
+        );
+        assert!(hook_id > 0);
+
+        Ok(EmissionHookId { signal_id, hook_id })
+    }
+}
+
+/// Removes an emission hook that was added with `signal_add_emission_hook()`.
+///
+/// Must not be called for hooks that already removed themselves by returning `false`.
+#[allow(clippy::needless_pass_by_value)]
+pub fn signal_remove_emission_hook(hook_id: EmissionHookId) {
+    unsafe {
+        gobject_sys::g_signal_remove_emission_hook(hook_id.signal_id, hook_id.hook_id);
+    }
+}
+
+unsafe extern "C" fn destroy_hook<F>(ptr: gpointer) {
+    Box::<F>::from_raw(ptr as *mut _);
+}
+
+/// Disconnects a signal handler when dropped.
+///
+/// The guard only keeps a weak reference to the object, so it does not keep the object alive.
+///
+/// ```ignore
+/// let guard = glib::SignalHandlerGuard::new(&obj, obj.connect_notify(Some("name"), on_name_changed));
+///
+/// {
+///     // Don't call the handler for this change
+///     let _blocked = guard.block();
+///     obj.set_property("name", &"new-name").unwrap();
+/// }
+/// ```
+pub struct SignalHandlerGuard<T: ObjectType> {
+    object: WeakRef<T>,
+    handler_id: Option<SignalHandlerId>,
+}
+
+impl<T: ObjectType> SignalHandlerGuard<T> {
+    /// Creates a guard for the signal handler `handler_id` connected on `instance`.
+    pub fn new(instance: &T, handler_id: SignalHandlerId) -> SignalHandlerGuard<T> {
+        SignalHandlerGuard {
+            object: instance.downgrade(),
+            handler_id: Some(handler_id),
+        }
+    }
+
+    pub fn handler_id(&self) -> &SignalHandlerId {
+        self.handler_id.as_ref().unwrap()
+    }
+
+    /// Blocks the signal handler until the returned guard is dropped.
+    pub fn block(&self) -> SignalBlockGuard<T> {
+        let object = self.object.upgrade();
+        if let Some(ref object) = object {
+            signal_handler_block(object, self.handler_id());
+        }
+
+        SignalBlockGuard {
+            object,
+            handler_id: self.handler_id(),
+        }
+    }
+
+    /// Returns the signal handler id without disconnecting the signal handler.
+    pub fn into_handler_id(mut self) -> SignalHandlerId {
+        self.handler_id.take().unwrap()
+    }
+}
+
+impl<T: ObjectType> Drop for SignalHandlerGuard<T> {
+    fn drop(&mut self) {
+        let handler_id = match self.handler_id.take() {
+            Some(handler_id) => handler_id,
+            None => return,
+        };
+
+        if let Some(object) = self.object.upgrade() {
+            unsafe {
+                let instance = object.as_object_ref().to_glib_none().0;
+                // The handler might have been disconnected already in the meantime
+                if gobject_sys::g_signal_handler_is_connected(instance, handler_id.to_glib()) != 0 {
+                    gobject_sys::g_signal_handler_disconnect(instance, handler_id.to_glib());
+                }
+            }
+        }
+    }
+}
+
+impl<T: ObjectType> fmt::Debug for SignalHandlerGuard<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("SignalHandlerGuard")
+            .field("handler_id", &self.handler_id)
+            .finish()
+    }
+}
+
+/// Unblocks a signal handler when dropped.
+///
+/// See [`SignalHandlerGuard::block()`].
+///
+/// [`SignalHandlerGuard::block()`]: struct.SignalHandlerGuard.html#method.block
+#[derive(Debug)]
+#[must_use]
+pub struct SignalBlockGuard<'a, T: ObjectType> {
+    object: Option<T>,
+    handler_id: &'a SignalHandlerId,
+}
+
+impl<'a, T: ObjectType> Drop for SignalBlockGuard<'a, T> {
+    fn drop(&mut self) {
+        if let Some(ref object) = self.object {
+            signal_handler_unblock(object, self.handler_id);
+        }
+    }
+}
+
 unsafe extern "C" fn destroy_closure<F>(ptr: *mut c_void, _: *mut gobject_sys::GClosure) {
     // destroy
     Box::<F>::from_raw(ptr as *mut _);
 }
+
+/// Extracts the argument at `index` from the arguments passed to a signal handler connected
+/// with `ObjectExt::connect()`.
+///
+/// The first argument is always the object the signal was emitted on. Returns `Ok(None)` for
+/// arguments that are `None`, and an error if there is no argument at `index` or its type does
+/// not match `T`.
+///
+/// ```ignore
+/// obj.connect("name-changed", false, |args| {
+///     let obj = glib::signal_arg::<glib::Object>(args, 0).unwrap().unwrap();
+///     let name = glib::signal_arg::<String>(args, 1).unwrap();
+///     None
+/// })
+/// ```
+pub fn signal_arg<'a, T: FromValueOptional<'a>>(args: &'a [Value], index: usize) -> Result<Option<T>, BoolError> {
+    let value = match args.get(index) {
+        Some(value) => value,
+        None => return Err(glib_bool_error!("signal argument index out of range")),
+    };
+
+    if !value.type_().is_a(&T::static_type()) {
+        return Err(glib_bool_error!("signal argument type doesn't match the requested type"));
+    }
+
+    Ok(value.get::<T>())
+}
+
+/// Information about a signal that is registered for a type.
+///
+/// ```ignore
+/// for query in glib::SignalQuery::list_all(obj.get_type()) {
+///     println!("{}({:?}) -> {}", query.signal_name(), query.param_types(), query.return_type());
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SignalQuery {
+    signal_id: u32,
+    signal_name: String,
+    type_: Type,
+    flags: SignalFlags,
+    return_type: Type,
+    param_types: Vec<Type>,
+}
+
+impl SignalQuery {
+    /// Queries the signal with the ID `signal_id`, or returns `None` if there is no such signal.
+    pub fn new(signal_id: u32) -> Option<SignalQuery> {
+        unsafe {
+            let mut details: gobject_sys::GSignalQuery = mem::zeroed();
+            gobject_sys::g_signal_query(signal_id, &mut details);
+            if signal_id == 0 || details.signal_id != signal_id {
+                return None;
+            }
+
+            // The G_SIGNAL_TYPE_STATIC_SCOPE flag is stored in the type IDs
+            let strip = |type_: glib_sys::GType| from_glib(type_ & (!gobject_sys::G_TYPE_FLAG_RESERVED_ID_BIT));
+
+            let param_types = (0..details.n_params as usize)
+                .map(|i| strip(*details.param_types.add(i)))
+                .collect();
+
+            Some(SignalQuery {
+                signal_id,
+                signal_name: from_glib_none(details.signal_name),
+                type_: from_glib(details.itype),
+                flags: from_glib(details.signal_flags),
+                return_type: strip(details.return_type),
+                param_types,
+            })
+        }
+    }
+
+    /// Looks up the signal `signal_name` of `type_` or any of its ancestors or interfaces.
+    ///
+    /// The name must not contain a detail.
+    pub fn lookup(type_: Type, signal_name: &str) -> Option<SignalQuery> {
+        unsafe {
+            let _class = ClassRef::new(type_);
+            SignalQuery::new(gobject_sys::g_signal_lookup(signal_name.to_glib_none().0, type_.to_glib()))
+        }
+    }
+
+    /// Lists the signals that are registered by `type_` itself, without the signals of its
+    /// ancestors, in the order in which they were registered.
+    pub fn list(type_: Type) -> Vec<SignalQuery> {
+        if !type_.is_instantiatable() && !type_.is_interface() {
+            return Vec::new();
+        }
+
+        unsafe {
+            let _class = ClassRef::new(type_);
+
+            let mut n_ids = 0u32;
+            let ids = gobject_sys::g_signal_list_ids(type_.to_glib(), &mut n_ids);
+            let mut res = (0..n_ids as usize)
+                .filter_map(|i| SignalQuery::new(*ids.add(i)))
+                .collect::<Vec<_>>();
+            glib_sys::g_free(ids as *mut _);
+
+            // Signal IDs are assigned in registration order
+            res.sort_by_key(|query| query.signal_id);
+
+            res
+        }
+    }
+
+    /// Lists the signals of `type_` and all its ancestors, starting with the ones of `type_`
+    /// itself.
+    pub fn list_all(type_: Type) -> Vec<SignalQuery> {
+        type_.ancestors().into_iter().flat_map(SignalQuery::list).collect()
+    }
+
+    pub fn signal_id(&self) -> u32 {
+        self.signal_id
+    }
+
+    pub fn signal_name(&self) -> &str {
+        &self.signal_name
+    }
+
+    /// Returns the type that registered the signal.
+    pub fn type_(&self) -> Type {
+        self.type_
+    }
+
+    pub fn flags(&self) -> SignalFlags {
+        self.flags
+    }
+
+    pub fn return_type(&self) -> Type {
+        self.return_type
+    }
+
+    /// Returns the types of the signal's parameters, without the instance itself.
+    pub fn param_types(&self) -> &[Type] {
+        &self.param_types
+    }
+}
+
+// Signals are only registered once the class or default interface vtable is initialized
+struct ClassRef(glib_sys::gpointer, bool);
+
+impl ClassRef {
+    unsafe fn new(type_: Type) -> ClassRef {
+        if type_.is_interface() {
+            ClassRef(gobject_sys::g_type_default_interface_ref(type_.to_glib()), true)
+        } else if from_glib(gobject_sys::g_type_test_flags(type_.to_glib(), gobject_sys::G_TYPE_FLAG_CLASSED)) {
+            ClassRef(gobject_sys::g_type_class_ref(type_.to_glib()), false)
+        } else {
+            ClassRef(ptr::null_mut(), false)
+        }
+    }
+}
+
+impl Drop for ClassRef {
+    fn drop(&mut self) {
+        unsafe {
+            if self.0.is_null() {
+                return;
+            }
+
+            if self.1 {
+                gobject_sys::g_type_default_interface_unref(self.0);
+            } else {
+                gobject_sys::g_type_class_unref(self.0);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use value::ToValue;
+
+    #[cfg(feature = "subclassing")]
+    use subclass::object::test::SimpleObject;
+    #[cfg(feature = "subclassing")]
+    use subclass::types::ObjectSubclass;
+    #[cfg(feature = "subclassing")]
+    use Object;
+
+    #[test]
+    fn test_signal_arg() {
+        let args = [1i32.to_value(), "test".to_value(), None::<&str>.to_value()];
+
+        assert_eq!(signal_arg::<i32>(&args, 0).unwrap(), Some(1));
+        assert_eq!(signal_arg::<&str>(&args, 1).unwrap(), Some("test"));
+        assert_eq!(signal_arg::<String>(&args, 2).unwrap(), None);
+        assert!(signal_arg::<i32>(&args, 1).is_err());
+        assert!(signal_arg::<i32>(&args, 3).is_err());
+    }
+
+    #[test]
+    fn test_signal_query() {
+        use Object;
+        use ParamSpec;
+        use StaticType;
+
+        let notify = SignalQuery::lookup(Object::static_type(), "notify").unwrap();
+        assert_eq!(notify.signal_name(), "notify");
+        assert_eq!(notify.type_(), Object::static_type());
+        assert_eq!(notify.return_type(), Type::Unit);
+        assert_eq!(notify.param_types(), &[ParamSpec::static_type()]);
+        assert!(notify.flags().contains(SignalFlags::DETAILED));
+        assert_eq!(SignalQuery::new(notify.signal_id()), Some(notify.clone()));
+
+        assert_eq!(SignalQuery::list(Object::static_type()), vec![notify]);
+        assert!(SignalQuery::lookup(Object::static_type(), "no-such-signal").is_none());
+        assert!(SignalQuery::new(0).is_none());
+        assert!(SignalQuery::list(Type::I32).is_empty());
+    }
+
+    #[cfg(feature = "subclassing")]
+    #[test]
+    fn test_signal_handler_guard() {
+        use std::sync::{Arc, Mutex};
+
+        let type_ = SimpleObject::get_type();
+        let obj = Object::new(type_, &[]).unwrap();
+
+        let notified = Arc::new(Mutex::new(0));
+        let notified_clone = notified.clone();
+        let guard = SignalHandlerGuard::new(
+            &obj,
+            obj.connect_notify(Some("name"), move |_, _| {
+                *notified_clone.lock().unwrap() += 1;
+            }),
+        );
+
+        obj.set_property("name", &"test").unwrap();
+        assert_eq!(*notified.lock().unwrap(), 1);
+
+        {
+            let _blocked = guard.block();
+            obj.set_property("name", &"test2").unwrap();
+        }
+        assert_eq!(*notified.lock().unwrap(), 1);
+
+        obj.set_property("name", &"test3").unwrap();
+        assert_eq!(*notified.lock().unwrap(), 2);
+
+        drop(guard);
+        obj.set_property("name", &"test4").unwrap();
+        assert_eq!(*notified.lock().unwrap(), 2);
+    }
+
+    #[cfg(feature = "subclassing")]
+    #[test]
+    fn test_connect_local() {
+        use std::cell::RefCell;
+        use std::rc::Rc;
+
+        let type_ = SimpleObject::get_type();
+        let obj = Object::new(type_, &[]).unwrap();
+
+        let names = Rc::new(RefCell::new(Vec::new()));
+        let names_clone = names.clone();
+        obj.connect_local("name-changed", false, move |args| {
+            names_clone.borrow_mut().push(args[1].get::<String>());
+            None
+        })
+        .unwrap();
+
+        let notified = Rc::new(RefCell::new(0));
+        let notified_clone = notified.clone();
+        obj.connect_notify_local(Some("name"), move |_, _| {
+            *notified_clone.borrow_mut() += 1;
+        });
+
+        obj.set_property("name", &"test").unwrap();
+        assert_eq!(*names.borrow(), vec![Some(String::from("test"))]);
+        assert_eq!(*notified.borrow(), 1);
+    }
+
+    #[cfg(feature = "subclassing")]
+    #[test]
+    fn test_connect_local_other_thread() {
+        use std::thread;
+
+        // Objects are not Send, but emitting signals from other threads is allowed
+        struct SendObject(Object);
+        unsafe impl Send for SendObject {}
+
+        let obj = Object::new(SimpleObject::get_type(), &[]).unwrap();
+        obj.connect_local("handled", false, |_| Some(true.to_value())).unwrap();
+        assert_eq!(
+            obj.emit("handled", &[]).unwrap().and_then(|v| v.get::<bool>()),
+            Some(true)
+        );
+
+        // The handler is skipped with a critical warning and the default value is returned
+        let send_obj = SendObject(obj.clone());
+        let ret = thread::spawn(move || {
+            let obj = send_obj;
+            obj.0.emit("handled", &[]).unwrap().and_then(|v| v.get::<bool>())
+        })
+        .join()
+        .unwrap();
+        assert_eq!(ret, Some(false));
+    }
+
+    #[cfg(feature = "subclassing")]
+    #[test]
+    fn test_connect_returning() {
+        let type_ = SimpleObject::get_type();
+        let obj = Object::new(type_, &[]).unwrap();
+
+        obj.connect_returning("handled", false, |_| false).unwrap();
+        assert_eq!(
+            obj.emit("handled", &[]).unwrap().and_then(|v| v.get::<bool>()),
+            Some(false)
+        );
+
+        obj.connect_returning("handled", false, |_| true).unwrap();
+        assert_eq!(
+            obj.emit("handled", &[]).unwrap().and_then(|v| v.get::<bool>()),
+            Some(true)
+        );
+
+        // Return types are checked when connecting
+        assert!(obj.connect_returning("handled", false, |_| 1i32).is_err());
+        assert!(obj.connect_returning("name-changed", false, |_| true).is_err());
+        assert!(obj.connect_returning("no-such-signal", false, |_| true).is_err());
+
+        // Runs after the class handler and overrides its return value
+        obj.connect_returning("change-name", true, |_| String::from("old-name")).unwrap();
+        assert_eq!(
+            obj.emit("change-name", &[&"new-name"])
+                .unwrap()
+                .and_then(|v| v.get::<String>()),
+            Some(String::from("old-name"))
+        );
+    }
+}
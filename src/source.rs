@@ -6,15 +6,20 @@ use glib_sys::{self, gboolean, gpointer};
 #[cfg(all(not(unix), feature = "dox"))]
 use libc::c_int as RawFd;
 use std::cell::RefCell;
+use std::cmp;
 use std::mem::transmute;
 #[cfg(unix)]
 use std::os::unix::io::RawFd;
 use std::process;
+use std::sync::atomic::{AtomicIsize, Ordering};
 use std::thread;
+use std::time::Duration;
 use translate::{from_glib, from_glib_full, FromGlib, ToGlib, ToGlibPtr};
 #[cfg(any(unix, feature = "dox"))]
 use IOCondition;
 
+use is_main_thread;
+use FragileSend;
 use MainContext;
 use Source;
 
@@ -100,9 +105,37 @@ impl Drop for CallbackGuard {
     }
 }
 
+// Converts `duration` to the milliseconds used by timeout sources, saturating at
+// `u32::max_value()`
+pub(crate) fn duration_to_millis(duration: Duration) -> u32 {
+    let millis = duration
+        .as_secs()
+        .saturating_mul(1000)
+        .saturating_add(u64::from(duration.subsec_millis()));
+    if millis > u64::from(u32::max_value()) {
+        u32::max_value()
+    } else {
+        millis as u32
+    }
+}
+
+// Converts `duration` to the microseconds used for ready times, saturating at
+// `i64::max_value()`
+pub(crate) fn duration_to_micros(duration: Duration) -> i64 {
+    let micros = duration
+        .as_secs()
+        .saturating_mul(1_000_000)
+        .saturating_add(u64::from(duration.subsec_micros()));
+    if micros > i64::max_value() as u64 {
+        i64::max_value()
+    } else {
+        micros as i64
+    }
+}
+
 unsafe extern "C" fn trampoline<F: FnMut() -> Continue + 'static>(func: gpointer) -> gboolean {
     let func: &RefCell<F> = &*(func as *const RefCell<F>);
-    (&mut *func.borrow_mut())().to_glib()
+    ::profiler::profile(|| (&mut *func.borrow_mut())()).to_glib()
 }
 
 unsafe extern "C" fn destroy_closure<F: FnMut() -> Continue + 'static>(ptr: gpointer) {
@@ -116,7 +149,7 @@ fn into_raw<F: FnMut() -> Continue + 'static>(func: F) -> gpointer {
 
 unsafe extern "C" fn trampoline_child_watch<F: FnMut(Pid, i32) + 'static>(pid: glib_sys::GPid, status: i32, func: gpointer) {
     let func: &RefCell<F> = &*(func as *const RefCell<F>);
-    (&mut *func.borrow_mut())(Pid(pid), status)
+    ::profiler::profile(|| (&mut *func.borrow_mut())(Pid(pid), status))
 }
 
 unsafe extern "C" fn destroy_closure_child_watch<F: FnMut(Pid, i32) + 'static>(ptr: gpointer) {
@@ -131,7 +164,7 @@ fn into_raw_child_watch<F: FnMut(Pid, i32) + 'static>(func: F) -> gpointer {
 #[cfg(any(unix, feature = "dox"))]
 unsafe extern "C" fn trampoline_unix_fd<F: FnMut(RawFd, IOCondition) -> Continue + 'static>(fd: i32, condition: glib_sys::GIOCondition, func: gpointer) -> gboolean {
     let func: &RefCell<F> = &*(func as *const RefCell<F>);
-    (&mut *func.borrow_mut())(fd, from_glib(condition)).to_glib()
+    ::profiler::profile(|| (&mut *func.borrow_mut())(fd, from_glib(condition))).to_glib()
 }
 
 #[cfg(any(unix, feature = "dox"))]
@@ -145,6 +178,62 @@ fn into_raw_unix_fd<F: FnMut(RawFd, IOCondition) -> Continue + 'static>(func: F)
     Box::into_raw(func) as gpointer
 }
 
+// The callbacks of the `_local` functions are wrapped in a `FragileSend`, so that they are only
+// ever called and dropped on the main thread, even if their source is dispatched or destroyed
+// on another thread. Calls from other threads log a critical warning and remove the source.
+fn local_func<F: FnMut() -> Continue + 'static>(func: F) -> impl FnMut() -> Continue + 'static {
+    let mut func = FragileSend::new(func);
+    move || match func.try_get_mut() {
+        Some(func) => func(),
+        None => {
+            g_critical!("glib-rs", "Local source dispatched on a different thread than the main thread");
+            Continue(false)
+        }
+    }
+}
+
+fn local_func_child_watch<F: FnMut(Pid, i32) + 'static>(func: F) -> impl FnMut(Pid, i32) + 'static {
+    let mut func = FragileSend::new(func);
+    move |pid, status| match func.try_get_mut() {
+        Some(func) => func(pid, status),
+        None => g_critical!("glib-rs", "Local source dispatched on a different thread than the main thread"),
+    }
+}
+
+#[cfg(any(unix, feature = "dox"))]
+fn local_func_unix_fd<F: FnMut(RawFd, IOCondition) -> Continue + 'static>(func: F)
+    -> impl FnMut(RawFd, IOCondition) -> Continue + 'static {
+    let mut func = FragileSend::new(func);
+    move |fd, condition| match func.try_get_mut() {
+        Some(func) => func(fd, condition),
+        None => {
+            g_critical!("glib-rs", "Local source dispatched on a different thread than the main thread");
+            Continue(false)
+        }
+    }
+}
+
+// Returns the trampoline, the boxed closure and its destroy function for `func`
+fn into_raw_parts<F: FnMut() -> Continue + 'static>(func: F)
+    -> (glib_sys::GSourceFunc, gpointer, glib_sys::GDestroyNotify) {
+    (Some(trampoline::<F>), into_raw(func), Some(destroy_closure::<F>))
+}
+
+fn into_raw_parts_child_watch<F: FnMut(Pid, i32) + 'static>(func: F)
+    -> (glib_sys::GChildWatchFunc, gpointer, glib_sys::GDestroyNotify) {
+    unsafe {
+        (Some(transmute(trampoline_child_watch::<F> as usize)), into_raw_child_watch(func), Some(destroy_closure_child_watch::<F>))
+    }
+}
+
+#[cfg(any(unix, feature = "dox"))]
+fn into_raw_parts_unix_fd<F: FnMut(RawFd, IOCondition) -> Continue + 'static>(func: F)
+    -> (glib_sys::GUnixFDSourceFunc, gpointer, glib_sys::GDestroyNotify) {
+    unsafe {
+        (Some(transmute(trampoline_unix_fd::<F> as usize)), into_raw_unix_fd(func), Some(destroy_closure_unix_fd::<F>))
+    }
+}
+
 /// Adds a closure to be called by the default main loop when it's idle.
 ///
 /// `func` will be called repeatedly until it returns `Continue(false)`.
@@ -154,7 +243,7 @@ fn into_raw_unix_fd<F: FnMut(RawFd, IOCondition) -> Continue + 'static>(func: F)
 pub fn idle_add<F>(func: F) -> SourceId
 where F: FnMut() -> Continue + Send + 'static {
     unsafe {
-        from_glib(glib_sys::g_idle_add_full(glib_sys::G_PRIORITY_DEFAULT_IDLE, Some(trampoline::<F>),
+        from_glib(glib_sys::g_idle_add_full(get_default_source_priority(SourceKind::Idle).to_glib(), Some(trampoline::<F>),
             into_raw(func), Some(destroy_closure::<F>)))
     }
 }
@@ -174,9 +263,10 @@ where F: FnMut() -> Continue + Send + 'static {
 pub fn idle_add_local<F>(func: F) -> SourceId
 where F: FnMut() -> Continue + 'static {
     unsafe {
-        assert!(MainContext::default().is_owner());
-        from_glib(glib_sys::g_idle_add_full(glib_sys::G_PRIORITY_DEFAULT_IDLE, Some(trampoline::<F>),
-            into_raw(func), Some(destroy_closure::<F>)))
+        assert!(is_main_thread(), "Local source added from a thread other than the main thread");
+        let (trampoline, func, destroy) = into_raw_parts(local_func(func));
+        from_glib(glib_sys::g_idle_add_full(get_default_source_priority(SourceKind::Idle).to_glib(), trampoline,
+            func, destroy))
     }
 }
 
@@ -193,7 +283,7 @@ where F: FnMut() -> Continue + 'static {
 pub fn timeout_add<F>(interval: u32, func: F) -> SourceId
 where F: FnMut() -> Continue + Send + 'static {
     unsafe {
-        from_glib(glib_sys::g_timeout_add_full(glib_sys::G_PRIORITY_DEFAULT, interval,
+        from_glib(glib_sys::g_timeout_add_full(get_default_source_priority(SourceKind::Timeout).to_glib(), interval,
             Some(trampoline::<F>), into_raw(func), Some(destroy_closure::<F>)))
     }
 }
@@ -217,9 +307,10 @@ where F: FnMut() -> Continue + Send + 'static {
 pub fn timeout_add_local<F>(interval: u32, func: F) -> SourceId
 where F: FnMut() -> Continue + 'static {
     unsafe {
-        assert!(MainContext::default().is_owner());
-        from_glib(glib_sys::g_timeout_add_full(glib_sys::G_PRIORITY_DEFAULT, interval,
-            Some(trampoline::<F>), into_raw(func), Some(destroy_closure::<F>)))
+        assert!(is_main_thread(), "Local source added from a thread other than the main thread");
+        let (trampoline, func, destroy) = into_raw_parts(local_func(func));
+        from_glib(glib_sys::g_timeout_add_full(get_default_source_priority(SourceKind::Timeout).to_glib(), interval,
+            trampoline, func, destroy))
     }
 }
 
@@ -235,7 +326,7 @@ where F: FnMut() -> Continue + 'static {
 pub fn timeout_add_seconds<F>(interval: u32, func: F) -> SourceId
 where F: FnMut() -> Continue + Send + 'static {
     unsafe {
-        from_glib(glib_sys::g_timeout_add_seconds_full(glib_sys::G_PRIORITY_DEFAULT, interval,
+        from_glib(glib_sys::g_timeout_add_seconds_full(get_default_source_priority(SourceKind::Timeout).to_glib(), interval,
             Some(trampoline::<F>), into_raw(func), Some(destroy_closure::<F>)))
     }
 }
@@ -258,9 +349,10 @@ where F: FnMut() -> Continue + Send + 'static {
 pub fn timeout_add_seconds_local<F>(interval: u32, func: F) -> SourceId
 where F: FnMut() -> Continue + 'static {
     unsafe {
-        assert!(MainContext::default().is_owner());
-        from_glib(glib_sys::g_timeout_add_seconds_full(glib_sys::G_PRIORITY_DEFAULT, interval,
-            Some(trampoline::<F>), into_raw(func), Some(destroy_closure::<F>)))
+        assert!(is_main_thread(), "Local source added from a thread other than the main thread");
+        let (trampoline, func, destroy) = into_raw_parts(local_func(func));
+        from_glib(glib_sys::g_timeout_add_seconds_full(get_default_source_priority(SourceKind::Timeout).to_glib(), interval,
+            trampoline, func, destroy))
     }
 }
 
@@ -289,9 +381,10 @@ where F: FnMut(Pid, i32) + Send + 'static {
 pub fn child_watch_add_local<F>(pid: Pid, func: F) -> SourceId
 where F: FnMut(Pid, i32) + 'static {
     unsafe {
-        assert!(MainContext::default().is_owner());
+        assert!(is_main_thread(), "Local source added from a thread other than the main thread");
+        let (trampoline, func, destroy) = into_raw_parts_child_watch(local_func_child_watch(func));
         from_glib(glib_sys::g_child_watch_add_full(glib_sys::G_PRIORITY_DEFAULT, pid.0,
-            Some(transmute(trampoline_child_watch::<F> as usize)), into_raw_child_watch(func), Some(destroy_closure_child_watch::<F>)))
+            trampoline, func, destroy))
     }
 }
 
@@ -328,9 +421,10 @@ where F: FnMut() -> Continue + Send + 'static {
 pub fn unix_signal_add_local<F>(signum: i32, func: F) -> SourceId
 where F: FnMut() -> Continue + 'static {
     unsafe {
-        assert!(MainContext::default().is_owner());
+        assert!(is_main_thread(), "Local source added from a thread other than the main thread");
+        let (trampoline, func, destroy) = into_raw_parts(local_func(func));
         from_glib(glib_sys::g_unix_signal_add_full(glib_sys::G_PRIORITY_DEFAULT, signum,
-            Some(trampoline::<F>), into_raw(func), Some(destroy_closure::<F>)))
+            trampoline, func, destroy))
     }
 }
 
@@ -346,7 +440,7 @@ where F: FnMut() -> Continue + 'static {
 pub fn unix_fd_add<F>(fd: RawFd, condition: IOCondition, func: F) -> SourceId
 where F: FnMut(RawFd, IOCondition) -> Continue + Send + 'static {
     unsafe {
-        from_glib(glib_sys::g_unix_fd_add_full(glib_sys::G_PRIORITY_DEFAULT, fd, condition.to_glib(),
+        from_glib(glib_sys::g_unix_fd_add_full(get_default_source_priority(SourceKind::FdWatch).to_glib(), fd, condition.to_glib(),
             Some(transmute(trampoline_unix_fd::<F> as usize)), into_raw_unix_fd(func), Some(destroy_closure_unix_fd::<F>)))
     }
 }
@@ -369,9 +463,10 @@ where F: FnMut(RawFd, IOCondition) -> Continue + Send + 'static {
 pub fn unix_fd_add_local<F>(fd: RawFd, condition: IOCondition, func: F) -> SourceId
 where F: FnMut(RawFd, IOCondition) -> Continue + 'static {
     unsafe {
-        assert!(MainContext::default().is_owner());
-        from_glib(glib_sys::g_unix_fd_add_full(glib_sys::G_PRIORITY_DEFAULT, fd, condition.to_glib(),
-            Some(transmute(trampoline_unix_fd::<F> as usize)), into_raw_unix_fd(func), Some(destroy_closure_unix_fd::<F>)))
+        assert!(is_main_thread(), "Local source added from a thread other than the main thread");
+        let (trampoline, func, destroy) = into_raw_parts_unix_fd(local_func_unix_fd(func));
+        from_glib(glib_sys::g_unix_fd_add_full(get_default_source_priority(SourceKind::FdWatch).to_glib(), fd, condition.to_glib(),
+            trampoline, func, destroy))
     }
 }
 
@@ -391,9 +486,61 @@ pub fn source_remove(source_id: SourceId) {
 
 /// The priority of sources
 ///
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+/// Sources with a higher priority are dispatched before sources with a lower priority. In
+/// `GLib` a higher priority corresponds to a numerically lower value, the ordering of this type
+/// is by importance however: `Priority::HIGH > Priority::LOW`.
+///
+/// Custom priorities can be created relative to the standard levels, e.g. for slotting a
+/// source between GTK's resize (`Priority::HIGH_IDLE.lower_by(10)`) and redraw
+/// (`Priority::HIGH_IDLE.lower_by(20)`) priorities.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
 pub struct Priority(i32);
 
+impl Priority {
+    /// Use this for high priority event sources.
+    pub const HIGH: Priority = Priority(glib_sys::G_PRIORITY_HIGH);
+    /// Use this for default priority event sources.
+    pub const DEFAULT: Priority = Priority(glib_sys::G_PRIORITY_DEFAULT);
+    /// Use this for high priority idle functions.
+    pub const HIGH_IDLE: Priority = Priority(glib_sys::G_PRIORITY_HIGH_IDLE);
+    /// Use this for default priority idle functions.
+    pub const DEFAULT_IDLE: Priority = Priority(glib_sys::G_PRIORITY_DEFAULT_IDLE);
+    /// Use this for very low priority background tasks.
+    pub const LOW: Priority = Priority(glib_sys::G_PRIORITY_LOW);
+
+    /// Creates a priority from its raw `GLib` value, where lower values mean higher priority.
+    pub fn new(value: i32) -> Priority {
+        Priority(value)
+    }
+
+    /// Returns the raw `GLib` value of the priority.
+    pub fn value(self) -> i32 {
+        self.0
+    }
+
+    /// Returns a priority that is higher by `amount` steps.
+    pub fn higher_by(self, amount: i32) -> Priority {
+        Priority(self.0.saturating_sub(amount))
+    }
+
+    /// Returns a priority that is lower by `amount` steps.
+    pub fn lower_by(self, amount: i32) -> Priority {
+        Priority(self.0.saturating_add(amount))
+    }
+}
+
+impl PartialOrd for Priority {
+    fn partial_cmp(&self, other: &Priority) -> Option<cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Priority {
+    fn cmp(&self, other: &Priority) -> cmp::Ordering {
+        other.0.cmp(&self.0)
+    }
+}
+
 #[doc(hidden)]
 impl ToGlib for Priority {
     type GlibType = i32;
@@ -424,6 +571,58 @@ pub const PRIORITY_HIGH_IDLE: Priority = Priority(glib_sys::G_PRIORITY_HIGH_IDLE
 pub const PRIORITY_DEFAULT_IDLE: Priority = Priority(glib_sys::G_PRIORITY_DEFAULT_IDLE);
 pub const PRIORITY_LOW: Priority = Priority(glib_sys::G_PRIORITY_LOW);
 
+/// Kind of the sources created by the convenience functions that don't take a priority.
+///
+/// See [`set_default_source_priority()`].
+///
+/// [`set_default_source_priority()`]: fn.set_default_source_priority.html
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum SourceKind {
+    /// Main context channels created by `MainContext::default_priority_channel()` and
+    /// `MainContext::default_priority_sync_channel()`. Defaults to `Priority::DEFAULT`.
+    Channel,
+    /// Timeouts created by `timeout_add()` and related functions, including the timeout futures
+    /// and interval streams. Defaults to `Priority::DEFAULT`.
+    Timeout,
+    /// Idle sources created by `idle_add()` and `idle_add_local()`. Defaults to
+    /// `Priority::DEFAULT_IDLE`.
+    Idle,
+    /// File descriptor watches created by `unix_fd_add()` and related functions, including the
+    /// file descriptor futures and streams. Defaults to `Priority::DEFAULT`.
+    FdWatch,
+}
+
+// Marks a kind for which no default priority was configured
+const PRIORITY_UNSET: isize = ::std::isize::MIN;
+
+static DEFAULT_PRIORITIES: [AtomicIsize; 4] = [
+    AtomicIsize::new(PRIORITY_UNSET),
+    AtomicIsize::new(PRIORITY_UNSET),
+    AtomicIsize::new(PRIORITY_UNSET),
+    AtomicIsize::new(PRIORITY_UNSET),
+];
+
+/// Sets the priority that is used for all sources of the given `kind` that are created
+/// afterwards by the convenience functions that don't take a priority.
+///
+/// This allows applications to e.g. globally lower the priority of the timeouts created by the
+/// libraries they use.
+pub fn set_default_source_priority(kind: SourceKind, priority: Priority) {
+    DEFAULT_PRIORITIES[kind as usize].store(priority.0 as isize, Ordering::SeqCst);
+}
+
+/// Returns the priority that is used for sources of the given `kind`, see
+/// [`set_default_source_priority()`].
+///
+/// [`set_default_source_priority()`]: fn.set_default_source_priority.html
+pub fn get_default_source_priority(kind: SourceKind) -> Priority {
+    match DEFAULT_PRIORITIES[kind as usize].load(Ordering::SeqCst) {
+        PRIORITY_UNSET if kind == SourceKind::Idle => PRIORITY_DEFAULT_IDLE,
+        PRIORITY_UNSET => PRIORITY_DEFAULT,
+        priority => Priority(priority as i32),
+    }
+}
+
 /// Adds a closure to be called by the main loop the return `Source` is attached to when it's idle.
 ///
 /// `func` will be called repeatedly until it returns `Continue(false)`.
@@ -558,4 +757,184 @@ impl Source {
             glib_result_from_gboolean!(glib_sys::g_source_remove(tag.to_glib()), "Failed to remove source")
         }
     }
+
+    /// Sets the closure that is called whenever the source is dispatched.
+    ///
+    /// This replaces any previously set callback.
+    ///
+    /// # Safety
+    ///
+    /// The callback is called as a `GSourceFunc` without any arguments. This must only be used
+    /// for sources that call their callback like that, e.g. idle and timeout sources, and
+    /// sources that were created by `Source::new_from_impl()` and call the callback themselves.
+    /// Child watch and unix fd sources, for example, call it with different arguments.
+    pub unsafe fn set_callback<F>(&self, func: F)
+    where F: FnMut() -> Continue + Send + 'static {
+        glib_sys::g_source_set_callback(self.to_glib_none().0, Some(trampoline::<F>), into_raw(func), Some(destroy_closure::<F>));
+    }
+
+    /// Sets the name of the source, which is used for debugging and profiling.
+    pub fn set_name(&self, name: &str) {
+        unsafe {
+            glib_sys::g_source_set_name(self.to_glib_none().0, name.to_glib_none().0);
+        }
+    }
+
+    /// Sets the priority of the source.
+    ///
+    /// It is a programmer error to call this on a source that was added as child source to
+    /// another source.
+    pub fn set_priority(&self, priority: Priority) {
+        unsafe {
+            glib_sys::g_source_set_priority(self.to_glib_none().0, priority.to_glib());
+        }
+    }
+
+    /// Sets whether the source can be dispatched recursively, i.e. while it is already being
+    /// dispatched.
+    pub fn set_can_recurse(&self, can_recurse: bool) {
+        unsafe {
+            glib_sys::g_source_set_can_recurse(self.to_glib_none().0, can_recurse.to_glib());
+        }
+    }
+
+    /// Sets the monotonic time in microseconds at which the source will be dispatched.
+    ///
+    /// `0` means the source is ready immediately, `-1` means it does not become ready based on
+    /// the time.
+    pub fn set_ready_time(&self, ready_time: i64) {
+        unsafe {
+            glib_sys::g_source_set_ready_time(self.to_glib_none().0, ready_time);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use MainLoop;
+
+    #[test]
+    fn test_duration_to_millis() {
+        assert_eq!(duration_to_millis(Duration::from_millis(1500)), 1500);
+        assert_eq!(duration_to_millis(Duration::from_micros(1999)), 1);
+        assert_eq!(duration_to_millis(Duration::from_secs(u64::from(u32::max_value()))), u32::max_value());
+        assert_eq!(duration_to_millis(Duration::from_secs(u64::max_value())), u32::max_value());
+    }
+
+    #[test]
+    fn test_duration_to_micros() {
+        assert_eq!(duration_to_micros(Duration::from_millis(1500)), 1_500_000);
+        assert_eq!(duration_to_micros(Duration::from_nanos(1999)), 1);
+        assert_eq!(duration_to_micros(Duration::from_secs(u64::max_value())), i64::max_value());
+    }
+
+    #[test]
+    fn test_source_setters() {
+        let c = MainContext::new();
+        let l = MainLoop::new(Some(&c), false);
+
+        c.acquire();
+
+        let source = idle_source_new(None, PRIORITY_DEFAULT, || Continue(false));
+        source.set_name("test-source");
+        source.set_priority(PRIORITY_HIGH);
+        source.set_can_recurse(true);
+        assert_eq!(source.get_name().as_ref().map(|s| s.as_str()), Some("test-source"));
+        assert_eq!(source.get_priority(), PRIORITY_HIGH.to_glib());
+        assert!(source.get_can_recurse());
+
+        let count = Arc::new(AtomicUsize::new(0));
+        let count_clone = count.clone();
+        let l_clone = l.clone();
+        unsafe {
+            source.set_callback(move || {
+                count_clone.fetch_add(1, Ordering::SeqCst);
+                l_clone.quit();
+                Continue(false)
+            });
+        }
+        source.attach(Some(&c));
+
+        l.run();
+
+        assert_eq!(count.load(Ordering::SeqCst), 1);
+        assert!(source.is_destroyed());
+    }
+
+    #[test]
+    fn test_source_ready_time() {
+        let c = MainContext::new();
+        let l = MainLoop::new(Some(&c), false);
+
+        c.acquire();
+
+        let source = timeout_source_new_seconds(3600, None, PRIORITY_DEFAULT, || Continue(true));
+        let l_clone = l.clone();
+        unsafe {
+            source.set_callback(move || {
+                l_clone.quit();
+                Continue(false)
+            });
+        }
+        source.attach(Some(&c));
+        source.set_ready_time(0);
+        assert_eq!(source.get_ready_time(), 0);
+
+        l.run();
+    }
+
+    #[test]
+    fn test_main_depth_and_current_source() {
+        let c = MainContext::new();
+        let l = MainLoop::new(Some(&c), false);
+
+        c.acquire();
+
+        assert_eq!(::main_depth(), 0);
+        assert!(::main_current_source().is_none());
+
+        let source = idle_source_new(Some("test-current-source"), PRIORITY_DEFAULT, || Continue(false));
+        let l_clone = l.clone();
+        unsafe {
+            source.set_callback(move || {
+                assert_eq!(::main_depth(), 1);
+                let current = ::main_current_source().expect("No current source");
+                assert_eq!(current.get_name().as_ref().map(|s| s.as_str()), Some("test-current-source"));
+                l_clone.quit();
+                Continue(false)
+            });
+        }
+        source.attach(Some(&c));
+
+        l.run();
+    }
+
+    #[test]
+    fn test_default_source_priority() {
+        assert_eq!(get_default_source_priority(SourceKind::Idle), PRIORITY_DEFAULT_IDLE);
+
+        set_default_source_priority(SourceKind::Channel, Priority::LOW);
+        assert_eq!(get_default_source_priority(SourceKind::Channel), Priority::LOW);
+        set_default_source_priority(SourceKind::Channel, Priority::DEFAULT);
+        assert_eq!(get_default_source_priority(SourceKind::Channel), Priority::DEFAULT);
+    }
+
+    #[test]
+    fn test_priority() {
+        assert_eq!(Priority::default(), Priority::DEFAULT);
+        assert_eq!(Priority::DEFAULT, PRIORITY_DEFAULT);
+        assert!(Priority::HIGH > Priority::DEFAULT);
+        assert!(Priority::DEFAULT > Priority::HIGH_IDLE);
+        assert!(Priority::DEFAULT_IDLE > Priority::LOW);
+
+        let custom = Priority::HIGH_IDLE.lower_by(15);
+        assert_eq!(custom.value(), glib_sys::G_PRIORITY_HIGH_IDLE + 15);
+        assert!(custom < Priority::HIGH_IDLE.lower_by(10));
+        assert!(custom > Priority::HIGH_IDLE.lower_by(20));
+        assert_eq!(custom.higher_by(15), Priority::HIGH_IDLE);
+        assert_eq!(Priority::new(42).value(), 42);
+    }
 }
@@ -6,8 +6,17 @@ use futures::channel::{mpsc, oneshot};
 use futures::prelude::*;
 use futures::task;
 use futures::task::Poll;
+use source::duration_to_millis;
 use std::marker::Unpin;
 use std::pin;
+use std::time::Duration;
+
+#[cfg(any(unix, feature = "dox"))]
+use IOCondition;
+#[cfg(all(not(unix), feature = "dox"))]
+use libc::c_int as RawFd;
+#[cfg(unix)]
+use std::os::unix::io::RawFd;
 
 use Continue;
 use MainContext;
@@ -105,7 +114,7 @@ impl<T, F> Drop for SourceFuture<T, F> {
 ///
 /// The `Future` must be spawned on an `Executor` backed by a `glib::MainContext`.
 pub fn timeout_future(value: u32) -> Box<Future<Output = ()> + std::marker::Unpin + Send> {
-    timeout_future_with_priority(::PRIORITY_DEFAULT, value)
+    timeout_future_with_priority(::get_default_source_priority(::SourceKind::Timeout), value)
 }
 
 /// Create a `Future` that will resolve after the given number of milliseconds.
@@ -114,7 +123,7 @@ pub fn timeout_future(value: u32) -> Box<Future<Output = ()> + std::marker::Unpi
 pub fn timeout_future_with_priority(priority: Priority, value: u32) -> Box<Future<Output = ()> + std::marker::Unpin + Send> {
     Box::new(SourceFuture::new(move |send| {
         let mut send = Some(send);
-        ::timeout_source_new(value, None, priority, move || {
+        ::timeout_source_new(value, Some("glib::timeout_future"), priority, move || {
             let _ = send.take().unwrap().send(());
             Continue(false)
         })
@@ -125,7 +134,7 @@ pub fn timeout_future_with_priority(priority: Priority, value: u32) -> Box<Futur
 ///
 /// The `Future` must be spawned on an `Executor` backed by a `glib::MainContext`.
 pub fn timeout_future_seconds(value: u32) -> Box<Future<Output = ()> + std::marker::Unpin + Send> {
-    timeout_future_seconds_with_priority(::PRIORITY_DEFAULT, value)
+    timeout_future_seconds_with_priority(::get_default_source_priority(::SourceKind::Timeout), value)
 }
 
 /// Create a `Future` that will resolve after the given number of seconds.
@@ -137,13 +146,34 @@ pub fn timeout_future_seconds_with_priority(
 ) -> Box<Future<Output = ()> + std::marker::Unpin + Send> {
     Box::new(SourceFuture::new(move |send| {
         let mut send = Some(send);
-        ::timeout_source_new_seconds(value, None, priority, move || {
+        ::timeout_source_new_seconds(value, Some("glib::timeout_future_seconds"), priority, move || {
             let _ = send.take().unwrap().send(());
             Continue(false)
         })
     }))
 }
 
+/// Create a `Future` that will resolve after the given `Duration`.
+///
+/// The duration is rounded down to millisecond granularity.
+///
+/// The `Future` must be spawned on an `Executor` backed by a `glib::MainContext`.
+pub fn timeout_future_duration(value: Duration) -> Box<Future<Output = ()> + std::marker::Unpin + Send> {
+    timeout_future_duration_with_priority(::get_default_source_priority(::SourceKind::Timeout), value)
+}
+
+/// Create a `Future` that will resolve after the given `Duration`.
+///
+/// The duration is rounded down to millisecond granularity.
+///
+/// The `Future` must be spawned on an `Executor` backed by a `glib::MainContext`.
+pub fn timeout_future_duration_with_priority(
+    priority: Priority,
+    value: Duration,
+) -> Box<Future<Output = ()> + std::marker::Unpin + Send> {
+    timeout_future_with_priority(priority, duration_to_millis(value))
+}
+
 /// Create a `Future` that will resolve once the child process with the given pid exits
 ///
 /// The `Future` will resolve to the pid of the child process and the exit code.
@@ -164,7 +194,7 @@ pub fn child_watch_future_with_priority(
 ) -> Box<Future<Output = (::Pid, i32)> + std::marker::Unpin + Send> {
     Box::new(SourceFuture::new(move |send| {
         let mut send = Some(send);
-        ::child_watch_source_new(pid, None, priority, move |pid, code| {
+        ::child_watch_source_new(pid, Some("glib::child_watch_future"), priority, move |pid, code| {
             let _ = send.take().unwrap().send((pid, code));
         })
     }))
@@ -185,13 +215,45 @@ pub fn unix_signal_future(signum: i32) -> Box<Future<Output = ()> + std::marker:
 pub fn unix_signal_future_with_priority(priority: Priority, signum: i32) -> Box<Future<Output = ()> + std::marker::Unpin + Send> {
     Box::new(SourceFuture::new(move |send| {
         let mut send = Some(send);
-        ::unix_signal_source_new(signum, None, priority, move || {
+        ::unix_signal_source_new(signum, Some("glib::unix_signal_future"), priority, move || {
             let _ = send.take().unwrap().send(());
             Continue(false)
         })
     }))
 }
 
+#[cfg(any(unix, feature = "dox"))]
+/// Create a `Future` that will resolve once the given UNIX file descriptor reaches one of the
+/// given IO conditions
+///
+/// The `Future` will resolve to the condition that was reached.
+///
+/// The `Future` must be spawned on an `Executor` backed by a `glib::MainContext`.
+pub fn unix_fd_future(fd: RawFd, condition: IOCondition) -> Box<Future<Output = IOCondition> + std::marker::Unpin + Send> {
+    unix_fd_future_with_priority(::get_default_source_priority(::SourceKind::FdWatch), fd, condition)
+}
+
+#[cfg(any(unix, feature = "dox"))]
+/// Create a `Future` that will resolve once the given UNIX file descriptor reaches one of the
+/// given IO conditions
+///
+/// The `Future` will resolve to the condition that was reached.
+///
+/// The `Future` must be spawned on an `Executor` backed by a `glib::MainContext`.
+pub fn unix_fd_future_with_priority(
+    priority: Priority,
+    fd: RawFd,
+    condition: IOCondition,
+) -> Box<Future<Output = IOCondition> + std::marker::Unpin + Send> {
+    Box::new(SourceFuture::new(move |send| {
+        let mut send = Some(send);
+        ::unix_fd_source_new(fd, condition, Some("glib::unix_fd_future"), priority, move |_, condition| {
+            let _ = send.take().unwrap().send(condition);
+            Continue(false)
+        })
+    }))
+}
+
 /// Represents a `Stream` around a `glib::Source`. The stream will
 /// be provide all values that are provided by the source
 pub struct SourceStream<F, T> {
@@ -284,7 +346,7 @@ impl<T, F> Drop for SourceStream<T, F> {
 ///
 /// The `Future` must be spawned on an `Executor` backed by a `glib::MainContext`.
 pub fn interval_stream(value: u32) -> Box<Stream<Item = ()> + std::marker::Unpin + Send> {
-    interval_stream_with_priority(::PRIORITY_DEFAULT, value)
+    interval_stream_with_priority(::get_default_source_priority(::SourceKind::Timeout), value)
 }
 
 /// Create a `Stream` that will provide a value every given number of milliseconds.
@@ -292,7 +354,7 @@ pub fn interval_stream(value: u32) -> Box<Stream<Item = ()> + std::marker::Unpin
 /// The `Future` must be spawned on an `Executor` backed by a `glib::MainContext`.
 pub fn interval_stream_with_priority(priority: Priority, value: u32) -> Box<Stream<Item = ()> + std::marker::Unpin + Send> {
     Box::new(SourceStream::new(move |send| {
-        ::timeout_source_new(value, None, priority, move || {
+        ::timeout_source_new(value, Some("glib::interval_stream"), priority, move || {
             if send.unbounded_send(()).is_err() {
                 Continue(false)
             } else {
@@ -302,11 +364,32 @@ pub fn interval_stream_with_priority(priority: Priority, value: u32) -> Box<Stre
     }))
 }
 
+/// Create a `Stream` that will provide a value every given `Duration`.
+///
+/// The duration is rounded down to millisecond granularity.
+///
+/// The `Stream` must be spawned on an `Executor` backed by a `glib::MainContext`.
+pub fn interval_stream_duration(value: Duration) -> Box<Stream<Item = ()> + std::marker::Unpin + Send> {
+    interval_stream_duration_with_priority(::get_default_source_priority(::SourceKind::Timeout), value)
+}
+
+/// Create a `Stream` that will provide a value every given `Duration`.
+///
+/// The duration is rounded down to millisecond granularity.
+///
+/// The `Stream` must be spawned on an `Executor` backed by a `glib::MainContext`.
+pub fn interval_stream_duration_with_priority(
+    priority: Priority,
+    value: Duration,
+) -> Box<Stream<Item = ()> + std::marker::Unpin + Send> {
+    interval_stream_with_priority(priority, duration_to_millis(value))
+}
+
 /// Create a `Stream` that will provide a value every given number of seconds.
 ///
 /// The `Stream` must be spawned on an `Executor` backed by a `glib::MainContext`.
 pub fn interval_stream_seconds(value: u32) -> Box<Stream<Item = ()> + std::marker::Unpin + Send> {
-    interval_stream_seconds_with_priority(::PRIORITY_DEFAULT, value)
+    interval_stream_seconds_with_priority(::get_default_source_priority(::SourceKind::Timeout), value)
 }
 
 /// Create a `Stream` that will provide a value every given number of seconds.
@@ -317,7 +400,7 @@ pub fn interval_stream_seconds_with_priority(
     value: u32,
 ) -> Box<Stream<Item = ()> + std::marker::Unpin + Send> {
     Box::new(SourceStream::new(move |send| {
-        ::timeout_source_new_seconds(value, None, priority, move || {
+        ::timeout_source_new_seconds(value, Some("glib::interval_stream_seconds"), priority, move || {
             if send.unbounded_send(()).is_err() {
                 Continue(false)
             } else {
@@ -341,7 +424,7 @@ pub fn unix_signal_stream(signum: i32) -> Box<Stream<Item = ()> + std::marker::U
 /// The `Stream` must be spawned on an `Executor` backed by a `glib::MainContext`.
 pub fn unix_signal_stream_with_priority(priority: Priority, signum: i32) -> Box<Stream<Item = ()> + std::marker::Unpin + Send> {
     Box::new(SourceStream::new(move |send| {
-        ::unix_signal_source_new(signum, None, priority, move || {
+        ::unix_signal_source_new(signum, Some("glib::unix_signal_stream"), priority, move || {
             if send.unbounded_send(()).is_err() {
                 Continue(false)
             } else {
@@ -351,6 +434,36 @@ pub fn unix_signal_stream_with_priority(priority: Priority, signum: i32) -> Box<
     }))
 }
 
+#[cfg(any(unix, feature = "dox"))]
+/// Create a `Stream` that will provide a value whenever the given UNIX file descriptor reaches
+/// one of the given IO conditions
+///
+/// The `Stream` must be spawned on an `Executor` backed by a `glib::MainContext`.
+pub fn unix_fd_stream(fd: RawFd, condition: IOCondition) -> Box<Stream<Item = IOCondition> + std::marker::Unpin + Send> {
+    unix_fd_stream_with_priority(::get_default_source_priority(::SourceKind::FdWatch), fd, condition)
+}
+
+#[cfg(any(unix, feature = "dox"))]
+/// Create a `Stream` that will provide a value whenever the given UNIX file descriptor reaches
+/// one of the given IO conditions
+///
+/// The `Stream` must be spawned on an `Executor` backed by a `glib::MainContext`.
+pub fn unix_fd_stream_with_priority(
+    priority: Priority,
+    fd: RawFd,
+    condition: IOCondition,
+) -> Box<Stream<Item = IOCondition> + std::marker::Unpin + Send> {
+    Box::new(SourceStream::new(move |send| {
+        ::unix_fd_source_new(fd, condition, Some("glib::unix_fd_stream"), priority, move |_, condition| {
+            if send.unbounded_send(condition).is_err() {
+                Continue(false)
+            } else {
+                Continue(true)
+            }
+        })
+    }))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -365,6 +478,28 @@ mod tests {
         assert_eq!(res, ());
     }
 
+    #[test]
+    fn test_timeout_duration() {
+        let c = MainContext::new();
+
+        let res = c.block_on(timeout_future_duration(Duration::from_millis(20)));
+
+        assert_eq!(res, ());
+    }
+
+    #[test]
+    fn test_interval_duration() {
+        let c = MainContext::new();
+
+        let count = c.block_on(
+            interval_stream_duration(Duration::from_millis(20))
+                .take(3)
+                .fold(0, |count, ()| future::ready(count + 1)),
+        );
+
+        assert_eq!(count, 3);
+    }
+
     #[test]
     fn test_timeout_send() {
         let c = MainContext::new();
@@ -420,4 +555,20 @@ mod tests {
 
         assert_eq!(res, 1);
     }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_unix_fd() {
+        use std::io::Write;
+        use std::os::unix::io::AsRawFd;
+        use std::os::unix::net::UnixStream;
+
+        let c = MainContext::new();
+
+        let (mut a, b) = UnixStream::pair().unwrap();
+        a.write_all(b"x").unwrap();
+
+        let condition = c.block_on(unix_fd_future(b.as_raw_fd(), IOCondition::IN));
+        assert!(condition.contains(IOCondition::IN));
+    }
 }
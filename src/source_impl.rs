@@ -0,0 +1,189 @@
+// Copyright 2019, The Gtk-rs Project Developers.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+// Licensed under the MIT license, see the LICENSE file or <http://opensource.org/licenses/MIT>
+
+use glib_sys;
+use std::mem;
+use std::ptr;
+use translate::{from_glib_borrow, from_glib_full, mut_override, ToGlib};
+use Continue;
+use Source;
+
+/// Trait for implementing custom `GSource`s in Rust.
+///
+/// The implementation is stored inside the `GSource` that is returned by
+/// [`Source::new_from_impl()`] and dropped together with it.
+///
+/// All functions are called from the thread that is currently iterating the main context the
+/// source is attached to, and never concurrently. Mutable state has to use interior mutability,
+/// e.g. via `Cell` or `RefCell`.
+///
+/// [`Source::new_from_impl()`]: struct.Source.html#method.new_from_impl
+pub trait SourceImpl: Send + 'static {
+    /// Called before polling the file descriptors of the main context.
+    ///
+    /// Returns `true` if the source is ready to be dispatched, and optionally the maximum
+    /// timeout in milliseconds that may be used for polling.
+    ///
+    /// Sources that only rely on `Source::set_ready_time()` or child sources do not need to
+    /// implement this.
+    fn prepare(&self, _source: &Source) -> (bool, Option<u32>) {
+        (false, None)
+    }
+
+    /// Called after polling the file descriptors of the main context.
+    ///
+    /// Returns `true` if the source is ready to be dispatched.
+    fn check(&self, _source: &Source) -> bool {
+        false
+    }
+
+    /// Called when the source is ready.
+    ///
+    /// The source is removed from its main context if this returns `Continue(false)`.
+    fn dispatch(&self, source: &Source) -> Continue;
+
+    /// Called when the source is finalized, right before the implementation is dropped.
+    fn finalize(&self) {}
+}
+
+#[repr(C)]
+struct RustSource<T: SourceImpl> {
+    source: glib_sys::GSource,
+    source_funcs: Option<Box<glib_sys::GSourceFuncs>>,
+    imp: Option<T>,
+}
+
+impl<T: SourceImpl> RustSource<T> {
+    unsafe fn imp<'a>(source: *mut glib_sys::GSource) -> &'a T {
+        let source = &*(source as *const RustSource<T>);
+        source.imp.as_ref().expect("Source without implementation")
+    }
+}
+
+unsafe extern "C" fn prepare<T: SourceImpl>(
+    source: *mut glib_sys::GSource,
+    timeout: *mut i32,
+) -> glib_sys::gboolean {
+    let (ready, new_timeout) = RustSource::<T>::imp(source).prepare(&from_glib_borrow(source));
+
+    *timeout = match new_timeout {
+        Some(new_timeout) if new_timeout <= i32::max_value() as u32 => new_timeout as i32,
+        _ => -1,
+    };
+
+    ready.to_glib()
+}
+
+unsafe extern "C" fn check<T: SourceImpl>(source: *mut glib_sys::GSource) -> glib_sys::gboolean {
+    RustSource::<T>::imp(source)
+        .check(&from_glib_borrow(source))
+        .to_glib()
+}
+
+unsafe extern "C" fn dispatch<T: SourceImpl>(
+    source: *mut glib_sys::GSource,
+    _callback: glib_sys::GSourceFunc,
+    _user_data: glib_sys::gpointer,
+) -> glib_sys::gboolean {
+    let imp = RustSource::<T>::imp(source);
+    ::profiler::profile(|| imp.dispatch(&from_glib_borrow(source))).to_glib()
+}
+
+unsafe extern "C" fn finalize<T: SourceImpl>(source: *mut glib_sys::GSource) {
+    let source = &mut *(source as *mut RustSource<T>);
+
+    // Drop all memory we own by taking it out of the Options
+    if let Some(imp) = source.imp.take() {
+        imp.finalize();
+    }
+    let _ = source.source_funcs.take();
+}
+
+impl Source {
+    /// Creates a new `Source` that is driven by the given `SourceImpl`.
+    ///
+    /// The returned source has to be attached to a main context with `Source::attach()`.
+    pub fn new_from_impl<T: SourceImpl>(imp: T) -> Source {
+        unsafe {
+            let source_funcs = Box::new(glib_sys::GSourceFuncs {
+                prepare: Some(prepare::<T>),
+                check: Some(check::<T>),
+                dispatch: Some(dispatch::<T>),
+                finalize: Some(finalize::<T>),
+                closure_callback: None,
+                closure_marshal: None,
+            });
+
+            let source = glib_sys::g_source_new(
+                mut_override(&*source_funcs),
+                mem::size_of::<RustSource<T>>() as u32,
+            ) as *mut RustSource<T>;
+            assert!(!source.is_null());
+
+            // Store all our data inside our part of the GSource
+            {
+                let source = &mut *source;
+                ptr::write(&mut source.imp, Some(imp));
+                ptr::write(&mut source.source_funcs, Some(source_funcs));
+            }
+
+            from_glib_full(mut_override(&(*source).source))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use MainContext;
+    use MainLoop;
+
+    struct CountingSource {
+        count: AtomicUsize,
+        finalized: Arc<AtomicUsize>,
+        main_loop: MainLoop,
+    }
+
+    impl SourceImpl for CountingSource {
+        fn prepare(&self, _source: &Source) -> (bool, Option<u32>) {
+            (true, None)
+        }
+
+        fn dispatch(&self, _source: &Source) -> Continue {
+            if self.count.fetch_add(1, Ordering::SeqCst) == 2 {
+                self.main_loop.quit();
+                Continue(false)
+            } else {
+                Continue(true)
+            }
+        }
+
+        fn finalize(&self) {
+            self.finalized.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    #[test]
+    fn test_source_impl() {
+        let c = MainContext::new();
+        let l = MainLoop::new(Some(&c), false);
+
+        c.acquire();
+
+        let finalized = Arc::new(AtomicUsize::new(0));
+        let source = Source::new_from_impl(CountingSource {
+            count: AtomicUsize::new(0),
+            finalized: finalized.clone(),
+            main_loop: l.clone(),
+        });
+        source.attach(Some(&c));
+        drop(source);
+
+        l.run();
+
+        assert_eq!(finalized.load(Ordering::SeqCst), 1);
+    }
+}
@@ -0,0 +1,305 @@
+// Copyright 2019, The Gtk-rs Project Developers.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+// Licensed under the MIT license, see the LICENSE file or <http://opensource.org/licenses/MIT>
+
+use source::duration_to_millis;
+use std::cmp;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use translate::ToGlib;
+use Continue;
+use LogLevel;
+use MainContext;
+use Priority;
+use Source;
+
+/// Dispatch statistics of one priority band tracked by a [`StarvationMonitor`].
+///
+/// [`StarvationMonitor`]: struct.StarvationMonitor.html
+#[derive(Clone, Debug)]
+pub struct PriorityBand {
+    /// The priority of the band.
+    pub priority: Priority,
+    /// Number of times sources of this priority were dispatched so far.
+    pub dispatches: u64,
+    /// Time since sources of this priority were dispatched the last time.
+    pub since_dispatch: Duration,
+}
+
+/// Report passed to the callback of a [`StarvationMonitor`] whenever sources of the monitored
+/// priority were not dispatched for longer than the configured threshold.
+///
+/// [`StarvationMonitor`]: struct.StarvationMonitor.html
+#[derive(Clone, Debug)]
+pub struct StarvationWarning {
+    /// The monitored priority.
+    pub priority: Priority,
+    /// Time since sources of the monitored priority were dispatched the last time.
+    pub starved_for: Duration,
+    /// Number of consecutive checks that found the priority starved, including this one.
+    pub checks: u32,
+    /// Number of times sources of the monitored priority were dispatched so far.
+    pub dispatches: u64,
+    /// Statistics of the standard priorities that are higher than the monitored priority,
+    /// followed by the monitored priority itself.
+    pub bands: Vec<PriorityBand>,
+}
+
+impl fmt::Display for StarvationWarning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Sources with priority {} were not dispatched for {}ms ({} consecutive checks, {} dispatches in total, {})",
+            self.priority.to_glib(),
+            duration_to_millis(self.starved_for),
+            self.checks,
+            self.dispatches,
+            format_bands(&self.bands),
+        )
+    }
+}
+
+fn format_bands(bands: &[PriorityBand]) -> String {
+    bands
+        .iter()
+        .map(|band| {
+            format!(
+                "priority {}: {} dispatches",
+                band.priority.to_glib(),
+                band.dispatches
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(any(feature = "v2_50", feature = "dox"))]
+fn log_warning(warning: &StarvationWarning) {
+    glib_log!(
+        "glib-rs",
+        LogLevel::Warning,
+        warning.to_string(),
+        STARVED_PRIORITY = warning.priority.to_glib(),
+        STARVED_FOR_MS = duration_to_millis(warning.starved_for),
+        STARVATION_CHECKS = warning.checks,
+        STARVATION_BANDS = format_bands(&warning.bands),
+    );
+}
+
+#[cfg(not(any(feature = "v2_50", feature = "dox")))]
+fn log_warning(warning: &StarvationWarning) {
+    ::log_message(Some("glib-rs"), LogLevel::Warning, &warning.to_string());
+}
+
+#[derive(Debug)]
+struct BandState {
+    priority: Priority,
+    last_dispatch: Instant,
+    dispatches: u64,
+}
+
+#[derive(Debug)]
+struct MonitorState {
+    // The bands from the highest priority to the monitored priority, which is the last one
+    bands: Vec<BandState>,
+    checks: u32,
+}
+
+/// Diagnostic helper for finding sources that starve sources of a lower priority.
+///
+/// The monitor attaches probe sources with the monitored priority and with every standard
+/// priority above it to the main context, and counts their dispatches per priority band. A
+/// checker source with the highest possible priority, which can't be starved itself, checks
+/// when the probe with the monitored priority was dispatched the last time. If that was longer
+/// ago than the threshold, e.g. because sources with a higher priority are always ready, a
+/// structured warning is logged in the `glib-rs` domain and the callback is called with a
+/// [`StarvationWarning`]. The probes of the higher priorities show which band starves the
+/// monitored one.
+///
+/// Monitoring stops once the `StarvationMonitor` is dropped.
+///
+/// See [`MainContext::monitor_starvation()`] for how to create such a monitor.
+///
+/// [`StarvationWarning`]: struct.StarvationWarning.html
+/// [`MainContext::monitor_starvation()`]: struct.MainContext.html#method.monitor_starvation
+#[derive(Debug)]
+pub struct StarvationMonitor {
+    probes: Vec<Source>,
+    checker: Source,
+}
+
+impl Drop for StarvationMonitor {
+    fn drop(&mut self) {
+        for probe in &self.probes {
+            probe.destroy();
+        }
+        self.checker.destroy();
+    }
+}
+
+impl MainContext {
+    /// Starts monitoring whether sources with the given `priority` are starved by sources with
+    /// a higher priority.
+    ///
+    /// A warning is logged and `func` is called from this main context whenever sources with
+    /// `priority` were not dispatched for longer than `threshold`, repeatedly every `threshold`
+    /// for as long as this is the case.
+    ///
+    /// This is meant as a debugging aid and adds some overhead to the main context.
+    pub fn monitor_starvation<F>(
+        &self,
+        priority: Priority,
+        threshold: Duration,
+        func: F,
+    ) -> StarvationMonitor
+    where
+        F: Fn(&StarvationWarning) + Send + 'static,
+    {
+        let now = Instant::now();
+        let bands = [
+            Priority::HIGH,
+            Priority::DEFAULT,
+            Priority::HIGH_IDLE,
+            Priority::DEFAULT_IDLE,
+            Priority::LOW,
+        ]
+        .iter()
+        .cloned()
+        .filter(|&band| band > priority)
+        .chain(Some(priority))
+        .map(|priority| BandState {
+            priority,
+            last_dispatch: now,
+            dispatches: 0,
+        })
+        .collect::<Vec<_>>();
+        let monitored = bands.len() - 1;
+        let state = Arc::new(Mutex::new(MonitorState { bands, checks: 0 }));
+
+        let interval = cmp::max(duration_to_millis(threshold / 2), 1);
+        let probes = state
+            .lock()
+            .unwrap()
+            .bands
+            .iter()
+            .enumerate()
+            .map(|(index, band)| {
+                let probe_state = state.clone();
+                ::timeout_source_new(
+                    interval,
+                    Some("glib-rs starvation probe"),
+                    band.priority,
+                    move || {
+                        let mut state = probe_state.lock().unwrap();
+                        if index == monitored {
+                            state.checks = 0;
+                        }
+                        let band = &mut state.bands[index];
+                        band.last_dispatch = Instant::now();
+                        band.dispatches += 1;
+                        Continue(true)
+                    },
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let checker = ::timeout_source_new(
+            cmp::max(duration_to_millis(threshold), 1),
+            Some("glib-rs starvation checker"),
+            Priority::new(i32::min_value()),
+            move || {
+                let warning = {
+                    let mut state = state.lock().unwrap();
+                    let starved_for = state.bands[monitored].last_dispatch.elapsed();
+                    if starved_for <= threshold {
+                        return Continue(true);
+                    }
+
+                    state.checks += 1;
+                    StarvationWarning {
+                        priority,
+                        starved_for,
+                        checks: state.checks,
+                        dispatches: state.bands[monitored].dispatches,
+                        bands: state
+                            .bands
+                            .iter()
+                            .map(|band| PriorityBand {
+                                priority: band.priority,
+                                dispatches: band.dispatches,
+                                since_dispatch: band.last_dispatch.elapsed(),
+                            })
+                            .collect(),
+                    }
+                };
+
+                log_warning(&warning);
+                func(&warning);
+                Continue(true)
+            },
+        );
+
+        for probe in &probes {
+            probe.attach(Some(self));
+        }
+        checker.attach(Some(self));
+
+        StarvationMonitor { probes, checker }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use MainLoop;
+    use PRIORITY_DEFAULT;
+
+    fn run_starved(busy_priority: Priority) -> StarvationWarning {
+        let c = MainContext::new();
+        let l = MainLoop::new(Some(&c), false);
+
+        c.acquire();
+
+        let warning = Arc::new(Mutex::new(None));
+        let warning_clone = warning.clone();
+        let l_clone = l.clone();
+        let _monitor = c.monitor_starvation(PRIORITY_DEFAULT, Duration::from_millis(20), move |warning| {
+            *warning_clone.lock().unwrap() = Some(warning.clone());
+            l_clone.quit();
+        });
+
+        // Busy source that is always ready
+        let busy = ::idle_source_new(None, busy_priority, || {
+            ::std::thread::sleep(Duration::from_millis(1));
+            Continue(true)
+        });
+        busy.attach(Some(&c));
+
+        l.run();
+        busy.destroy();
+
+        let warning = warning.lock().unwrap().take();
+        warning.expect("No starvation detected")
+    }
+
+    #[test]
+    fn test_starvation_detected() {
+        let warning = run_starved(Priority::HIGH);
+        assert_eq!(warning.priority, PRIORITY_DEFAULT);
+        assert!(warning.starved_for > Duration::from_millis(20));
+        assert_eq!(
+            warning.bands.iter().map(|band| band.priority).collect::<Vec<_>>(),
+            vec![Priority::HIGH, PRIORITY_DEFAULT]
+        );
+        assert_eq!(warning.bands[1].dispatches, warning.dispatches);
+    }
+
+    #[test]
+    fn test_starvation_above_high_priority() {
+        // The checker must not be starved by sources with a priority above PRIORITY_HIGH
+        let warning = run_starved(Priority::HIGH.higher_by(100));
+        assert_eq!(warning.priority, PRIORITY_DEFAULT);
+        assert!(warning.bands[0].since_dispatch > Duration::from_millis(20));
+    }
+}
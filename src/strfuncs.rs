@@ -0,0 +1,396 @@
+// Copyright 2019, The Gtk-rs Project Developers.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+// Licensed under the MIT license, see the LICENSE file or <http://opensource.org/licenses/MIT>
+
+use glib_sys;
+use libc::c_char;
+use std::cmp::Ordering;
+use std::ffi::CStr;
+use std::ptr;
+use translate::*;
+use GString;
+use StrV;
+
+/// Escapes `source` like a C string literal.
+///
+/// Special characters like `\n` and `"` are escaped with a backslash, all other bytes that are
+/// not printable ASCII are escaped as octal `\NNN`, unless they are contained in `exceptions`.
+///
+/// ```
+/// assert_eq!(glib::strescape("a\tb\"\u{e4}", None), "a\\tb\\\"\\303\\244");
+/// assert_eq!(glib::strescape("a\tb", Some("\t")), "a\tb");
+/// ```
+///
+/// # Panics
+///
+/// Panics if `exceptions` contains non-ASCII characters. The exceptions are applied to single
+/// bytes, so they could leave only parts of a multi-byte character unescaped.
+pub fn strescape(source: &str, exceptions: Option<&str>) -> GString {
+    assert!(
+        exceptions.map_or(true, |exceptions| exceptions.is_ascii()),
+        "Only ASCII characters can be exceptions"
+    );
+    unsafe {
+        from_glib_full(glib_sys::g_strescape(
+            source.to_glib_none().0,
+            exceptions.to_glib_none().0,
+        ))
+    }
+}
+
+/// Reverses [`strescape()`].
+///
+/// The result is returned as bytes because octal escapes can produce any byte, and ends at the
+/// first NUL byte.
+///
+/// [`strescape()`]: fn.strescape.html
+pub fn strcompress(source: &str) -> Vec<u8> {
+    unsafe {
+        let ptr = glib_sys::g_strcompress(source.to_glib_none().0);
+        let res = CStr::from_ptr(ptr).to_bytes().to_vec();
+        glib_sys::g_free(ptr as glib_sys::gpointer);
+        res
+    }
+}
+
+/// Converts all ASCII characters of `s` to lower case, ignoring the locale.
+pub fn ascii_strdown(s: &str) -> GString {
+    unsafe { from_glib_full(glib_sys::g_ascii_strdown(s.to_glib_none().0, s.len() as isize)) }
+}
+
+/// Converts all ASCII characters of `s` to upper case, ignoring the locale.
+pub fn ascii_strup(s: &str) -> GString {
+    unsafe { from_glib_full(glib_sys::g_ascii_strup(s.to_glib_none().0, s.len() as isize)) }
+}
+
+/// Compares `s1` and `s2` ignoring the case of ASCII characters.
+///
+/// Unlike `strcasecmp()` this does not depend on the locale, which makes it suitable for
+/// identifiers and keywords.
+pub fn ascii_strcasecmp(s1: &str, s2: &str) -> Ordering {
+    unsafe { glib_sys::g_ascii_strcasecmp(s1.to_glib_none().0, s2.to_glib_none().0).cmp(&0) }
+}
+
+/// Same as [`ascii_strcasecmp()`], but only compares the first `n` bytes.
+///
+/// [`ascii_strcasecmp()`]: fn.ascii_strcasecmp.html
+pub fn ascii_strncasecmp(s1: &str, s2: &str, n: usize) -> Ordering {
+    unsafe { glib_sys::g_ascii_strncasecmp(s1.to_glib_none().0, s2.to_glib_none().0, n).cmp(&0) }
+}
+
+/// Returns the value of `c` as decimal digit.
+pub fn ascii_digit_value(c: char) -> Option<u32> {
+    if !c.is_ascii() {
+        return None;
+    }
+
+    let value = unsafe { glib_sys::g_ascii_digit_value(c as u8 as _) };
+    if value < 0 {
+        None
+    } else {
+        Some(value as u32)
+    }
+}
+
+/// Returns the value of `c` as hexadecimal digit.
+pub fn ascii_xdigit_value(c: char) -> Option<u32> {
+    if !c.is_ascii() {
+        return None;
+    }
+
+    let value = unsafe { glib_sys::g_ascii_xdigit_value(c as u8 as _) };
+    if value < 0 {
+        None
+    } else {
+        Some(value as u32)
+    }
+}
+
+/// Parses a floating point number at the start of `nptr`, ignoring the locale.
+///
+/// Leading whitespace is skipped. Returns the number and the number of bytes that were parsed,
+/// or `None` if `nptr` does not start with a number. Use this for configuration files and
+/// protocols, where the decimal separator is always `.` regardless of the user's locale.
+///
+/// ```
+/// assert_eq!(glib::ascii_strtod("1.5e3 m"), Some((1500.0, 5)));
+/// assert_eq!(glib::ascii_strtod("m"), None);
+/// ```
+pub fn ascii_strtod(nptr: &str) -> Option<(f64, usize)> {
+    unsafe {
+        let start = nptr.to_glib_none();
+        let mut end = ptr::null_mut();
+        let value = glib_sys::g_ascii_strtod(start.0, &mut end);
+        parsed(start.0, end).map(|len| (value, len))
+    }
+}
+
+/// Parses a signed integer in `base` at the start of `nptr`, ignoring the locale.
+///
+/// `base` can be between 2 and 36, or 0 to detect it from a `0x` or `0` prefix like in C.
+/// Returns the number and the number of bytes that were parsed, or `None` if `nptr` does not
+/// start with a number. Numbers out of range are clamped to `i64::MIN` or `i64::MAX`.
+///
+/// # Panics
+///
+/// Panics if `base` is not valid.
+pub fn ascii_strtoll(nptr: &str, base: u32) -> Option<(i64, usize)> {
+    assert!(base == 0 || (2 <= base && base <= 36), "Invalid base");
+    unsafe {
+        let start = nptr.to_glib_none();
+        let mut end = ptr::null_mut();
+        let value = glib_sys::g_ascii_strtoll(start.0, &mut end, base);
+        parsed(start.0, end).map(|len| (value, len))
+    }
+}
+
+/// Parses an unsigned integer in `base` at the start of `nptr`, ignoring the locale.
+///
+/// See [`ascii_strtoll()`]. Numbers out of range are clamped to `u64::MAX`, and like in C a
+/// leading `-` negates the number.
+///
+/// [`ascii_strtoll()`]: fn.ascii_strtoll.html
+pub fn ascii_strtoull(nptr: &str, base: u32) -> Option<(u64, usize)> {
+    assert!(base == 0 || (2 <= base && base <= 36), "Invalid base");
+    unsafe {
+        let start = nptr.to_glib_none();
+        let mut end = ptr::null_mut();
+        let value = glib_sys::g_ascii_strtoull(start.0, &mut end, base);
+        parsed(start.0, end).map(|len| (value, len))
+    }
+}
+
+// Returns the number of parsed bytes, the end pointer points at the start if nothing was parsed
+fn parsed(start: *const c_char, end: *mut c_char) -> Option<usize> {
+    let len = end as usize - start as usize;
+    if len == 0 { None } else { Some(len) }
+}
+
+/// Formats `d` ignoring the locale, with enough digits to parse it back exactly with
+/// [`ascii_strtod()`].
+///
+/// [`ascii_strtod()`]: fn.ascii_strtod.html
+pub fn ascii_dtostr(d: f64) -> GString {
+    let mut buffer = [0 as c_char; glib_sys::G_ASCII_DTOSTR_BUF_SIZE as usize];
+    unsafe {
+        let ptr = glib_sys::g_ascii_dtostr(buffer.as_mut_ptr(), buffer.len() as i32, d);
+        GString::from(CStr::from_ptr(ptr))
+    }
+}
+
+/// Formats `d` with the `printf()` directive `format` ignoring the locale.
+///
+/// # Panics
+///
+/// Panics if `format` is not a single `%e`, `%f` or `%g` directive, upper case variants and
+/// flags, width and precision included.
+///
+/// ```
+/// assert_eq!(glib::ascii_formatd("%.2f", 3.14159), "3.14");
+/// assert_eq!(glib::ascii_formatd("%08.1E", -2.5), "-2.5E+00");
+/// ```
+pub fn ascii_formatd(format: &str, d: f64) -> GString {
+    let bytes = format.as_bytes();
+    assert!(
+        bytes.len() >= 2
+            && bytes[0] == b'%'
+            && b"eEfFgG".contains(&bytes[bytes.len() - 1])
+            && bytes[1..bytes.len() - 1].iter().all(|c| b"0123456789.+- #".contains(c)),
+        "Invalid format for a double"
+    );
+
+    let format = format.to_glib_none();
+    let mut buffer = vec![0 as c_char; glib_sys::G_ASCII_DTOSTR_BUF_SIZE as usize];
+    loop {
+        unsafe {
+            let ptr = glib_sys::g_ascii_formatd(buffer.as_mut_ptr(), buffer.len() as i32, format.0, d);
+            let res = CStr::from_ptr(ptr);
+            // The output is truncated to the buffer size, e.g. for large numbers with `%f`
+            if res.to_bytes().len() < buffer.len() - 1 {
+                return GString::from(res);
+            }
+        }
+        let len = buffer.len() * 2;
+        buffer.resize(len, 0);
+    }
+}
+
+/// Transliterates `str` to plain ASCII, e.g. to `"Uber"` for `"\u{dc}ber"`.
+///
+/// The transliteration depends on `from_locale`, or the current locale if `None`. Characters
+/// without an ASCII equivalent are replaced by `?`.
+///
+/// ```
+/// assert_eq!(glib::str_to_ascii("\u{c0} la cr\u{e8}me", Some("C")), "A la creme");
+/// ```
+pub fn str_to_ascii(str: &str, from_locale: Option<&str>) -> GString {
+    unsafe {
+        from_glib_full(glib_sys::g_str_to_ascii(
+            str.to_glib_none().0,
+            from_locale.to_glib_none().0,
+        ))
+    }
+}
+
+/// Splits `string` into case-folded and normalized tokens for search.
+///
+/// Returns the tokens and their ASCII alternates, i.e. the transliterations of the tokens that
+/// contain non-ASCII characters. See [`str_to_ascii()`] for `translit_locale`.
+///
+/// [`str_to_ascii()`]: fn.str_to_ascii.html
+pub fn str_tokenize_and_fold(string: &str, translit_locale: Option<&str>) -> (StrV, StrV) {
+    unsafe {
+        let mut ascii_alternates = ptr::null_mut();
+        let tokens = glib_sys::g_str_tokenize_and_fold(
+            string.to_glib_none().0,
+            translit_locale.to_glib_none().0,
+            &mut ascii_alternates,
+        );
+        (from_glib_full(tokens), from_glib_full(ascii_alternates))
+    }
+}
+
+/// Checks whether `search_term` matches `potential_hit`, as done by search entries in GNOME.
+///
+/// Both are tokenized with [`str_tokenize_and_fold()`], and each token of `search_term` has to
+/// be a prefix of a token of `potential_hit`. With `accept_alternates`, ASCII search terms also
+/// match the transliterations of `potential_hit`, so that `"uber"` finds `"\u{dc}ber"`.
+///
+/// ```
+/// assert!(glib::str_match_string("wor", "Hello World", false));
+/// assert!(!glib::str_match_string("orld", "Hello World", false));
+/// ```
+///
+/// [`str_tokenize_and_fold()`]: fn.str_tokenize_and_fold.html
+pub fn str_match_string(search_term: &str, potential_hit: &str, accept_alternates: bool) -> bool {
+    unsafe {
+        from_glib(glib_sys::g_str_match_string(
+            search_term.to_glib_none().0,
+            potential_hit.to_glib_none().0,
+            accept_alternates.to_glib(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escape() {
+        let source = "line\n\"quoted\"\\ \u{e4}\x01";
+        let escaped = strescape(source, None);
+        assert_eq!(escaped, "line\\n\\\"quoted\\\"\\\\ \\303\\244\\001");
+        assert_eq!(strcompress(&escaped), source.as_bytes());
+
+        assert_eq!(strescape("\u{e4}\n", Some("\n")), "\\303\\244\n");
+        assert_eq!(strcompress("\\377x"), b"\xffx");
+        assert_eq!(strcompress("a\\000b"), b"a");
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_escape_non_ascii_exceptions() {
+        strescape("\u{e4}", Some("\u{c3}"));
+    }
+
+    #[test]
+    fn test_case() {
+        assert_eq!(ascii_strdown("HeLLo \u{c4}"), "hello \u{c4}");
+        assert_eq!(ascii_strup("HeLLo \u{e4}"), "HELLO \u{e4}");
+
+        assert_eq!(ascii_strcasecmp("Hello", "hELLO"), Ordering::Equal);
+        assert_eq!(ascii_strcasecmp("a", "B"), Ordering::Less);
+        assert_eq!(ascii_strcasecmp("b", "A"), Ordering::Greater);
+        assert_eq!(ascii_strcasecmp("\u{e4}", "\u{c4}"), Ordering::Greater);
+        assert_eq!(ascii_strncasecmp("HelloX", "helloY", 5), Ordering::Equal);
+        assert_eq!(ascii_strncasecmp("HelloX", "helloY", 6), Ordering::Less);
+    }
+
+    #[test]
+    fn test_digit_value() {
+        assert_eq!(ascii_digit_value('7'), Some(7));
+        assert_eq!(ascii_digit_value('a'), None);
+        assert_eq!(ascii_digit_value('\u{664}'), None);
+        assert_eq!(ascii_xdigit_value('F'), Some(15));
+        assert_eq!(ascii_xdigit_value('9'), Some(9));
+        assert_eq!(ascii_xdigit_value('g'), None);
+    }
+
+    #[test]
+    fn test_strtod() {
+        assert_eq!(ascii_strtod("1.5"), Some((1.5, 3)));
+        assert_eq!(ascii_strtod("  -0.25,1"), Some((-0.25, 7)));
+        assert_eq!(ascii_strtod("1,5"), Some((1.0, 1)));
+        assert_eq!(ascii_strtod("inf").map(|(d, _)| d), Some(::std::f64::INFINITY));
+        assert_eq!(ascii_strtod(""), None);
+        assert_eq!(ascii_strtod("x1"), None);
+    }
+
+    #[test]
+    fn test_strtoll() {
+        assert_eq!(ascii_strtoll("-42 apples", 10), Some((-42, 3)));
+        assert_eq!(ascii_strtoll("ff", 16), Some((255, 2)));
+        assert_eq!(ascii_strtoll("0x1f", 0), Some((31, 4)));
+        assert_eq!(ascii_strtoll("017", 0), Some((15, 3)));
+        assert_eq!(ascii_strtoll("99999999999999999999", 10), Some((::std::i64::MAX, 20)));
+        assert_eq!(ascii_strtoll("z", 10), None);
+
+        assert_eq!(ascii_strtoull("18446744073709551615", 10), Some((::std::u64::MAX, 20)));
+        assert_eq!(ascii_strtoull("101", 2), Some((5, 3)));
+        assert_eq!(ascii_strtoull("", 10), None);
+    }
+
+    #[test]
+    fn test_dtostr() {
+        assert_eq!(ascii_dtostr(1.0), "1");
+        assert_eq!(ascii_dtostr(-2.5), "-2.5");
+        for &d in &[0.1, 1.0 / 3.0, 1e300, -5e-324] {
+            assert_eq!(ascii_strtod(&ascii_dtostr(d)).unwrap().0, d);
+        }
+    }
+
+    #[test]
+    fn test_formatd() {
+        assert_eq!(ascii_formatd("%.3f", 2.0 / 3.0), "0.667");
+        assert_eq!(ascii_formatd("%g", 1e-5), "1e-05");
+        assert_eq!(ascii_formatd("%+.1e", 12.5), "+1.2e+01");
+        assert_eq!(ascii_formatd("%.300f", 1.0).len(), 302);
+        assert_eq!(ascii_formatd("%f", 1e300).len(), 308);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_formatd_invalid() {
+        ascii_formatd("%d", 1.0);
+    }
+
+    #[test]
+    fn test_to_ascii() {
+        assert_eq!(str_to_ascii("\u{dc}ber", Some("C")), "Uber");
+        assert_eq!(str_to_ascii("Stra\u{df}e", Some("C")), "Strasse");
+        assert_eq!(str_to_ascii("plain", None), "plain");
+    }
+
+    #[test]
+    fn test_tokenize_and_fold() {
+        let (tokens, alternates) = str_tokenize_and_fold("Hello \u{dc}ber-World", Some("C"));
+        assert_eq!(tokens, ["hello", "\u{fc}ber", "world"][..]);
+        assert_eq!(alternates, ["uber"][..]);
+
+        let (tokens, alternates) = str_tokenize_and_fold("", None);
+        assert!(tokens.is_empty());
+        assert!(alternates.is_empty());
+    }
+
+    #[test]
+    fn test_match_string() {
+        assert!(str_match_string("hel", "Hello World", false));
+        assert!(str_match_string("WORLD hello", "Hello World", false));
+        assert!(!str_match_string("llo", "Hello World", false));
+
+        assert!(str_match_string("\u{fc}b", "\u{dc}ber", false));
+        assert!(!str_match_string("ub", "\u{dc}ber", false));
+        assert!(str_match_string("ub", "\u{dc}ber", true));
+    }
+}
@@ -4,6 +4,7 @@
 
 use glib_sys;
 use gobject_sys;
+use gstring::GString;
 use std::borrow;
 use std::cmp;
 use std::convert;
@@ -71,11 +72,43 @@ impl String {
         self
     }
 
+    pub fn append_unichar(&mut self, c: char) -> &mut Self {
+        unsafe { glib_sys::g_string_append_unichar(self.to_glib_none_mut().0, c.to_glib()); }
+        self
+    }
+
+    /// Removes `len` bytes starting at byte position `pos`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is out of bounds.
+    pub fn erase(&mut self, pos: usize, len: usize) -> &mut Self {
+        assert!(pos.checked_add(len).map_or(false, |end| end <= self.len()), "Range out of bounds");
+        unsafe { glib_sys::g_string_erase(self.to_glib_none_mut().0, pos as isize, len as isize); }
+        self
+    }
+
     pub fn truncate(&mut self, len: usize) -> &mut Self {
         unsafe { glib_sys::g_string_truncate(self.to_glib_none_mut().0, len); }
         self
     }
 
+    /// Converts into a `GString` without copying the contents.
+    ///
+    /// Returns the string itself as error if the contents are not valid UTF-8 or contain a nul
+    /// byte, which would cut off the `GString`. Converting to a Rust `String` always requires a
+    /// copy, as the memory is allocated by GLib.
+    pub fn into_gstring(self) -> Result<GString, String> {
+        if self.to_str().is_err() || self.as_ref().contains(&0) {
+            return Err(self);
+        }
+
+        unsafe {
+            let ptr = self.0.into_raw();
+            Ok(GString::new(glib_sys::g_string_free(ptr, glib_sys::GFALSE)))
+        }
+    }
+
     /// Returns `&str` slice when contained data is valid UTF-8 string, or an error otherwise.
     pub fn to_str(&self) -> Result<&str, str::Utf8Error> {
         str::from_utf8(self.as_ref())
@@ -107,6 +140,14 @@ impl fmt::Display for String {
     }
 }
 
+/// Allows to append formatted text with the `write!` macro.
+impl fmt::Write for String {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.append(s);
+        Ok(())
+    }
+}
+
 impl PartialEq for String {
     fn eq(&self, other: &Self) -> bool {
         unsafe {
@@ -193,6 +234,45 @@ mod tests {
         assert_eq!(&*s, b"12");
     }
 
+    #[test]
+    fn erase() {
+        let mut s = ::String::new("foobarbaz");
+        s.erase(3, 3);
+        assert_eq!(&*s, b"foobaz");
+        s.erase(0, 6);
+        assert_eq!(&*s, b"");
+    }
+
+    #[test]
+    #[should_panic]
+    fn erase_out_of_bounds() {
+        ::String::new("foo").erase(2, 2);
+    }
+
+    #[test]
+    fn write() {
+        use std::fmt::Write;
+
+        let mut s = ::String::default();
+        write!(s, "{}-{:03}", "id", 7).unwrap();
+        s.append_unichar('→').append_unichar('x');
+        assert_eq!(s.to_str(), Ok("id-007→x"));
+    }
+
+    #[test]
+    fn into_gstring() {
+        let mut s = ::String::new("Hello");
+        s.append(" there!");
+        let gstring = s.into_gstring().unwrap();
+        assert_eq!(gstring, "Hello there!");
+
+        let s = ::String::new(b"\xF0\x90");
+        assert!(s.into_gstring().is_err());
+
+        let s = ::String::new(b"a\0b");
+        assert_eq!(&*s.into_gstring().unwrap_err(), b"a\0b");
+    }
+
     #[test]
     fn default() {
         let s1: ::String = Default::default();
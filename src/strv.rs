@@ -0,0 +1,358 @@
+// Copyright 2019, The Gtk-rs Project Developers.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+// Licensed under the MIT license, see the LICENSE file or <http://opensource.org/licenses/MIT>
+
+use glib_sys;
+use libc::c_char;
+use std::ffi::CStr;
+use std::fmt;
+use std::iter::FromIterator;
+use std::ops::Deref;
+use std::ptr;
+use std::slice;
+use std::vec;
+use translate::*;
+use types::{StaticType, Type};
+use GString;
+
+/// An owned `NULL`-terminated array of strings, i.e. a `GStrv`.
+///
+/// Arrays returned with full ownership are taken over without copying the strings, and a
+/// `StrV` can be passed to C functions expecting a `const gchar * const *` without allocating.
+/// It dereferences to a slice of `GString`s.
+///
+/// ```
+/// use glib::StrV;
+///
+/// let strv = StrV::from(vec!["a", "b"]);
+/// assert_eq!(strv.len(), 2);
+/// assert_eq!(strv[1], "b");
+/// ```
+pub struct StrV {
+    strings: Vec<GString>,
+    // Pointers to the contents of `strings` followed by NULL. The contents don't move when the
+    // `GString`s are moved.
+    ptrs: Ptrs,
+}
+
+struct Ptrs(Vec<*const c_char>);
+
+// The pointers only point into the `GString`s of the same `StrV` and are never used to modify
+// them, so the array is exactly as thread-safe as the `GString`s, which are `Send` and `Sync`.
+// `StrV` itself gets its `Send` and `Sync` impls from its fields.
+unsafe impl Send for Ptrs {}
+unsafe impl Sync for Ptrs {}
+
+impl StrV {
+    /// Creates a new empty `StrV`.
+    pub fn new() -> StrV {
+        StrV {
+            strings: Vec::new(),
+            ptrs: Ptrs(vec![ptr::null()]),
+        }
+    }
+
+    /// Appends `s` at the end.
+    pub fn push<T: Into<GString>>(&mut self, s: T) {
+        let s = s.into();
+        let len = self.ptrs.0.len();
+        self.ptrs.0.insert(len - 1, s.as_c_str().as_ptr());
+        self.strings.push(s);
+    }
+
+    /// Returns the `NULL`-terminated array of strings.
+    ///
+    /// The pointer is only valid as long as the `StrV` is alive and not modified.
+    pub fn as_ptr(&self) -> *const *const c_char {
+        self.ptrs.0.as_ptr()
+    }
+
+    /// Returns the strings as a slice.
+    pub fn as_slice(&self) -> &[GString] {
+        &self.strings
+    }
+
+    /// Returns the strings without copying them.
+    pub fn into_vec(self) -> Vec<GString> {
+        self.strings
+    }
+}
+
+impl Default for StrV {
+    fn default() -> StrV {
+        StrV::new()
+    }
+}
+
+impl Clone for StrV {
+    fn clone(&self) -> StrV {
+        self.strings.iter().cloned().collect()
+    }
+}
+
+impl fmt::Debug for StrV {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.strings.iter()).finish()
+    }
+}
+
+impl PartialEq for StrV {
+    fn eq(&self, other: &StrV) -> bool {
+        self.strings == other.strings
+    }
+}
+
+impl Eq for StrV {}
+
+impl<'a> PartialEq<[&'a str]> for StrV {
+    fn eq(&self, other: &[&'a str]) -> bool {
+        self.strings.len() == other.len() && self.strings.iter().zip(other).all(|(a, b)| a == b)
+    }
+}
+
+impl Deref for StrV {
+    type Target = [GString];
+
+    fn deref(&self) -> &[GString] {
+        &self.strings
+    }
+}
+
+impl AsRef<[GString]> for StrV {
+    fn as_ref(&self) -> &[GString] {
+        &self.strings
+    }
+}
+
+impl<T: Into<GString>> FromIterator<T> for StrV {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> StrV {
+        let mut strv = StrV::new();
+        strv.extend(iter);
+        strv
+    }
+}
+
+impl<T: Into<GString>> Extend<T> for StrV {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+        self.strings.reserve(iter.size_hint().0);
+        self.ptrs.0.reserve(iter.size_hint().0);
+        for s in iter {
+            self.push(s);
+        }
+    }
+}
+
+impl IntoIterator for StrV {
+    type Item = GString;
+    type IntoIter = vec::IntoIter<GString>;
+
+    fn into_iter(self) -> vec::IntoIter<GString> {
+        self.strings.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a StrV {
+    type Item = &'a GString;
+    type IntoIter = slice::Iter<'a, GString>;
+
+    fn into_iter(self) -> slice::Iter<'a, GString> {
+        self.strings.iter()
+    }
+}
+
+impl From<Vec<GString>> for StrV {
+    fn from(strings: Vec<GString>) -> StrV {
+        let mut ptrs = strings
+            .iter()
+            .map(|s| s.as_c_str().as_ptr())
+            .collect::<Vec<_>>();
+        ptrs.push(ptr::null());
+
+        StrV {
+            strings,
+            ptrs: Ptrs(ptrs),
+        }
+    }
+}
+
+impl From<Vec<String>> for StrV {
+    fn from(strings: Vec<String>) -> StrV {
+        strings.into_iter().collect()
+    }
+}
+
+impl<'a> From<Vec<&'a str>> for StrV {
+    fn from(strings: Vec<&'a str>) -> StrV {
+        strings.into_iter().collect()
+    }
+}
+
+impl<'a> From<&'a [&'a str]> for StrV {
+    fn from(strings: &'a [&'a str]) -> StrV {
+        strings.iter().cloned().collect()
+    }
+}
+
+impl From<StrV> for Vec<GString> {
+    fn from(strv: StrV) -> Vec<GString> {
+        strv.strings
+    }
+}
+
+impl StaticType for StrV {
+    fn static_type() -> Type {
+        unsafe { from_glib(glib_sys::g_strv_get_type()) }
+    }
+}
+
+impl GlibPtrDefault for StrV {
+    type GlibType = *mut *mut c_char;
+}
+
+#[doc(hidden)]
+impl<'a> ToGlibPtr<'a, *const *const c_char> for StrV {
+    type Storage = &'a Self;
+
+    #[inline]
+    fn to_glib_none(&'a self) -> Stash<'a, *const *const c_char, Self> {
+        Stash(self.as_ptr(), self)
+    }
+
+    #[inline]
+    fn to_glib_full(&self) -> *const *const c_char {
+        ToGlibPtr::<*mut *mut c_char>::to_glib_full(self) as *const *const c_char
+    }
+}
+
+#[doc(hidden)]
+impl<'a> ToGlibPtr<'a, *mut *mut c_char> for StrV {
+    type Storage = &'a Self;
+
+    #[inline]
+    fn to_glib_none(&'a self) -> Stash<'a, *mut *mut c_char, Self> {
+        Stash(self.as_ptr() as *mut *mut c_char, self)
+    }
+
+    #[inline]
+    fn to_glib_full(&self) -> *mut *mut c_char {
+        // Same as g_strdupv(), without having to count the strings first
+        unsafe {
+            let res = glib_sys::g_malloc(self.ptrs.0.len() * ::std::mem::size_of::<*mut c_char>())
+                as *mut *mut c_char;
+            for (i, s) in self.ptrs.0.iter().enumerate() {
+                *res.add(i) = glib_sys::g_strdup(*s);
+            }
+            res
+        }
+    }
+}
+
+#[doc(hidden)]
+impl FromGlibPtrFull<*mut *mut c_char> for StrV {
+    unsafe fn from_glib_full(ptr: *mut *mut c_char) -> StrV {
+        let mut strv = StrV::new();
+        if ptr.is_null() {
+            return strv;
+        }
+
+        // Validate all strings first, so that nothing is leaked if one of them is invalid
+        let mut len = 0;
+        while !(*ptr.add(len)).is_null() {
+            if CStr::from_ptr(*ptr.add(len)).to_str().is_err() {
+                glib_sys::g_strfreev(ptr);
+                panic!("Invalid UTF-8 in string array");
+            }
+            len += 1;
+        }
+
+        // The strings are taken over, only the array itself is freed
+        strv.strings.reserve(len);
+        strv.ptrs.0.reserve(len);
+        for i in 0..len {
+            strv.push(GString::new(*ptr.add(i)));
+        }
+        glib_sys::g_free(ptr as glib_sys::gpointer);
+        strv
+    }
+}
+
+#[doc(hidden)]
+impl FromGlibPtrNone<*const *const c_char> for StrV {
+    unsafe fn from_glib_none(ptr: *const *const c_char) -> StrV {
+        let mut strv = StrV::new();
+        if ptr.is_null() {
+            return strv;
+        }
+
+        let mut i = 0;
+        while !(*ptr.add(i)).is_null() {
+            strv.push(GString::from_glib_none(*ptr.add(i)));
+            i += 1;
+        }
+        strv
+    }
+}
+
+#[doc(hidden)]
+impl FromGlibPtrNone<*mut *mut c_char> for StrV {
+    unsafe fn from_glib_none(ptr: *mut *mut c_char) -> StrV {
+        from_glib_none(ptr as *const *const c_char)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strv() {
+        let mut strv = StrV::new();
+        assert!(strv.is_empty());
+        assert!(unsafe { (*strv.as_ptr()).is_null() });
+
+        strv.push("a");
+        strv.push(String::from("b"));
+        strv.extend(vec!["c"]);
+        assert_eq!(strv, ["a", "b", "c"][..]);
+        assert_eq!(strv.iter().map(|s| s.as_str()).collect::<Vec<_>>(), vec!["a", "b", "c"]);
+        assert_eq!(strv.clone(), strv);
+        assert_eq!(format!("{:?}", strv), r#"["a", "b", "c"]"#);
+
+        unsafe {
+            let ptr = strv.as_ptr();
+            assert_eq!(CStr::from_ptr(*ptr.add(2)).to_str().unwrap(), "c");
+            assert!((*ptr.add(3)).is_null());
+            assert_eq!(glib_sys::g_strv_length(ptr as *mut _), 3);
+        }
+
+        let strings: Vec<GString> = strv.into();
+        assert_eq!(strings.len(), 3);
+    }
+
+    #[test]
+    fn test_translate() {
+        let strv = StrV::from(vec![String::from("x"), String::from("y\u{e4}")]);
+        unsafe {
+            let ptr: *mut *mut c_char = strv.to_glib_full();
+            let copy: StrV = from_glib_none(ptr);
+            assert_eq!(copy, strv);
+
+            let owned: StrV = from_glib_full(ptr);
+            assert_eq!(owned, strv);
+
+            let stash: Stash<*const *const c_char, _> = strv.to_glib_none();
+            let copy: StrV = from_glib_none(stash.0);
+            assert_eq!(copy, strv);
+
+            let empty: StrV = from_glib_full(ptr::null_mut::<*mut c_char>());
+            assert!(empty.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_send_sync() {
+        fn is_send_sync<T: Send + Sync>() {}
+        is_send_sync::<StrV>();
+    }
+}
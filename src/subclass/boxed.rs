@@ -7,6 +7,7 @@
 use glib_sys;
 use gobject_sys;
 use std::ops;
+use std::ptr;
 use translate::*;
 use value::*;
 
@@ -119,8 +120,10 @@ macro_rules! glib_boxed_derive_traits {
 
         impl $crate::value::SetValueOptional for $name {
             unsafe fn set_value_optional(value: &mut $crate::value::Value, this: Option<&Self>) {
-                let this = this.expect("None not allowed");
-                let ptr: *mut $name = Box::into_raw(Box::new(this.clone()));
+                let ptr: *mut $name = match this {
+                    Some(this) => Box::into_raw(Box::new(this.clone())),
+                    None => ::std::ptr::null_mut(),
+                };
                 $crate::gobject_sys::g_value_take_boxed(
                     $crate::translate::ToGlibPtrMut::to_glib_none_mut(value).0,
                     ptr as *mut _,
@@ -133,8 +136,11 @@ macro_rules! glib_boxed_derive_traits {
                 let ptr = $crate::gobject_sys::g_value_get_boxed(
                     $crate::translate::ToGlibPtr::to_glib_none(value).0,
                 );
-                assert!(!ptr.is_null());
-                Some(&*(ptr as *mut $name))
+                if ptr.is_null() {
+                    None
+                } else {
+                    Some(&*(ptr as *mut $name))
+                }
             }
         }
 
@@ -147,6 +153,52 @@ macro_rules! glib_boxed_derive_traits {
                 &*(ptr as *mut $name)
             }
         }
+
+        impl<'a> $crate::value::FromValueOptional<'a> for $name {
+            unsafe fn from_value_optional(value: &'a $crate::value::Value) -> Option<Self> {
+                <&$name as $crate::value::FromValueOptional>::from_value_optional(value)
+                    .map(Clone::clone)
+            }
+        }
+
+        impl<'a> $crate::value::FromValue<'a> for $name {
+            unsafe fn from_value(value: &'a $crate::value::Value) -> Self {
+                <&$name as $crate::value::FromValue>::from_value(value).clone()
+            }
+        }
+    };
+}
+
+#[macro_export]
+/// Macro for registering a `Clone`-able Rust type as boxed type.
+///
+/// This implements [`BoxedType`] with the given type name via [`glib_boxed_type!`] and derives
+/// the `glib::Value` traits via [`glib_boxed_derive_traits!`], so the type can be stored in
+/// `Value`s, used as signal parameter and return type, and as value type of
+/// `ParamSpec::boxed()` properties.
+///
+/// ```ignore
+/// #[derive(Clone, Debug, PartialEq)]
+/// struct Point {
+///     x: i32,
+///     y: i32,
+/// }
+///
+/// glib_boxed!(Point, "MyAppPoint");
+/// ```
+///
+/// [`BoxedType`]: subclass/boxed/trait.BoxedType.html
+/// [`glib_boxed_type!`]: macro.glib_boxed_type.html
+/// [`glib_boxed_derive_traits!`]: macro.glib_boxed_derive_traits.html
+macro_rules! glib_boxed {
+    ($name:ident, $type_name:expr) => {
+        impl $crate::subclass::boxed::BoxedType for $name {
+            const NAME: &'static str = $type_name;
+
+            glib_boxed_type!();
+        }
+
+        glib_boxed_derive_traits!($name);
     };
 }
 
@@ -188,8 +240,10 @@ impl<T: BoxedType> SetValue for Boxed<T> {
 
 impl<T: BoxedType> SetValueOptional for Boxed<T> {
     unsafe fn set_value_optional(value: &mut Value, this: Option<&Self>) {
-        let this = this.expect("None not allowed");
-        let ptr: *mut Boxed<T> = Box::into_raw(Box::new(this.clone()));
+        let ptr: *mut Boxed<T> = match this {
+            Some(this) => Box::into_raw(Box::new(this.clone())),
+            None => ptr::null_mut(),
+        };
         gobject_sys::g_value_take_boxed(value.to_glib_none_mut().0, ptr as *mut _);
     }
 }
@@ -197,8 +251,11 @@ impl<T: BoxedType> SetValueOptional for Boxed<T> {
 impl<'a, T: BoxedType> FromValueOptional<'a> for &'a Boxed<T> {
     unsafe fn from_value_optional(value: &'a Value) -> Option<Self> {
         let ptr = gobject_sys::g_value_get_boxed(value.to_glib_none().0);
-        assert!(!ptr.is_null());
-        Some(&*(ptr as *mut Boxed<T>))
+        if ptr.is_null() {
+            None
+        } else {
+            Some(&*(ptr as *mut Boxed<T>))
+        }
     }
 }
 
@@ -213,6 +270,7 @@ impl<'a, T: BoxedType> FromValue<'a> for &'a Boxed<T> {
 #[cfg(test)]
 mod test {
     use super::*;
+    use StaticType;
 
     #[derive(Clone, Debug, PartialEq, Eq)]
     struct MyBoxed(String);
@@ -230,6 +288,15 @@ mod test {
         assert_ne!(::Type::Invalid, MyBoxed::get_type());
     }
 
+    glib_export_type!(boxed glib_rs_test_my_boxed_get_type, MyBoxed);
+
+    #[test]
+    fn test_export() {
+        use translate::ToGlib;
+
+        assert_eq!(glib_rs_test_my_boxed_get_type(), MyBoxed::get_type().to_glib());
+    }
+
     #[test]
     fn test_value_boxed() {
         assert_ne!(::Type::Invalid, MyBoxed::get_type());
@@ -249,4 +316,43 @@ mod test {
         let b2 = v.get::<&MyBoxed>().unwrap();
         assert_eq!(&b, b2);
     }
+
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    struct MyPoint {
+        x: i32,
+        y: i32,
+    }
+
+    glib_boxed!(MyPoint, "MyPoint");
+
+    #[test]
+    fn test_value_owned() {
+        let p = MyPoint { x: 1, y: 2 };
+        let v = p.to_value();
+        assert_eq!(v.type_(), MyPoint::static_type());
+        assert_eq!(v.get::<MyPoint>(), Some(p));
+    }
+
+    #[test]
+    fn test_value_none() {
+        let v = None::<MyPoint>.to_value();
+        assert_eq!(v.type_(), MyPoint::static_type());
+        assert_eq!(v.get::<MyPoint>(), None);
+        assert_eq!(v.get::<&MyPoint>(), None);
+
+        let v = None::<Boxed<MyBoxed>>.to_value();
+        assert!(v.get::<&Boxed<MyBoxed>>().is_none());
+    }
+
+    #[test]
+    fn test_param_spec() {
+        let pspec = ::ParamSpec::boxed(
+            "point",
+            "Point",
+            "Some point",
+            MyPoint::static_type(),
+            ::ParamFlags::READWRITE,
+        );
+        assert_eq!(pspec.get_value_type(), MyPoint::static_type());
+    }
 }
@@ -0,0 +1,440 @@
+// Copyright 2019, The Gtk-rs Project Developers.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+// Licensed under the MIT license, see the LICENSE file or <http://opensource.org/licenses/MIT>
+
+//! Module for registering enum and flags types for Rust types.
+
+use gobject_sys;
+use std::ffi::CString;
+use std::ptr;
+use translate::*;
+
+/// Trait for defining enum types.
+///
+/// Links together the type name and the values with the type itself, which is usually a
+/// fieldless Rust `enum`.
+///
+/// See [`register_enum_type`] for registering an implementation of this trait with the type
+/// system.
+///
+/// [`register_enum_type`]: fn.register_enum_type.html
+pub trait EnumType: Copy + Sized + 'static {
+    /// Enum type name.
+    ///
+    /// This must be unique in the whole process.
+    const NAME: &'static str;
+
+    /// All values of the enum together with their name and nick.
+    const VALUES: &'static [(Self, &'static str, &'static str)];
+
+    /// Returns the type ID.
+    ///
+    /// This is usually defined via the [`glib_enum_type!`] macro.
+    ///
+    /// [`glib_enum_type!`]: ../../macro.glib_enum_type.html
+    fn get_type() -> ::Type;
+
+    /// Returns the integer value of `self`.
+    ///
+    /// This is usually defined via the [`glib_enum_type!`] macro.
+    ///
+    /// [`glib_enum_type!`]: ../../macro.glib_enum_type.html
+    fn to_i32(self) -> i32;
+
+    /// Returns the value corresponding to `value`, if any.
+    fn from_i32(value: i32) -> Option<Self> {
+        Self::VALUES
+            .iter()
+            .map(|&(v, _, _)| v)
+            .find(|v| v.to_i32() == value)
+    }
+}
+
+/// Trait for defining flags types.
+///
+/// Links together the type name and the values with the type itself, which is usually a struct
+/// defined with the `bitflags!` macro.
+///
+/// See [`register_flags_type`] for registering an implementation of this trait with the type
+/// system.
+///
+/// [`register_flags_type`]: fn.register_flags_type.html
+pub trait FlagsType: Copy + Sized + 'static {
+    /// Flags type name.
+    ///
+    /// This must be unique in the whole process.
+    const NAME: &'static str;
+
+    /// All single flags together with their name and nick.
+    const VALUES: &'static [(Self, &'static str, &'static str)];
+
+    /// Returns the type ID.
+    ///
+    /// This is usually defined via the [`glib_flags_type!`] macro.
+    ///
+    /// [`glib_flags_type!`]: ../../macro.glib_flags_type.html
+    fn get_type() -> ::Type;
+
+    /// Returns the bits of `self`.
+    ///
+    /// This is usually defined via the [`glib_flags_type!`] macro.
+    ///
+    /// [`glib_flags_type!`]: ../../macro.glib_flags_type.html
+    fn to_u32(self) -> u32;
+
+    /// Returns the flags corresponding to `value`, dropping unknown bits.
+    ///
+    /// This is usually defined via the [`glib_flags_type!`] macro.
+    ///
+    /// [`glib_flags_type!`]: ../../macro.glib_flags_type.html
+    fn from_u32(value: u32) -> Self;
+}
+
+/// Register an enum `glib::Type` ID for `T`.
+///
+/// This must be called only once and will panic on a second call.
+///
+/// See [`glib_enum_type!`] for defining a function that ensures that
+/// this is only called once and returns the type id.
+///
+/// [`glib_enum_type!`]: ../../macro.glib_enum_type.html
+pub fn register_enum_type<T: EnumType>() -> ::Type {
+    unsafe {
+        let type_name = CString::new(T::NAME).unwrap();
+        assert_eq!(
+            gobject_sys::g_type_from_name(type_name.as_ptr()),
+            gobject_sys::G_TYPE_INVALID
+        );
+
+        // The values are referenced by the type for the rest of the process
+        let mut values = T::VALUES
+            .iter()
+            .map(|&(value, name, nick)| gobject_sys::GEnumValue {
+                value: value.to_i32(),
+                value_name: CString::new(name).unwrap().into_raw(),
+                value_nick: CString::new(nick).unwrap().into_raw(),
+            })
+            .collect::<Vec<_>>();
+        values.push(gobject_sys::GEnumValue {
+            value: 0,
+            value_name: ptr::null(),
+            value_nick: ptr::null(),
+        });
+        let values = Box::into_raw(values.into_boxed_slice());
+
+        from_glib(gobject_sys::g_enum_register_static(
+            type_name.as_ptr(),
+            values as *const gobject_sys::GEnumValue,
+        ))
+    }
+}
+
+/// Register a flags `glib::Type` ID for `T`.
+///
+/// This must be called only once and will panic on a second call.
+///
+/// See [`glib_flags_type!`] for defining a function that ensures that
+/// this is only called once and returns the type id.
+///
+/// [`glib_flags_type!`]: ../../macro.glib_flags_type.html
+pub fn register_flags_type<T: FlagsType>() -> ::Type {
+    unsafe {
+        let type_name = CString::new(T::NAME).unwrap();
+        assert_eq!(
+            gobject_sys::g_type_from_name(type_name.as_ptr()),
+            gobject_sys::G_TYPE_INVALID
+        );
+
+        // The values are referenced by the type for the rest of the process
+        let mut values = T::VALUES
+            .iter()
+            .map(|&(value, name, nick)| gobject_sys::GFlagsValue {
+                value: value.to_u32(),
+                value_name: CString::new(name).unwrap().into_raw(),
+                value_nick: CString::new(nick).unwrap().into_raw(),
+            })
+            .collect::<Vec<_>>();
+        values.push(gobject_sys::GFlagsValue {
+            value: 0,
+            value_name: ptr::null(),
+            value_nick: ptr::null(),
+        });
+        let values = Box::into_raw(values.into_boxed_slice());
+
+        from_glib(gobject_sys::g_flags_register_static(
+            type_name.as_ptr(),
+            values as *const gobject_sys::GFlagsValue,
+        ))
+    }
+}
+
+#[macro_export]
+/// Macro for defining the `get_type` and `to_i32` functions of an [`EnumType`].
+///
+/// `get_type` returns a `glib::Type` and registers `Self` via [`register_enum_type`]
+/// the first time it is called.
+///
+/// [`EnumType`]: subclass/enums/trait.EnumType.html
+/// [`register_enum_type`]: subclass/enums/fn.register_enum_type.html
+macro_rules! glib_enum_type {
+    () => {
+        fn get_type() -> $crate::Type {
+            static mut TYPE_: $crate::Type = $crate::Type::Invalid;
+            static ONCE: ::std::sync::Once = ::std::sync::Once::new();
+
+            ONCE.call_once(|| {
+                let type_ = $crate::subclass::register_enum_type::<Self>();
+                unsafe {
+                    TYPE_ = type_;
+                }
+            });
+
+            unsafe { TYPE_ }
+        }
+
+        fn to_i32(self) -> i32 {
+            self as i32
+        }
+    };
+}
+
+#[macro_export]
+/// Macro for defining the `get_type`, `to_u32` and `from_u32` functions of a [`FlagsType`]
+/// that was defined with the `bitflags!` macro.
+///
+/// `get_type` returns a `glib::Type` and registers `Self` via [`register_flags_type`]
+/// the first time it is called.
+///
+/// [`FlagsType`]: subclass/enums/trait.FlagsType.html
+/// [`register_flags_type`]: subclass/enums/fn.register_flags_type.html
+macro_rules! glib_flags_type {
+    () => {
+        fn get_type() -> $crate::Type {
+            static mut TYPE_: $crate::Type = $crate::Type::Invalid;
+            static ONCE: ::std::sync::Once = ::std::sync::Once::new();
+
+            ONCE.call_once(|| {
+                let type_ = $crate::subclass::register_flags_type::<Self>();
+                unsafe {
+                    TYPE_ = type_;
+                }
+            });
+
+            unsafe { TYPE_ }
+        }
+
+        fn to_u32(self) -> u32 {
+            self.bits()
+        }
+
+        fn from_u32(value: u32) -> Self {
+            Self::from_bits_truncate(value)
+        }
+    };
+}
+
+#[macro_export]
+/// Macro for deriving the `glib::Value` traits for an [`EnumType`].
+///
+/// Values that don't correspond to any of the enum's values are returned as `None` by
+/// `Value::get()`. `Value::get_some()` logs a critical warning for them and returns the first
+/// value of [`EnumType::VALUES`] instead.
+///
+/// [`EnumType::VALUES`]: subclass/enums/trait.EnumType.html#associatedconstant.VALUES
+/// [`EnumType`]: subclass/enums/trait.EnumType.html
+macro_rules! glib_enum_derive_traits {
+    ($name:ident) => {
+        impl $crate::StaticType for $name {
+            fn static_type() -> $crate::Type {
+                <$name as $crate::subclass::enums::EnumType>::get_type()
+            }
+        }
+
+        impl $crate::value::SetValue for $name {
+            unsafe fn set_value(value: &mut $crate::value::Value, this: &Self) {
+                $crate::gobject_sys::g_value_set_enum(
+                    $crate::translate::ToGlibPtrMut::to_glib_none_mut(value).0,
+                    $crate::subclass::enums::EnumType::to_i32(*this),
+                );
+            }
+        }
+
+        impl<'a> $crate::value::FromValueOptional<'a> for $name {
+            unsafe fn from_value_optional(value: &'a $crate::value::Value) -> Option<Self> {
+                let v = $crate::gobject_sys::g_value_get_enum(
+                    $crate::translate::ToGlibPtr::to_glib_none(value).0,
+                );
+                <$name as $crate::subclass::enums::EnumType>::from_i32(v)
+            }
+        }
+
+        impl<'a> $crate::value::FromValue<'a> for $name {
+            unsafe fn from_value(value: &'a $crate::value::Value) -> Self {
+                match $crate::value::FromValueOptional::from_value_optional(value) {
+                    Some(v) => v,
+                    None => {
+                        // The value can be set to anything from C, fall back to the first value
+                        $crate::g_critical!(
+                            "glib-rs",
+                            "Invalid value {} for enum {}",
+                            $crate::gobject_sys::g_value_get_enum(
+                                $crate::translate::ToGlibPtr::to_glib_none(value).0,
+                            ),
+                            <$name as $crate::subclass::enums::EnumType>::NAME,
+                        );
+                        <$name as $crate::subclass::enums::EnumType>::VALUES[0].0
+                    }
+                }
+            }
+        }
+    };
+}
+
+#[macro_export]
+/// Macro for deriving the `glib::Value` traits for a [`FlagsType`].
+///
+/// [`FlagsType`]: subclass/enums/trait.FlagsType.html
+macro_rules! glib_flags_derive_traits {
+    ($name:ident) => {
+        impl $crate::StaticType for $name {
+            fn static_type() -> $crate::Type {
+                <$name as $crate::subclass::enums::FlagsType>::get_type()
+            }
+        }
+
+        impl $crate::value::SetValue for $name {
+            unsafe fn set_value(value: &mut $crate::value::Value, this: &Self) {
+                $crate::gobject_sys::g_value_set_flags(
+                    $crate::translate::ToGlibPtrMut::to_glib_none_mut(value).0,
+                    $crate::subclass::enums::FlagsType::to_u32(*this),
+                );
+            }
+        }
+
+        impl<'a> $crate::value::FromValueOptional<'a> for $name {
+            unsafe fn from_value_optional(value: &'a $crate::value::Value) -> Option<Self> {
+                Some($crate::value::FromValue::from_value(value))
+            }
+        }
+
+        impl<'a> $crate::value::FromValue<'a> for $name {
+            unsafe fn from_value(value: &'a $crate::value::Value) -> Self {
+                let v = $crate::gobject_sys::g_value_get_flags(
+                    $crate::translate::ToGlibPtr::to_glib_none(value).0,
+                );
+                <$name as $crate::subclass::enums::FlagsType>::from_u32(v)
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use value::ToValue;
+    use {EnumClass, FlagsClass, StaticType};
+
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    enum Animal {
+        Cat = 1,
+        Dog = 5,
+    }
+
+    impl EnumType for Animal {
+        const NAME: &'static str = "TestAnimal";
+        const VALUES: &'static [(Self, &'static str, &'static str)] = &[
+            (Animal::Cat, "TEST_ANIMAL_CAT", "cat"),
+            (Animal::Dog, "TEST_ANIMAL_DOG", "dog"),
+        ];
+
+        glib_enum_type!();
+    }
+
+    glib_enum_derive_traits!(Animal);
+
+    bitflags! {
+        struct Permissions: u32 {
+            const READ = 1;
+            const WRITE = 2;
+            const EXECUTE = 4;
+        }
+    }
+
+    impl FlagsType for Permissions {
+        const NAME: &'static str = "TestPermissions";
+        const VALUES: &'static [(Self, &'static str, &'static str)] = &[
+            (Permissions::READ, "TEST_PERMISSIONS_READ", "read"),
+            (Permissions::WRITE, "TEST_PERMISSIONS_WRITE", "write"),
+            (Permissions::EXECUTE, "TEST_PERMISSIONS_EXECUTE", "execute"),
+        ];
+
+        glib_flags_type!();
+    }
+
+    glib_flags_derive_traits!(Permissions);
+
+    #[test]
+    fn test_enum() {
+        assert!(Animal::static_type().is_a(&::Type::BaseEnum));
+
+        let class = EnumClass::new(Animal::static_type()).unwrap();
+        let values = class.get_values();
+        assert_eq!(values.len(), 2);
+        assert_eq!(values[0].get_value(), 1);
+        assert_eq!(values[0].get_name(), "TEST_ANIMAL_CAT");
+        assert_eq!(values[1].get_nick(), "dog");
+        assert_eq!(class.get_value_by_nick("dog").unwrap().get_value(), 5);
+
+        let v = Animal::Dog.to_value();
+        assert_eq!(v.get::<Animal>(), Some(Animal::Dog));
+        assert_eq!(class.to_value_by_nick("cat").unwrap().get::<Animal>(), Some(Animal::Cat));
+
+        assert_eq!(Animal::from_i32(2), None);
+    }
+
+    #[test]
+    fn test_enum_invalid_value() {
+        use translate::ToGlibPtrMut;
+
+        let mut v = Animal::Dog.to_value();
+        unsafe {
+            gobject_sys::g_value_set_enum(v.to_glib_none_mut().0, 2);
+        }
+        assert_eq!(v.get::<Animal>(), None);
+        assert_eq!(v.get_some::<Animal>(), Ok(Animal::Cat));
+    }
+
+    #[test]
+    fn test_flags() {
+        assert!(Permissions::static_type().is_a(&::Type::BaseFlags));
+
+        let class = FlagsClass::new(Permissions::static_type()).unwrap();
+        assert_eq!(class.get_values().len(), 3);
+        assert_eq!(class.get_value_by_nick("write").unwrap().get_value(), 2);
+
+        let v = (Permissions::READ | Permissions::EXECUTE).to_value();
+        assert!(class.is_set_by_nick(&v, "execute"));
+        assert!(!class.is_set_by_nick(&v, "write"));
+        assert_eq!(
+            v.get::<Permissions>(),
+            Some(Permissions::READ | Permissions::EXECUTE)
+        );
+    }
+
+    #[test]
+    fn test_param_spec() {
+        let pspec = ::ParamSpec::enum_(
+            "animal",
+            "Animal",
+            "Some animal",
+            Animal::static_type(),
+            Animal::Cat.to_i32(),
+            ::ParamFlags::READWRITE,
+        );
+        assert_eq!(pspec.get_value_type(), Animal::static_type());
+        assert_eq!(
+            pspec.get_default_value().unwrap().get::<Animal>(),
+            Some(Animal::Cat)
+        );
+    }
+}
@@ -182,19 +182,28 @@ pub mod object;
 #[macro_use]
 pub mod boxed;
 
+#[macro_use]
+pub mod enums;
+
+pub mod type_module;
+
 pub mod prelude {
     //! Prelude that re-exports all important traits from this crate.
     pub use super::boxed::BoxedType;
+    pub use super::enums::{EnumType, FlagsType};
     pub use super::interface::{ObjectInterface, ObjectInterfaceExt};
     pub use super::object::{ObjectClassSubclassExt, ObjectImpl, ObjectImplExt};
+    pub use super::type_module::TypeModuleImpl;
     pub use super::types::{
         ClassStruct, InstanceStruct, IsImplementable, IsSubclassable, ObjectSubclass,
     };
 }
 
 pub use self::boxed::register_boxed_type;
+pub use self::enums::{register_enum_type, register_flags_type};
 pub use self::interface::register_interface;
 pub use self::object::Property;
 pub use self::types::{
-    register_type, InitializingType, SignalClassHandlerToken, SignalInvocationHint, TypeData,
+    register_dynamic_type, register_type, signal_accumulator_first_wins,
+    signal_accumulator_true_handled, InitializingType, SignalClassHandlerToken, SignalInvocationHint, TypeData,
 };
@@ -14,7 +14,7 @@ use std::fmt;
 use std::mem;
 use std::ptr;
 use translate::*;
-use {Object, ObjectClass, ObjectType, SignalFlags, Type, Value};
+use {InitiallyUnownedClass, Object, ObjectClass, ObjectType, SignalFlags, Type, Value};
 
 #[macro_export]
 /// Macro for boilerplate of [`ObjectImpl`] implementations.
@@ -63,6 +63,18 @@ pub trait ObjectImpl: ObjectImplExt + 'static {
     fn constructed(&self, obj: &Object) {
         self.parent_constructed(obj);
     }
+
+    /// Disposes of all references to other objects.
+    ///
+    /// This is called when the instance is about to be finalized, and possibly multiple times,
+    /// e.g. for breaking reference cycles. The instance must stay usable afterwards. Freeing of
+    /// any other resources should happen when the implementation struct is dropped, which
+    /// happens during finalization of the instance.
+    ///
+    /// Should chain up to the parent class' implementation.
+    fn dispose(&self, obj: &Object) {
+        self.parent_dispose(obj);
+    }
 }
 
 unsafe extern "C" fn get_property<T: ObjectSubclass>(
@@ -117,6 +129,13 @@ unsafe extern "C" fn constructed<T: ObjectSubclass>(obj: *mut gobject_sys::GObje
     imp.constructed(&from_glib_borrow(obj));
 }
 
+unsafe extern "C" fn dispose<T: ObjectSubclass>(obj: *mut gobject_sys::GObject) {
+    let instance = &*(obj as *mut T::Instance);
+    let imp = instance.get_impl();
+
+    imp.dispose(&from_glib_borrow(obj));
+}
+
 /// Definition of a property.
 #[derive(Clone)]
 pub struct Property<'a>(pub &'a str, pub fn(&str) -> ::ParamSpec);
@@ -134,7 +153,13 @@ pub unsafe trait ObjectClassSubclassExt: Sized + 'static {
     /// Install properties on the subclass.
     ///
     /// The index in the properties array is going to be the index passed to the
-    /// property setters and getters.
+    /// property setters and getters. If properties were already installed on the
+    /// subclass before, e.g. by calling this multiple times, the indices continue
+    /// after the ones of the previously installed properties.
+    ///
+    /// Properties with the `CONSTRUCT` or `CONSTRUCT_ONLY` flags are set via the
+    /// property setter during construction of every instance, before `constructed()`
+    /// is called, with either the value passed to `Object::new()` or their default value.
     fn install_properties<'a, T: Borrow<Property<'a>>>(&mut self, properties: &[T]) {
         if properties.is_empty() {
             return;
@@ -149,19 +174,47 @@ pub unsafe trait ObjectClassSubclassExt: Sized + 'static {
         }
 
         unsafe {
-            let mut pspecs_ptrs = Vec::with_capacity(properties.len());
+            let klass = self as *mut _ as *mut gobject_sys::GObjectClass;
+            let offset = n_own_properties(klass);
+
+            if offset == 0 {
+                let mut pspecs_ptrs = Vec::with_capacity(properties.len());
 
-            pspecs_ptrs.push(ptr::null_mut());
+                pspecs_ptrs.push(ptr::null_mut());
 
-            for pspec in &pspecs {
-                pspecs_ptrs.push(pspec.to_glib_none().0);
+                for pspec in &pspecs {
+                    pspecs_ptrs.push(pspec.to_glib_none().0);
+                }
+
+                gobject_sys::g_object_class_install_properties(
+                    klass,
+                    pspecs_ptrs.len() as u32,
+                    pspecs_ptrs.as_mut_ptr(),
+                );
+            } else {
+                for (i, pspec) in pspecs.iter().enumerate() {
+                    gobject_sys::g_object_class_install_property(
+                        klass,
+                        (offset + i + 1) as u32,
+                        pspec.to_glib_none().0,
+                    );
+                }
             }
+        }
+    }
 
-            gobject_sys::g_object_class_install_properties(
-                self as *mut _ as *mut gobject_sys::GObjectClass,
-                pspecs_ptrs.len() as u32,
-                pspecs_ptrs.as_mut_ptr(),
-            );
+    /// Overrides the property `name` of a parent class or of an interface implemented by the
+    /// subclass.
+    ///
+    /// This is required for all properties of the interfaces implemented by the subclass. The
+    /// returned index is passed to the property setters and getters for the property and
+    /// continues after the indices of the previously installed properties.
+    fn override_property(&mut self, name: &str) -> usize {
+        unsafe {
+            let klass = self as *mut _ as *mut gobject_sys::GObjectClass;
+            let id = n_own_properties(klass);
+            gobject_sys::g_object_class_override_property(klass, (id + 1) as u32, name.to_glib_none().0);
+            id
         }
     }
 
@@ -290,6 +343,20 @@ pub unsafe trait ObjectClassSubclassExt: Sized + 'static {
     }
 }
 
+// Number of properties that were installed on this very class, and not any of its parents
+unsafe fn n_own_properties(klass: *mut gobject_sys::GObjectClass) -> usize {
+    let type_ = (*klass).g_type_class.g_type;
+
+    let mut n_properties = 0;
+    let props = gobject_sys::g_object_class_list_properties(klass, &mut n_properties);
+    let n_own = (0..n_properties as usize)
+        .filter(|&i| (**props.add(i)).owner_type == type_)
+        .count();
+    glib_sys::g_free(props as glib_sys::gpointer);
+
+    n_own
+}
+
 unsafe impl ObjectClassSubclassExt for ObjectClass {}
 
 unsafe impl<T: ObjectSubclass> IsSubclassable<T> for ObjectClass {
@@ -299,14 +366,24 @@ unsafe impl<T: ObjectSubclass> IsSubclassable<T> for ObjectClass {
             klass.set_property = Some(set_property::<T>);
             klass.get_property = Some(get_property::<T>);
             klass.constructed = Some(constructed::<T>);
+            klass.dispose = Some(dispose::<T>);
         }
     }
 }
 
+unsafe impl<T: ObjectSubclass> IsSubclassable<T> for InitiallyUnownedClass {
+    fn override_vfuncs(&mut self) {
+        <ObjectClass as IsSubclassable<T>>::override_vfuncs(self);
+    }
+}
+
 pub trait ObjectImplExt {
     /// Chain up to the parent class' implementation of `glib::Object::constructed()`.
     fn parent_constructed(&self, obj: &Object);
 
+    /// Chain up to the parent class' implementation of `glib::Object::dispose()`.
+    fn parent_dispose(&self, obj: &Object);
+
     fn signal_chain_from_overridden(
         &self,
         token: &super::SignalClassHandlerToken,
@@ -326,6 +403,17 @@ impl<T: ObjectImpl + ObjectSubclass> ObjectImplExt for T {
         }
     }
 
+    fn parent_dispose(&self, obj: &Object) {
+        unsafe {
+            let data = self.get_type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut gobject_sys::GObjectClass;
+
+            if let Some(ref func) = (*parent_class).dispose {
+                func(obj.to_glib_none().0);
+            }
+        }
+    }
+
     fn signal_chain_from_overridden(
         &self,
         token: &super::SignalClassHandlerToken,
@@ -342,7 +430,7 @@ impl<T: ObjectImpl + ObjectSubclass> ObjectImplExt for T {
 }
 
 #[cfg(test)]
-mod test {
+pub(crate) mod test {
     use super::super::super::object::ObjectExt;
     use super::super::super::subclass;
     use super::super::super::value::{ToValue, Value};
@@ -375,6 +463,7 @@ mod test {
     pub struct SimpleObject {
         name: RefCell<Option<String>>,
         constructed: RefCell<bool>,
+        instance_initialized: RefCell<bool>,
     }
 
     impl ObjectSubclass for SimpleObject {
@@ -399,6 +488,14 @@ mod test {
                 ::Type::Unit,
             );
 
+            klass.add_signal_with_accumulator(
+                "handled",
+                SignalFlags::RUN_LAST,
+                &[],
+                bool::static_type(),
+                subclass::signal_accumulator_true_handled,
+            );
+
             klass.add_signal_with_class_handler(
                 "change-name",
                 SignalFlags::RUN_LAST | SignalFlags::ACTION,
@@ -423,8 +520,15 @@ mod test {
             Self {
                 name: RefCell::new(None),
                 constructed: RefCell::new(false),
+                instance_initialized: RefCell::new(false),
             }
         }
+
+        fn instance_init(obj: &Object) {
+            let imp = Self::from_instance(obj);
+            assert!(!*imp.constructed.borrow());
+            *imp.instance_initialized.borrow_mut() = true;
+        }
     }
 
     impl ObjectImpl for SimpleObject {
@@ -492,6 +596,215 @@ mod test {
         unsafe extern "C" fn interface_init(_iface: glib_sys::gpointer, _iface_data: glib_sys::gpointer) {}
     }
 
+    static CONSTRUCT_PROPERTIES: [Property; 1] = [Property("label", |name| {
+        ::ParamSpec::string(
+            name,
+            "Label",
+            "Label set during construction",
+            Some("default"),
+            ::ParamFlags::READWRITE | ::ParamFlags::CONSTRUCT,
+        )
+    })];
+
+    static CONSTRUCT_ONLY_PROPERTIES: [Property; 1] = [Property("id", |name| {
+        ::ParamSpec::uint(
+            name,
+            "ID",
+            "ID that can only be set during construction",
+            0,
+            u32::max_value(),
+            0,
+            ::ParamFlags::READWRITE | ::ParamFlags::CONSTRUCT_ONLY,
+        )
+    })];
+
+    pub struct ConstructObject {
+        label: RefCell<Option<String>>,
+        id: RefCell<Option<u32>>,
+        count: RefCell<u32>,
+    }
+
+    impl ObjectSubclass for ConstructObject {
+        const NAME: &'static str = "ConstructObject";
+        type ParentType = Object;
+        type Instance = subclass::simple::InstanceStruct<Self>;
+        type Class = subclass::simple::ClassStruct<Self>;
+
+        glib_object_subclass!();
+
+        fn type_init(type_: &mut subclass::InitializingType<Self>) {
+            type_.add_interface::<CounterInterface>();
+        }
+
+        fn class_init(klass: &mut subclass::simple::ClassStruct<Self>) {
+            klass.install_properties(&CONSTRUCT_PROPERTIES);
+            klass.install_properties(&CONSTRUCT_ONLY_PROPERTIES);
+            assert_eq!(klass.override_property("count"), 2);
+        }
+
+        fn new() -> Self {
+            Self {
+                label: RefCell::new(None),
+                id: RefCell::new(None),
+                count: RefCell::new(0),
+            }
+        }
+    }
+
+    impl ObjectImpl for ConstructObject {
+        glib_object_impl!();
+
+        fn set_property(&self, _obj: &Object, id: usize, value: &Value) {
+            match id {
+                0 => {
+                    self.label.replace(value.get());
+                }
+                1 => {
+                    self.id.replace(value.get());
+                }
+                _ => unimplemented!(),
+            }
+        }
+
+        fn get_property(&self, _obj: &Object, id: usize) -> Result<Value, ()> {
+            match id {
+                0 => Ok(self.label.borrow().to_value()),
+                1 => Ok(self.id.borrow().unwrap_or(0).to_value()),
+                2 => Ok(self.count.borrow().to_value()),
+                _ => unimplemented!(),
+            }
+        }
+
+        fn constructed(&self, obj: &Object) {
+            self.parent_constructed(obj);
+
+            // All construct properties are set at this point
+            assert!(self.label.borrow().is_some());
+            assert!(self.id.borrow().is_some());
+        }
+
+        fn dispose(&self, obj: &Object) {
+            if let Some(disposed) = obj.steal_data(&DISPOSED) {
+                disposed.fetch_add(1, ::std::sync::atomic::Ordering::SeqCst);
+            }
+
+            self.parent_dispose(obj);
+        }
+    }
+
+    static DISPOSED: ::DataKey<::std::sync::Arc<::std::sync::atomic::AtomicUsize>> =
+        ::DataKey::new("glib-rs-test-disposed");
+
+    impl CounterImpl for ConstructObject {
+        fn increment(&self, _obj: &Object, by: u32) -> u32 {
+            *self.count.borrow_mut() += by;
+            *self.count.borrow()
+        }
+    }
+
+    // Interface with a virtual method, implemented by filling in its vtable with a trampoline
+    // to the Rust implementation of the implementor
+    #[repr(C)]
+    pub struct CounterInterface {
+        parent: gobject_sys::GTypeInterface,
+        increment: Option<unsafe extern "C" fn(*mut gobject_sys::GObject, u32) -> u32>,
+        get_step: Option<unsafe extern "C" fn(*mut gobject_sys::GObject) -> u32>,
+    }
+
+    static COUNTER_PROPERTIES: [Property; 1] = [Property("count", |name| {
+        ::ParamSpec::uint(
+            name,
+            "Count",
+            "Current value of the counter",
+            0,
+            u32::max_value(),
+            0,
+            ::ParamFlags::READABLE,
+        )
+    })];
+
+    unsafe extern "C" fn counter_default_get_step(_obj: *mut gobject_sys::GObject) -> u32 {
+        1
+    }
+
+    impl ObjectInterface for CounterInterface {
+        const NAME: &'static str = "CounterInterface";
+
+        glib_object_interface!();
+
+        fn type_init(type_: &mut subclass::InitializingType<Self>) {
+            type_.add_prerequisite::<Object>();
+        }
+
+        fn interface_init(&mut self) {
+            self.install_properties(&COUNTER_PROPERTIES);
+            // Default implementation, used by all implementors that don't override it
+            self.get_step = Some(counter_default_get_step);
+        }
+    }
+
+    impl StaticType for CounterInterface {
+        fn static_type() -> Type {
+            CounterInterface::get_type()
+        }
+    }
+
+    pub trait CounterImpl: ObjectImpl + 'static {
+        fn increment(&self, obj: &Object, by: u32) -> u32;
+    }
+
+    unsafe extern "C" fn counter_increment<T: ObjectSubclass + CounterImpl>(
+        obj: *mut gobject_sys::GObject,
+        by: u32,
+    ) -> u32 {
+        let instance = &*(obj as *mut T::Instance);
+        let imp = instance.get_impl();
+        imp.increment(&from_glib_borrow(obj), by)
+    }
+
+    unsafe impl<T: ObjectSubclass + CounterImpl> IsImplementable<T> for CounterInterface {
+        unsafe extern "C" fn interface_init(iface: glib_sys::gpointer, _iface_data: glib_sys::gpointer) {
+            let iface = &mut *(iface as *mut CounterInterface);
+            iface.increment = Some(counter_increment::<T>);
+        }
+    }
+
+    pub struct FloatingObject;
+
+    impl ObjectSubclass for FloatingObject {
+        const NAME: &'static str = "FloatingObject";
+        type ParentType = ::InitiallyUnowned;
+        type Instance = subclass::simple::InstanceStruct<Self>;
+        type Class = subclass::simple::ClassStruct<Self>;
+
+        glib_object_subclass!();
+
+        fn new() -> Self {
+            FloatingObject
+        }
+    }
+
+    impl ObjectImpl for FloatingObject {
+        glib_object_impl!();
+    }
+
+    glib_wrapper! {
+        pub struct Counter(Interface<gobject_sys::GObject>);
+
+        match fn {
+            get_type => || CounterInterface::get_type().to_glib(),
+        }
+    }
+
+    // Final wrapper without class struct for the ConstructObject subclass
+    glib_wrapper! {
+        pub struct ConstructObjectWrapper(Object<gobject_sys::GObject, ConstructObjectWrapperClass>) @implements Counter;
+
+        match fn {
+            get_type => || ConstructObject::get_type().to_glib(),
+        }
+    }
+
     #[test]
     fn test_create() {
         let type_ = SimpleObject::get_type();
@@ -499,6 +812,7 @@ mod test {
 
         assert!(obj.get_type().is_a(&DummyInterface::static_type()));
 
+        assert!(*SimpleObject::from_instance(&obj).instance_initialized.borrow());
         assert_eq!(
             obj.get_property("constructed").unwrap().get::<bool>(),
             Some(true)
@@ -516,6 +830,26 @@ mod test {
         assert!(weak.upgrade().is_none());
     }
 
+    #[test]
+    fn test_typed_property() {
+        let type_ = SimpleObject::get_type();
+        let obj = Object::new(type_, &[]).unwrap();
+
+        assert_eq!(obj.property::<bool, _>("constructed").unwrap(), Some(true));
+        assert_eq!(obj.property::<String, _>("name").unwrap(), None);
+
+        obj.set_property("name", &"test").unwrap();
+        assert_eq!(
+            obj.property::<String, _>("name").unwrap(),
+            Some(String::from("test"))
+        );
+
+        assert!(obj.property::<i32, _>("name").is_err());
+        assert!(obj.property::<String, _>("does-not-exist").is_err());
+        assert!(obj.set_property("name", &1i32).is_err());
+        assert!(obj.set_property("does-not-exist", &"test").is_err());
+    }
+
     #[test]
     fn test_signals() {
         use std::sync::{Arc, Mutex};
@@ -550,4 +884,260 @@ mod test {
         assert_eq!(old_name, Some(String::from("old-name")));
         assert!(*name_changed_triggered.lock().unwrap());
     }
+
+    #[test]
+    fn test_signal_query() {
+        let type_ = SimpleObject::get_type();
+
+        let signals = ::SignalQuery::list(type_);
+        let names = signals.iter().map(|s| s.signal_name()).collect::<Vec<_>>();
+        assert_eq!(names, vec!["name-changed", "handled", "change-name"]);
+        assert!(signals.iter().all(|s| s.type_() == type_));
+
+        let change_name = ::SignalQuery::lookup(type_, "change-name").unwrap();
+        assert_eq!(change_name.return_type(), String::static_type());
+        assert_eq!(change_name.param_types(), &[String::static_type()]);
+        assert!(change_name.flags().contains(SignalFlags::ACTION));
+
+        // Includes the signals of GObject
+        let all = ::SignalQuery::list_all(type_);
+        assert_eq!(all.len(), 4);
+        assert_eq!(all[3].signal_name(), "notify");
+    }
+
+    #[test]
+    fn test_emission_hook() {
+        use std::sync::{Arc, Mutex};
+
+        let type_ = SimpleObject::get_type();
+        let obj1 = Object::new(type_, &[]).unwrap();
+        let obj2 = Object::new(type_, &[]).unwrap();
+
+        // Hooks are global, ignore emissions from other tests that run at the same time
+        let names = Arc::new(Mutex::new(Vec::new()));
+        let names_clone = names.clone();
+        let hook_id = ::signal_add_emission_hook(type_, "name-changed", move |args| {
+            match args[1].get::<String>() {
+                Some(ref name) if name.starts_with("hook-") => names_clone.lock().unwrap().push(name.clone()),
+                _ => (),
+            }
+            true
+        })
+        .unwrap();
+
+        let once = Arc::new(Mutex::new(0));
+        let once_clone = once.clone();
+        ::signal_add_emission_hook(type_, "name-changed", move |args| {
+            match args[1].get::<String>() {
+                Some(ref name) if name.starts_with("hook-") => (),
+                _ => return true,
+            }
+            *once_clone.lock().unwrap() += 1;
+            false
+        })
+        .unwrap();
+
+        obj1.set_property("name", &"hook-first").unwrap();
+        obj2.set_property("name", &"hook-second").unwrap();
+        assert_eq!(*names.lock().unwrap(), vec!["hook-first", "hook-second"]);
+        assert_eq!(*once.lock().unwrap(), 1);
+
+        ::signal_remove_emission_hook(hook_id);
+        obj1.set_property("name", &"hook-third").unwrap();
+        assert_eq!(names.lock().unwrap().len(), 2);
+
+        assert!(::signal_add_emission_hook(type_, "no-such-signal", |_| true).is_err());
+        // notify is registered with NO_HOOKS
+        assert!(::signal_add_emission_hook(type_, "notify::name", |_| true).is_err());
+    }
+
+    #[test]
+    fn test_emit_errors() {
+        let type_ = SimpleObject::get_type();
+        let obj = Object::new(type_, &[]).unwrap();
+
+        assert!(obj.emit("does-not-exist", &[]).is_err());
+        assert!(obj.emit("change-name", &[]).is_err());
+        assert!(obj.emit("change-name", &[&1i32]).is_err());
+        assert!(obj.emit("change-name", &[&"new-name"]).is_ok());
+    }
+
+    glib_export_type!(glib_rs_test_simple_object_get_type, SimpleObject);
+    glib_export_new!(glib_rs_test_simple_object_new, SimpleObject);
+
+    #[test]
+    fn test_export() {
+        assert_eq!(
+            glib_rs_test_simple_object_get_type(),
+            SimpleObject::get_type().to_glib()
+        );
+
+        let obj: Object = unsafe { from_glib_full(glib_rs_test_simple_object_new()) };
+        assert_eq!(obj.get_type(), SimpleObject::get_type());
+        assert_eq!(obj.ref_count(), 1);
+    }
+
+    #[test]
+    fn test_construct_properties() {
+        let type_ = ConstructObject::get_type();
+
+        let obj = Object::new(type_, &[("id", &42u32)]).unwrap();
+        assert_eq!(obj.get_property("label").unwrap().get::<&str>(), Some("default"));
+        assert_eq!(obj.get_property("id").unwrap().get::<u32>(), Some(42));
+
+        let obj = Object::new(type_, &[("label", &"custom")]).unwrap();
+        assert_eq!(obj.get_property("label").unwrap().get::<&str>(), Some("custom"));
+        assert_eq!(obj.get_property("id").unwrap().get::<u32>(), Some(0));
+
+        // Construct-only properties can't be changed afterwards
+        obj.set_property("label", &"changed").unwrap();
+        assert!(obj.set_property("id", &1u32).is_err());
+        assert_eq!(obj.get_property("id").unwrap().get::<u32>(), Some(0));
+    }
+
+    #[test]
+    fn test_signal_accumulator() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+
+        let type_ = SimpleObject::get_type();
+        let obj = Object::new(type_, &[]).unwrap();
+
+        assert_eq!(
+            obj.emit("handled", &[]).unwrap().and_then(|v| v.get::<bool>()),
+            Some(false)
+        );
+
+        let calls = Arc::new(AtomicUsize::new(0));
+        for &handled in &[false, true, false] {
+            let calls = calls.clone();
+            obj.connect("handled", false, move |_| {
+                calls.fetch_add(1, Ordering::SeqCst);
+                Some(handled.to_value())
+            })
+            .unwrap();
+        }
+
+        // The third handler is not called anymore
+        assert_eq!(
+            obj.emit("handled", &[]).unwrap().and_then(|v| v.get::<bool>()),
+            Some(true)
+        );
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_interface_vfuncs() {
+        let type_ = ConstructObject::get_type();
+        let obj = Object::new(type_, &[]).unwrap();
+
+        assert!(obj.is::<CounterInterface>());
+        assert!(!Object::new(SimpleObject::get_type(), &[]).unwrap().is::<CounterInterface>());
+
+        let iface = CounterInterface::from_instance(&obj);
+        let increment = iface.increment.unwrap();
+        unsafe {
+            assert_eq!(increment(obj.as_ptr(), 2), 2);
+            assert_eq!(increment(obj.as_ptr(), 3), 5);
+            assert_eq!(iface.get_step.unwrap()(obj.as_ptr()), 1);
+        }
+
+        // Interface property, overridden by the implementor. Looking it up gives the
+        // interface's property
+        assert_eq!(obj.get_property("count").unwrap().get::<u32>(), Some(5));
+        assert_eq!(
+            obj.find_property("count").unwrap().get_owner_type(),
+            CounterInterface::get_type()
+        );
+
+        // GObject itself does not implement the interface
+        unsafe {
+            assert!(ConstructObject::type_data()
+                .as_ref()
+                .get_parent_interface::<CounterInterface>()
+                .is_null());
+        }
+    }
+
+    #[test]
+    fn test_wrapper_interfaces() {
+        fn assert_derivable<T: ::IsDerivable>() {}
+        assert_derivable::<Object>();
+        assert_derivable::<::InitiallyUnowned>();
+        assert_derivable::<::TypeModule>();
+
+        let obj = Object::new(ConstructObject::get_type(), &[])
+            .unwrap()
+            .downcast::<ConstructObjectWrapper>()
+            .unwrap();
+        assert!(obj.is::<Counter>());
+
+        let counter: &Counter = obj.upcast_ref();
+        assert_eq!(counter.get_property("count").unwrap().get::<u32>(), Some(0));
+        assert_eq!(counter.clone().downcast::<ConstructObjectWrapper>().unwrap(), obj);
+        assert!(Object::new(SimpleObject::get_type(), &[])
+            .unwrap()
+            .downcast::<Counter>()
+            .is_err());
+    }
+
+    #[test]
+    fn test_floating_reference() {
+        let type_ = FloatingObject::get_type();
+
+        let obj = Object::new(type_, &[]).unwrap();
+        assert!(obj.is::<::InitiallyUnowned>());
+        assert!(!obj.is_floating());
+        assert_eq!(obj.ref_count(), 1);
+
+        unsafe {
+            let raw = gobject_sys::g_object_new(type_.to_glib(), ptr::null());
+            assert_ne!(gobject_sys::g_object_is_floating(raw), glib_sys::GFALSE);
+            let obj: Object = from_glib_full(raw as *mut gobject_sys::GObject);
+            assert!(!obj.is_floating());
+            assert_eq!(obj.ref_count(), 1);
+
+            let raw = gobject_sys::g_object_new(type_.to_glib(), ptr::null());
+            let obj: ::InitiallyUnowned = from_glib_none(raw as *mut gobject_sys::GInitiallyUnowned);
+            assert!(!obj.is_floating());
+            assert_eq!(obj.ref_count(), 1);
+        }
+    }
+
+    #[test]
+    fn test_dispose() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+
+        let obj = Object::new(ConstructObject::get_type(), &[]).unwrap();
+        let disposed = Arc::new(AtomicUsize::new(0));
+        obj.set_data(&DISPOSED, disposed.clone());
+
+        let weak = obj.downgrade();
+        assert_eq!(disposed.load(Ordering::SeqCst), 0);
+        drop(obj);
+        assert_eq!(disposed.load(Ordering::SeqCst), 1);
+        assert!(weak.upgrade().is_none());
+    }
+
+    #[test]
+    fn test_instance_private() {
+        let obj = Object::new(SimpleObject::get_type(), &[]).unwrap();
+        let imp = SimpleObject::from_instance(&obj);
+
+        // The implementation is stored in the instance private data, which GLib places right
+        // in front of the instance struct, and not in a separate allocation
+        unsafe {
+            let offset = SimpleObject::type_data().as_ref().get_private_offset();
+            assert!(offset < 0);
+
+            let priv_ptr = (obj.as_ptr() as *const u8).offset(offset) as *const Option<SimpleObject>;
+            assert_eq!(
+                (*priv_ptr).as_ref().unwrap() as *const SimpleObject,
+                imp as *const SimpleObject
+            );
+        }
+
+        assert_eq!(imp.get_instance(), obj);
+    }
 }
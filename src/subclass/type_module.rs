@@ -0,0 +1,141 @@
+// Copyright 2019, The Gtk-rs Project Developers.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+// Licensed under the MIT license, see the LICENSE file or <http://opensource.org/licenses/MIT>
+
+//! Module for implementing `GTypeModule` subclasses, which allow registering types dynamically
+//! from loadable plugins.
+
+use super::prelude::*;
+use glib_sys;
+use gobject_sys;
+use translate::*;
+use {ObjectClass, TypeModule, TypeModuleClass};
+
+/// Trait for implementors of `glib::TypeModule` subclasses.
+///
+/// The types of the module are registered with [`register_dynamic_type`] from `load()`.
+///
+/// [`register_dynamic_type`]: ../types/fn.register_dynamic_type.html
+pub trait TypeModuleImpl: ObjectImpl + 'static {
+    /// Loads the module and registers its types.
+    ///
+    /// Returns `false` if loading failed.
+    fn load(&self, module: &TypeModule) -> bool;
+
+    /// Unloads the module, i.e. frees everything that was set up by `load()`.
+    fn unload(&self, module: &TypeModule);
+}
+
+unsafe extern "C" fn load<T: ObjectSubclass + TypeModuleImpl>(
+    module: *mut gobject_sys::GTypeModule,
+) -> glib_sys::gboolean {
+    let instance = &*(module as *mut T::Instance);
+    let imp = instance.get_impl();
+
+    imp.load(&from_glib_borrow(module)).to_glib()
+}
+
+unsafe extern "C" fn unload<T: ObjectSubclass + TypeModuleImpl>(
+    module: *mut gobject_sys::GTypeModule,
+) {
+    let instance = &*(module as *mut T::Instance);
+    let imp = instance.get_impl();
+
+    imp.unload(&from_glib_borrow(module));
+}
+
+unsafe impl<T: ObjectSubclass + TypeModuleImpl> IsSubclassable<T> for TypeModuleClass {
+    fn override_vfuncs(&mut self) {
+        <ObjectClass as IsSubclassable<T>>::override_vfuncs(self);
+        unsafe {
+            let klass = &mut *(self as *mut Self as *mut gobject_sys::GTypeModuleClass);
+            klass.load = Some(load::<T>);
+            klass.unload = Some(unload::<T>);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use prelude::*;
+    use std::cell::Cell;
+    use std::mem;
+    use subclass;
+    use Object;
+
+    pub struct DynamicObject;
+
+    impl ObjectSubclass for DynamicObject {
+        const NAME: &'static str = "DynamicObject";
+        type ParentType = Object;
+        type Instance = subclass::simple::InstanceStruct<Self>;
+        type Class = subclass::simple::ClassStruct<Self>;
+
+        glib_object_subclass_dynamic!();
+
+        fn new() -> Self {
+            DynamicObject
+        }
+    }
+
+    impl ObjectImpl for DynamicObject {
+        glib_object_impl!();
+    }
+
+    pub struct TestTypeModule {
+        loaded: Cell<u32>,
+    }
+
+    impl ObjectSubclass for TestTypeModule {
+        const NAME: &'static str = "TestTypeModule";
+        type ParentType = TypeModule;
+        type Instance = subclass::simple::InstanceStruct<Self>;
+        type Class = subclass::simple::ClassStruct<Self>;
+
+        glib_object_subclass!();
+
+        fn new() -> Self {
+            Self {
+                loaded: Cell::new(0),
+            }
+        }
+    }
+
+    impl ObjectImpl for TestTypeModule {
+        glib_object_impl!();
+    }
+
+    impl TypeModuleImpl for TestTypeModule {
+        fn load(&self, module: &TypeModule) -> bool {
+            subclass::register_dynamic_type::<DynamicObject>(module);
+            self.loaded.set(self.loaded.get() + 1);
+            true
+        }
+
+        fn unload(&self, _module: &TypeModule) {}
+    }
+
+    #[test]
+    fn test_dynamic_type() {
+        let module = Object::new(TestTypeModule::get_type(), &[])
+            .unwrap()
+            .downcast::<TypeModule>()
+            .unwrap();
+        let imp = TestTypeModule::from_instance(&module);
+        module.set_name("test-module");
+
+        assert!(module.use_());
+        assert_eq!(imp.loaded.get(), 1);
+
+        let obj = Object::new(DynamicObject::get_type(), &[]).unwrap();
+        assert!(obj.get_type().is_a(&DynamicObject::get_type()));
+        let _ = DynamicObject::from_instance(&obj);
+        drop(obj);
+
+        module.unuse();
+
+        // Type modules must never be finalized
+        mem::forget(module);
+    }
+}
@@ -7,7 +7,7 @@
 use super::object::ObjectImpl;
 use glib_sys;
 use gobject_sys;
-use object::{ObjectExt, ObjectType};
+use object::{IsDerivable, ObjectExt, ObjectType};
 use std::fmt;
 use std::marker;
 use std::mem;
@@ -24,6 +24,11 @@ pub struct InitializingType<T>(pub(crate) Type, pub(crate) marker::PhantomData<T
 
 impl<T: ObjectSubclass> InitializingType<T> {
     /// Adds an interface implementation for `I` to the type.
+    ///
+    /// `I::interface_init()` is called with the interface struct of this type once the class is
+    /// initialized, and should fill in the interface's virtual methods with trampolines that
+    /// call into the Rust implementation. Interfaces already implemented by a parent class can be
+    /// overridden this way too, see `TypeData::get_parent_interface()` for chaining up.
     pub fn add_interface<I: IsImplementable<T>>(&mut self) {
         unsafe {
             let iface_info = gobject_sys::GInterfaceInfo {
@@ -176,6 +181,21 @@ impl TypeData {
         }
     }
 
+    /// Returns a pointer to the parent class' implementation of the interface `I`, or
+    /// `NULL` if the parent class does not implement `I`.
+    ///
+    /// This is used for chaining up to the parent class' implementation of interface
+    /// methods when overriding an interface that is already implemented by a parent class.
+    pub fn get_parent_interface<I: StaticType>(&self) -> glib_sys::gpointer {
+        unsafe {
+            if self.parent_class.is_null() {
+                return ptr::null_mut();
+            }
+
+            gobject_sys::g_type_interface_peek(self.parent_class, I::static_type().to_glib())
+        }
+    }
+
     /// Returns the offset of the private struct in bytes relative to the
     /// beginning of the instance struct.
     pub fn get_private_offset(&self) -> isize {
@@ -218,6 +238,107 @@ macro_rules! glib_object_subclass {
     };
 }
 
+#[macro_export]
+/// Macro for boilerplate of [`ObjectSubclass`] implementations of types that are registered
+/// dynamically with [`register_dynamic_type`].
+///
+/// Different to [`glib_object_subclass!`], the generated `get_type()` function does not register
+/// the type but panics if it was not registered yet.
+///
+/// [`ObjectSubclass`]: subclass/types/trait.ObjectSubclass.html
+/// [`register_dynamic_type`]: subclass/types/fn.register_dynamic_type.html
+/// [`glib_object_subclass!`]: macro.glib_object_subclass.html
+macro_rules! glib_object_subclass_dynamic {
+    () => {
+        fn type_data() -> ::std::ptr::NonNull<$crate::subclass::TypeData> {
+            static mut DATA: $crate::subclass::TypeData = $crate::subclass::TypeData {
+                type_: $crate::Type::Invalid,
+                parent_class: ::std::ptr::null_mut(),
+                interface_data: ::std::ptr::null_mut(),
+                private_offset: 0,
+            };
+
+            unsafe { ::std::ptr::NonNull::new_unchecked(&mut DATA) }
+        }
+
+        fn get_type() -> $crate::Type {
+            let type_ = unsafe { Self::type_data().as_ref().get_type() };
+            assert_ne!(type_, $crate::Type::Invalid, "Dynamic type was not registered yet");
+
+            type_
+        }
+    };
+}
+
+#[macro_export]
+/// Macro for exporting the `get_type()` function of a Rust-defined type as C function.
+///
+/// This allows C code, or other languages via GObject-Introspection, to use types implemented in
+/// Rust. The type can be an [`ObjectSubclass`], or a [`BoxedType`] if prefixed with `boxed`.
+///
+/// ```ignore
+/// // GType my_object_get_type(void);
+/// glib_export_type!(my_object_get_type, MyObject);
+/// // GType my_boxed_get_type(void);
+/// glib_export_type!(boxed my_boxed_get_type, MyBoxed);
+/// ```
+///
+/// [`ObjectSubclass`]: subclass/types/trait.ObjectSubclass.html
+/// [`BoxedType`]: subclass/boxed/trait.BoxedType.html
+macro_rules! glib_export_type {
+    ($(#[$attr:meta])* boxed $get_type:ident, $type:ty) => {
+        $(#[$attr])*
+        #[no_mangle]
+        pub extern "C" fn $get_type() -> $crate::glib_sys::GType {
+            $crate::translate::ToGlib::to_glib(&<$type as $crate::subclass::boxed::BoxedType>::get_type())
+        }
+    };
+    ($(#[$attr:meta])* $get_type:ident, $type:ty) => {
+        $(#[$attr])*
+        #[no_mangle]
+        pub extern "C" fn $get_type() -> $crate::glib_sys::GType {
+            $crate::translate::ToGlib::to_glib(&<$type as $crate::subclass::types::ObjectSubclass>::get_type())
+        }
+    };
+}
+
+#[macro_export]
+/// Macro for exporting a C function that creates a new instance of a Rust-defined
+/// [`ObjectSubclass`] without any construct properties.
+///
+/// The returned object is owned by the caller, or floating for types deriving from
+/// `GInitiallyUnowned`. If the object can't be created, a critical warning is logged and `NULL`
+/// is returned.
+///
+/// ```ignore
+/// // MyObject *my_object_new(void);
+/// glib_export_new!(my_object_new, MyObject);
+/// ```
+///
+/// [`ObjectSubclass`]: subclass/types/trait.ObjectSubclass.html
+macro_rules! glib_export_new {
+    ($(#[$attr:meta])* $new:ident, $type:ty) => {
+        $(#[$attr])*
+        #[no_mangle]
+        pub extern "C" fn $new() -> *mut $crate::gobject_sys::GObject {
+            unsafe {
+                let ptr = $crate::gobject_sys::g_object_newv(
+                    $crate::translate::ToGlib::to_glib(
+                        &<$type as $crate::subclass::types::ObjectSubclass>::get_type(),
+                    ),
+                    0,
+                    ::std::ptr::null_mut(),
+                );
+                if ptr.is_null() {
+                    // Panicking here would unwind into the calling C code
+                    $crate::g_critical!("glib-rs", "Failed to create an instance of {}", stringify!($type));
+                }
+                ptr
+            }
+        }
+    };
+}
+
 /// The central trait for subclassing a `GObject` type.
 ///
 /// Links together the type name, parent type and the instance and
@@ -245,7 +366,13 @@ pub trait ObjectSubclass: ObjectImpl + Sized + 'static {
     const ABSTRACT: bool = false;
 
     /// Parent Rust type to inherit from.
+    ///
+    /// Only classes marked as `@derivable` in `glib_wrapper!` can be inherited from, see
+    /// [`IsDerivable`].
+    ///
+    /// [`IsDerivable`]: ../../trait.IsDerivable.html
     type ParentType: ObjectType
+        + IsDerivable
         + FromGlibPtrBorrow<*mut <Self::ParentType as ObjectType>::GlibType>
         + FromGlibPtrNone<*mut <Self::ParentType as ObjectType>::GlibType>;
 
@@ -361,6 +488,18 @@ pub trait ObjectSubclass: ObjectImpl + Sized + 'static {
     fn new_with_class(_klass: &Self::Class) -> Self {
         Self::new()
     }
+
+    /// Instance initialization.
+    ///
+    /// This is called right after the private struct was created by `new()` or
+    /// `new_with_class()` and allows subclasses to do instance-specific initialization that
+    /// needs access to the instance, e.g. initializing widget templates.
+    ///
+    /// The instance is not fully initialized at this point: subclasses of this type are not
+    /// initialized yet and no construct properties were set.
+    ///
+    /// Optional
+    fn instance_init(_obj: &Self::ParentType) {}
 }
 
 unsafe extern "C" fn class_init<T: ObjectSubclass>(klass: glib_sys::gpointer, _klass_data: glib_sys::gpointer)
@@ -419,6 +558,9 @@ unsafe extern "C" fn instance_init<T: ObjectSubclass>(
     let imp = T::new_with_class(klass);
 
     ptr::write(imp_storage, Some(imp));
+
+    let obj: T::ParentType = from_glib_borrow(obj as *mut <T::ParentType as ObjectType>::GlibType);
+    T::instance_init(&obj);
 }
 
 unsafe extern "C" fn finalize<T: ObjectSubclass>(obj: *mut gobject_sys::GObject) {
@@ -497,6 +639,66 @@ where
     }
 }
 
+/// Register a `glib::Type` ID for `T` with the type module `module`.
+///
+/// This is meant to be called from the `load()` implementation of the module, every time it is
+/// loaded. The type keeps its ID when the module is unloaded and loaded again, but its class is
+/// initialized anew after every load. `T::type_init()` is only called on the first load.
+///
+/// Subclasses registered this way should use the [`glib_object_subclass_dynamic!`] macro.
+///
+/// [`glib_object_subclass_dynamic!`]: ../../macro.glib_object_subclass_dynamic.html
+pub fn register_dynamic_type<T: ObjectSubclass>(module: &::TypeModule) -> Type
+where
+    <<T as ObjectSubclass>::ParentType as ObjectType>::RustClassType: IsSubclassable<T>,
+{
+    unsafe {
+        use std::ffi::CString;
+
+        let type_info = gobject_sys::GTypeInfo {
+            class_size: mem::size_of::<T::Class>() as u16,
+            base_init: None,
+            base_finalize: None,
+            class_init: Some(class_init::<T>),
+            class_finalize: None,
+            class_data: ptr::null_mut(),
+            instance_size: mem::size_of::<T::Instance>() as u16,
+            n_preallocs: 0,
+            instance_init: Some(instance_init::<T>),
+            value_table: ptr::null(),
+        };
+
+        let type_name = CString::new(T::NAME).unwrap();
+
+        let type_ = from_glib(gobject_sys::g_type_module_register_type(
+            module.to_glib_none().0,
+            <T::ParentType as StaticType>::static_type().to_glib(),
+            type_name.as_ptr(),
+            &type_info,
+            if T::ABSTRACT {
+                gobject_sys::G_TYPE_FLAG_ABSTRACT
+            } else {
+                0
+            },
+        ));
+        assert_ne!(type_, Type::Invalid);
+
+        // The private struct can't be added to dynamic types right away. Instead the size is
+        // stored here and it's added during class initialization, which happens again after
+        // every load of the module.
+        let mut data = T::type_data();
+        let first_load = data.as_ref().type_ == Type::Invalid;
+        (*data.as_mut()).type_ = type_;
+        (*data.as_mut()).private_offset = mem::size_of::<Option<T>>() as isize;
+
+        if first_load {
+            T::type_init(&mut InitializingType::<T>(type_, marker::PhantomData));
+        }
+
+        type_
+    }
+}
+
 pub(crate) unsafe fn add_signal(
     type_: glib_sys::GType,
     name: &str,
@@ -542,6 +744,31 @@ impl fmt::Debug for SignalInvocationHint {
     }
 }
 
+/// Signal accumulator that stops the emission once a handler returned `true`.
+///
+/// This is meant for signals with a `bool` return value that signals whether the event was
+/// handled, and can be passed to e.g. `add_signal_with_accumulator()`. The emission returns
+/// `true` if any handler returned `true`.
+pub fn signal_accumulator_true_handled(
+    _hint: &SignalInvocationHint,
+    return_accu: &mut Value,
+    handler_return: &Value,
+) -> bool {
+    let handled = handler_return.get::<bool>().unwrap_or(false);
+    *return_accu = handler_return.clone();
+    !handled
+}
+
+/// Signal accumulator that stops the emission after the first handler and returns its value.
+pub fn signal_accumulator_first_wins(
+    _hint: &SignalInvocationHint,
+    return_accu: &mut Value,
+    handler_return: &Value,
+) -> bool {
+    *return_accu = handler_return.clone();
+    false
+}
+
 pub(crate) unsafe fn add_signal_with_accumulator<F>(
     type_: glib_sys::GType,
     name: &str,
@@ -564,7 +791,7 @@ pub(crate) unsafe fn add_signal_with_accumulator<F>(
         handler_return: *const gobject_sys::GValue,
         data: glib_sys::gpointer,
     ) -> glib_sys::gboolean {
-        let accumulator: &F = &*(data as *const &F);
+        let accumulator: &F = &*(data as *const F);
         accumulator(
             &SignalInvocationHint(*ihint),
             &mut *(return_accu as *mut Value),
@@ -655,7 +882,7 @@ pub(crate) unsafe fn add_signal_with_class_handler_and_accumulator<F, G>(
         handler_return: *const gobject_sys::GValue,
         data: glib_sys::gpointer,
     ) -> glib_sys::gboolean {
-        let accumulator: &G = &*(data as *const &G);
+        let accumulator: &G = &*(data as *const G);
         accumulator(
             &SignalInvocationHint(*ihint),
             &mut *(return_accu as *mut Value),
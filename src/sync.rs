@@ -0,0 +1,344 @@
+// Copyright 2019, The Gtk-rs Project Developers.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+// Licensed under the MIT license, see the LICENSE file or <http://opensource.org/licenses/MIT>
+
+//! `GLib` locking primitives.
+//!
+//! Pure Rust code should use the locks from `std::sync`. These bindings are meant for
+//! interoperating with C libraries that expose their own `GMutex`, `GRecMutex`, `GRWLock` or
+//! `GCond` and require callers to take them, which is possible via the `lock_raw()` functions
+//! of the guards.
+
+use glib_sys;
+use std::cell::UnsafeCell;
+use std::fmt;
+use std::marker::PhantomData;
+use std::mem;
+use translate::*;
+
+macro_rules! glib_lock {
+    ($(#[$attr:meta])* $name:ident, $ffi_name:ty, $init:path, $clear:path) => {
+        $(#[$attr])*
+        pub struct $name(Box<UnsafeCell<$ffi_name>>);
+
+        unsafe impl Send for $name {}
+        unsafe impl Sync for $name {}
+
+        impl $name {
+            pub fn new() -> $name {
+                unsafe {
+                    let lock = Box::new(UnsafeCell::new(mem::zeroed()));
+                    $init(lock.get());
+                    $name(lock)
+                }
+            }
+
+            /// Returns the underlying C pointer.
+            pub fn as_ptr(&self) -> *mut $ffi_name {
+                self.0.get()
+            }
+        }
+
+        impl Default for $name {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+
+        impl Drop for $name {
+            fn drop(&mut self) {
+                unsafe {
+                    $clear(self.0.get());
+                }
+            }
+        }
+
+        impl fmt::Debug for $name {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.debug_tuple(stringify!($name)).field(&self.as_ptr()).finish()
+            }
+        }
+    };
+}
+
+macro_rules! glib_lock_guard {
+    ($(#[$attr:meta])* $name:ident, $ffi_name:ty, $lock:path, $trylock:path, $unlock:path) => {
+        $(#[$attr])*
+        #[must_use]
+        pub struct $name<'a> {
+            ptr: *mut $ffi_name,
+            phantom: PhantomData<&'a $ffi_name>,
+        }
+
+        impl<'a> $name<'a> {
+            /// Takes the lock behind `ptr` and returns a guard that releases it again when dropped.
+            ///
+            /// # Safety
+            ///
+            /// `ptr` must point to an initialized lock that stays valid for the lifetime of the
+            /// returned guard.
+            pub unsafe fn lock_raw(ptr: *mut $ffi_name) -> $name<'a> {
+                assert!(!ptr.is_null());
+                $lock(ptr);
+                $name {
+                    ptr,
+                    phantom: PhantomData,
+                }
+            }
+
+            /// Tries to take the lock behind `ptr` without blocking.
+            ///
+            /// # Safety
+            ///
+            /// `ptr` must point to an initialized lock that stays valid for the lifetime of the
+            /// returned guard.
+            pub unsafe fn try_lock_raw(ptr: *mut $ffi_name) -> Option<$name<'a>> {
+                assert!(!ptr.is_null());
+                if from_glib($trylock(ptr)) {
+                    Some($name {
+                        ptr,
+                        phantom: PhantomData,
+                    })
+                } else {
+                    None
+                }
+            }
+        }
+
+        impl<'a> Drop for $name<'a> {
+            fn drop(&mut self) {
+                unsafe {
+                    $unlock(self.ptr);
+                }
+            }
+        }
+
+        impl<'a> fmt::Debug for $name<'a> {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.debug_tuple(stringify!($name)).field(&self.ptr).finish()
+            }
+        }
+    };
+}
+
+glib_lock!(
+    /// A `GMutex`.
+    Mutex,
+    glib_sys::GMutex,
+    glib_sys::g_mutex_init,
+    glib_sys::g_mutex_clear
+);
+
+glib_lock_guard!(
+    /// Guard of a locked `GMutex`.
+    MutexGuard,
+    glib_sys::GMutex,
+    glib_sys::g_mutex_lock,
+    glib_sys::g_mutex_trylock,
+    glib_sys::g_mutex_unlock
+);
+
+impl Mutex {
+    /// Locks the mutex, blocking until it is available.
+    pub fn lock(&self) -> MutexGuard {
+        unsafe { MutexGuard::lock_raw(self.as_ptr()) }
+    }
+
+    /// Tries to lock the mutex without blocking.
+    pub fn try_lock(&self) -> Option<MutexGuard> {
+        unsafe { MutexGuard::try_lock_raw(self.as_ptr()) }
+    }
+}
+
+glib_lock!(
+    /// A recursive `GRecMutex`, which can be locked multiple times by the same thread.
+    RecMutex,
+    glib_sys::GRecMutex,
+    glib_sys::g_rec_mutex_init,
+    glib_sys::g_rec_mutex_clear
+);
+
+glib_lock_guard!(
+    /// Guard of a locked `GRecMutex`.
+    RecMutexGuard,
+    glib_sys::GRecMutex,
+    glib_sys::g_rec_mutex_lock,
+    glib_sys::g_rec_mutex_trylock,
+    glib_sys::g_rec_mutex_unlock
+);
+
+impl RecMutex {
+    /// Locks the mutex, blocking until it is available unless it is already held by this thread.
+    pub fn lock(&self) -> RecMutexGuard {
+        unsafe { RecMutexGuard::lock_raw(self.as_ptr()) }
+    }
+
+    /// Tries to lock the mutex without blocking.
+    pub fn try_lock(&self) -> Option<RecMutexGuard> {
+        unsafe { RecMutexGuard::try_lock_raw(self.as_ptr()) }
+    }
+}
+
+glib_lock!(
+    /// A `GRWLock`, which can be held by many readers or a single writer.
+    RwLock,
+    glib_sys::GRWLock,
+    glib_sys::g_rw_lock_init,
+    glib_sys::g_rw_lock_clear
+);
+
+glib_lock_guard!(
+    /// Guard of a `GRWLock` that is locked for reading.
+    RwLockReadGuard,
+    glib_sys::GRWLock,
+    glib_sys::g_rw_lock_reader_lock,
+    glib_sys::g_rw_lock_reader_trylock,
+    glib_sys::g_rw_lock_reader_unlock
+);
+
+glib_lock_guard!(
+    /// Guard of a `GRWLock` that is locked for writing.
+    RwLockWriteGuard,
+    glib_sys::GRWLock,
+    glib_sys::g_rw_lock_writer_lock,
+    glib_sys::g_rw_lock_writer_trylock,
+    glib_sys::g_rw_lock_writer_unlock
+);
+
+impl RwLock {
+    /// Locks the lock for reading, blocking while it is held by a writer.
+    pub fn read(&self) -> RwLockReadGuard {
+        unsafe { RwLockReadGuard::lock_raw(self.as_ptr()) }
+    }
+
+    /// Tries to lock the lock for reading without blocking.
+    pub fn try_read(&self) -> Option<RwLockReadGuard> {
+        unsafe { RwLockReadGuard::try_lock_raw(self.as_ptr()) }
+    }
+
+    /// Locks the lock for writing, blocking while it is held by anybody else.
+    pub fn write(&self) -> RwLockWriteGuard {
+        unsafe { RwLockWriteGuard::lock_raw(self.as_ptr()) }
+    }
+
+    /// Tries to lock the lock for writing without blocking.
+    pub fn try_write(&self) -> Option<RwLockWriteGuard> {
+        unsafe { RwLockWriteGuard::try_lock_raw(self.as_ptr()) }
+    }
+}
+
+glib_lock!(
+    /// A `GCond` condition variable, used together with a `GMutex`.
+    Cond,
+    glib_sys::GCond,
+    glib_sys::g_cond_init,
+    glib_sys::g_cond_clear
+);
+
+impl Cond {
+    /// Atomically releases the mutex of `guard` and waits until the condition is signalled.
+    ///
+    /// The mutex is locked again before returning. Spurious wakeups are possible.
+    pub fn wait(&self, guard: &mut MutexGuard) {
+        unsafe {
+            glib_sys::g_cond_wait(self.as_ptr(), guard.ptr);
+        }
+    }
+
+    /// Same as `wait()` but returns `false` if the monotonic time `end_time` in microseconds
+    /// passed before the condition was signalled.
+    pub fn wait_until(&self, guard: &mut MutexGuard, end_time: i64) -> bool {
+        unsafe { from_glib(glib_sys::g_cond_wait_until(self.as_ptr(), guard.ptr, end_time)) }
+    }
+
+    /// Wakes up one thread waiting on the condition.
+    pub fn signal(&self) {
+        unsafe {
+            glib_sys::g_cond_signal(self.as_ptr());
+        }
+    }
+
+    /// Wakes up all threads waiting on the condition.
+    pub fn broadcast(&self) {
+        unsafe {
+            glib_sys::g_cond_broadcast(self.as_ptr());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::thread;
+
+    #[test]
+    fn test_mutex() {
+        let m = Arc::new(Mutex::new());
+        let guard = m.lock();
+
+        // Locking a GMutex again from the same thread is undefined behaviour
+        let m_clone = m.clone();
+        thread::spawn(move || {
+            assert!(m_clone.try_lock().is_none());
+        })
+        .join()
+        .unwrap();
+
+        drop(guard);
+        assert!(m.try_lock().is_some());
+    }
+
+    #[test]
+    fn test_rec_mutex() {
+        let m = RecMutex::new();
+        let _guard = m.lock();
+        let _guard2 = m.lock();
+        assert!(m.try_lock().is_some());
+    }
+
+    #[test]
+    fn test_rw_lock() {
+        // Taking a GRWLock that is already held by the same thread is undefined behaviour, so
+        // all contended attempts are made from a second thread
+        let l = Arc::new(RwLock::new());
+        {
+            let _read = l.read();
+            let l_clone = l.clone();
+            thread::spawn(move || {
+                let _read2 = l_clone.try_read().unwrap();
+                assert!(l_clone.try_write().is_none());
+            })
+            .join()
+            .unwrap();
+        }
+
+        let _write = l.write();
+        let l_clone = l.clone();
+        thread::spawn(move || {
+            assert!(l_clone.try_read().is_none());
+            assert!(l_clone.try_write().is_none());
+        })
+        .join()
+        .unwrap();
+    }
+
+    #[test]
+    fn test_cond() {
+        let state = Arc::new((Mutex::new(), Cond::new()));
+
+        let mut guard = state.0.lock();
+
+        let state_clone = state.clone();
+        let thread = thread::spawn(move || {
+            let _guard = state_clone.0.lock();
+            state_clone.1.signal();
+        });
+
+        let end_time = ::get_monotonic_time() + 10_000_000;
+        assert!(state.1.wait_until(&mut guard, end_time));
+        drop(guard);
+
+        thread.join().unwrap();
+    }
+}
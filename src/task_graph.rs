@@ -0,0 +1,444 @@
+// Copyright 2019, The Gtk-rs Project Developers.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+// Licensed under the MIT license, see the LICENSE file or <http://opensource.org/licenses/MIT>
+
+//! Scheduling of dependent tasks on a main context.
+
+#[cfg(feature = "futures")]
+use futures::prelude::*;
+use std::any::Any;
+use std::cell::RefCell;
+use std::fmt;
+use std::panic;
+use std::rc::Rc;
+use std::thread;
+use Continue;
+use Error;
+use FileError;
+use MainContext;
+use Priority;
+
+/// Identifier of a task inside a [`TaskGraph`].
+///
+/// [`TaskGraph`]: struct.TaskGraph.html
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct TaskId(usize);
+
+/// Events reported by a running [`TaskGraph`].
+///
+/// [`TaskGraph`]: struct.TaskGraph.html
+#[derive(Debug)]
+pub enum TaskEvent<'a> {
+    /// The task finished successfully.
+    Completed(TaskId, &'a str),
+    /// The task failed with the given error.
+    Failed(TaskId, &'a str, Error),
+    /// The task was not run because one of its dependencies failed or was skipped.
+    Skipped(TaskId, &'a str),
+    /// All tasks of the graph finished, failed or were skipped.
+    Finished,
+}
+
+type Completion = Box<FnMut(Result<(), Error>)>;
+type Starter = Box<FnMut(&MainContext, Priority, Completion)>;
+
+struct Task {
+    name: String,
+    priority: Priority,
+    dependents: Vec<usize>,
+    remaining_dependencies: usize,
+    starter: Option<Starter>,
+}
+
+/// A set of tasks with dependencies between them that is run on a main context.
+///
+/// Tasks are started in dependency order once all their dependencies completed successfully,
+/// with the given priority. Tasks can either run directly on the main context, on a separate
+/// thread or as a future. If a task fails, all tasks depending on it are skipped.
+///
+/// Dependencies can only be added to tasks that were added to the graph before, which ensures
+/// that there are no cycles.
+///
+/// ```ignore
+/// let mut graph = glib::TaskGraph::new();
+/// let config = graph.add_task("load-config", glib::Priority::HIGH, &[], || load_config());
+/// let caches = graph.add_thread_task("init-caches", glib::Priority::DEFAULT, &[config], || init_caches());
+/// graph.add_task("build-ui-data", glib::Priority::DEFAULT, &[config, caches], || build_ui_data());
+///
+/// graph.run(None, move |event| match event {
+///     glib::TaskEvent::Failed(_, name, err) => eprintln!("Task {} failed: {}", name, err),
+///     glib::TaskEvent::Finished => main_loop.quit(),
+///     _ => (),
+/// });
+/// ```
+pub struct TaskGraph {
+    tasks: Vec<Task>,
+}
+
+impl fmt::Debug for TaskGraph {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list()
+            .entries(self.tasks.iter().map(|task| &task.name))
+            .finish()
+    }
+}
+
+impl Default for TaskGraph {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TaskGraph {
+    /// Creates a new, empty task graph.
+    pub fn new() -> TaskGraph {
+        TaskGraph { tasks: Vec::new() }
+    }
+
+    fn add(&mut self, name: &str, priority: Priority, dependencies: &[TaskId], starter: Starter) -> TaskId {
+        let id = self.tasks.len();
+
+        let mut remaining_dependencies = 0;
+        for dependency in dependencies {
+            let task = self
+                .tasks
+                .get_mut(dependency.0)
+                .expect("Dependency on a task of a different graph");
+            if !task.dependents.contains(&id) {
+                task.dependents.push(id);
+                remaining_dependencies += 1;
+            }
+        }
+
+        self.tasks.push(Task {
+            name: String::from(name),
+            priority,
+            dependents: Vec::new(),
+            remaining_dependencies,
+            starter: Some(starter),
+        });
+
+        TaskId(id)
+    }
+
+    /// Adds a task that is run on the main context once all `dependencies` completed.
+    pub fn add_task<F>(&mut self, name: &str, priority: Priority, dependencies: &[TaskId], func: F) -> TaskId
+    where
+        F: FnOnce() -> Result<(), Error> + 'static,
+    {
+        let mut func = Some(func);
+        self.add(
+            name,
+            priority,
+            dependencies,
+            Box::new(move |context, priority, mut completion| {
+                let func = func.take().expect("Task started multiple times");
+                // Dispatch from the main context with the task's priority
+                let (sender, receiver) = MainContext::channel(priority);
+                let mut func = Some(func);
+                receiver.attach(Some(context), move |()| {
+                    let func = func.take().expect("Task started multiple times");
+                    completion(func());
+                    Continue(false)
+                });
+                sender.send(()).expect("Task receiver destroyed");
+            }),
+        )
+    }
+
+    /// Adds a task that is run on a new thread once all `dependencies` completed.
+    ///
+    /// Completion of the task is reported back to the main context with the task's priority.
+    /// If `func` panics or the thread can't be spawned, the task is reported as failed.
+    pub fn add_thread_task<F>(&mut self, name: &str, priority: Priority, dependencies: &[TaskId], func: F) -> TaskId
+    where
+        F: FnOnce() -> Result<(), Error> + Send + 'static,
+    {
+        let mut func = Some(func);
+        let thread_name = String::from(name);
+        self.add(
+            name,
+            priority,
+            dependencies,
+            Box::new(move |context, priority, mut completion| {
+                let func = func.take().expect("Task started multiple times");
+                let (sender, receiver) = MainContext::channel(priority);
+                receiver.attach(Some(context), move |res| {
+                    completion(res);
+                    Continue(false)
+                });
+
+                // Report failure to spawn the thread through the channel too, so that the task
+                // is reported as failed from the main context like any other failure
+                let spawn_sender = sender.clone();
+                let spawned = thread::Builder::new()
+                    .name(thread_name.clone())
+                    .spawn(move || {
+                        let res = panic::catch_unwind(panic::AssertUnwindSafe(func))
+                            .unwrap_or_else(|err| Err(panic_error(&*err)));
+                        let _ = sender.send(res);
+                    });
+                if let Err(err) = spawned {
+                    let _ = spawn_sender.send(Err(Error::new(
+                        FileError::Failed,
+                        &format!("Failed to spawn thread for task {}: {}", thread_name, err),
+                    )));
+                }
+            }),
+        )
+    }
+
+    #[cfg(feature = "futures")]
+    /// Adds a task that is spawned as future on the main context once all `dependencies`
+    /// completed.
+    pub fn add_future_task<F>(&mut self, name: &str, priority: Priority, dependencies: &[TaskId], future: F) -> TaskId
+    where
+        F: Future<Output = Result<(), Error>> + 'static,
+    {
+        let mut future = Some(future);
+        self.add(
+            name,
+            priority,
+            dependencies,
+            Box::new(move |context, priority, mut completion| {
+                let future = future.take().expect("Task started multiple times");
+                context.spawn_local_with_priority(priority, future.map(move |res| completion(res)));
+            }),
+        )
+    }
+
+    /// Runs all tasks of the graph on `context` and reports their progress to `func`.
+    ///
+    /// Passing `None` for the context will run the tasks on the thread default main context.
+    /// The tasks only make progress while the main context is iterated, e.g. by a `MainLoop`.
+    ///
+    /// `func` is called from the main context for every task that finished, failed or was
+    /// skipped, and with `TaskEvent::Finished` once all tasks are done.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if called from a thread that is not the owner of the provided
+    /// `context`, or, if `None` is provided, of the thread default main context.
+    pub fn run<F>(self, context: Option<&MainContext>, func: F)
+    where
+        F: FnMut(TaskEvent) + 'static,
+    {
+        let context = match context {
+            Some(context) => context.clone(),
+            None => MainContext::ref_thread_default(),
+        };
+        assert!(context.is_owner());
+
+        let remaining = self.tasks.len();
+        let state = Rc::new(RefCell::new(State {
+            context,
+            tasks: self.tasks,
+            remaining,
+        }));
+        let func: Rc<RefCell<FnMut(TaskEvent)>> = Rc::new(RefCell::new(func));
+
+        if remaining == 0 {
+            // Report from the main context as for non-empty graphs
+            let (sender, receiver) = MainContext::channel(::PRIORITY_DEFAULT);
+            receiver.attach(Some(&state.borrow().context), move |()| {
+                (&mut *func.borrow_mut())(TaskEvent::Finished);
+                Continue(false)
+            });
+            sender.send(()).expect("Receiver destroyed");
+            return;
+        }
+
+        let ready = state
+            .borrow()
+            .tasks
+            .iter()
+            .enumerate()
+            .filter(|&(_, task)| task.remaining_dependencies == 0)
+            .map(|(id, _)| id)
+            .collect::<Vec<_>>();
+        for id in ready {
+            start(&state, &func, id);
+        }
+    }
+}
+
+fn panic_error(err: &(Any + Send)) -> Error {
+    let msg = if let Some(msg) = err.downcast_ref::<&str>() {
+        *msg
+    } else if let Some(msg) = err.downcast_ref::<String>() {
+        msg.as_str()
+    } else {
+        "unknown panic"
+    };
+    Error::new(FileError::Failed, &format!("Task panicked: {}", msg))
+}
+
+struct State {
+    context: MainContext,
+    tasks: Vec<Task>,
+    remaining: usize,
+}
+
+fn start(state: &Rc<RefCell<State>>, func: &Rc<RefCell<FnMut(TaskEvent)>>, id: usize) {
+    let (context, priority, mut starter) = {
+        let mut state = state.borrow_mut();
+        let context = state.context.clone();
+        let task = &mut state.tasks[id];
+        let starter = task.starter.take().expect("Task started multiple times");
+        (context, task.priority, starter)
+    };
+
+    let state = state.clone();
+    let func = func.clone();
+    starter(
+        &context,
+        priority,
+        Box::new(move |res| finish(&state, &func, id, res)),
+    );
+}
+
+fn finish(
+    state: &Rc<RefCell<State>>,
+    func: &Rc<RefCell<FnMut(TaskEvent)>>,
+    id: usize,
+    res: Result<(), Error>,
+) {
+    let mut ready = Vec::new();
+    let mut skipped = Vec::new();
+    let (name, finished) = {
+        let mut state = state.borrow_mut();
+        state.remaining -= 1;
+
+        if res.is_ok() {
+            for dependent in state.tasks[id].dependents.clone() {
+                let task = &mut state.tasks[dependent];
+                task.remaining_dependencies -= 1;
+                if task.remaining_dependencies == 0 && task.starter.is_some() {
+                    ready.push(dependent);
+                }
+            }
+        } else {
+            // Skip all tasks that transitively depend on this one
+            let mut queue = state.tasks[id].dependents.clone();
+            while let Some(dependent) = queue.pop() {
+                if state.tasks[dependent].starter.take().is_some() {
+                    state.remaining -= 1;
+                    skipped.push((dependent, state.tasks[dependent].name.clone()));
+                    queue.extend_from_slice(&state.tasks[dependent].dependents);
+                }
+            }
+            skipped.sort_by_key(|&(dependent, _)| dependent);
+        }
+
+        (state.tasks[id].name.clone(), state.remaining == 0)
+    };
+
+    {
+        let func = &mut *func.borrow_mut();
+        match res {
+            Ok(()) => func(TaskEvent::Completed(TaskId(id), &name)),
+            Err(err) => func(TaskEvent::Failed(TaskId(id), &name, err)),
+        }
+        for &(dependent, ref name) in &skipped {
+            func(TaskEvent::Skipped(TaskId(dependent), name));
+        }
+        if finished {
+            func(TaskEvent::Finished);
+        }
+    }
+
+    for dependent in ready {
+        start(state, func, dependent);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use MainLoop;
+
+    fn run_graph(graph: TaskGraph) -> Vec<String> {
+        let c = MainContext::new();
+        let l = MainLoop::new(Some(&c), false);
+
+        c.acquire();
+
+        let events = Rc::new(RefCell::new(Vec::new()));
+        let events_clone = events.clone();
+        let l_clone = l.clone();
+        graph.run(Some(&c), move |event| {
+            let event = match event {
+                TaskEvent::Completed(_, name) => format!("completed {}", name),
+                TaskEvent::Failed(_, name, _) => format!("failed {}", name),
+                TaskEvent::Skipped(_, name) => format!("skipped {}", name),
+                TaskEvent::Finished => {
+                    l_clone.quit();
+                    String::from("finished")
+                }
+            };
+            events_clone.borrow_mut().push(event);
+        });
+
+        l.run();
+
+        let events = events.borrow().clone();
+        events
+    }
+
+    #[test]
+    fn test_dependency_order() {
+        let order = Rc::new(RefCell::new(Vec::new()));
+
+        let mut graph = TaskGraph::new();
+        let order_clone = order.clone();
+        let a = graph.add_task("a", Priority::DEFAULT, &[], move || {
+            order_clone.borrow_mut().push("a");
+            Ok(())
+        });
+        let b = graph.add_thread_task("b", Priority::DEFAULT, &[a], || Ok(()));
+        let order_clone = order.clone();
+        graph.add_task("c", Priority::DEFAULT, &[a, b], move || {
+            order_clone.borrow_mut().push("c");
+            Ok(())
+        });
+
+        let events = run_graph(graph);
+        assert_eq!(
+            events,
+            vec!["completed a", "completed b", "completed c", "finished"]
+        );
+        assert_eq!(*order.borrow(), vec!["a", "c"]);
+    }
+
+    #[test]
+    fn test_failure_skips_dependents() {
+        let mut graph = TaskGraph::new();
+        let a = graph.add_task("a", Priority::DEFAULT, &[], || {
+            Err(Error::new(FileError::Failed, "failed"))
+        });
+        let b = graph.add_task("b", Priority::DEFAULT, &[a], || Ok(()));
+        graph.add_task("c", Priority::DEFAULT, &[b], || Ok(()));
+        graph.add_task("d", Priority::LOW, &[], || Ok(()));
+
+        let events = run_graph(graph);
+        assert_eq!(
+            events,
+            vec!["failed a", "skipped b", "skipped c", "completed d", "finished"]
+        );
+    }
+
+    #[test]
+    fn test_thread_task_panic() {
+        let mut graph = TaskGraph::new();
+        let a = graph.add_thread_task("a", Priority::DEFAULT, &[], || panic!("task panicked"));
+        graph.add_task("b", Priority::DEFAULT, &[a], || Ok(()));
+
+        let events = run_graph(graph);
+        assert_eq!(events, vec!["failed a", "skipped b", "finished"]);
+    }
+
+    #[test]
+    fn test_empty() {
+        assert_eq!(run_graph(TaskGraph::new()), vec!["finished"]);
+    }
+}
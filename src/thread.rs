@@ -0,0 +1,78 @@
+// Copyright 2019, The Gtk-rs Project Developers.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+// Licensed under the MIT license, see the LICENSE file or <http://opensource.org/licenses/MIT>
+
+//! Helpers for spawning named threads, optionally with their own main context.
+
+use std::io;
+use std::thread;
+use MainContext;
+
+/// Spawns a new thread with the given `name`.
+///
+/// The name is set as the operating system's thread name where supported, so that it shows up
+/// in debuggers and profilers, and is also available via `std::thread::current().name()`.
+pub fn spawn_named<F, T>(name: &str, f: F) -> io::Result<thread::JoinHandle<T>>
+where
+    F: FnOnce() -> T + Send + 'static,
+    T: Send + 'static,
+{
+    thread::Builder::new().name(String::from(name)).spawn(f)
+}
+
+/// Spawns a new thread with the given `name` and its own main context.
+///
+/// A new `MainContext` is created, acquired and pushed as thread default main context for the
+/// whole lifetime of `f`, which gets the context passed. This allows to directly run a
+/// `MainLoop` or attach sources and channel receivers from the new thread.
+///
+/// See [`spawn_named()`] for details about the thread name.
+///
+/// [`spawn_named()`]: fn.spawn_named.html
+pub fn spawn_named_with_context<F, T>(name: &str, f: F) -> io::Result<thread::JoinHandle<T>>
+where
+    F: FnOnce(&MainContext) -> T + Send + 'static,
+    T: Send + 'static,
+{
+    spawn_named(name, move || {
+        let context = MainContext::new();
+        context.acquire();
+        let res = context.with_thread_default(|| f(&context));
+        context.release();
+        res
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use MainLoop;
+
+    #[test]
+    fn test_spawn_named() {
+        let handle = spawn_named("glib-test-thread", || {
+            thread::current().name().map(String::from)
+        })
+        .unwrap();
+
+        assert_eq!(handle.join().unwrap(), Some(String::from("glib-test-thread")));
+    }
+
+    #[test]
+    fn test_spawn_named_with_context() {
+        let handle = spawn_named_with_context("glib-test-context", |context| {
+            assert!(context.is_owner());
+            assert_eq!(&MainContext::ref_thread_default(), context);
+
+            let l = MainLoop::new(Some(context), false);
+            let l_clone = l.clone();
+            context.invoke(move || l_clone.quit());
+            l.run();
+
+            thread::current().name().map(String::from)
+        })
+        .unwrap();
+
+        assert_eq!(handle.join().unwrap(), Some(String::from("glib-test-context")));
+    }
+}
@@ -0,0 +1,364 @@
+// Copyright 2019, The Gtk-rs Project Developers.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+// Licensed under the MIT license, see the LICENSE file or <http://opensource.org/licenses/MIT>
+
+use get_thread_id;
+use std::fmt;
+use std::mem;
+use Continue;
+use MainContext;
+use MainLoop;
+use Source;
+
+/// Marker trait for types that may be used from any thread.
+///
+/// This is implemented for the `GLib` types that are documented as thread-safe and allows
+/// generic code to require thread-safety explicitly instead of relying on `Send` and `Sync`,
+/// which are also implemented by wrappers like [`ThreadGuard`].
+///
+/// [`ThreadGuard`]: struct.ThreadGuard.html
+pub unsafe trait ThreadSafe: Send + Sync {}
+
+unsafe impl ThreadSafe for MainContext {}
+unsafe impl ThreadSafe for MainLoop {}
+unsafe impl ThreadSafe for Source {}
+
+/// Marker trait for types that must only be used from the main thread, i.e. the thread owning
+/// the default main context.
+///
+/// Bindings for libraries like GTK can implement this for their types and require it for APIs
+/// that check [`is_main_thread()`], like the `_local` variants of the source functions, e.g.
+/// [`idle_add_local()`]. Values of such types can be moved to other threads inside a
+/// [`ThreadGuard`] or [`FragileSend`].
+///
+/// [`is_main_thread()`]: fn.is_main_thread.html
+/// [`idle_add_local()`]: fn.idle_add_local.html
+/// [`ThreadGuard`]: struct.ThreadGuard.html
+/// [`FragileSend`]: struct.FragileSend.html
+pub unsafe trait MainThreadOnly {}
+
+/// Returns `true` if the current thread is the owner of the default main context.
+pub fn is_main_thread() -> bool {
+    MainContext::default().is_owner()
+}
+
+/// Wrapper that allows sharing a value between threads while only allowing access to it from
+/// the thread it was created on.
+///
+/// All accessors panic if called from any other thread. If the guard is dropped on another
+/// thread, a critical warning is logged and the value is leaked instead of being dropped on the
+/// wrong thread. Panicking there would abort the process if the guard is dropped while
+/// unwinding.
+pub struct ThreadGuard<T> {
+    value: Option<T>,
+    thread_id: usize,
+}
+
+unsafe impl<T> Send for ThreadGuard<T> {}
+unsafe impl<T> Sync for ThreadGuard<T> {}
+
+impl<T> ThreadGuard<T> {
+    /// Creates a new guard that only allows access to `value` from the current thread.
+    pub fn new(value: T) -> ThreadGuard<T> {
+        ThreadGuard {
+            value: Some(value),
+            thread_id: get_thread_id(),
+        }
+    }
+
+    /// Returns `true` if the current thread is the thread the guard was created on.
+    pub fn is_owner(&self) -> bool {
+        self.thread_id == get_thread_id()
+    }
+
+    /// Returns a reference to the value.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if called from a thread other than the one the guard was created on.
+    pub fn get_ref(&self) -> &T {
+        assert!(self.is_owner(), "Value accessed from a different thread than where it was created");
+        self.value.as_ref().unwrap()
+    }
+
+    /// Returns a mutable reference to the value.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if called from a thread other than the one the guard was created on.
+    pub fn get_mut(&mut self) -> &mut T {
+        assert!(self.is_owner(), "Value accessed from a different thread than where it was created");
+        self.value.as_mut().unwrap()
+    }
+
+    /// Returns the value.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if called from a thread other than the one the guard was created on.
+    pub fn into_inner(mut self) -> T {
+        assert!(self.is_owner(), "Value accessed from a different thread than where it was created");
+        self.value.take().unwrap()
+    }
+}
+
+impl<T> Drop for ThreadGuard<T> {
+    fn drop(&mut self) {
+        if self.value.is_some() && !self.is_owner() {
+            g_critical!("glib-rs", "Value dropped on a different thread than where it was created");
+            mem::forget(self.value.take());
+        }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for ThreadGuard<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut d = f.debug_struct("ThreadGuard");
+        if self.is_owner() {
+            d.field("value", self.get_ref());
+        }
+        d.field("thread_id", &self.thread_id).finish()
+    }
+}
+
+// Value that is only ever dropped on the thread it was created on, and leaked otherwise
+struct DropOnOwner<T> {
+    value: Option<T>,
+    thread_id: usize,
+}
+
+unsafe impl<T> Send for DropOnOwner<T> {}
+
+impl<T> Drop for DropOnOwner<T> {
+    fn drop(&mut self) {
+        if self.thread_id != get_thread_id() {
+            mem::forget(self.value.take());
+        }
+    }
+}
+
+// Non-`Send` callback that is passed to C code and could be called or dropped from any thread.
+//
+// Different to `ThreadGuard` this never panics, as that would unwind across the FFI boundary:
+// calls from other threads are skipped with a critical warning and if it is dropped on another
+// thread the callback is leaked.
+pub(crate) struct LocalCallback<F>(DropOnOwner<F>);
+
+unsafe impl<F> Send for LocalCallback<F> {}
+unsafe impl<F> Sync for LocalCallback<F> {}
+
+impl<F> LocalCallback<F> {
+    pub(crate) fn new(callback: F) -> LocalCallback<F> {
+        LocalCallback(DropOnOwner {
+            value: Some(callback),
+            thread_id: get_thread_id(),
+        })
+    }
+
+    // Returns `None` after logging a critical warning if called from another thread
+    pub(crate) fn get(&self) -> Option<&F> {
+        if self.0.thread_id == get_thread_id() {
+            self.0.value.as_ref()
+        } else {
+            g_critical!("glib-rs", "Local callback called from a different thread than where it was created");
+            None
+        }
+    }
+}
+
+/// Wrapper that allows moving a value to other threads while only allowing access to it from
+/// the thread it was created on.
+///
+/// Different to [`ThreadGuard`], dropping a `FragileSend` on another thread does not panic.
+/// Instead the value is sent back to the thread default main context of the thread it was
+/// created on and dropped there the next time that context is iterated. If that never happens,
+/// e.g. because the context is not iterated anymore, the value is leaked.
+///
+/// [`ThreadGuard`]: struct.ThreadGuard.html
+pub struct FragileSend<T: 'static> {
+    value: Option<T>,
+    thread_id: usize,
+    context: MainContext,
+}
+
+unsafe impl<T: 'static> Send for FragileSend<T> {}
+
+impl<T: 'static> FragileSend<T> {
+    /// Creates a new `FragileSend` that only allows access to `value` from the current thread.
+    pub fn new(value: T) -> FragileSend<T> {
+        FragileSend {
+            value: Some(value),
+            thread_id: get_thread_id(),
+            context: MainContext::ref_thread_default(),
+        }
+    }
+
+    /// Returns `true` if the current thread is the thread the value was created on.
+    pub fn is_owner(&self) -> bool {
+        self.thread_id == get_thread_id()
+    }
+
+    /// Returns a reference to the value, or `None` if called from a thread other than the one
+    /// the value was created on.
+    pub fn try_get(&self) -> Option<&T> {
+        if self.is_owner() {
+            self.value.as_ref()
+        } else {
+            None
+        }
+    }
+
+    /// Returns a mutable reference to the value, or `None` if called from a thread other than
+    /// the one the value was created on.
+    pub fn try_get_mut(&mut self) -> Option<&mut T> {
+        if self.is_owner() {
+            self.value.as_mut()
+        } else {
+            None
+        }
+    }
+
+    /// Returns a reference to the value.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if called from a thread other than the one the value was created on.
+    pub fn get(&self) -> &T {
+        self.try_get()
+            .expect("Value accessed from a different thread than where it was created")
+    }
+
+    /// Returns a mutable reference to the value.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if called from a thread other than the one the value was created on.
+    pub fn get_mut(&mut self) -> &mut T {
+        self.try_get_mut()
+            .expect("Value accessed from a different thread than where it was created")
+    }
+
+    /// Returns the value, or `Err(self)` if called from a thread other than the one the value was
+    /// created on.
+    pub fn try_into_inner(mut self) -> Result<T, Self> {
+        if self.is_owner() {
+            Ok(self.value.take().unwrap())
+        } else {
+            Err(self)
+        }
+    }
+}
+
+impl<T: 'static> Drop for FragileSend<T> {
+    fn drop(&mut self) {
+        let value = match self.value.take() {
+            Some(value) => value,
+            None => return,
+        };
+
+        if self.is_owner() {
+            return;
+        }
+
+        // Not using MainContext::invoke() here as that would run the closure right away on
+        // this thread if the context is not owned by any thread at this time
+        let mut value = Some(DropOnOwner {
+            value: Some(value),
+            thread_id: self.thread_id,
+        });
+        let source = ::idle_source_new(Some("glib::FragileSend"), ::PRIORITY_DEFAULT_IDLE, move || {
+            let _ = value.take();
+            Continue(false)
+        });
+        source.attach(Some(&self.context));
+    }
+}
+
+impl<T: fmt::Debug + 'static> fmt::Debug for FragileSend<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut d = f.debug_struct("FragileSend");
+        if let Some(value) = self.try_get() {
+            d.field("value", value);
+        }
+        d.field("thread_id", &self.thread_id).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use std::sync::{Arc, Mutex};
+    use std::thread;
+
+    #[test]
+    fn test_thread_guard() {
+        let guard = ThreadGuard::new(Rc::new(RefCell::new(1)));
+        *guard.get_ref().borrow_mut() += 1;
+        assert_eq!(*guard.get_ref().borrow(), 2);
+
+        let guard = Arc::new(guard);
+        let guard_clone = guard.clone();
+        let res = thread::spawn(move || {
+            assert!(!guard_clone.is_owner());
+            let _ = guard_clone.get_ref();
+        })
+        .join();
+        assert!(res.is_err());
+
+        let value = Arc::try_unwrap(guard).ok().unwrap().into_inner();
+        assert_eq!(*value.borrow(), 2);
+    }
+
+    #[test]
+    fn test_thread_safe() {
+        fn assert_thread_safe<T: ThreadSafe>() {}
+
+        assert_thread_safe::<MainContext>();
+        assert_thread_safe::<MainLoop>();
+        assert_thread_safe::<Source>();
+        assert_thread_safe::<::Sender<i32>>();
+        assert_thread_safe::<::SyncSender<i32>>();
+    }
+
+    #[test]
+    fn test_thread_guard_drop_other_thread() {
+        let dropped = Arc::new(Mutex::new(None));
+        let guard = ThreadGuard::new(DropCheck(dropped.clone()));
+
+        thread::spawn(move || drop(guard)).join().unwrap();
+        // Leaked instead of dropped on the other thread
+        assert_eq!(*dropped.lock().unwrap(), None);
+    }
+
+    struct DropCheck(Arc<Mutex<Option<usize>>>);
+
+    impl Drop for DropCheck {
+        fn drop(&mut self) {
+            *self.0.lock().unwrap() = Some(get_thread_id());
+        }
+    }
+
+    #[test]
+    fn test_fragile_send() {
+        let c = MainContext::new();
+        c.acquire();
+
+        let dropped = Arc::new(Mutex::new(None));
+        let fragile = c.with_thread_default(|| FragileSend::new(DropCheck(dropped.clone())));
+        assert!(fragile.try_get().is_some());
+
+        thread::spawn(move || {
+            assert!(fragile.try_get().is_none());
+            drop(fragile);
+        })
+        .join()
+        .unwrap();
+
+        // Not dropped on the other thread but only here once the context is iterated
+        assert_eq!(*dropped.lock().unwrap(), None);
+        while c.iteration(false) {}
+        assert_eq!(*dropped.lock().unwrap(), Some(get_thread_id()));
+    }
+}
@@ -0,0 +1,215 @@
+// Copyright 2019, The Gtk-rs Project Developers.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+// Licensed under the MIT license, see the LICENSE file or <http://opensource.org/licenses/MIT>
+
+//! Time based interpolation of values driven by a main context.
+
+use source::duration_to_millis;
+use std::cmp;
+use std::f64::consts::PI;
+use std::time::{Duration, Instant};
+use Continue;
+use MainContext;
+use Priority;
+use Source;
+
+/// Easing function of a [`Tween`].
+///
+/// [`Tween`]: struct.Tween.html
+#[derive(Clone, Copy, Debug)]
+pub enum Easing {
+    Linear,
+    EaseIn,
+    EaseOut,
+    EaseInOut,
+    Sine,
+    /// Custom easing function, mapping the progress in `[0, 1]` to the interpolation factor.
+    Custom(fn(f64) -> f64),
+}
+
+impl Easing {
+    /// Applies the easing function to `progress`, which must be in `[0, 1]`.
+    pub fn apply(self, progress: f64) -> f64 {
+        match self {
+            Easing::Linear => progress,
+            Easing::EaseIn => progress * progress * progress,
+            Easing::EaseOut => {
+                let p = 1.0 - progress;
+                1.0 - p * p * p
+            }
+            Easing::EaseInOut => {
+                if progress < 0.5 {
+                    4.0 * progress * progress * progress
+                } else {
+                    let p = -2.0 * progress + 2.0;
+                    1.0 - p * p * p / 2.0
+                }
+            }
+            Easing::Sine => -((PI * progress).cos() - 1.0) / 2.0,
+            Easing::Custom(func) => func(progress),
+        }
+    }
+}
+
+impl Default for Easing {
+    fn default() -> Self {
+        Easing::Linear
+    }
+}
+
+/// Drives a value from `from` to `to` over a given duration.
+///
+/// The interpolated value is reported once per frame from a timeout source on the main
+/// context, and the last reported value is always exactly `to`.
+///
+/// ```ignore
+/// let source = glib::Tween::new(0.0, 100.0, Duration::from_millis(300))
+///     .easing(glib::Easing::EaseOut)
+///     .start(None, |value| render(value), || println!("done"));
+/// ```
+#[derive(Clone, Debug)]
+pub struct Tween {
+    from: f64,
+    to: f64,
+    duration: Duration,
+    easing: Easing,
+    frame_interval: Duration,
+    priority: Priority,
+}
+
+impl Tween {
+    /// Creates a new linear tween from `from` to `to` with ~60 frames per second.
+    pub fn new(from: f64, to: f64, duration: Duration) -> Tween {
+        Tween {
+            from,
+            to,
+            duration,
+            easing: Easing::Linear,
+            frame_interval: Duration::from_millis(16),
+            priority: ::PRIORITY_DEFAULT,
+        }
+    }
+
+    /// Sets the easing function.
+    pub fn easing(mut self, easing: Easing) -> Tween {
+        self.easing = easing;
+        self
+    }
+
+    /// Sets the interval between two frames.
+    pub fn frame_interval(mut self, frame_interval: Duration) -> Tween {
+        self.frame_interval = frame_interval;
+        self
+    }
+
+    /// Sets the priority of the frame timeout source.
+    pub fn priority(mut self, priority: Priority) -> Tween {
+        self.priority = priority;
+        self
+    }
+
+    /// Returns the value at `elapsed` time since the start of the tween.
+    pub fn value_at(&self, elapsed: Duration) -> f64 {
+        if elapsed >= self.duration || self.duration == Duration::from_secs(0) {
+            return self.to;
+        }
+
+        let as_secs = |d: Duration| d.as_secs() as f64 + f64::from(d.subsec_nanos()) / 1e9;
+        let progress = as_secs(elapsed) / as_secs(self.duration);
+
+        self.from + (self.to - self.from) * self.easing.apply(progress)
+    }
+
+    /// Starts the tween on `context`.
+    ///
+    /// `func` is called with the interpolated value for every frame and `done` after the final
+    /// value was reported. The returned source can be destroyed to stop the tween early, in which
+    /// case `done` is not called.
+    ///
+    /// Passing `None` for the context will attach it to the thread default main context.
+    pub fn start<F, G>(self, context: Option<&MainContext>, mut func: F, done: G) -> Source
+    where
+        F: FnMut(f64) + Send + 'static,
+        G: FnOnce() + Send + 'static,
+    {
+        let interval = cmp::max(duration_to_millis(self.frame_interval), 1);
+
+        let mut start = None;
+        let mut done = Some(done);
+        let priority = self.priority;
+        let source = ::timeout_source_new(interval, Some("glib::Tween"), priority, move || {
+            let start = *start.get_or_insert_with(Instant::now);
+            let elapsed = start.elapsed();
+
+            func(self.value_at(elapsed));
+
+            if elapsed >= self.duration {
+                if let Some(done) = done.take() {
+                    done();
+                }
+                Continue(false)
+            } else {
+                Continue(true)
+            }
+        });
+
+        match context {
+            Some(context) => source.attach(Some(context)),
+            None => source.attach(Some(&MainContext::ref_thread_default())),
+        };
+
+        source
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+    use MainLoop;
+
+    #[test]
+    fn test_easing() {
+        for &easing in &[Easing::Linear, Easing::EaseIn, Easing::EaseOut, Easing::EaseInOut, Easing::Sine] {
+            assert!(easing.apply(0.0).abs() < 1e-9);
+            assert!((easing.apply(1.0) - 1.0).abs() < 1e-9);
+        }
+        assert!((Easing::EaseInOut.apply(0.5) - 0.5).abs() < 1e-9);
+        assert!((Easing::Custom(|p| p * p).apply(0.5) - 0.25).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_value_at() {
+        let tween = Tween::new(10.0, 20.0, Duration::from_millis(100));
+        assert!((tween.value_at(Duration::from_millis(0)) - 10.0).abs() < 1e-9);
+        assert!((tween.value_at(Duration::from_millis(50)) - 15.0).abs() < 1e-9);
+        assert!((tween.value_at(Duration::from_millis(200)) - 20.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_tween() {
+        let c = MainContext::new();
+        let l = MainLoop::new(Some(&c), false);
+
+        c.acquire();
+
+        let values = Arc::new(Mutex::new(Vec::new()));
+        let values_clone = values.clone();
+        let l_clone = l.clone();
+        Tween::new(0.0, 1.0, Duration::from_millis(50))
+            .frame_interval(Duration::from_millis(5))
+            .easing(Easing::EaseInOut)
+            .start(
+                Some(&c),
+                move |value| values_clone.lock().unwrap().push(value),
+                move || l_clone.quit(),
+            );
+
+        l.run();
+
+        let values = values.lock().unwrap();
+        assert!(values.len() > 1);
+        assert!((values.last().unwrap() - 1.0).abs() < 1e-9);
+        assert!(values.windows(2).all(|w| w[0] <= w[1]));
+    }
+}
@@ -0,0 +1,38 @@
+// Copyright 2019, The Gtk-rs Project Developers.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+// Licensed under the MIT license, see the LICENSE file or <http://opensource.org/licenses/MIT>
+
+use gobject_sys;
+use translate::*;
+
+glib_wrapper! {
+    /// Base class for loadable modules that register types dynamically.
+    ///
+    /// Type modules are never finalized: once a type was registered with a module, the module
+    /// has to stay alive for the rest of the process so that it can be loaded again whenever
+    /// the type is used after being unloaded.
+    pub struct TypeModule(Object<gobject_sys::GTypeModule, gobject_sys::GTypeModuleClass, TypeModuleClass>) @derivable;
+
+    match fn {
+        get_type => || gobject_sys::g_type_module_get_type(),
+    }
+}
+
+impl TypeModule {
+    /// Increments the use count of the module and loads it if it was not loaded yet.
+    ///
+    /// Returns `false` if loading the module failed.
+    pub fn use_(&self) -> bool {
+        unsafe { from_glib(gobject_sys::g_type_module_use(self.to_glib_none().0)) }
+    }
+
+    /// Decrements the use count of the module and unloads it once it reaches zero.
+    pub fn unuse(&self) {
+        unsafe { gobject_sys::g_type_module_unuse(self.to_glib_none().0) }
+    }
+
+    /// Sets the name of the module, which is used in error messages.
+    pub fn set_name(&self, name: &str) {
+        unsafe { gobject_sys::g_type_module_set_name(self.to_glib_none().0, name.to_glib_none().0) }
+    }
+}
@@ -86,6 +86,56 @@ impl Type {
         }
     }
 
+    /// Returns the fundamental type this type is derived from, e.g. `Type::BaseObject` for all
+    /// object types.
+    pub fn fundamental(&self) -> Self {
+        unsafe {
+            from_glib(gobject_sys::g_type_fundamental(self.to_glib()))
+        }
+    }
+
+    /// Returns the length of the ancestry of the type, i.e. `1` for fundamental types.
+    pub fn depth(&self) -> u32 {
+        unsafe {
+            gobject_sys::g_type_depth(self.to_glib())
+        }
+    }
+
+    /// Returns the ancestors of the type, starting with the type itself and ending with its
+    /// fundamental type.
+    pub fn ancestors(&self) -> Vec<Self> {
+        let mut ancestors = vec![*self];
+        let mut type_ = *self;
+        while let Some(parent) = type_.parent() {
+            ancestors.push(parent);
+            type_ = parent;
+        }
+        ancestors
+    }
+
+    pub fn is_abstract(&self) -> bool {
+        unsafe {
+            from_glib(gobject_sys::g_type_test_flags(self.to_glib(), gobject_sys::G_TYPE_FLAG_ABSTRACT))
+        }
+    }
+
+    pub fn is_instantiatable(&self) -> bool {
+        unsafe {
+            from_glib(gobject_sys::g_type_test_flags(self.to_glib(), gobject_sys::G_TYPE_FLAG_INSTANTIATABLE))
+        }
+    }
+
+    pub fn is_interface(&self) -> bool {
+        self.fundamental() == Type::BaseInterface
+    }
+
+    /// Returns `true` if `Value`s can be created for this type.
+    pub fn is_value_type(&self) -> bool {
+        unsafe {
+            from_glib(gobject_sys::g_type_check_is_value_type(self.to_glib()))
+        }
+    }
+
     pub fn parent(&self) -> Option<Self> {
         unsafe {
             let parent = gobject_sys::g_type_parent(self.to_glib());
@@ -114,6 +164,7 @@ impl Type {
             FromGlibContainerAsVec::from_glib_full_num_as_vec(interfaces, n_interfaces as usize)
         }
     }
+
     pub fn interface_prerequisites(&self) -> Vec<Self> {
         unsafe {
             let mut n_prereqs = 0u32;
@@ -369,3 +420,34 @@ impl FromGlibContainerAsVec<Type, *mut glib_sys::GType> for Type {
         FromGlibContainerAsVec::from_glib_container_num_as_vec(ptr, num)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use Binding;
+    use InitiallyUnowned;
+    use Object;
+
+    #[test]
+    fn test_hierarchy() {
+        let type_ = InitiallyUnowned::static_type();
+        assert_eq!(type_.parent(), Some(Object::static_type()));
+        assert_eq!(type_.fundamental(), Type::BaseObject);
+        assert_eq!(type_.depth(), 2);
+        assert_eq!(type_.ancestors(), vec![type_, Object::static_type()]);
+        assert!(Object::static_type().children().contains(&type_));
+        assert!(Object::static_type().children().contains(&Binding::static_type()));
+        assert_eq!(Type::from_name("GInitiallyUnowned"), Some(type_));
+        assert_eq!(Type::from_name("NoSuchType"), None);
+
+        assert!(type_.is_abstract());
+        assert!(type_.is_instantiatable());
+        assert!(!type_.is_interface());
+        assert!(type_.is_value_type());
+
+        assert_eq!(Type::I32.fundamental(), Type::I32);
+        assert_eq!(Type::I32.depth(), 1);
+        assert!(!Type::I32.is_instantiatable());
+        assert_eq!(Type::I32.parent(), None);
+    }
+}
@@ -0,0 +1,213 @@
+// Copyright 2019, The Gtk-rs Project Developers.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+// Licensed under the MIT license, see the LICENSE file or <http://opensource.org/licenses/MIT>
+
+use glib_sys;
+use libc::c_char;
+use std::str;
+use translate::*;
+
+macro_rules! unichar_predicate {
+    ($(#[$attr:meta])* $name:ident, $ffi:ident) => {
+        $(#[$attr])*
+        pub fn $name(c: char) -> bool {
+            unsafe { from_glib(glib_sys::$ffi(c.to_glib())) }
+        }
+    };
+}
+
+unichar_predicate!(
+    /// Checks whether `c` is alphanumeric.
+    unichar_isalnum,
+    g_unichar_isalnum
+);
+unichar_predicate!(
+    /// Checks whether `c` is alphabetic, i.e. a letter.
+    unichar_isalpha,
+    g_unichar_isalpha
+);
+unichar_predicate!(
+    /// Checks whether `c` is a control character.
+    unichar_iscntrl,
+    g_unichar_iscntrl
+);
+unichar_predicate!(
+    /// Checks whether `c` is assigned in the Unicode version supported by GLib.
+    unichar_isdefined,
+    g_unichar_isdefined
+);
+unichar_predicate!(
+    /// Checks whether `c` is a decimal digit.
+    unichar_isdigit,
+    g_unichar_isdigit
+);
+unichar_predicate!(
+    /// Checks whether `c` is printable and not a space.
+    unichar_isgraph,
+    g_unichar_isgraph
+);
+unichar_predicate!(
+    /// Checks whether `c` is a lowercase letter.
+    unichar_islower,
+    g_unichar_islower
+);
+unichar_predicate!(
+    /// Checks whether `c` is a combining mark, e.g. an accent.
+    unichar_ismark,
+    g_unichar_ismark
+);
+unichar_predicate!(
+    /// Checks whether `c` is printable. Unlike `unichar_isgraph()`, spaces are printable.
+    unichar_isprint,
+    g_unichar_isprint
+);
+unichar_predicate!(
+    /// Checks whether `c` is a punctuation or symbol character.
+    unichar_ispunct,
+    g_unichar_ispunct
+);
+unichar_predicate!(
+    /// Checks whether `c` is a space, tab, line or paragraph separator.
+    unichar_isspace,
+    g_unichar_isspace
+);
+unichar_predicate!(
+    /// Checks whether `c` is a titlecase letter like `ǅ`.
+    unichar_istitle,
+    g_unichar_istitle
+);
+unichar_predicate!(
+    /// Checks whether `c` is an uppercase letter.
+    unichar_isupper,
+    g_unichar_isupper
+);
+unichar_predicate!(
+    /// Checks whether `c` is typically rendered in a double-width cell, e.g. in a terminal.
+    unichar_iswide,
+    g_unichar_iswide
+);
+unichar_predicate!(
+    /// Same as `unichar_iswide()`, but also treats characters of ambiguous width as wide, as is
+    /// usual in legacy East Asian locales.
+    unichar_iswide_cjk,
+    g_unichar_iswide_cjk
+);
+unichar_predicate!(
+    /// Checks whether `c` is a hexadecimal digit.
+    unichar_isxdigit,
+    g_unichar_isxdigit
+);
+unichar_predicate!(
+    /// Checks whether `c` is a zero-width character that is not rendered on its own, e.g. a
+    /// combining mark or a zero-width space.
+    unichar_iszerowidth,
+    g_unichar_iszerowidth
+);
+
+/// Converts `c` to uppercase.
+///
+/// Characters without a single-character uppercase form are returned unchanged.
+pub fn unichar_toupper(c: char) -> char {
+    unsafe { from_glib(glib_sys::g_unichar_toupper(c.to_glib())) }
+}
+
+/// Converts `c` to lowercase.
+///
+/// Characters without a single-character lowercase form are returned unchanged.
+pub fn unichar_tolower(c: char) -> char {
+    unsafe { from_glib(glib_sys::g_unichar_tolower(c.to_glib())) }
+}
+
+/// Converts `c` to titlecase, e.g. `ǆ` to `ǅ`.
+pub fn unichar_totitle(c: char) -> char {
+    unsafe { from_glib(glib_sys::g_unichar_totitle(c.to_glib())) }
+}
+
+/// Encodes `c` as UTF-8.
+///
+/// This returns a `String` and not a `GString`, which can't contain the nul character.
+pub fn unichar_to_utf8(c: char) -> String {
+    unsafe {
+        // g_unichar_to_utf8() writes at most 6 bytes and no nul terminator
+        let mut buf = [0u8; 6];
+        let len = glib_sys::g_unichar_to_utf8(c.to_glib(), buf.as_mut_ptr() as *mut c_char);
+        assert!(len > 0 && len as usize <= buf.len());
+        String::from(str::from_utf8(&buf[..len as usize]).unwrap())
+    }
+}
+
+/// Returns the numeric value of `c` if it is a decimal digit.
+pub fn unichar_digit_value(c: char) -> Option<u32> {
+    let value = unsafe { glib_sys::g_unichar_digit_value(c.to_glib()) };
+    if value < 0 {
+        None
+    } else {
+        Some(value as u32)
+    }
+}
+
+/// Returns the numeric value of `c` if it is a hexadecimal digit.
+pub fn unichar_xdigit_value(c: char) -> Option<u32> {
+    let value = unsafe { glib_sys::g_unichar_xdigit_value(c.to_glib()) };
+    if value < 0 {
+        None
+    } else {
+        Some(value as u32)
+    }
+}
+
+/// Checks whether `c` is a valid Unicode character as GLib sees it.
+///
+/// Every Rust `char` is valid, so this is only useful for raw code points from C APIs, which can
+/// be converted with `std::char::from_u32()` afterwards.
+pub fn unichar_validate(c: u32) -> bool {
+    unsafe { from_glib(glib_sys::g_unichar_validate(c)) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify() {
+        assert!(unichar_isalpha('\u{e4}'));
+        assert!(!unichar_isalpha('1'));
+        assert!(unichar_isdigit('\u{663}'));
+        assert!(unichar_isspace('\u{2029}'));
+        assert!(unichar_ismark('\u{301}'));
+        assert!(unichar_iszerowidth('\u{200b}'));
+        assert!(unichar_iswide('\u{4e2d}'));
+        assert!(!unichar_iswide('a'));
+        assert!(unichar_istitle('\u{1c5}'));
+        assert!(unichar_isupper('\u{c4}'));
+        assert!(unichar_islower('\u{e4}'));
+    }
+
+    #[test]
+    fn test_convert() {
+        assert_eq!(unichar_toupper('\u{e4}'), '\u{c4}');
+        assert_eq!(unichar_tolower('\u{c4}'), '\u{e4}');
+        assert_eq!(unichar_totitle('\u{1c6}'), '\u{1c5}');
+        assert_eq!(unichar_toupper('1'), '1');
+
+        assert_eq!(unichar_to_utf8('a'), "a");
+        assert_eq!(unichar_to_utf8('\u{1f600}'), "\u{1f600}");
+        assert_eq!(unichar_to_utf8('\0'), "\0");
+    }
+
+    #[test]
+    fn test_digit_value() {
+        assert_eq!(unichar_digit_value('7'), Some(7));
+        assert_eq!(unichar_digit_value('\u{663}'), Some(3));
+        assert_eq!(unichar_digit_value('a'), None);
+        assert_eq!(unichar_xdigit_value('F'), Some(15));
+        assert_eq!(unichar_xdigit_value('g'), None);
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(unichar_validate('a' as u32));
+        assert!(!unichar_validate(0xd800));
+        assert!(!unichar_validate(0x110000));
+    }
+}
@@ -0,0 +1,142 @@
+// Copyright 2019, The Gtk-rs Project Developers.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+// Licensed under the MIT license, see the LICENSE file or <http://opensource.org/licenses/MIT>
+
+use glib_sys;
+use libc::c_char;
+use translate::*;
+use GString;
+
+/// Escapes `unescaped` for use in a URI.
+///
+/// Characters in `reserved_chars_allowed` are not escaped, e.g. `"/"` when escaping a path. If
+/// `allow_utf8` is `true`, non-ASCII characters are left as they are, which gives an IRI
+/// instead of a URI. NUL characters are always escaped.
+pub fn uri_escape_string(unescaped: &str, reserved_chars_allowed: Option<&str>, allow_utf8: bool) -> GString {
+    // NUL can't be passed to GLib, so the parts in between are escaped separately
+    let reserved_chars_allowed = reserved_chars_allowed.map(|chars| chars.replace('\0', ""));
+    let reserved_chars_allowed = reserved_chars_allowed.as_ref().map(|chars| chars.as_str());
+
+    let mut parts = unescaped.split('\0').map(|part| unsafe {
+        from_glib_full::<_, GString>(glib_sys::g_uri_escape_string(
+            part.to_glib_none().0,
+            reserved_chars_allowed.to_glib_none().0,
+            allow_utf8.to_glib(),
+        ))
+    }).collect::<Vec<_>>();
+
+    if parts.len() == 1 {
+        parts.remove(0)
+    } else {
+        parts.iter().map(|part| part.as_str()).collect::<Vec<_>>().join("%00").into()
+    }
+}
+
+/// Unescapes a whole escaped URI string.
+///
+/// Returns `None` if `escaped_string` contains invalid escape sequences, escaped characters
+/// from `illegal_characters` or an escaped NUL character, or if the result is not valid UTF-8.
+pub fn uri_unescape_string(escaped_string: &str, illegal_characters: Option<&str>) -> Option<GString> {
+    unescape(escaped_string, illegal_characters)
+}
+
+/// Unescapes a segment of a URI, e.g. a single path component.
+///
+/// Pass `"/"` as `illegal_characters` to make sure the result is a single path component. See
+/// [`uri_unescape_string()`] for the cases in which `None` is returned.
+///
+/// [`uri_unescape_string()`]: fn.uri_unescape_string.html
+pub fn uri_unescape_segment(escaped_segment: &str, illegal_characters: Option<&str>) -> Option<GString> {
+    unescape(escaped_segment, illegal_characters)
+}
+
+fn unescape(escaped: &str, illegal_characters: Option<&str>) -> Option<GString> {
+    // Unescaped NUL characters would end up in the result
+    if escaped.contains('\0') {
+        return None;
+    }
+    let illegal_characters = illegal_characters.map(|chars| chars.replace('\0', ""));
+    let illegal_characters = illegal_characters.as_ref().map(|chars| chars.as_str());
+
+    unsafe {
+        let start = escaped.as_ptr() as *const c_char;
+        let ptr = glib_sys::g_uri_unescape_segment(
+            start,
+            start.add(escaped.len()),
+            illegal_characters.to_glib_none().0,
+        );
+        if ptr.is_null() {
+            return None;
+        }
+
+        // Escaped bytes are not necessarily UTF-8
+        GString::try_new(ptr).ok()
+    }
+}
+
+/// Returns the scheme of `uri`, e.g. `"file"` for `file:///home`.
+///
+/// Returns `None` if `uri` is not a valid URI.
+pub fn uri_parse_scheme(uri: &str) -> Option<GString> {
+    if uri.contains('\0') {
+        return None;
+    }
+
+    unsafe { from_glib_full(glib_sys::g_uri_parse_scheme(uri.to_glib_none().0)) }
+}
+
+/// Splits a `text/uri-list` as used for drag and drop into its URIs, skipping comments.
+///
+/// Anything after a NUL character is ignored, like it would be in C.
+pub fn uri_list_extract_uris(uri_list: &str) -> Vec<GString> {
+    let uri_list = uri_list.split('\0').next().unwrap_or("");
+
+    unsafe {
+        FromGlibPtrContainer::from_glib_full(glib_sys::g_uri_list_extract_uris(uri_list.to_glib_none().0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escape() {
+        assert_eq!(uri_escape_string("a b/c", None, false), "a%20b%2Fc");
+        assert_eq!(uri_escape_string("a b/c", Some("/"), false), "a%20b/c");
+        assert_eq!(uri_escape_string("\u{e9}", None, false), "%C3%A9");
+        assert_eq!(uri_escape_string("\u{e9}", None, true), "\u{e9}");
+        assert_eq!(uri_escape_string("a\0b\0", Some("\0"), false), "a%00b%00");
+    }
+
+    #[test]
+    fn test_unescape() {
+        assert_eq!(uri_unescape_string("a%20b%2Fc", None).unwrap(), "a b/c");
+        assert!(uri_unescape_string("a%20b%2Fc", Some("/")).is_none());
+        assert_eq!(uri_unescape_segment("%C3%A9", Some("/")).unwrap(), "\u{e9}");
+        assert!(uri_unescape_string("a%2", None).is_none());
+        assert!(uri_unescape_string("a%00b", None).is_none());
+        assert!(uri_unescape_string("a\0b", None).is_none());
+        assert!(uri_unescape_string("%FF", None).is_none());
+
+        let s = "x y\0/z";
+        assert!(uri_unescape_string(&uri_escape_string(s, None, false), None).is_none());
+        let s = "x y/z \u{e9}";
+        assert_eq!(uri_unescape_string(&uri_escape_string(s, None, false), None).unwrap(), s);
+    }
+
+    #[test]
+    fn test_parse_scheme() {
+        assert_eq!(uri_parse_scheme("file:///home").unwrap(), "file");
+        assert_eq!(uri_parse_scheme("HTTPS://example.com").unwrap(), "https");
+        assert!(uri_parse_scheme("/home").is_none());
+        assert!(uri_parse_scheme("fi\0le:///home").is_none());
+    }
+
+    #[test]
+    fn test_list_extract_uris() {
+        let list = "# comment\r\nfile:///a\r\nfile:///b%20c\r\n\0file:///d";
+        assert_eq!(uri_list_extract_uris(list), vec!["file:///a", "file:///b%20c"]);
+        assert!(uri_list_extract_uris("").is_empty());
+    }
+}
@@ -0,0 +1,136 @@
+// Copyright 2019, The Gtk-rs Project Developers.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+// Licensed under the MIT license, see the LICENSE file or <http://opensource.org/licenses/MIT>
+
+use glib_sys;
+use libc::c_char;
+use std::borrow::Cow;
+use std::ffi::CStr;
+use std::ptr;
+use std::str;
+use translate::*;
+use GString;
+use NormalizeMode;
+
+/// Converts `s` into a Unicode normalization form.
+///
+/// `NormalizeMode::DefaultCompose` (NFC) is usually the right choice for comparing user input,
+/// `NormalizeMode::AllCompose` (NFKC) additionally treats compatibility characters like `ﬁ` the
+/// same as their plain counterparts.
+pub fn utf8_normalize(s: &str, mode: NormalizeMode) -> GString {
+    unsafe {
+        from_glib_full(glib_sys::g_utf8_normalize(
+            s.as_ptr() as *const _,
+            s.len() as isize,
+            mode.to_glib(),
+        ))
+    }
+}
+
+/// Converts `s` into a form that is independent of case, for case-insensitive comparisons.
+///
+/// The result is only suitable for comparing for equality or as a key, not for display or for
+/// sorting.
+pub fn utf8_casefold(s: &str) -> GString {
+    unsafe { from_glib_full(glib_sys::g_utf8_casefold(s.as_ptr() as *const _, s.len() as isize)) }
+}
+
+/// Checks whether `data` is valid UTF-8.
+///
+/// Returns the length of the valid prefix of `data` as error otherwise. Different to
+/// `std::str::from_utf8()`, nul bytes are treated as invalid like in all of GLib.
+pub fn utf8_validate(data: &[u8]) -> Result<&str, usize> {
+    unsafe {
+        let mut end = ptr::null();
+        let valid: bool = from_glib(glib_sys::g_utf8_validate(
+            data.as_ptr() as *const _,
+            data.len() as isize,
+            &mut end,
+        ));
+
+        if valid {
+            Ok(str::from_utf8_unchecked(data))
+        } else {
+            Err(end as usize - data.as_ptr() as usize)
+        }
+    }
+}
+
+/// Converts `data` into valid UTF-8 by replacing invalid sequences with the replacement
+/// character U+FFFD.
+#[cfg(any(feature = "v2_52", feature = "dox"))]
+pub fn utf8_make_valid(data: &[u8]) -> GString {
+    unsafe {
+        from_glib_full(glib_sys::g_utf8_make_valid(
+            data.as_ptr() as *const _,
+            data.len() as isize,
+        ))
+    }
+}
+
+/// Converts the nul-terminated string at `ptr`, e.g. a filename or a string from an external
+/// source, into a string slice.
+///
+/// Invalid UTF-8 sequences are replaced with the replacement character U+FFFD, in which case the
+/// string is copied. Returns `None` if `ptr` is `NULL`.
+pub unsafe fn c_str_to_str_lossy<'a>(ptr: *const c_char) -> Option<Cow<'a, str>> {
+    if ptr.is_null() {
+        return None;
+    }
+
+    Some(CStr::from_ptr(ptr).to_string_lossy())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize() {
+        let composed = "\u{e9}";
+        let decomposed = "e\u{301}";
+        assert_eq!(utf8_normalize(decomposed, NormalizeMode::DefaultCompose), composed);
+        assert_eq!(utf8_normalize(composed, NormalizeMode::Default), decomposed);
+        assert_eq!(utf8_normalize("\u{fb01}", NormalizeMode::DefaultCompose), "\u{fb01}");
+        assert_eq!(utf8_normalize("\u{fb01}", NormalizeMode::AllCompose), "fi");
+    }
+
+    #[test]
+    fn test_casefold() {
+        assert_eq!(utf8_casefold("Hello WORLD"), utf8_casefold("hello world"));
+        assert_eq!(utf8_casefold("Stra\u{df}e"), utf8_casefold("STRASSE"));
+    }
+
+    #[test]
+    fn test_validate() {
+        assert_eq!(utf8_validate(b"abc"), Ok("abc"));
+        assert_eq!(utf8_validate("\u{e9}t\u{e9}".as_bytes()), Ok("\u{e9}t\u{e9}"));
+        assert_eq!(utf8_validate(b"ab\xffc"), Err(2));
+        assert_eq!(utf8_validate(b"ab\0c"), Err(2));
+        assert_eq!(utf8_validate(b"\xc3"), Err(0));
+        assert_eq!(utf8_validate(b""), Ok(""));
+    }
+
+    #[cfg(feature = "v2_52")]
+    #[test]
+    fn test_make_valid() {
+        assert_eq!(utf8_make_valid(b"abc"), "abc");
+        assert_eq!(utf8_make_valid(b"ab\xffc"), "ab\u{fffd}c");
+    }
+
+    #[test]
+    fn test_c_str_to_str_lossy() {
+        unsafe {
+            let valid = c_str_to_str_lossy(b"abc\0".as_ptr() as *const c_char).unwrap();
+            assert!(match valid {
+                Cow::Borrowed("abc") => true,
+                _ => false,
+            });
+
+            let invalid = c_str_to_str_lossy(b"ab\xffc\0".as_ptr() as *const c_char).unwrap();
+            assert_eq!(invalid, "ab\u{fffd}c");
+
+            assert!(c_str_to_str_lossy(ptr::null()).is_none());
+        }
+    }
+}
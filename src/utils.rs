@@ -39,9 +39,9 @@ pub fn set_prgname(name: Option<&str>) {
 }
 
 pub fn getenv<K: AsRef<OsStr>>(variable_name: K) -> Option<OsString> {
-    #[cfg(windows)]
+    #[cfg(all(windows,target_arch="x86"))]
     use glib_sys::g_getenv_utf8 as g_getenv;
-    #[cfg(not(windows))]
+    #[cfg(not(all(windows,target_arch="x86")))]
     use glib_sys::g_getenv;
 
     unsafe {
@@ -50,9 +50,9 @@ pub fn getenv<K: AsRef<OsStr>>(variable_name: K) -> Option<OsString> {
 }
 
 pub fn setenv<K: AsRef<OsStr>, V: AsRef<OsStr>>(variable_name: K, value: V, overwrite: bool) -> Result<(), BoolError> {
-    #[cfg(windows)]
+    #[cfg(all(windows,target_arch="x86"))]
     use glib_sys::g_setenv_utf8 as g_setenv;
-    #[cfg(not(windows))]
+    #[cfg(not(all(windows,target_arch="x86")))]
     use glib_sys::g_setenv;
 
     unsafe {
@@ -68,9 +68,9 @@ pub fn setenv<K: AsRef<OsStr>, V: AsRef<OsStr>>(variable_name: K, value: V, over
 }
 
 pub fn unsetenv<K: AsRef<OsStr>>(variable_name: K) {
-    #[cfg(windows)]
+    #[cfg(all(windows,target_arch="x86"))]
     use glib_sys::g_unsetenv_utf8 as g_unsetenv;
-    #[cfg(not(windows))]
+    #[cfg(not(all(windows,target_arch="x86")))]
     use glib_sys::g_unsetenv;
 
     unsafe {
@@ -107,9 +107,9 @@ pub fn get_real_name() -> Option<OsString> {
 }
 
 pub fn get_current_dir() -> Option<PathBuf> {
-    #[cfg(windows)]
+    #[cfg(all(windows,target_arch="x86"))]
     use glib_sys::g_get_current_dir_utf8 as g_get_current_dir;
-    #[cfg(not(windows))]
+    #[cfg(not(all(windows,target_arch="x86")))]
     use glib_sys::g_get_current_dir;
 
     unsafe {
@@ -117,10 +117,87 @@ pub fn get_current_dir() -> Option<PathBuf> {
     }
 }
 
+/// Converts `filename` from the GLib filename encoding to UTF-8.
+///
+/// On UNIX filenames are in the encoding given by the `G_FILENAME_ENCODING` environment
+/// variable, or in the locale encoding if `G_BROKEN_FILENAMES` is set, and UTF-8 otherwise. On
+/// Windows filenames are always UTF-8.
+///
+/// Returns an error if `filename` can't be converted, use [`filename_display_name()`] for a
+/// conversion that can't fail.
+///
+/// [`filename_display_name()`]: fn.filename_display_name.html
+pub fn filename_to_utf8<P: AsRef<Path>>(filename: P) -> Result<GString, Error> {
+    #[cfg(all(windows,target_arch="x86"))]
+    use glib_sys::g_filename_to_utf8_utf8 as g_filename_to_utf8;
+    #[cfg(not(all(windows,target_arch="x86")))]
+    use glib_sys::g_filename_to_utf8;
+
+    unsafe {
+        let mut error = ptr::null_mut();
+        let ret = g_filename_to_utf8(
+            filename.as_ref().to_glib_none().0,
+            -1,
+            ptr::null_mut(),
+            ptr::null_mut(),
+            &mut error,
+        );
+        if error.is_null() { Ok(from_glib_full(ret)) } else { Err(from_glib_full(error)) }
+    }
+}
+
+/// Converts `utf8` to a filename in the GLib filename encoding.
+///
+/// Returns an error if `utf8` contains NUL characters or can't be represented in the filename
+/// encoding.
+pub fn filename_from_utf8(utf8: &str) -> Result<PathBuf, Error> {
+    #[cfg(all(windows,target_arch="x86"))]
+    use glib_sys::g_filename_from_utf8_utf8 as g_filename_from_utf8;
+    #[cfg(not(all(windows,target_arch="x86")))]
+    use glib_sys::g_filename_from_utf8;
+
+    unsafe {
+        let mut error = ptr::null_mut();
+        // Passing the length lets GLib report NUL characters as conversion error
+        let ret = g_filename_from_utf8(
+            utf8.as_ptr() as *const _,
+            utf8.len() as isize,
+            ptr::null_mut(),
+            ptr::null_mut(),
+            &mut error,
+        );
+        if error.is_null() { Ok(from_glib_full(ret)) } else { Err(from_glib_full(error)) }
+    }
+}
+
+/// Converts `filename` to a string for display to the user.
+///
+/// Unlike [`filename_to_utf8()`] this never fails, invalid characters are replaced by the
+/// Unicode replacement character.
+///
+/// [`filename_to_utf8()`]: fn.filename_to_utf8.html
+pub fn filename_display_name<P: AsRef<Path>>(filename: P) -> GString {
+    unsafe {
+        from_glib_full(glib_sys::g_filename_display_name(filename.as_ref().to_glib_none().0))
+    }
+}
+
+/// Converts the last component of `filename` to a string for display to the user.
+///
+/// See [`filename_display_name()`].
+///
+/// [`filename_display_name()`]: fn.filename_display_name.html
+pub fn filename_display_basename<P: AsRef<Path>>(filename: P) -> GString {
+    unsafe {
+        from_glib_full(glib_sys::g_filename_display_basename(filename.as_ref().to_glib_none().0))
+    }
+}
+
+/// Converts the absolute path `filename` to a `file://` URI, optionally with `hostname`.
 pub fn filename_to_uri<P: AsRef<Path>>(filename: P, hostname: Option<&str>) -> Result<GString, Error> {
-    #[cfg(windows)]
+    #[cfg(all(windows,target_arch="x86"))]
     use glib_sys::g_filename_to_uri_utf8 as g_filename_to_uri;
-    #[cfg(not(windows))]
+    #[cfg(not(all(windows,target_arch="x86")))]
     use glib_sys::g_filename_to_uri;
 
     let hostname = hostname.to_glib_none();
@@ -131,10 +208,11 @@ pub fn filename_to_uri<P: AsRef<Path>>(filename: P, hostname: Option<&str>) -> R
     }
 }
 
-pub fn filename_from_uri(uri: &str) -> Result<(std::path::PathBuf, Option<GString>), Error> {
-    #[cfg(windows)]
+/// Converts a `file://` URI to an absolute path and the hostname of the URI, if any.
+pub fn filename_from_uri(uri: &str) -> Result<(PathBuf, Option<GString>), Error> {
+    #[cfg(all(windows,target_arch="x86"))]
     use glib_sys::g_filename_from_uri_utf8 as g_filename_from_uri;
-    #[cfg(not(windows))]
+    #[cfg(not(all(windows,target_arch="x86")))]
     use glib_sys::g_filename_from_uri;
 
     unsafe {
@@ -179,9 +257,9 @@ pub fn get_tmp_dir() -> Option<std::path::PathBuf> {
 }
 
 pub fn mkstemp<P: AsRef<std::path::Path>>(tmpl: P) -> i32 {
-    #[cfg(windows)]
+    #[cfg(all(windows,target_arch="x86"))]
     use glib_sys::g_mkstemp_utf8 as g_mkstemp;
-    #[cfg(not(windows))]
+    #[cfg(not(all(windows,target_arch="x86")))]
     use glib_sys::g_mkstemp;
 
     unsafe {
@@ -189,6 +267,44 @@ pub fn mkstemp<P: AsRef<std::path::Path>>(tmpl: P) -> i32 {
     }
 }
 
+fn resolve_file<P: AsRef<Path>>(
+    relative_path: P,
+    override_variable: &str,
+    user_dir: Option<PathBuf>,
+    system_dirs: Vec<PathBuf>,
+) -> Option<PathBuf> {
+    let relative_path = relative_path.as_ref();
+    let override_dirs = getenv(override_variable)
+        .map(|dirs| std::env::split_paths(&dirs).collect::<Vec<_>>())
+        .unwrap_or_default();
+
+    override_dirs
+        .into_iter()
+        .chain(user_dir)
+        .chain(system_dirs)
+        .map(|dir| dir.join(relative_path))
+        .find(|path| path.exists())
+}
+
+/// Looks up `relative_path` in the data directories and returns the first existing file.
+///
+/// The directories from the `G_DATA_PATH` environment variable are searched first, followed by
+/// the user data directory and the system data directories, see `get_user_data_dir()` and
+/// `get_system_data_dirs()`. `G_DATA_PATH` uses the platform's search path separator.
+pub fn resolve_data_file<P: AsRef<Path>>(relative_path: P) -> Option<PathBuf> {
+    resolve_file(relative_path, "G_DATA_PATH", ::get_user_data_dir(), ::get_system_data_dirs())
+}
+
+/// Looks up `relative_path` in the configuration directories and returns the first existing file.
+///
+/// The directories from the `G_CONFIG_PATH` environment variable are searched first, followed by
+/// the user configuration directory and the system configuration directories, see
+/// `get_user_config_dir()` and `get_system_config_dirs()`. `G_CONFIG_PATH` uses the platform's
+/// search path separator.
+pub fn resolve_config_file<P: AsRef<Path>>(relative_path: P) -> Option<PathBuf> {
+    resolve_file(relative_path, "G_CONFIG_PATH", ::get_user_config_dir(), ::get_system_config_dirs())
+}
+
 #[cfg(test)]
 mod tests {
     use std::env;
@@ -250,4 +366,80 @@ mod tests {
         }
 
     }
+
+    #[test]
+    fn test_filename_utf8() {
+        use std::path::PathBuf;
+
+        assert_eq!(::filename_to_utf8("/tmp/t\u{e9}st.txt").unwrap(), "/tmp/t\u{e9}st.txt");
+        assert_eq!(::filename_from_utf8("/tmp/t\u{e9}st.txt").unwrap(), PathBuf::from("/tmp/t\u{e9}st.txt"));
+        assert!(::filename_from_utf8("/tmp/a\0b").is_err());
+
+        assert_eq!(::filename_display_name("/tmp/test.txt"), "/tmp/test.txt");
+        assert_eq!(::filename_display_basename("/tmp/test.txt"), "test.txt");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_filename_invalid_utf8() {
+        use std::ffi::OsStr;
+        use std::os::unix::ffi::OsStrExt;
+
+        let filename = OsStr::from_bytes(b"/tmp/t\xe9st.txt");
+        assert!(::filename_to_utf8(filename).is_err());
+        assert_eq!(::filename_display_name(filename), "/tmp/t\u{fffd}st.txt");
+    }
+
+    #[test]
+    fn test_prgname_and_application_name() {
+        ::set_prgname(Some("glib-rs-test"));
+        assert_eq!(::get_prgname(), Some(String::from("glib-rs-test")));
+        assert_eq!(::get_program_name(), Some(String::from("glib-rs-test")));
+
+        ::set_application_name("GLib-rs Tëst");
+        assert_eq!(::get_application_name().as_ref().map(|s| s.as_str()), Some("GLib-rs Tëst"));
+    }
+
+    #[test]
+    fn test_resolve_file() {
+        use std::fs;
+
+        let _data = LOCK.lock().unwrap();
+
+        let dir = env::temp_dir().join(format!("glib-rs-resolve-test-{}", ::std::process::id()));
+        let override_dir = dir.join("override");
+        let user_dir = dir.join("user");
+        let system_dir = dir.join("system");
+        fs::create_dir_all(override_dir.join("app")).unwrap();
+        fs::create_dir_all(user_dir.join("app")).unwrap();
+        fs::create_dir_all(system_dir.join("app")).unwrap();
+        fs::write(override_dir.join("app").join("data.txt"), b"test").unwrap();
+        fs::write(user_dir.join("app").join("data.txt"), b"test").unwrap();
+        fs::write(system_dir.join("app").join("data.txt"), b"test").unwrap();
+        fs::write(system_dir.join("app").join("other.txt"), b"test").unwrap();
+
+        let resolve = |path| {
+            super::resolve_file(path, "GLIB_RS_TEST_RESOLVE_PATH", Some(user_dir.clone()), vec![system_dir.clone()])
+        };
+
+        // Without the override the user directory is searched before the system directories
+        env::remove_var("GLIB_RS_TEST_RESOLVE_PATH");
+        assert_eq!(resolve("app/data.txt"), Some(user_dir.join("app/data.txt")));
+        assert_eq!(resolve("app/other.txt"), Some(system_dir.join("app/other.txt")));
+        assert_eq!(resolve("app/does-not-exist.txt"), None);
+
+        // The override directories are searched first and fall back to the other directories
+        let override_path = env::join_paths(&[dir.join("does-not-exist"), override_dir.clone()]).unwrap();
+        env::set_var("GLIB_RS_TEST_RESOLVE_PATH", &override_path);
+        assert_eq!(resolve("app/data.txt"), Some(override_dir.join("app/data.txt")));
+        assert_eq!(resolve("app/other.txt"), Some(system_dir.join("app/other.txt")));
+        assert_eq!(resolve("app/does-not-exist.txt"), None);
+        env::remove_var("GLIB_RS_TEST_RESOLVE_PATH");
+
+        env::set_var("G_DATA_PATH", &override_path);
+        assert_eq!(::resolve_data_file("app/data.txt"), Some(override_dir.join("app/data.txt")));
+        env::remove_var("G_DATA_PATH");
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
 }
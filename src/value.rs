@@ -45,6 +45,13 @@
 //! assert_eq!(hello.get::<String>(), Some(String::from("Hello!")));
 //! assert_eq!(str_none.get::<String>(), None);
 //!
+//! // `get_checked` distinguishes type mismatches from `None` values.
+//! assert_eq!(str_none.get_checked::<String>(), Ok(None));
+//! assert!(num.get_checked::<String>().is_err());
+//!
+//! // `get_some` is available for types that can't be `None`.
+//! assert_eq!(num.get_some::<i32>(), Ok(10));
+//!
 //! // `typed` tries to convert a `Value` to `TypedValue`.
 //! let mut typed_num = num.downcast::<i32>().unwrap();
 //! let mut typed_hello = hello.downcast::<String>().unwrap();
@@ -76,6 +83,7 @@
 
 use libc::{c_char, c_void};
 use std::borrow::Borrow;
+use std::error;
 use std::ffi::CStr;
 use std::fmt;
 use std::marker::PhantomData;
@@ -158,7 +166,7 @@ impl Value {
     /// Returns `Some` if the type is correct and the value is not `None`.
     ///
     /// This function doesn't distinguish between type mismatches and correctly
-    /// typed `None` values. Use `downcast` or `is` for that.
+    /// typed `None` values. Use `get_checked`, `downcast` or `is` for that.
     pub fn get<'a, T: FromValueOptional<'a>>(&'a self) -> Option<T> {
         unsafe {
            let ok = from_glib(
@@ -173,6 +181,41 @@ impl Value {
         }
     }
 
+    /// Tries to get a value of type `T`.
+    ///
+    /// Returns `Err` if the type of the value is not compatible with `T`, and `Ok(None)` if the
+    /// type is correct but the value is `None`.
+    pub fn get_checked<'a, T: FromValueOptional<'a>>(&'a self) -> Result<Option<T>, GetError> {
+        unsafe {
+            let ok = from_glib(
+                gobject_sys::g_type_check_value_holds(mut_override(self.to_glib_none().0),
+                    T::static_type().to_glib()));
+            if ok {
+                Ok(T::from_value_optional(self))
+            }
+            else {
+                Err(GetError::new(self.type_(), T::static_type()))
+            }
+        }
+    }
+
+    /// Tries to get a value of type `T` that can't be `None`, e.g. numeric types.
+    ///
+    /// Returns `Err` if the type of the value is not compatible with `T`.
+    pub fn get_some<'a, T: FromValue<'a>>(&'a self) -> Result<T, GetError> {
+        unsafe {
+            let ok = from_glib(
+                gobject_sys::g_type_check_value_holds(mut_override(self.to_glib_none().0),
+                    T::static_type().to_glib()));
+            if ok {
+                Ok(T::from_value(self))
+            }
+            else {
+                Err(GetError::new(self.type_(), T::static_type()))
+            }
+        }
+    }
+
     /// Returns `true` if the type of the value corresponds to `T`
     /// or is a sub-type of `T`.
     #[inline]
@@ -192,6 +235,30 @@ impl Value {
         }
     }
 
+    /// Tries to transform the value into a value of type `T`.
+    ///
+    /// Returns `None` if no transformation from the type of the value to `T` is registered, see
+    /// `type_transformable()`.
+    pub fn transform<T: StaticType + SetValue>(&self) -> Option<Value> {
+        self.transform_with_type(T::static_type())
+    }
+
+    /// Tries to transform the value into a value of type `type_`.
+    ///
+    /// Returns `None` if no transformation from the type of the value to `type_` is registered,
+    /// see `type_transformable()`.
+    pub fn transform_with_type(&self, type_: Type) -> Option<Value> {
+        unsafe {
+            let mut dest = Value::from_type(type_);
+            if from_glib(gobject_sys::g_value_transform(self.to_glib_none().0, dest.to_glib_none_mut().0)) {
+                Some(dest)
+            }
+            else {
+                None
+            }
+        }
+    }
+
     #[doc(hidden)]
     pub fn into_raw(mut self) -> gobject_sys::GValue {
         unsafe {
@@ -644,6 +711,45 @@ impl ToValue for Value {
     }
 }
 
+/// Error returned by [`Value::get_checked()`] and [`Value::get_some()`] if the value holds a
+/// type that is not compatible with the requested type.
+///
+/// [`Value::get_checked()`]: struct.Value.html#method.get_checked
+/// [`Value::get_some()`]: struct.Value.html#method.get_some
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GetError {
+    actual: Type,
+    requested: Type,
+}
+
+impl GetError {
+    fn new(actual: Type, requested: Type) -> Self {
+        GetError { actual, requested }
+    }
+
+    /// The type of the value.
+    pub fn actual(&self) -> Type {
+        self.actual
+    }
+
+    /// The type that was requested.
+    pub fn requested(&self) -> Type {
+        self.requested
+    }
+}
+
+impl fmt::Display for GetError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Value type mismatch. Actual {} requested {}", self.actual, self.requested)
+    }
+}
+
+impl error::Error for GetError {
+    fn description(&self) -> &str {
+        "Value type mismatch"
+    }
+}
+
 /// A version of [`Value`](struct.Value.html) for storing `Send` types, that implements Send
 /// itself.
 ///
@@ -682,12 +788,37 @@ impl SendValue {
         }
     }
 
+    /// Tries to convert a `Value` to a `SendValue`.
+    ///
+    /// This only succeeds for values of fundamental types that never carry thread-bound data,
+    /// i.e. booleans, numbers, strings, enums, flags and variants, and returns the value again
+    /// otherwise. Values of other types, e.g. objects, have to be converted from a statically
+    /// typed `Send` type instead.
+    pub fn try_from_value(value: Value) -> Result<SendValue, Value> {
+        let fundamental: Type = unsafe {
+            from_glib(gobject_sys::g_type_fundamental(value.type_().to_glib()))
+        };
+
+        match fundamental {
+            Type::Bool | Type::I8 | Type::U8 | Type::I32 | Type::U32 | Type::ILong | Type::ULong |
+            Type::I64 | Type::U64 | Type::F32 | Type::F64 | Type::String | Type::BaseEnum |
+            Type::BaseFlags | Type::Variant => Ok(SendValue(value)),
+            _ => Err(value),
+        }
+    }
+
     #[doc(hidden)]
     pub fn into_raw(self) -> gobject_sys::GValue {
         self.0.into_raw()
     }
 }
 
+impl From<SendValue> for Value {
+    fn from(value: SendValue) -> Self {
+        value.0
+    }
+}
+
 impl fmt::Debug for SendValue {
     fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
         f.debug_tuple("SendValue")
@@ -966,6 +1097,65 @@ mod tests {
         thread::spawn(move || drop(v)).join().unwrap();
     }
 
+    #[test]
+    fn test_send_value_channel() {
+        let c = ::MainContext::new();
+        c.acquire();
+
+        let (sender, receiver) = ::MainContext::sync_channel(::PRIORITY_DEFAULT, 1);
+        let values = vec![1i32.to_send_value(), "abc".to_send_value()];
+        ::std::thread::spawn(move || sender.send(values).unwrap()).join().unwrap();
+
+        let l = ::MainLoop::new(Some(&c), false);
+        let l_clone = l.clone();
+        receiver.attach(Some(&c), move |values: Vec<SendValue>| {
+            assert_eq!(values[0].get_some::<i32>(), Ok(1));
+            assert_eq!(values[1].get::<String>(), Some(String::from("abc")));
+            l_clone.quit();
+            ::Continue(false)
+        });
+        l.run();
+    }
+
+    #[test]
+    fn test_send_value_try_from_value() {
+        let v = SendValue::try_from_value(Value::from("abc")).unwrap();
+        assert_eq!(Value::from(v).get::<String>(), Some(String::from("abc")));
+
+        let obj = ::Object::new(::Object::static_type(), &[]).unwrap();
+        assert!(SendValue::try_from_value(obj.to_value()).is_err());
+    }
+
+    #[test]
+    fn test_transform() {
+        let v = 123i32.to_value();
+        assert!(Value::type_transformable(Type::I32, Type::F64));
+        assert_eq!(v.transform::<f64>().unwrap().get::<f64>(), Some(123.0));
+        assert_eq!(v.transform::<String>().unwrap().get::<String>(), Some(String::from("123")));
+
+        let v = ::BindingFlags::SYNC_CREATE.to_value();
+        assert_eq!(v.transform::<String>().unwrap().get::<String>(), Some(String::from("G_BINDING_SYNC_CREATE")));
+
+        assert!(!Value::type_transformable(Type::String, Type::I32));
+        assert!(Value::from("123").transform::<i32>().is_none());
+    }
+
+    #[test]
+    fn test_get_checked() {
+        let v = 123i32.to_value();
+        assert_eq!(v.get_checked::<i32>(), Ok(Some(123)));
+        assert_eq!(v.get_some::<i32>(), Ok(123));
+
+        let err = v.get_checked::<String>().unwrap_err();
+        assert_eq!(err.actual(), Type::I32);
+        assert_eq!(err.requested(), Type::String);
+        assert_eq!(v.get_some::<u64>(), Err(GetError::new(Type::I32, Type::U64)));
+
+        let v = Value::from(None::<&str>);
+        assert_eq!(v.get_checked::<String>(), Ok(None));
+        assert!(v.get_checked::<i32>().is_err());
+    }
+
     #[test]
     fn test_strv() {
         let v = vec!["123", "456"].to_value();
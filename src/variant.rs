@@ -10,9 +10,14 @@
 //! `Variant` types are described by [`VariantType`](../struct.VariantType.html)
 //! "type strings".
 //!
-//! Although `GVariant` supports arbitrarily complex types, this binding is
-//! currently limited to the basic ones: `bool`, `u8`, `i16`, `u16`, `i32`,
-//! `u32`, `i64`, `u64`, `f64` and `&str`/`String`.
+//! The basic types `bool`, `u8`, `i16`, `u16`, `i32`, `u32`, `i64`, `u64`,
+//! `f64` and `&str`/`String` are supported, as well as containers of them:
+//! `Option<T>` (maybe types), `Vec<T>` and `&[T]` (arrays), `HashMap<K, V>`
+//! and `BTreeMap<K, V>` (dictionaries, `K` has to be a basic type) and tuples
+//! with up to 12 fields.
+//!
+//! The conversion traits can be derived for structs and enums with
+//! [`derive(Variant)`](../derive.Variant.html).
 //!
 //! # Examples
 //!
@@ -36,23 +41,37 @@
 //! // `get_str` tries to borrow a string slice.
 //! assert_eq!(hello.get_str(), Some("Hello!"));
 //! assert_eq!(num.get_str(), None);
+//!
+//! // Containers are converted recursively
+//! let list = vec![(1, String::from("one")), (2, String::from("two"))].to_variant();
+//! assert_eq!(list.type_().to_str(), "a(is)");
+//! assert_eq!(list.get::<Vec<(i32, String)>>().unwrap()[1].1, "two");
 //! ```
 
 use glib_sys;
 use gobject_sys;
 use gstring::GString;
+use libc::c_char;
 use std::borrow::Cow;
-use std::cmp::{Eq, Ordering, PartialEq, PartialOrd};
+use std::cmp::{Eq, Ord, Ordering, PartialEq, PartialOrd};
+use std::collections::{BTreeMap, HashMap};
 use std::fmt;
 use std::hash::{Hash, Hasher};
+use std::mem;
+use std::ptr;
 use std::slice;
 use std::str;
 use translate::*;
 use value;
+use Bytes;
+use Error;
 use StaticType;
 use Type;
 use Value;
+use VariantParseError;
 use VariantTy;
+use VariantType;
+use {VariantIter, VariantTypedIter};
 
 glib_wrapper! {
     /// A generic immutable value capable of carrying various types.
@@ -113,6 +132,153 @@ impl Variant {
         T::from_variant(self)
     }
 
+    /// Boxes `value` into a variant of type `v`.
+    ///
+    /// `Variant` itself does not implement `ToVariant`, use this for e.g. the values of
+    /// `a{sv}` dictionaries.
+    pub fn new_variant(value: &Variant) -> Variant {
+        unsafe { from_glib_none(glib_sys::g_variant_new_variant(value.to_glib_none().0)) }
+    }
+
+    /// Unboxes the value of a variant of type `v`.
+    ///
+    /// Returns `None` if the variant has a different type.
+    pub fn as_variant(&self) -> Option<Variant> {
+        unsafe {
+            if self.is::<Variant>() {
+                Some(from_glib_full(glib_sys::g_variant_get_variant(self.to_glib_none().0)))
+            } else {
+                None
+            }
+        }
+    }
+
+    /// Creates an array with elements of type `child_type`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any of `children` is not of type `child_type`.
+    pub fn new_array(child_type: &VariantTy, children: &[Variant]) -> Variant {
+        assert!(children.iter().all(|child| child.type_() == child_type),
+            "Array elements must be of type {}", child_type);
+
+        let ptrs = children.iter().map(|child| child.to_glib_none().0).collect::<Vec<_>>();
+        unsafe {
+            from_glib_none(glib_sys::g_variant_new_array(
+                child_type.to_glib_none().0,
+                ptrs.as_ptr(),
+                ptrs.len(),
+            ))
+        }
+    }
+
+    /// Creates a tuple of `children`.
+    pub fn new_tuple(children: &[Variant]) -> Variant {
+        let ptrs = children.iter().map(|child| child.to_glib_none().0).collect::<Vec<_>>();
+        unsafe { from_glib_none(glib_sys::g_variant_new_tuple(ptrs.as_ptr(), ptrs.len())) }
+    }
+
+    /// Creates a dictionary entry, which are the elements of dictionaries of type `a{kv}`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `key` is not of a basic type.
+    pub fn new_dict_entry(key: &Variant, value: &Variant) -> Variant {
+        unsafe {
+            assert!(from_glib::<_, bool>(glib_sys::g_variant_type_is_basic(key.type_().to_glib_none().0)),
+                "Dictionary keys must be of a basic type");
+            from_glib_none(glib_sys::g_variant_new_dict_entry(
+                key.to_glib_none().0,
+                value.to_glib_none().0,
+            ))
+        }
+    }
+
+    /// Returns the number of children of a container variant, i.e. the number of elements of an
+    /// array, the number of fields of a tuple, 2 for a dictionary entry and 0 or 1 for a maybe.
+    ///
+    /// Returns 0 for variants that are not containers.
+    pub fn n_children(&self) -> usize {
+        unsafe {
+            if self.type_().is_container() {
+                glib_sys::g_variant_n_children(self.to_glib_none().0)
+            } else {
+                0
+            }
+        }
+    }
+
+    /// Returns the child at `index` of a container variant.
+    ///
+    /// This does not copy any data, the child shares the serialized data of its parent.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds, see [`n_children()`](#method.n_children).
+    pub fn child_value(&self, index: usize) -> Variant {
+        assert!(index < self.n_children(), "Child index {} out of bounds", index);
+        unsafe { from_glib_full(glib_sys::g_variant_get_child_value(self.to_glib_none().0, index)) }
+    }
+
+    /// Borrows the elements of an array of a fixed size type like `ay`, `ai` or `ad` without
+    /// copying them.
+    ///
+    /// Returns `None` if the variant is not an array with elements of type `T`.
+    ///
+    /// ```
+    /// use glib::prelude::*;
+    ///
+    /// let variant = vec![1.5f64, 2.5, 3.5].to_variant();
+    /// assert_eq!(variant.fixed_array::<f64>(), Some(&[1.5, 2.5, 3.5][..]));
+    /// assert_eq!(variant.fixed_array::<i64>(), None);
+    /// ```
+    pub fn fixed_array<T: FixedSizeVariantType>(&self) -> Option<&[T]> {
+        if !self.is::<Vec<T>>() {
+            return None;
+        }
+
+        unsafe {
+            let mut n_elements = 0;
+            let ptr = glib_sys::g_variant_get_fixed_array(
+                self.to_glib_none().0,
+                &mut n_elements,
+                mem::size_of::<T>(),
+            ) as *const T;
+
+            if n_elements == 0 {
+                return Some(&[]);
+            }
+            assert_eq!(ptr as usize % mem::align_of::<T>(), 0, "Misaligned variant data");
+            Some(slice::from_raw_parts(ptr, n_elements))
+        }
+    }
+
+    /// Returns an iterator over the children of a container variant, i.e. the elements of an
+    /// array, the fields of a tuple, the key and value of a dictionary entry or the value of a
+    /// maybe.
+    ///
+    /// The iterator is empty for variants that are not containers.
+    pub fn iter(&self) -> VariantIter {
+        VariantIter::new(self.clone())
+    }
+
+    /// Returns an iterator over the elements of an array as values of type `T`.
+    ///
+    /// The elements of dictionaries are returned as tuples of key and value, e.g. a variant of
+    /// type `a{sv}` can be iterated as `(String, Variant)`. Returns `None` if the variant is
+    /// not an array with elements of type `T`, and elements that can't be converted to `T` are
+    /// returned as `Err`.
+    ///
+    /// ```
+    /// use glib::prelude::*;
+    ///
+    /// let variant = vec![1u32, 2, 3].to_variant();
+    /// assert_eq!(variant.iter_as::<u32>().unwrap().sum::<Result<u32, _>>(), Ok(6));
+    /// ```
+    pub fn iter_as<T: FromVariant>(&self) -> Option<VariantTypedIter<T>> {
+        VariantTypedIter::new(self.clone())
+    }
+
     /// Tries to extract a `&str`.
     ///
     /// Returns `Some` if the variant has a string type (`s`, `o` or `g` type
@@ -131,6 +297,158 @@ impl Variant {
             }
         }
     }
+
+    /// Pretty-prints the value in the text format also used by `gsettings` and `gdbus`.
+    ///
+    /// If `type_annotate` is `true`, type annotations are added where the type would be
+    /// ambiguous otherwise, so the output can be parsed back into the same type with
+    /// [`parse()`](#method.parse) without giving an expected type.
+    pub fn print(&self, type_annotate: bool) -> GString {
+        unsafe {
+            from_glib_full(glib_sys::g_variant_print(self.to_glib_none().0, type_annotate.to_glib()))
+        }
+    }
+
+    /// Parses a variant from the text format produced by [`print()`](#method.print).
+    ///
+    /// If `type_` is given, the text is parsed as a value of that type, which allows to leave
+    /// out type annotations. The message of the returned `VariantParseError` points out the
+    /// location of the error in `text`.
+    ///
+    /// ```
+    /// use glib::{Variant, VariantParseError, VariantTy};
+    ///
+    /// let variant = Variant::parse(None, "[(1, 'one'), (2, 'two')]").unwrap();
+    /// assert_eq!(variant.type_().to_str(), "a(is)");
+    ///
+    /// let variant = Variant::parse(Some(VariantTy::new("ay").unwrap()), "[1, 2]").unwrap();
+    /// assert_eq!(variant.type_().to_str(), "ay");
+    ///
+    /// let err = Variant::parse(None, "[1, 'two']").unwrap_err();
+    /// assert_eq!(err.kind::<VariantParseError>(), Some(VariantParseError::NoCommonType));
+    /// ```
+    pub fn parse(type_: Option<&VariantTy>, text: &str) -> Result<Variant, Error> {
+        unsafe {
+            let text_ptr = text.as_ptr() as *const c_char;
+            let mut error = ptr::null_mut();
+            let variant = glib_sys::g_variant_parse(
+                type_.map_or(ptr::null(), |t| t.as_ptr()),
+                text_ptr,
+                text_ptr.add(text.len()),
+                ptr::null_mut(),
+                &mut error,
+            );
+            if error.is_null() {
+                return Ok(from_glib_full(variant));
+            }
+
+            let kind = VariantParseError::from_glib((*error).code);
+            let error: Error = from_glib_full(error);
+
+            // The context can only be printed for nul-terminated text, keep the plain message
+            // with the character offsets of the error otherwise
+            if text.contains('\0') {
+                return Err(error);
+            }
+
+            // The message only contains the character offsets of the error, replace it by one
+            // that also shows the affected part of the text
+            let context: GString = from_glib_full(glib_sys::g_variant_parse_error_print_context(
+                mut_override(error.to_glib_none().0),
+                text.to_glib_none().0,
+            ));
+            Err(Error::new(kind, context.trim_end()))
+        }
+    }
+
+    /// Creates a variant of type `T` from `bytes` in the serialized GVariant format without
+    /// copying.
+    ///
+    /// The data is expected in the byte order of the host; data written on a machine with a
+    /// different byte order has to be converted with [`byteswap()`](#method.byteswap). The data
+    /// is not validated up front: if it turns out to be invalid while accessing the variant, the
+    /// invalid parts are treated as default values of their type, e.g. empty strings or zero.
+    /// Use [`normal_form()`](#method.normal_form) to get a valid copy.
+    pub fn from_bytes<T: StaticVariantType>(bytes: &Bytes) -> Variant {
+        Variant::from_bytes_with_type(bytes, &T::static_variant_type())
+    }
+
+    /// Creates a variant of type `type_` from `bytes` in the serialized GVariant format without
+    /// copying.
+    ///
+    /// See [`from_bytes()`](#method.from_bytes).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `type_` is not a definite type.
+    pub fn from_bytes_with_type(bytes: &Bytes, type_: &VariantTy) -> Variant {
+        assert!(type_.is_definite(), "Variants must have a definite type, got {}", type_);
+        unsafe {
+            from_glib_none(glib_sys::g_variant_new_from_bytes(
+                type_.as_ptr(),
+                bytes.to_glib_none().0,
+                false.to_glib(),
+            ))
+        }
+    }
+
+    /// Creates a variant of type `T` from `data` in the serialized GVariant format, taking
+    /// ownership of `data` without copying.
+    ///
+    /// See [`from_bytes()`](#method.from_bytes).
+    pub fn from_data<T: StaticVariantType, A: AsRef<[u8]> + Send + 'static>(data: A) -> Variant {
+        Variant::from_bytes::<T>(&Bytes::from_owned(data))
+    }
+
+    /// Creates a variant of type `T` from `data` in the serialized GVariant format, taking
+    /// ownership of `data` without copying or validating it.
+    ///
+    /// This is only safe for data that is known to be in normal form, e.g. because it was
+    /// produced by [`data_as_bytes()`](#method.data_as_bytes) on a machine with the same byte
+    /// order, as the accessors of variants rely on the data being valid.
+    pub unsafe fn from_data_trusted<T: StaticVariantType, A: AsRef<[u8]> + Send + 'static>(
+        data: A,
+    ) -> Variant {
+        let bytes = Bytes::from_owned(data);
+        from_glib_none(glib_sys::g_variant_new_from_bytes(
+            T::static_variant_type().as_ptr(),
+            bytes.to_glib_none().0,
+            true.to_glib(),
+        ))
+    }
+
+    /// Returns `true` if the value is in normal form, i.e. if its serialized data is the
+    /// canonical representation of the value.
+    ///
+    /// Values created by this crate are always in normal form, values created from untrusted
+    /// serialized data might not be.
+    pub fn is_normal_form(&self) -> bool {
+        unsafe { from_glib(glib_sys::g_variant_is_normal_form(self.to_glib_none().0)) }
+    }
+
+    /// Returns the value in normal form.
+    ///
+    /// Invalid parts of the serialized data are replaced by default values of their type, so
+    /// the result is always valid and can safely be processed or passed on as trusted data.
+    pub fn normal_form(&self) -> Variant {
+        unsafe { from_glib_full(glib_sys::g_variant_get_normal_form(self.to_glib_none().0)) }
+    }
+
+    /// Returns the value with the byte order of all numbers swapped.
+    ///
+    /// This converts values created from serialized data in the other byte order than that of
+    /// the host. The result is in normal form.
+    pub fn byteswap(&self) -> Variant {
+        unsafe { from_glib_full(glib_sys::g_variant_byteswap(self.to_glib_none().0)) }
+    }
+
+    /// Returns the serialized GVariant format of the value, in the byte order of the host.
+    ///
+    /// This does not copy the data if the variant is already serialized, e.g. if it was created
+    /// from serialized data or is a child of such a variant.
+    pub fn data_as_bytes(&self) -> Bytes {
+        unsafe { from_glib_full(glib_sys::g_variant_get_data_as_bytes(self.to_glib_none().0)) }
+    }
 }
 
 unsafe impl Send for Variant { }
@@ -145,10 +463,15 @@ impl fmt::Debug for Variant {
 
 impl fmt::Display for Variant {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let serialized: GString = unsafe {
-            from_glib_full(glib_sys::g_variant_print(self.to_glib_none().0, false.to_glib()))
-        };
-        f.write_str(&serialized)
+        f.write_str(&self.print(false))
+    }
+}
+
+impl str::FromStr for Variant {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Variant::parse(None, s)
     }
 }
 
@@ -216,6 +539,15 @@ pub trait StaticVariantType {
     fn static_variant_type() -> Cow<'static, VariantTy>;
 }
 
+/// Types whose values are serialized as is in the GVariant format, which allows to borrow arrays
+/// of them from variants with [`Variant::fixed_array()`].
+///
+/// This is implemented for all numeric types and is unsafe to implement, as the serialized data
+/// is reinterpreted as values of the type.
+///
+/// [`Variant::fixed_array()`]: struct.Variant.html#method.fixed_array
+pub unsafe trait FixedSizeVariantType: StaticVariantType + Copy {}
+
 impl<'a, T: ?Sized + ToVariant> ToVariant for &'a T {
     fn to_variant(&self) -> Variant {
         <T as ToVariant>::to_variant(self)
@@ -253,6 +585,8 @@ macro_rules! impl_numeric {
                 }
             }
         }
+
+        unsafe impl FixedSizeVariantType for $name {}
     }
 }
 
@@ -319,6 +653,213 @@ impl ToVariant for str {
     }
 }
 
+impl StaticVariantType for Variant {
+    fn static_variant_type() -> Cow<'static, VariantTy> {
+        unsafe { VariantTy::from_str_unchecked("v").into() }
+    }
+}
+
+impl FromVariant for Variant {
+    /// Unboxes the value of a variant of type `v`, see `Variant::as_variant()`.
+    fn from_variant(variant: &Variant) -> Option<Self> {
+        variant.as_variant()
+    }
+}
+
+impl StaticVariantType for () {
+    fn static_variant_type() -> Cow<'static, VariantTy> {
+        unsafe { VariantTy::from_str_unchecked("()").into() }
+    }
+}
+
+impl ToVariant for () {
+    fn to_variant(&self) -> Variant {
+        Variant::new_tuple(&[])
+    }
+}
+
+impl FromVariant for () {
+    fn from_variant(variant: &Variant) -> Option<Self> {
+        if variant.is::<Self>() {
+            Some(())
+        } else {
+            None
+        }
+    }
+}
+
+impl<T: StaticVariantType> StaticVariantType for Option<T> {
+    fn static_variant_type() -> Cow<'static, VariantTy> {
+        container_type(&["m", T::static_variant_type().to_str()])
+    }
+}
+
+impl<T: StaticVariantType + ToVariant> ToVariant for Option<T> {
+    fn to_variant(&self) -> Variant {
+        unsafe {
+            let child = self.as_ref().map(|v| v.to_variant());
+            from_glib_none(glib_sys::g_variant_new_maybe(
+                T::static_variant_type().to_glib_none().0,
+                child.to_glib_none().0,
+            ))
+        }
+    }
+}
+
+impl<T: FromVariant> FromVariant for Option<T> {
+    fn from_variant(variant: &Variant) -> Option<Self> {
+        unsafe {
+            if !variant.is::<Self>() {
+                return None;
+            }
+
+            let child: Option<Variant> = from_glib_full(glib_sys::g_variant_get_maybe(variant.to_glib_none().0));
+            match child {
+                Some(child) => child.get().map(Some),
+                None => Some(None),
+            }
+        }
+    }
+}
+
+impl<T: StaticVariantType> StaticVariantType for [T] {
+    fn static_variant_type() -> Cow<'static, VariantTy> {
+        container_type(&["a", T::static_variant_type().to_str()])
+    }
+}
+
+impl<T: StaticVariantType + ToVariant> ToVariant for [T] {
+    fn to_variant(&self) -> Variant {
+        let children = self.iter().map(ToVariant::to_variant).collect::<Vec<_>>();
+        Variant::new_array(&T::static_variant_type(), &children)
+    }
+}
+
+impl<T: StaticVariantType> StaticVariantType for Vec<T> {
+    fn static_variant_type() -> Cow<'static, VariantTy> {
+        <[T]>::static_variant_type()
+    }
+}
+
+impl<T: StaticVariantType + ToVariant> ToVariant for Vec<T> {
+    fn to_variant(&self) -> Variant {
+        self[..].to_variant()
+    }
+}
+
+impl<T: FromVariant> FromVariant for Vec<T> {
+    fn from_variant(variant: &Variant) -> Option<Self> {
+        if !variant.is::<Self>() {
+            return None;
+        }
+
+        children(variant).map(|child| child.get()).collect()
+    }
+}
+
+macro_rules! impl_map {
+    ($map:ident, $($key_bound:ident),+) => {
+        impl<K, V> StaticVariantType for $map<K, V>
+        where K: $($key_bound +)+ StaticVariantType, V: StaticVariantType {
+            fn static_variant_type() -> Cow<'static, VariantTy> {
+                container_type(&["a{", K::static_variant_type().to_str(), V::static_variant_type().to_str(), "}"])
+            }
+        }
+
+        impl<K, V> ToVariant for $map<K, V>
+        where K: $($key_bound +)+ StaticVariantType + ToVariant, V: StaticVariantType + ToVariant {
+            fn to_variant(&self) -> Variant {
+                let entry_type = container_type(&["{", K::static_variant_type().to_str(), V::static_variant_type().to_str(), "}"]);
+                let children = self.iter()
+                    .map(|(key, value)| Variant::new_dict_entry(&key.to_variant(), &value.to_variant()))
+                    .collect::<Vec<_>>();
+                Variant::new_array(&entry_type, &children)
+            }
+        }
+
+        impl<K, V> FromVariant for $map<K, V>
+        where K: $($key_bound +)+ FromVariant, V: FromVariant {
+            fn from_variant(variant: &Variant) -> Option<Self> {
+                if !variant.is::<Self>() {
+                    return None;
+                }
+
+                children(variant)
+                    .map(|entry| {
+                        let mut entry = children(&entry);
+                        match (entry.next().and_then(|k| k.get()), entry.next().and_then(|v| v.get())) {
+                            (Some(key), Some(value)) => Some((key, value)),
+                            _ => None,
+                        }
+                    })
+                    .collect()
+            }
+        }
+    }
+}
+
+impl_map!(HashMap, Eq, Hash);
+impl_map!(BTreeMap, Ord);
+
+macro_rules! impl_tuple {
+    ($($name:ident $n:tt),+) => {
+        impl<$($name),+> StaticVariantType for ($($name,)+)
+        where $($name: StaticVariantType,)+ {
+            fn static_variant_type() -> Cow<'static, VariantTy> {
+                container_type(&["(", $($name::static_variant_type().to_str(),)+ ")"])
+            }
+        }
+
+        impl<$($name),+> ToVariant for ($($name,)+)
+        where $($name: ToVariant,)+ {
+            fn to_variant(&self) -> Variant {
+                Variant::new_tuple(&[$(self.$n.to_variant()),+])
+            }
+        }
+
+        impl<$($name),+> FromVariant for ($($name,)+)
+        where $($name: FromVariant,)+ {
+            fn from_variant(variant: &Variant) -> Option<Self> {
+                if !variant.is::<Self>() {
+                    return None;
+                }
+
+                let mut children = children(variant);
+                Some(($(
+                    match children.next().and_then(|child| child.get::<$name>()) {
+                        Some(field) => field,
+                        None => return None,
+                    },
+                )+))
+            }
+        }
+    }
+}
+
+impl_tuple!(A 0);
+impl_tuple!(A 0, B 1);
+impl_tuple!(A 0, B 1, C 2);
+impl_tuple!(A 0, B 1, C 2, D 3);
+impl_tuple!(A 0, B 1, C 2, D 3, E 4);
+impl_tuple!(A 0, B 1, C 2, D 3, E 4, F 5);
+impl_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6);
+impl_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7);
+impl_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8);
+impl_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9);
+impl_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10);
+impl_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10, L 11);
+
+// Builds the type of a container from the given parts of its type string
+fn container_type(parts: &[&str]) -> Cow<'static, VariantTy> {
+    VariantType::new(&parts.concat())
+        .expect("Invalid container type")
+        .into()
+}
+
+fn children<'a>(variant: &'a Variant) -> impl Iterator<Item = Variant> + 'a {
+    (0..variant.n_children()).map(move |i| variant.child_value(i))
+}
+
 impl<T: ToVariant> From<T> for Variant {
     fn from(value: T) -> Variant {
         value.to_variant()
@@ -327,7 +868,7 @@ impl<T: ToVariant> From<T> for Variant {
 
 #[cfg(test)]
 mod tests {
-    use std::collections::HashSet;
+    use std::collections::{BTreeMap, HashMap, HashSet};
     use super::*;
 
     macro_rules! unsigned {
@@ -382,6 +923,172 @@ mod tests {
         assert_eq!(v.get(), Some(s.clone()));
     }
 
+    #[test]
+    fn test_option() {
+        let v = Some(5i32).to_variant();
+        assert_eq!(v.type_().to_str(), "mi");
+        assert_eq!(v.get::<Option<i32>>(), Some(Some(5)));
+
+        let v = None::<String>.to_variant();
+        assert_eq!(v.type_().to_str(), "ms");
+        assert_eq!(v.get::<Option<String>>(), Some(None));
+        assert_eq!(v.get::<Option<i32>>(), None);
+    }
+
+    #[test]
+    fn test_array() {
+        let v = vec![1u32, 2, 3].to_variant();
+        assert_eq!(v.type_().to_str(), "au");
+        assert_eq!(v.get::<Vec<u32>>(), Some(vec![1, 2, 3]));
+        assert_eq!(v.get::<Vec<i32>>(), None);
+
+        let v = ["a", "b"][..].to_variant();
+        assert_eq!(v.get::<Vec<String>>(), Some(vec![String::from("a"), String::from("b")]));
+
+        let v = Vec::<Vec<bool>>::new().to_variant();
+        assert_eq!(v.type_().to_str(), "aab");
+        assert_eq!(v.get::<Vec<Vec<bool>>>(), Some(Vec::new()));
+    }
+
+    #[test]
+    fn test_dict() {
+        let mut map = HashMap::new();
+        map.insert(String::from("one"), 1i64);
+        map.insert(String::from("two"), 2i64);
+
+        let v = map.to_variant();
+        assert_eq!(v.type_().to_str(), "a{sx}");
+        assert_eq!(v.get::<HashMap<String, i64>>(), Some(map));
+
+        let mut map = BTreeMap::new();
+        map.insert(1u8, vec![Some(true), None]);
+
+        let v = map.to_variant();
+        assert_eq!(v.type_().to_str(), "a{yamb}");
+        assert_eq!(v.get::<BTreeMap<u8, Vec<Option<bool>>>>(), Some(map));
+    }
+
+    #[test]
+    fn test_tuple() {
+        let v = (1u8, String::from("two"), 3.0f64).to_variant();
+        assert_eq!(v.type_().to_str(), "(ysd)");
+        assert_eq!(v.get::<(u8, String, f64)>(), Some((1, String::from("two"), 3.0)));
+        assert_eq!(v.get::<(u8, String)>(), None);
+
+        let v = (true,).to_variant();
+        assert_eq!(v.type_().to_str(), "(b)");
+        assert_eq!(v.get::<(bool,)>(), Some((true,)));
+    }
+
+    #[test]
+    fn test_boxed_variant() {
+        let v = Variant::new_variant(&5u8.to_variant());
+        assert_eq!(v.type_().to_str(), "v");
+        assert_eq!(v.as_variant().and_then(|v| v.get::<u8>()), Some(5));
+        assert_eq!(5u8.to_variant().as_variant(), None);
+    }
+
+    #[test]
+    fn test_print_parse() {
+        let v = (1u8, vec![String::from("a")], Some(-2i64)).to_variant();
+        assert_eq!(v.print(false), v.to_string());
+        assert!(v.print(true).starts_with("(byte 0x01,"));
+
+        assert_eq!(Variant::parse(None, &v.print(true)).unwrap(), v);
+        assert_eq!(Variant::parse(Some(v.type_()), &v.print(false)).unwrap(), v);
+        assert_eq!("uint32 7".parse::<Variant>().unwrap().get::<u32>(), Some(7));
+
+        let err = Variant::parse(Some(VariantTy::new("s").unwrap()), "7").unwrap_err();
+        assert!(err.kind::<VariantParseError>().is_some());
+        // The message shows the location of the error
+        assert!(err.to_string().lines().count() > 1);
+
+        let err = "(1, 2".parse::<Variant>().unwrap_err();
+        assert!(err.kind::<VariantParseError>().is_some());
+
+        let err = Variant::parse(None, "1\0").unwrap_err();
+        assert!(err.kind::<VariantParseError>().is_some());
+    }
+
+    #[test]
+    fn test_serialized_data() {
+        let v = (7u32, String::from("seven"), vec![true, false]).to_variant();
+        let bytes = v.data_as_bytes();
+        let copy = Variant::from_bytes::<(u32, String, Vec<bool>)>(&bytes);
+        assert_eq!(copy, v);
+        assert_eq!(copy.data_as_bytes(), bytes);
+
+        let copy = Variant::from_bytes_with_type(&bytes, v.type_());
+        assert_eq!(copy.get::<(u32, String, Vec<bool>)>().unwrap().1, "seven");
+
+        let copy = unsafe { Variant::from_data_trusted::<(u32, String, Vec<bool>), _>(bytes.to_vec()) };
+        assert_eq!(copy, v);
+
+        assert_eq!(Variant::from_data::<String, _>(b"abc\0".to_vec()).get_str(), Some("abc"));
+        // Strings without terminating nul are invalid
+        assert_eq!(Variant::from_data::<String, _>(b"abc".to_vec()).get_str(), Some(""));
+    }
+
+    #[test]
+    fn test_normal_form() {
+        let v = (String::from("a"), 5u32).to_variant();
+        assert!(v.is_normal_form());
+        assert_eq!(v.normal_form(), v);
+
+        let invalid = Variant::from_data::<(String, u32), _>(b"a\0\0\0\0\0\0\0\x05".to_vec());
+        assert!(!invalid.is_normal_form());
+        let normal = invalid.normal_form();
+        assert!(normal.is_normal_form());
+        assert_eq!(normal.type_(), invalid.type_());
+    }
+
+    #[test]
+    fn test_byteswap() {
+        let v = 0x0102_0304u32.to_variant();
+        assert_eq!(v.byteswap().get::<u32>(), Some(0x0403_0201));
+        assert_eq!(v.byteswap().byteswap(), v);
+
+        let v = (1u8, String::from("a"), vec![0x0102i16]).to_variant();
+        assert_eq!(v.byteswap().get::<(u8, String, Vec<i16>)>(), Some((1, String::from("a"), vec![0x0201])));
+    }
+
+    #[test]
+    fn test_children() {
+        let v = (1u8, String::from("a"), vec![2i32, 3]).to_variant();
+        assert_eq!(v.n_children(), 3);
+        assert_eq!(v.child_value(0).get::<u8>(), Some(1));
+        assert_eq!(v.child_value(1).get_str(), Some("a"));
+        assert_eq!(v.child_value(2).n_children(), 2);
+        assert_eq!(v.child_value(2).child_value(1).get::<i32>(), Some(3));
+        assert_eq!(5u8.to_variant().n_children(), 0);
+        assert_eq!(None::<u8>.to_variant().n_children(), 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_child_value_out_of_bounds() {
+        (1u8, 2u8).to_variant().child_value(2);
+    }
+
+    #[test]
+    fn test_fixed_array() {
+        let v = vec![1u8, 2, 3].to_variant();
+        assert_eq!(v.fixed_array::<u8>(), Some(&[1u8, 2, 3][..]));
+        assert_eq!(v.fixed_array::<i16>(), None);
+
+        let data = (0..1000i64).collect::<Vec<_>>();
+        let v = data.to_variant();
+        assert_eq!(v.fixed_array::<i64>(), Some(&data[..]));
+
+        let v = Vec::<u32>::new().to_variant();
+        assert_eq!(v.fixed_array::<u32>(), Some(&[][..]));
+        assert_eq!(5u32.to_variant().fixed_array::<u32>(), None);
+
+        // Children of serialized arrays borrow the data of their parent
+        let v = (String::from("a"), vec![1.5f64, 2.5]).to_variant();
+        assert_eq!(v.child_value(1).fixed_array::<f64>(), Some(&[1.5, 2.5][..]));
+    }
+
     #[test]
     fn test_eq() {
         let v1 = Variant::from("this is a test");
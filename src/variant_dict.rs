@@ -0,0 +1,201 @@
+// Copyright 2019, The Gtk-rs Project Developers.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+// Licensed under the MIT license, see the LICENSE file or <http://opensource.org/licenses/MIT>
+
+use glib_sys;
+use std::borrow::Cow;
+use std::ptr;
+use translate::*;
+use variant::{FromVariant, StaticVariantType, ToVariant, Variant};
+use BoolError;
+use VariantTy;
+
+glib_wrapper! {
+    /// A mutable dictionary of variants of type `a{sv}`.
+    ///
+    /// This is the usual way of building and consuming option dictionaries, which are passed to
+    /// many D-Bus and GNOME APIs.
+    ///
+    /// ```
+    /// use glib::prelude::*;
+    /// use glib::VariantDict;
+    ///
+    /// let dict = VariantDict::new(None);
+    /// dict.insert("width", &640i32);
+    /// dict.insert("title", &"Main");
+    ///
+    /// let options = dict.to_variant();
+    /// assert_eq!(options.type_().to_str(), "a{sv}");
+    ///
+    /// let dict = VariantDict::new(Some(&options));
+    /// assert_eq!(dict.lookup::<i32>("width").unwrap(), Some(640));
+    /// assert_eq!(dict.lookup::<i32>("height").unwrap(), None);
+    /// assert!(dict.lookup::<String>("width").is_err());
+    /// ```
+    pub struct VariantDict(Shared<glib_sys::GVariantDict>);
+
+    match fn {
+        ref => |ptr| glib_sys::g_variant_dict_ref(ptr),
+        unref => |ptr| glib_sys::g_variant_dict_unref(ptr),
+        get_type => || glib_sys::g_variant_dict_get_type(),
+    }
+}
+
+impl VariantDict {
+    /// Creates a new dictionary, optionally with the entries of `from_asv`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `from_asv` is not of type `a{sv}`.
+    pub fn new(from_asv: Option<&Variant>) -> VariantDict {
+        if let Some(from_asv) = from_asv {
+            assert!(from_asv.is::<VariantDict>(), "Variant dictionaries must be of type a{{sv}}");
+        }
+
+        unsafe { from_glib_full(glib_sys::g_variant_dict_new(from_asv.to_glib_none().0)) }
+    }
+
+    /// Returns whether there is an entry for `key`.
+    pub fn contains(&self, key: &str) -> bool {
+        unsafe {
+            from_glib(glib_sys::g_variant_dict_contains(self.to_glib_none().0, key.to_glib_none().0))
+        }
+    }
+
+    /// Looks up the value of `key`.
+    ///
+    /// Returns `Ok(None)` if there is no entry for `key`, and an error if the value is not of
+    /// type `T`.
+    pub fn lookup<T: FromVariant>(&self, key: &str) -> Result<Option<T>, BoolError> {
+        match self.lookup_value(key, None) {
+            Some(value) => value
+                .get::<T>()
+                .map(Some)
+                .ok_or_else(|| glib_bool_error!("Dictionary value has a different type")),
+            None => Ok(None),
+        }
+    }
+
+    /// Looks up the value of `key`.
+    ///
+    /// Returns `None` if there is no entry for `key`, or if `expected_type` is given and the
+    /// value is not of that type.
+    pub fn lookup_value(&self, key: &str, expected_type: Option<&VariantTy>) -> Option<Variant> {
+        unsafe {
+            from_glib_full(glib_sys::g_variant_dict_lookup_value(
+                self.to_glib_none().0,
+                key.to_glib_none().0,
+                expected_type.map_or(ptr::null(), |t| t.as_ptr()),
+            ))
+        }
+    }
+
+    /// Inserts `value` under `key`, replacing any previous value.
+    pub fn insert<T: ToVariant + ?Sized>(&self, key: &str, value: &T) {
+        self.insert_value(key, &value.to_variant())
+    }
+
+    /// Inserts the variant `value` under `key`, replacing any previous value.
+    pub fn insert_value(&self, key: &str, value: &Variant) {
+        unsafe {
+            glib_sys::g_variant_dict_insert_value(
+                self.to_glib_none().0,
+                key.to_glib_none().0,
+                value.to_glib_none().0,
+            )
+        }
+    }
+
+    /// Removes the entry for `key`.
+    ///
+    /// Returns `false` if there was no entry for `key`.
+    pub fn remove(&self, key: &str) -> bool {
+        unsafe {
+            from_glib(glib_sys::g_variant_dict_remove(self.to_glib_none().0, key.to_glib_none().0))
+        }
+    }
+
+    /// Returns the entries of the dictionary as a variant of type `a{sv}` and removes them from
+    /// the dictionary.
+    ///
+    /// Use `to_variant()` to keep the entries.
+    pub fn end(&self) -> Variant {
+        self.end_and_reinit(false)
+    }
+
+    // Ending a dictionary leaves it invalid until it is initialized again, optionally with the
+    // entries it had before
+    fn end_and_reinit(&self, keep_entries: bool) -> Variant {
+        unsafe {
+            let variant: Variant = from_glib_none(glib_sys::g_variant_dict_end(self.to_glib_none().0));
+            let entries = if keep_entries { Some(&variant) } else { None };
+            glib_sys::g_variant_dict_init(self.to_glib_none().0, entries.to_glib_none().0);
+            variant
+        }
+    }
+}
+
+impl Default for VariantDict {
+    fn default() -> VariantDict {
+        VariantDict::new(None)
+    }
+}
+
+impl StaticVariantType for VariantDict {
+    fn static_variant_type() -> Cow<'static, VariantTy> {
+        unsafe { VariantTy::from_str_unchecked("a{sv}").into() }
+    }
+}
+
+impl ToVariant for VariantDict {
+    fn to_variant(&self) -> Variant {
+        // There is no way to get the entries without ending the dictionary, so they are added
+        // back afterwards
+        self.end_and_reinit(true)
+    }
+}
+
+impl FromVariant for VariantDict {
+    fn from_variant(variant: &Variant) -> Option<Self> {
+        if variant.is::<Self>() {
+            Some(VariantDict::new(Some(variant)))
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_variant_dict() {
+        let dict = VariantDict::default();
+        assert!(!dict.contains("a"));
+
+        dict.insert("a", &1u32);
+        dict.insert("b", &vec![String::from("x"), String::from("y")]);
+        assert!(dict.contains("a"));
+        assert_eq!(dict.lookup::<u32>("a").unwrap(), Some(1));
+        assert!(dict.lookup::<i32>("a").is_err());
+        assert_eq!(dict.lookup::<Vec<String>>("b").unwrap().unwrap().len(), 2);
+        assert!(dict.lookup_value("a", Some(VariantTy::new("s").unwrap())).is_none());
+
+        let variant = dict.to_variant();
+        assert_eq!(variant.type_().to_str(), "a{sv}");
+        // Still contains the entries after converting
+        assert!(dict.contains("b"));
+
+        assert!(dict.remove("a"));
+        assert!(!dict.remove("a"));
+        assert_eq!(dict.lookup::<u32>("a").unwrap(), None);
+
+        let copy = variant.get::<VariantDict>().unwrap();
+        assert_eq!(copy.lookup::<u32>("a").unwrap(), Some(1));
+
+        let ended = copy.end();
+        assert_eq!(ended, variant);
+        assert!(!copy.contains("a"));
+    }
+}
@@ -0,0 +1,231 @@
+// Copyright 2019, The Gtk-rs Project Developers.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+// Licensed under the MIT license, see the LICENSE file or <http://opensource.org/licenses/MIT>
+
+use std::iter::{DoubleEndedIterator, ExactSizeIterator, FusedIterator};
+use std::marker::PhantomData;
+use variant::{FromVariant, Variant};
+
+/// Iterator over the children of a container variant.
+///
+/// See [`Variant::iter()`].
+///
+/// [`Variant::iter()`]: struct.Variant.html#method.iter
+#[derive(Debug, Clone)]
+pub struct VariantIter {
+    variant: Variant,
+    head: usize,
+    tail: usize,
+}
+
+impl VariantIter {
+    pub(crate) fn new(variant: Variant) -> VariantIter {
+        let tail = variant.n_children();
+        VariantIter { variant, head: 0, tail }
+    }
+
+    fn child(&self, index: usize) -> Variant {
+        self.variant.child_value(index)
+    }
+}
+
+impl Iterator for VariantIter {
+    type Item = Variant;
+
+    fn next(&mut self) -> Option<Variant> {
+        if self.head == self.tail {
+            return None;
+        }
+
+        let child = self.child(self.head);
+        self.head += 1;
+        Some(child)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let size = self.tail - self.head;
+        (size, Some(size))
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Variant> {
+        if n >= self.tail - self.head {
+            self.head = self.tail;
+            return None;
+        }
+
+        self.head += n;
+        self.next()
+    }
+}
+
+impl DoubleEndedIterator for VariantIter {
+    fn next_back(&mut self) -> Option<Variant> {
+        if self.head == self.tail {
+            return None;
+        }
+
+        self.tail -= 1;
+        Some(self.child(self.tail))
+    }
+}
+
+impl ExactSizeIterator for VariantIter {}
+
+impl FusedIterator for VariantIter {}
+
+/// Iterator over the elements of an array variant as values of type `T`.
+///
+/// Elements that can't be converted to `T` although their type matches, e.g. strings that are
+/// not a valid name of an enum, are returned as `Err` with the element.
+///
+/// See [`Variant::iter_as()`].
+///
+/// [`Variant::iter_as()`]: struct.Variant.html#method.iter_as
+#[derive(Debug, Clone)]
+pub struct VariantTypedIter<T> {
+    inner: VariantIter,
+    dict_entries: bool,
+    phantom: PhantomData<fn() -> T>,
+}
+
+impl<T: FromVariant> VariantTypedIter<T> {
+    pub(crate) fn new(variant: Variant) -> Option<VariantTypedIter<T>> {
+        let element_type = {
+            let type_ = variant.type_().to_str();
+            if !type_.starts_with('a') {
+                return None;
+            }
+            type_[1..].to_owned()
+        };
+
+        // Dictionary entries are returned as tuples of key and value
+        let dict_entries = element_type.starts_with('{');
+        let expected_type = if dict_entries {
+            format!("({})", &element_type[1..element_type.len() - 1])
+        } else {
+            element_type
+        };
+        if T::static_variant_type().to_str() != expected_type {
+            return None;
+        }
+
+        Some(VariantTypedIter {
+            inner: VariantIter::new(variant),
+            dict_entries,
+            phantom: PhantomData,
+        })
+    }
+
+    fn convert(&self, child: Variant) -> Result<T, Variant> {
+        let value = if self.dict_entries {
+            Variant::new_tuple(&VariantIter::new(child).collect::<Vec<_>>())
+        } else {
+            child
+        };
+
+        value.get::<T>().ok_or(value)
+    }
+}
+
+impl<T: FromVariant> Iterator for VariantTypedIter<T> {
+    type Item = Result<T, Variant>;
+
+    fn next(&mut self) -> Option<Result<T, Variant>> {
+        self.inner.next().map(|child| self.convert(child))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Result<T, Variant>> {
+        self.inner.nth(n).map(|child| self.convert(child))
+    }
+}
+
+impl<T: FromVariant> DoubleEndedIterator for VariantTypedIter<T> {
+    fn next_back(&mut self) -> Option<Result<T, Variant>> {
+        self.inner.next_back().map(|child| self.convert(child))
+    }
+}
+
+impl<T: FromVariant> ExactSizeIterator for VariantTypedIter<T> {}
+
+impl<T: FromVariant> FusedIterator for VariantTypedIter<T> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::borrow::Cow;
+    use std::collections::HashMap;
+    use variant::{StaticVariantType, ToVariant};
+    use VariantTy;
+
+    #[test]
+    fn test_iter() {
+        let v = vec![1i32, 2, 3].to_variant();
+        let children = v.iter().map(|c| c.get::<i32>().unwrap()).collect::<Vec<_>>();
+        assert_eq!(children, vec![1, 2, 3]);
+        assert_eq!(v.iter().len(), 3);
+        assert_eq!(v.iter().rev().next().unwrap().get::<i32>(), Some(3));
+        assert_eq!(v.iter().nth(1).unwrap().get::<i32>(), Some(2));
+        assert!(v.iter().nth(3).is_none());
+
+        let v = (1u8, String::from("a")).to_variant();
+        let mut iter = v.iter();
+        assert_eq!(iter.next().unwrap().get::<u8>(), Some(1));
+        assert_eq!(iter.next().unwrap().get_str(), Some("a"));
+        assert!(iter.next().is_none());
+
+        assert_eq!(5i32.to_variant().iter().count(), 0);
+    }
+
+    #[test]
+    fn test_typed_iter() {
+        let v = vec![String::from("a"), String::from("b")].to_variant();
+        assert_eq!(
+            v.iter_as::<String>().unwrap().collect::<Result<Vec<_>, _>>(),
+            Ok(vec![String::from("a"), String::from("b")])
+        );
+        assert!(v.iter_as::<i32>().is_none());
+        assert!((1, 2).to_variant().iter_as::<i32>().is_none());
+
+        let mut map = HashMap::new();
+        map.insert(String::from("one"), 1u32);
+        map.insert(String::from("two"), 2u32);
+        let v = map.to_variant();
+        let mut entries = v
+            .iter_as::<(String, u32)>()
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        entries.sort();
+        assert_eq!(entries, vec![(String::from("one"), 1), (String::from("two"), 2)]);
+    }
+
+    // Strings that are only valid if they are a known name
+    #[derive(Debug, PartialEq)]
+    struct Name(String);
+
+    impl StaticVariantType for Name {
+        fn static_variant_type() -> Cow<'static, VariantTy> {
+            String::static_variant_type()
+        }
+    }
+
+    impl FromVariant for Name {
+        fn from_variant(variant: &Variant) -> Option<Name> {
+            variant.get_str().filter(|s| *s == "known").map(|s| Name(s.to_owned()))
+        }
+    }
+
+    #[test]
+    fn test_typed_iter_invalid_element() {
+        let v = vec!["known", "unknown"].to_variant();
+        let mut iter = v.iter_as::<Name>().unwrap();
+        assert_eq!(iter.next(), Some(Ok(Name(String::from("known")))));
+        let err = iter.next().unwrap().unwrap_err();
+        assert_eq!(err.get_str(), Some("unknown"));
+        assert!(iter.next().is_none());
+    }
+}
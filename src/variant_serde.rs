@@ -0,0 +1,812 @@
+// Copyright 2019, The Gtk-rs Project Developers.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+// Licensed under the MIT license, see the LICENSE file or <http://opensource.org/licenses/MIT>
+
+//! Conversion between `serde` data structures and `Variant`.
+//!
+//! Requires the `serde` feature. The serde data model is mapped to GVariant types as follows:
+//!
+//! - `bool`, `u8`, `i16`, `u16`, `i32`, `u32`, `i64`, `u64` and `f64` to their basic types, `i8`
+//!   to `n` and `f32` to `d`
+//! - `char` and strings to `s`, byte arrays to `ay`
+//! - options to `mv`, so the type does not depend on whether there is a value
+//! - unit and unit structs to `()`, tuples and tuple structs to tuples
+//! - sequences to arrays, or to `av` if they are empty or their elements are of different types
+//! - maps to dictionaries, with boxed values if the values are of different types. All keys have
+//!   to be of the same basic type.
+//! - structs to `a{sv}` dictionaries of their fields
+//! - enum variants like with [`derive(Variant)`]: unit variants to their name as `s`, all
+//!   others to a `(sv)` tuple of their name and boxed value
+//!
+//! The deserializer accepts these types, looks through boxed variants and additionally accepts
+//! structs from tuples of their fields.
+//!
+//! ```ignore
+//! #[derive(Serialize, Deserialize)]
+//! struct Window {
+//!     title: String,
+//!     size: (i32, i32),
+//! }
+//!
+//! let variant = glib::variant_serde::to_variant(&window)?;
+//! assert_eq!(variant.type_().to_str(), "a{sv}");
+//! let window: Window = glib::variant_serde::from_variant(&variant)?;
+//! ```
+//!
+//! [`derive(Variant)`]: ../derive.Variant.html
+
+use glib_sys;
+use serde::de::{self, DeserializeOwned, DeserializeSeed, Visitor};
+use serde::ser::{self, Serialize};
+use std::error;
+use std::fmt;
+use translate::*;
+use variant::{FromVariant, ToVariant, Variant};
+use Bytes;
+use VariantDict;
+use VariantIter;
+use VariantTy;
+use VariantType;
+
+/// Converts `value` into a `Variant`.
+pub fn to_variant<T: ?Sized + Serialize>(value: &T) -> Result<Variant, Error> {
+    value.serialize(Serializer)
+}
+
+/// Converts `variant` into a value of type `T`.
+pub fn from_variant<T: DeserializeOwned>(variant: &Variant) -> Result<T, Error> {
+    T::deserialize(Deserializer::new(variant.clone()))
+}
+
+/// Error of serializing to or deserializing from a `Variant`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Error(String);
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl error::Error for Error {
+    fn description(&self) -> &str {
+        &self.0
+    }
+}
+
+impl ser::Error for Error {
+    fn custom<T: fmt::Display>(msg: T) -> Error {
+        Error(msg.to_string())
+    }
+}
+
+impl de::Error for Error {
+    fn custom<T: fmt::Display>(msg: T) -> Error {
+        Error(msg.to_string())
+    }
+}
+
+// Returns the common type of `children`, boxing them if they don't have one
+fn unify(children: Vec<Variant>) -> (VariantType, Vec<Variant>) {
+    let common_type = match children.first() {
+        Some(first) if children.iter().all(|child| child.type_() == first.type_()) => {
+            Some(first.type_().to_owned())
+        }
+        _ => None,
+    };
+
+    match common_type {
+        Some(common_type) => (common_type, children),
+        None => (
+            VariantTy::variant().to_owned(),
+            children.iter().map(Variant::new_variant).collect(),
+        ),
+    }
+}
+
+fn array(children: Vec<Variant>) -> Variant {
+    let (element_type, children) = unify(children);
+    Variant::new_array(&element_type, &children)
+}
+
+fn dictionary(entries: Vec<(Variant, Variant)>) -> Result<Variant, Error> {
+    let key_type = match entries.first() {
+        Some(&(ref key, _)) => key.type_().to_owned(),
+        None => return Ok(VariantDict::new(None).end()),
+    };
+    if !key_type.is_basic() || entries.iter().any(|&(ref key, _)| key.type_() != key_type) {
+        return Err(Error(String::from("Map keys must all be of the same basic type")));
+    }
+
+    let (keys, values): (Vec<_>, Vec<_>) = entries.into_iter().unzip();
+    let (value_type, values) = unify(values);
+    let entries = keys
+        .iter()
+        .zip(values.iter())
+        .map(|(key, value)| Variant::new_dict_entry(key, value))
+        .collect::<Vec<_>>();
+
+    Ok(Variant::new_array(&VariantType::new_dict_entry(&key_type, &value_type), &entries))
+}
+
+fn maybe(value: Option<&Variant>) -> Variant {
+    let value = value.map(Variant::new_variant);
+    unsafe {
+        from_glib_none(glib_sys::g_variant_new_maybe(
+            VariantTy::variant().as_ptr(),
+            value.to_glib_none().0,
+        ))
+    }
+}
+
+fn tagged(name: &str, value: &Variant) -> Variant {
+    Variant::new_tuple(&[name.to_variant(), Variant::new_variant(value)])
+}
+
+fn fields(fields: Vec<(&'static str, Variant)>) -> Variant {
+    let dict = VariantDict::new(None);
+    for (name, value) in fields {
+        dict.insert_value(name, &value);
+    }
+    dict.end()
+}
+
+/// Serializer producing `Variant`s.
+///
+/// See the [module documentation](index.html) for how values are mapped.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Serializer;
+
+impl ser::Serializer for Serializer {
+    type Ok = Variant;
+    type Error = Error;
+    type SerializeSeq = SerializeSeq;
+    type SerializeTuple = SerializeTuple;
+    type SerializeTupleStruct = SerializeTuple;
+    type SerializeTupleVariant = SerializeTupleVariant;
+    type SerializeMap = SerializeMap;
+    type SerializeStruct = SerializeStruct;
+    type SerializeStructVariant = SerializeStructVariant;
+
+    fn serialize_bool(self, v: bool) -> Result<Variant, Error> {
+        Ok(v.to_variant())
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<Variant, Error> {
+        Ok(i16::from(v).to_variant())
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<Variant, Error> {
+        Ok(v.to_variant())
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<Variant, Error> {
+        Ok(v.to_variant())
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<Variant, Error> {
+        Ok(v.to_variant())
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<Variant, Error> {
+        Ok(v.to_variant())
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<Variant, Error> {
+        Ok(v.to_variant())
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<Variant, Error> {
+        Ok(v.to_variant())
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<Variant, Error> {
+        Ok(v.to_variant())
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<Variant, Error> {
+        Ok(f64::from(v).to_variant())
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<Variant, Error> {
+        Ok(v.to_variant())
+    }
+
+    fn serialize_char(self, v: char) -> Result<Variant, Error> {
+        Ok(v.to_string().to_variant())
+    }
+
+    fn serialize_str(self, v: &str) -> Result<Variant, Error> {
+        Ok(v.to_variant())
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<Variant, Error> {
+        Ok(Variant::from_bytes::<Vec<u8>>(&Bytes::from(v)))
+    }
+
+    fn serialize_none(self) -> Result<Variant, Error> {
+        Ok(maybe(None))
+    }
+
+    fn serialize_some<T: ?Sized + Serialize>(self, value: &T) -> Result<Variant, Error> {
+        Ok(maybe(Some(&value.serialize(Serializer)?)))
+    }
+
+    fn serialize_unit(self) -> Result<Variant, Error> {
+        Ok(().to_variant())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Variant, Error> {
+        Ok(().to_variant())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<Variant, Error> {
+        Ok(variant.to_variant())
+    }
+
+    fn serialize_newtype_struct<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<Variant, Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<Variant, Error> {
+        Ok(tagged(variant, &value.serialize(Serializer)?))
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<SerializeSeq, Error> {
+        Ok(SerializeSeq(Vec::with_capacity(len.unwrap_or(0))))
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<SerializeTuple, Error> {
+        Ok(SerializeTuple(Vec::with_capacity(len)))
+    }
+
+    fn serialize_tuple_struct(self, _name: &'static str, len: usize) -> Result<SerializeTuple, Error> {
+        Ok(SerializeTuple(Vec::with_capacity(len)))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<SerializeTupleVariant, Error> {
+        Ok(SerializeTupleVariant(variant, Vec::with_capacity(len)))
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<SerializeMap, Error> {
+        Ok(SerializeMap {
+            entries: Vec::with_capacity(len.unwrap_or(0)),
+            key: None,
+        })
+    }
+
+    fn serialize_struct(self, _name: &'static str, len: usize) -> Result<SerializeStruct, Error> {
+        Ok(SerializeStruct(Vec::with_capacity(len)))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<SerializeStructVariant, Error> {
+        Ok(SerializeStructVariant(variant, Vec::with_capacity(len)))
+    }
+}
+
+#[doc(hidden)]
+#[derive(Debug)]
+pub struct SerializeSeq(Vec<Variant>);
+
+impl ser::SerializeSeq for SerializeSeq {
+    type Ok = Variant;
+    type Error = Error;
+
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Error> {
+        self.0.push(value.serialize(Serializer)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Variant, Error> {
+        Ok(array(self.0))
+    }
+}
+
+#[doc(hidden)]
+#[derive(Debug)]
+pub struct SerializeTuple(Vec<Variant>);
+
+impl ser::SerializeTuple for SerializeTuple {
+    type Ok = Variant;
+    type Error = Error;
+
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Error> {
+        self.0.push(value.serialize(Serializer)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Variant, Error> {
+        Ok(Variant::new_tuple(&self.0))
+    }
+}
+
+impl ser::SerializeTupleStruct for SerializeTuple {
+    type Ok = Variant;
+    type Error = Error;
+
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Error> {
+        ser::SerializeTuple::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<Variant, Error> {
+        ser::SerializeTuple::end(self)
+    }
+}
+
+#[doc(hidden)]
+#[derive(Debug)]
+pub struct SerializeTupleVariant(&'static str, Vec<Variant>);
+
+impl ser::SerializeTupleVariant for SerializeTupleVariant {
+    type Ok = Variant;
+    type Error = Error;
+
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Error> {
+        self.1.push(value.serialize(Serializer)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Variant, Error> {
+        Ok(tagged(self.0, &Variant::new_tuple(&self.1)))
+    }
+}
+
+#[doc(hidden)]
+#[derive(Debug)]
+pub struct SerializeMap {
+    entries: Vec<(Variant, Variant)>,
+    key: Option<Variant>,
+}
+
+impl ser::SerializeMap for SerializeMap {
+    type Ok = Variant;
+    type Error = Error;
+
+    fn serialize_key<T: ?Sized + Serialize>(&mut self, key: &T) -> Result<(), Error> {
+        self.key = Some(key.serialize(Serializer)?);
+        Ok(())
+    }
+
+    fn serialize_value<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Error> {
+        let key = self
+            .key
+            .take()
+            .ok_or_else(|| Error(String::from("Map value serialized before its key")))?;
+        self.entries.push((key, value.serialize(Serializer)?));
+        Ok(())
+    }
+
+    fn end(self) -> Result<Variant, Error> {
+        dictionary(self.entries)
+    }
+}
+
+#[doc(hidden)]
+#[derive(Debug)]
+pub struct SerializeStruct(Vec<(&'static str, Variant)>);
+
+impl ser::SerializeStruct for SerializeStruct {
+    type Ok = Variant;
+    type Error = Error;
+
+    fn serialize_field<T: ?Sized + Serialize>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        self.0.push((key, value.serialize(Serializer)?));
+        Ok(())
+    }
+
+    fn end(self) -> Result<Variant, Error> {
+        Ok(fields(self.0))
+    }
+}
+
+#[doc(hidden)]
+#[derive(Debug)]
+pub struct SerializeStructVariant(&'static str, Vec<(&'static str, Variant)>);
+
+impl ser::SerializeStructVariant for SerializeStructVariant {
+    type Ok = Variant;
+    type Error = Error;
+
+    fn serialize_field<T: ?Sized + Serialize>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        self.1.push((key, value.serialize(Serializer)?));
+        Ok(())
+    }
+
+    fn end(self) -> Result<Variant, Error> {
+        Ok(tagged(self.0, &fields(self.1)))
+    }
+}
+
+/// Deserializer reading from a `Variant`.
+///
+/// See the [module documentation](index.html) for how values are mapped.
+#[derive(Debug, Clone)]
+pub struct Deserializer {
+    variant: Variant,
+}
+
+impl Deserializer {
+    pub fn new(variant: Variant) -> Deserializer {
+        let mut variant = variant;
+        while let Some(inner) = variant.as_variant() {
+            variant = inner;
+        }
+
+        Deserializer { variant }
+    }
+
+    fn get<T: FromVariant>(&self) -> Result<T, Error> {
+        self.variant
+            .get()
+            .ok_or_else(|| Error(format!("Unexpected variant type {}", self.variant.type_())))
+    }
+}
+
+impl<'de> de::Deserializer<'de> for Deserializer {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        let type_ = self.variant.type_();
+        match type_.to_str().as_bytes()[0] {
+            b'b' => visitor.visit_bool(self.get()?),
+            b'y' => visitor.visit_u8(self.get()?),
+            b'n' => visitor.visit_i16(self.get()?),
+            b'q' => visitor.visit_u16(self.get()?),
+            b'i' => visitor.visit_i32(self.get()?),
+            b'u' => visitor.visit_u32(self.get()?),
+            b'x' => visitor.visit_i64(self.get()?),
+            b't' => visitor.visit_u64(self.get()?),
+            b'd' => visitor.visit_f64(self.get()?),
+            b'h' => visitor.visit_i32(unsafe {
+                glib_sys::g_variant_get_handle(self.variant.to_glib_none().0)
+            }),
+            b's' | b'o' | b'g' => visitor.visit_str(self.variant.get_str().unwrap_or_default()),
+            b'm' => match self.variant.iter().next() {
+                Some(child) => visitor.visit_some(Deserializer::new(child)),
+                None => visitor.visit_none(),
+            },
+            b'a' if type_.element().is_dict_entry() => visitor.visit_map(MapAccess {
+                iter: self.variant.iter(),
+                value: None,
+            }),
+            b'a' => visitor.visit_seq(SeqAccess(self.variant.iter())),
+            b'(' if type_.items().is_empty() => visitor.visit_unit(),
+            b'(' | b'{' => visitor.visit_seq(SeqAccess(self.variant.iter())),
+            _ => Err(Error(format!("Unsupported variant type {}", type_))),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        if self.variant.type_().is_maybe() {
+            de::Deserializer::deserialize_any(self, visitor)
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    fn deserialize_bytes<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        if self.variant.is::<Vec<u8>>() {
+            // The serialized data of a byte array is the bytes themselves
+            visitor.visit_bytes(&self.variant.data_as_bytes())
+        } else {
+            de::Deserializer::deserialize_any(self, visitor)
+        }
+    }
+
+    fn deserialize_byte_buf<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        de::Deserializer::deserialize_bytes(self, visitor)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        if self.variant.get_str().is_some() {
+            visitor.visit_enum(EnumAccess {
+                tag: self.variant,
+                value: None,
+            })
+        } else if let Some((tag, value)) = self.variant.get::<(String, Variant)>() {
+            visitor.visit_enum(EnumAccess {
+                tag: tag.to_variant(),
+                value: Some(value),
+            })
+        } else {
+            Err(Error(format!(
+                "Expected enum of type s or (sv), got {}",
+                self.variant.type_()
+            )))
+        }
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_unit()
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 u8 u16 u32 u64 f32 f64 char str string
+        unit unit_struct seq tuple tuple_struct map struct identifier
+    }
+}
+
+struct SeqAccess(VariantIter);
+
+impl<'de> de::SeqAccess<'de> for SeqAccess {
+    type Error = Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(&mut self, seed: T) -> Result<Option<T::Value>, Error> {
+        match self.0.next() {
+            Some(child) => seed.deserialize(Deserializer::new(child)).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.0.len())
+    }
+}
+
+struct MapAccess {
+    iter: VariantIter,
+    value: Option<Variant>,
+}
+
+impl<'de> de::MapAccess<'de> for MapAccess {
+    type Error = Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>, Error> {
+        let mut entry = match self.iter.next() {
+            Some(entry) => entry.iter(),
+            None => return Ok(None),
+        };
+
+        match (entry.next(), entry.next()) {
+            (Some(key), Some(value)) => {
+                self.value = Some(value);
+                seed.deserialize(Deserializer::new(key)).map(Some)
+            }
+            _ => Err(Error(String::from("Invalid dictionary entry"))),
+        }
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, Error> {
+        match self.value.take() {
+            Some(value) => seed.deserialize(Deserializer::new(value)),
+            None => Err(Error(String::from("Dictionary value requested before its key"))),
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.iter.len())
+    }
+}
+
+struct EnumAccess {
+    tag: Variant,
+    value: Option<Variant>,
+}
+
+impl<'de> de::EnumAccess<'de> for EnumAccess {
+    type Error = Error;
+    type Variant = VariantAccess;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(self, seed: V) -> Result<(V::Value, VariantAccess), Error> {
+        let tag = seed.deserialize(Deserializer::new(self.tag))?;
+        Ok((tag, VariantAccess(self.value)))
+    }
+}
+
+struct VariantAccess(Option<Variant>);
+
+impl VariantAccess {
+    fn content(self) -> Result<Deserializer, Error> {
+        self.0
+            .map(Deserializer::new)
+            .ok_or_else(|| Error(String::from("Expected enum variant with a value")))
+    }
+}
+
+impl<'de> de::VariantAccess<'de> for VariantAccess {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<(), Error> {
+        match self.0 {
+            Some(value) => de::Deserialize::deserialize(Deserializer::new(value)),
+            None => Ok(()),
+        }
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value, Error> {
+        seed.deserialize(self.content()?)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, _len: usize, visitor: V) -> Result<V::Value, Error> {
+        de::Deserializer::deserialize_any(self.content()?, visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        de::Deserializer::deserialize_any(self.content()?, visitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Window {
+        title: String,
+        size: (i32, i32),
+        position: Option<(i32, i32)>,
+        tags: Vec<String>,
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    enum Shape {
+        Empty,
+        Circle(f64),
+        Rect(u32, u32),
+        Polygon { corners: Vec<(i16, i16)> },
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Meters(u64);
+
+    #[test]
+    fn test_basic() {
+        assert_eq!(to_variant(&5u8).unwrap().type_().to_str(), "y");
+        assert_eq!(to_variant(&-5i8).unwrap().get::<i16>(), Some(-5));
+        assert_eq!(to_variant(&1.5f32).unwrap().get::<f64>(), Some(1.5));
+        assert_eq!(to_variant(&'x').unwrap().get_str(), Some("x"));
+        assert_eq!(to_variant(&Meters(3)).unwrap().get::<u64>(), Some(3));
+
+        assert_eq!(from_variant::<i8>(&(-5i16).to_variant()).unwrap(), -5);
+        assert!(from_variant::<i8>(&1000i16.to_variant()).is_err());
+        assert_eq!(from_variant::<char>(&"x".to_variant()).unwrap(), 'x');
+        assert_eq!(from_variant::<Meters>(&3u64.to_variant()).unwrap(), Meters(3));
+        assert!(from_variant::<String>(&3u64.to_variant()).is_err());
+    }
+
+    #[test]
+    fn test_containers() {
+        let v = to_variant(&vec![1u32, 2, 3]).unwrap();
+        assert_eq!(v.type_().to_str(), "au");
+        assert_eq!(from_variant::<Vec<u32>>(&v).unwrap(), vec![1, 2, 3]);
+
+        let v = to_variant(&Vec::<u32>::new()).unwrap();
+        assert_eq!(v.type_().to_str(), "av");
+        assert_eq!(from_variant::<Vec<u32>>(&v).unwrap(), Vec::<u32>::new());
+
+        let v = to_variant(&vec![Some(1u32), None]).unwrap();
+        assert_eq!(v.type_().to_str(), "amv");
+        assert_eq!(from_variant::<Vec<Option<u32>>>(&v).unwrap(), vec![Some(1), None]);
+
+        let mut map = HashMap::new();
+        map.insert(String::from("one"), 1i64);
+        map.insert(String::from("two"), 2i64);
+        let v = to_variant(&map).unwrap();
+        assert_eq!(v.type_().to_str(), "a{sx}");
+        assert_eq!(from_variant::<HashMap<String, i64>>(&v).unwrap(), map);
+
+        let mut map = HashMap::new();
+        map.insert((1, 2), 3);
+        assert!(to_variant(&map).is_err());
+
+        let v = to_variant(&(1u8, String::from("a"), ())).unwrap();
+        assert_eq!(v.type_().to_str(), "(ys())");
+        assert_eq!(from_variant::<(u8, String, ())>(&v).unwrap(), (1, String::from("a"), ()));
+
+        // Existing variants of native types can be deserialized too
+        let v = vec![(1i32, true)].to_variant();
+        assert_eq!(from_variant::<Vec<(i32, bool)>>(&v).unwrap(), vec![(1, true)]);
+    }
+
+    #[test]
+    fn test_struct() {
+        let window = Window {
+            title: String::from("Main"),
+            size: (640, 480),
+            position: None,
+            tags: vec![String::from("primary")],
+        };
+
+        let v = to_variant(&window).unwrap();
+        assert_eq!(v.type_().to_str(), "a{sv}");
+        let dict = VariantDict::new(Some(&v));
+        assert_eq!(dict.lookup::<String>("title").unwrap(), Some(String::from("Main")));
+        assert_eq!(dict.lookup::<(i32, i32)>("size").unwrap(), Some((640, 480)));
+        assert_eq!(from_variant::<Window>(&v).unwrap(), window);
+
+        let v = (String::from("Main"), (640i32, 480i32), None::<(i32, i32)>, vec![String::from("primary")]).to_variant();
+        assert_eq!(from_variant::<Window>(&v).unwrap(), window);
+    }
+
+    #[test]
+    fn test_enum() {
+        let shapes = vec![
+            Shape::Empty,
+            Shape::Circle(1.5),
+            Shape::Rect(2, 3),
+            Shape::Polygon { corners: vec![(0, 0), (1, 0), (0, 1)] },
+        ];
+
+        let v = to_variant(&shapes[0]).unwrap();
+        assert_eq!(v.get_str(), Some("Empty"));
+        let v = to_variant(&shapes[1]).unwrap();
+        assert_eq!(v.type_().to_str(), "(sv)");
+
+        let v = to_variant(&shapes).unwrap();
+        assert_eq!(v.type_().to_str(), "av");
+        assert_eq!(from_variant::<Vec<Shape>>(&v).unwrap(), shapes);
+
+        // Unit variants as produced by `derive(Variant)`
+        let v = Variant::new_tuple(&["Empty".to_variant(), Variant::new_variant(&().to_variant())]);
+        assert_eq!(from_variant::<Shape>(&v).unwrap(), Shape::Empty);
+        assert!(from_variant::<Shape>(&"Hexagon".to_variant()).is_err());
+    }
+
+    #[test]
+    fn test_bytes() {
+        struct ByteSlice<'a>(&'a [u8]);
+
+        impl<'a> Serialize for ByteSlice<'a> {
+            fn serialize<S: ser::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_bytes(self.0)
+            }
+        }
+
+        let v = to_variant(&ByteSlice(b"abc")).unwrap();
+        assert_eq!(v.type_().to_str(), "ay");
+        assert_eq!(v.get::<Vec<u8>>(), Some(b"abc".to_vec()));
+    }
+}
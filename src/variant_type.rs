@@ -10,10 +10,12 @@ use std::fmt;
 use std::hash::{Hash, Hasher};
 use std::ops::Deref;
 use std::slice;
+use std::str;
 use translate::*;
 use types::StaticType;
 use types::Type;
 use value::{FromValueOptional, SetValue, SetValueOptional, Value};
+use BoolError;
 
 /// Describes `Variant` types.
 ///
@@ -37,6 +39,52 @@ impl VariantType {
     pub fn new(type_string: &str) -> Result<VariantType, ()> {
         VariantTy::new(type_string).map(ToOwned::to_owned)
     }
+
+    /// Creates the type of arrays with elements of type `element`.
+    pub fn new_array(element: &VariantTy) -> VariantType {
+        unsafe { VariantType::from_ptr_full(glib_sys::g_variant_type_new_array(element.as_ptr())) }
+    }
+
+    /// Creates the type of maybes with a child of type `child`.
+    pub fn new_maybe(child: &VariantTy) -> VariantType {
+        unsafe { VariantType::from_ptr_full(glib_sys::g_variant_type_new_maybe(child.as_ptr())) }
+    }
+
+    /// Creates the type of tuples with fields of the types `items`.
+    pub fn new_tuple(items: &[&VariantTy]) -> VariantType {
+        let ptrs = items.iter().map(|item| item.as_ptr()).collect::<Vec<_>>();
+        unsafe {
+            VariantType::from_ptr_full(glib_sys::g_variant_type_new_tuple(ptrs.as_ptr(), ptrs.len() as i32))
+        }
+    }
+
+    /// Creates the type of dictionary entries with keys of type `key` and values of type
+    /// `value`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `key` is not a basic type.
+    pub fn new_dict_entry(key: &VariantTy, value: &VariantTy) -> VariantType {
+        assert!(key.is_basic(), "Dictionary keys must be of a basic type");
+        unsafe {
+            VariantType::from_ptr_full(glib_sys::g_variant_type_new_dict_entry(key.as_ptr(), value.as_ptr()))
+        }
+    }
+
+    unsafe fn from_ptr_full(ptr: *mut glib_sys::GVariantType) -> VariantType {
+        VariantType {
+            ptr,
+            len: glib_sys::g_variant_type_get_string_length(ptr) as usize,
+        }
+    }
+}
+
+impl str::FromStr for VariantType {
+    type Err = BoolError;
+
+    fn from_str(type_string: &str) -> Result<VariantType, BoolError> {
+        VariantType::new(type_string).map_err(|()| glib_bool_error!("Invalid variant type string"))
+    }
 }
 
 unsafe impl Send for VariantType { }
@@ -174,6 +222,180 @@ impl VariantTy {
     pub fn to_str(&self) -> &str {
         &self.inner
     }
+
+    /// Returns `true` if the type is not an indefinite type like `*`, `a*` or `(?s)`.
+    ///
+    /// Only definite types can be the type of a `Variant`.
+    pub fn is_definite(&self) -> bool {
+        unsafe { from_glib(glib_sys::g_variant_type_is_definite(self.as_ptr())) }
+    }
+
+    /// Returns `true` if the type is a container type, including the indefinite `*`, `a*`
+    /// and similar types.
+    pub fn is_container(&self) -> bool {
+        unsafe { from_glib(glib_sys::g_variant_type_is_container(self.as_ptr())) }
+    }
+
+    /// Returns `true` if the type is a basic type, i.e. a number, boolean or string type, or
+    /// the indefinite basic type `?`.
+    ///
+    /// Only basic types can be the keys of dictionaries.
+    pub fn is_basic(&self) -> bool {
+        unsafe { from_glib(glib_sys::g_variant_type_is_basic(self.as_ptr())) }
+    }
+
+    pub fn is_maybe(&self) -> bool {
+        unsafe { from_glib(glib_sys::g_variant_type_is_maybe(self.as_ptr())) }
+    }
+
+    pub fn is_array(&self) -> bool {
+        unsafe { from_glib(glib_sys::g_variant_type_is_array(self.as_ptr())) }
+    }
+
+    pub fn is_tuple(&self) -> bool {
+        unsafe { from_glib(glib_sys::g_variant_type_is_tuple(self.as_ptr())) }
+    }
+
+    pub fn is_dict_entry(&self) -> bool {
+        unsafe { from_glib(glib_sys::g_variant_type_is_dict_entry(self.as_ptr())) }
+    }
+
+    /// Returns `true` if the type is the type of boxed variants, `v`.
+    pub fn is_variant(&self) -> bool {
+        unsafe { from_glib(glib_sys::g_variant_type_is_variant(self.as_ptr())) }
+    }
+
+    /// Returns `true` if all values of this type are also values of `supertype`, e.g. `ai`
+    /// is a subtype of `a*` and of itself.
+    pub fn is_subtype_of(&self, supertype: &VariantTy) -> bool {
+        unsafe {
+            from_glib(glib_sys::g_variant_type_is_subtype_of(self.as_ptr(), supertype.as_ptr()))
+        }
+    }
+
+    /// Returns the element type of an array or maybe type.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the type is not an array or maybe type.
+    pub fn element(&self) -> &VariantTy {
+        assert!(self.is_array() || self.is_maybe(), "{} is not an array or maybe type", self);
+        unsafe { VariantTy::from_ptr(glib_sys::g_variant_type_element(self.as_ptr())) }
+    }
+
+    /// Returns the key type of a dictionary entry type.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the type is not a dictionary entry type.
+    pub fn key(&self) -> &VariantTy {
+        assert!(self.is_dict_entry(), "{} is not a dictionary entry type", self);
+        unsafe { VariantTy::from_ptr(glib_sys::g_variant_type_key(self.as_ptr())) }
+    }
+
+    /// Returns the value type of a dictionary entry type.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the type is not a dictionary entry type.
+    pub fn value(&self) -> &VariantTy {
+        assert!(self.is_dict_entry(), "{} is not a dictionary entry type", self);
+        unsafe { VariantTy::from_ptr(glib_sys::g_variant_type_value(self.as_ptr())) }
+    }
+
+    /// Returns the types of the fields of a tuple type, or the key and value type of a
+    /// dictionary entry type.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the type is not a definite tuple or dictionary entry type.
+    pub fn items(&self) -> Vec<&VariantTy> {
+        assert!(self.is_definite() && (self.is_tuple() || self.is_dict_entry()),
+            "{} is not a definite tuple or dictionary entry type", self);
+
+        let mut items = Vec::new();
+        unsafe {
+            let mut item = glib_sys::g_variant_type_first(self.as_ptr());
+            while !item.is_null() {
+                items.push(VariantTy::from_ptr(item));
+                item = glib_sys::g_variant_type_next(item);
+            }
+        }
+        items
+    }
+}
+
+/// Common types that are not the `StaticVariantType` of a Rust type.
+impl VariantTy {
+    /// The indefinite type `*` that every type is a subtype of.
+    pub fn any() -> &'static VariantTy {
+        unsafe { VariantTy::from_str_unchecked("*") }
+    }
+
+    /// The indefinite type `?` of all basic types.
+    pub fn basic() -> &'static VariantTy {
+        unsafe { VariantTy::from_str_unchecked("?") }
+    }
+
+    /// The indefinite type `m*` of all maybe types.
+    pub fn maybe() -> &'static VariantTy {
+        unsafe { VariantTy::from_str_unchecked("m*") }
+    }
+
+    /// The indefinite type `a*` of all array types.
+    pub fn array() -> &'static VariantTy {
+        unsafe { VariantTy::from_str_unchecked("a*") }
+    }
+
+    /// The indefinite type `r` of all tuple types.
+    pub fn tuple() -> &'static VariantTy {
+        unsafe { VariantTy::from_str_unchecked("r") }
+    }
+
+    /// The indefinite type `{?*}` of all dictionary entry types.
+    pub fn dict_entry() -> &'static VariantTy {
+        unsafe { VariantTy::from_str_unchecked("{?*}") }
+    }
+
+    /// The indefinite type `a{?*}` of all dictionary types.
+    pub fn dictionary() -> &'static VariantTy {
+        unsafe { VariantTy::from_str_unchecked("a{?*}") }
+    }
+
+    /// The type `a{sv}` of option dictionaries.
+    pub fn vardict() -> &'static VariantTy {
+        unsafe { VariantTy::from_str_unchecked("a{sv}") }
+    }
+
+    /// The type `v` of boxed variants.
+    pub fn variant() -> &'static VariantTy {
+        unsafe { VariantTy::from_str_unchecked("v") }
+    }
+
+    /// The type `()` of the empty tuple.
+    pub fn unit() -> &'static VariantTy {
+        unsafe { VariantTy::from_str_unchecked("()") }
+    }
+
+    /// The type `o` of D-Bus object paths.
+    pub fn object_path() -> &'static VariantTy {
+        unsafe { VariantTy::from_str_unchecked("o") }
+    }
+
+    /// The type `g` of D-Bus type signatures.
+    pub fn signature() -> &'static VariantTy {
+        unsafe { VariantTy::from_str_unchecked("g") }
+    }
+
+    /// The type `h` of file descriptor handles.
+    pub fn handle() -> &'static VariantTy {
+        unsafe { VariantTy::from_str_unchecked("h") }
+    }
+
+    /// The type `ay` of byte strings.
+    pub fn byte_string() -> &'static VariantTy {
+        unsafe { VariantTy::from_str_unchecked("ay") }
+    }
 }
 
 unsafe impl Sync for VariantTy { }
@@ -415,6 +637,54 @@ mod tests {
         }
     }
 
+    #[test]
+    fn from_str() {
+        let ty: VariantType = "a{sv}".parse().unwrap();
+        assert_eq!(ty, "a{sv}");
+        assert!("a{vs}".parse::<VariantType>().is_err());
+    }
+
+    #[test]
+    fn introspection() {
+        let ty = VariantTy::new("a{s(ib)}").unwrap();
+        assert!(ty.is_definite());
+        assert!(ty.is_array());
+        assert!(ty.is_container());
+        assert!(!ty.is_basic());
+        assert!(ty.is_subtype_of(VariantTy::dictionary()));
+        assert!(ty.is_subtype_of(VariantTy::array()));
+        assert!(!ty.is_subtype_of(VariantTy::vardict()));
+
+        let entry = ty.element();
+        assert_eq!(entry.to_str(), "{s(ib)}");
+        assert!(entry.is_dict_entry());
+        assert_eq!(entry.key().to_str(), "s");
+        assert_eq!(entry.value().to_str(), "(ib)");
+        assert!(entry.key().is_basic());
+        let items = entry.value().items().iter().map(|t| t.to_str()).collect::<Vec<_>>();
+        assert_eq!(items, vec!["i", "b"]);
+        assert_eq!(VariantTy::unit().items().len(), 0);
+
+        assert!(!VariantTy::any().is_definite());
+        assert!(VariantTy::variant().is_variant());
+        assert!(VariantTy::new("ms").unwrap().element().is_basic());
+    }
+
+    #[test]
+    fn constructors() {
+        let s = VariantTy::new("s").unwrap();
+        let i = VariantTy::new("i").unwrap();
+
+        assert_eq!(VariantType::new_array(s), "as");
+        assert_eq!(VariantType::new_maybe(i), "mi");
+        assert_eq!(VariantType::new_tuple(&[s, i, VariantTy::variant()]), "(siv)");
+        assert_eq!(VariantType::new_tuple(&[]), "()");
+
+        let entry = VariantType::new_dict_entry(s, VariantTy::variant());
+        assert_eq!(entry, "{sv}");
+        assert_eq!(VariantType::new_array(&entry), *VariantTy::vardict());
+    }
+
     #[test]
     fn value() {
         let ty1 = VariantType::new("*").unwrap();
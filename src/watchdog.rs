@@ -0,0 +1,173 @@
+// Copyright 2019, The Gtk-rs Project Developers.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+// Licensed under the MIT license, see the LICENSE file or <http://opensource.org/licenses/MIT>
+
+use source::duration_to_micros;
+use std::fmt;
+use std::sync::Mutex;
+use std::time::Duration;
+use Continue;
+use MainContext;
+use Priority;
+use Source;
+use SourceImpl;
+
+struct WatchdogSource<F: FnMut() -> Continue + Send + 'static> {
+    callback: Mutex<F>,
+    timeout: i64,
+}
+
+impl<F: FnMut() -> Continue + Send + 'static> SourceImpl for WatchdogSource<F> {
+    fn dispatch(&self, source: &Source) -> Continue {
+        // Re-arm first so that the callback can still kick the watchdog
+        source.set_ready_time(ready_time(self.timeout));
+        (&mut *self.callback.lock().unwrap())()
+    }
+}
+
+// Ready time of a watchdog that is kicked now, clamped for timeouts that are effectively infinite
+fn ready_time(timeout: i64) -> i64 {
+    ::get_monotonic_time().saturating_add(timeout)
+}
+
+/// A source that calls a handler if it was not kicked for a given time.
+///
+/// The watchdog is armed on creation and every call to [`kick()`], which can happen from any
+/// thread, postpones the handler by the timeout again. If the handler returns `Continue(true)`
+/// the watchdog is re-armed, otherwise it is stopped.
+///
+/// A hang of a main loop can't be detected by a watchdog attached to the same main context. For
+/// supervising long-running operations of e.g. the default main context the watchdog should be
+/// attached to a context iterated by another thread, see [`with_context()`].
+///
+/// The watchdog is stopped once the `Watchdog` is dropped.
+///
+/// [`kick()`]: #method.kick
+/// [`with_context()`]: #method.with_context
+pub struct Watchdog {
+    source: Source,
+    timeout: i64,
+}
+
+impl Watchdog {
+    /// Creates a new watchdog on the global default main context that calls `on_trigger` if it
+    /// was not kicked for `timeout`.
+    pub fn new<F: FnMut() -> Continue + Send + 'static>(timeout: Duration, on_trigger: F) -> Watchdog {
+        Watchdog::with_context(None, ::PRIORITY_HIGH, timeout, on_trigger)
+    }
+
+    /// Creates a new watchdog on `context` that calls `on_trigger` with `priority` if it was not
+    /// kicked for `timeout`.
+    pub fn with_context<F: FnMut() -> Continue + Send + 'static>(
+        context: Option<&MainContext>,
+        priority: Priority,
+        timeout: Duration,
+        on_trigger: F,
+    ) -> Watchdog {
+        let timeout = duration_to_micros(timeout);
+
+        let source = Source::new_from_impl(WatchdogSource {
+            callback: Mutex::new(on_trigger),
+            timeout,
+        });
+        source.set_name("glib::Watchdog");
+        source.set_priority(priority);
+        source.set_ready_time(ready_time(timeout));
+        source.attach(context);
+
+        Watchdog { source, timeout }
+    }
+
+    /// Postpones the handler by the timeout, counting from now.
+    ///
+    /// This does nothing once the watchdog is stopped.
+    pub fn kick(&self) {
+        if !self.source.is_destroyed() {
+            self.source.set_ready_time(ready_time(self.timeout));
+        }
+    }
+
+    /// Returns `true` if the handler returned `Continue(false)`, i.e. the watchdog is stopped.
+    pub fn is_stopped(&self) -> bool {
+        self.source.is_destroyed()
+    }
+}
+
+impl Drop for Watchdog {
+    fn drop(&mut self) {
+        self.source.destroy();
+    }
+}
+
+impl fmt::Debug for Watchdog {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Watchdog")
+            .field("source", &self.source)
+            .field("timeout", &self.timeout)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::thread;
+    use MainLoop;
+
+    #[test]
+    fn test_watchdog() {
+        let c = MainContext::new();
+        let l = MainLoop::new(Some(&c), false);
+
+        c.acquire();
+
+        let triggered = Arc::new(AtomicUsize::new(0));
+        let triggered_clone = triggered.clone();
+        let l_clone = l.clone();
+        let watchdog = Arc::new(Watchdog::with_context(
+            Some(&c),
+            ::PRIORITY_DEFAULT,
+            Duration::from_millis(50),
+            move || {
+                triggered_clone.fetch_add(1, Ordering::SeqCst);
+                l_clone.quit();
+                Continue(false)
+            },
+        ));
+
+        // Keep kicking the watchdog from another thread for a while before letting it trigger
+        let watchdog_clone = watchdog.clone();
+        let kicker = thread::spawn(move || {
+            for _ in 0..10 {
+                watchdog_clone.kick();
+                thread::sleep(Duration::from_millis(10));
+            }
+        });
+
+        let start = ::get_monotonic_time();
+        l.run();
+        kicker.join().unwrap();
+
+        assert!(::get_monotonic_time() - start >= 100_000);
+        assert_eq!(triggered.load(Ordering::SeqCst), 1);
+        assert!(watchdog.is_stopped());
+    }
+
+    #[test]
+    fn test_watchdog_huge_timeout() {
+        let c = MainContext::new();
+        let watchdog = Watchdog::with_context(
+            Some(&c),
+            ::PRIORITY_DEFAULT,
+            Duration::from_secs(u64::max_value()),
+            || Continue(false),
+        );
+        watchdog.kick();
+
+        assert_eq!(watchdog.timeout, i64::max_value());
+        assert!(!c.iteration(false));
+        assert!(!watchdog.is_stopped());
+    }
+}
@@ -0,0 +1,129 @@
+// Copyright 2019, The Gtk-rs Project Developers.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+// Licensed under the MIT license, see the LICENSE file or <http://opensource.org/licenses/MIT>
+
+use get_thread_id;
+use glib_sys;
+use std::cell::RefCell;
+use std::mem;
+use std::ptr;
+use translate::{from_glib_full, mut_override, ToGlib};
+use Continue;
+use MainContext;
+use Priority;
+use Source;
+
+// Special file descriptor that makes GLib's poll() also wait for messages on the thread's
+// Win32 message queue, see G_WIN32_MSG_HANDLE in gmain.h
+const WIN32_MSG_HANDLE: i64 = 19_981_206;
+
+#[repr(C)]
+struct Win32MessageSource<F: FnMut() -> Continue + 'static> {
+    source: glib_sys::GSource,
+    thread_id: usize,
+    poll_fd: glib_sys::GPollFD,
+    source_funcs: Option<Box<glib_sys::GSourceFuncs>>,
+    callback: Option<RefCell<F>>,
+}
+
+unsafe extern "C" fn prepare(_source: *mut glib_sys::GSource, timeout: *mut i32) -> glib_sys::gboolean {
+    *timeout = -1;
+    glib_sys::GFALSE
+}
+
+unsafe extern "C" fn check<F: FnMut() -> Continue + 'static>(source: *mut glib_sys::GSource) -> glib_sys::gboolean {
+    let source = &*(source as *const Win32MessageSource<F>);
+    (source.poll_fd.revents & glib_sys::G_IO_IN as u16 != 0).to_glib()
+}
+
+unsafe extern "C" fn dispatch<F: FnMut() -> Continue + 'static>(
+    source: *mut glib_sys::GSource,
+    _callback: glib_sys::GSourceFunc,
+    _user_data: glib_sys::gpointer,
+) -> glib_sys::gboolean {
+    let source = &mut *(source as *mut Win32MessageSource<F>);
+    source.poll_fd.revents = 0;
+
+    // Check the thread to ensure we're only ever called from the same thread
+    assert_eq!(
+        get_thread_id(),
+        source.thread_id,
+        "Source dispatched on a different thread than before"
+    );
+
+    let callback = source
+        .callback
+        .as_ref()
+        .expect("Win32MessageSource without callback");
+    let res = ::profiler::profile(|| (&mut *callback.borrow_mut())());
+    res.to_glib()
+}
+
+unsafe extern "C" fn finalize<F: FnMut() -> Continue + 'static>(source: *mut glib_sys::GSource) {
+    let source = &mut *(source as *mut Win32MessageSource<F>);
+
+    // Drop all memory we own by taking it out of the Options
+    let _ = source.callback.take();
+    let _ = source.source_funcs.take();
+}
+
+impl MainContext {
+    /// Calls `func` whenever messages are available on the current thread's Win32 message queue.
+    ///
+    /// This allows applications with an existing Win32 message pump to run it from the `GLib`
+    /// main loop instead, without requiring a second thread: `func` would usually process all
+    /// pending messages with `PeekMessage()`, `TranslateMessage()` and `DispatchMessage()`.
+    ///
+    /// `func` will be called repeatedly until it returns `Continue(false)` or the returned source
+    /// is destroyed.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if called from a thread that is not the owner of this main context,
+    /// as only messages of the thread that iterates the main context can be waited for.
+    pub fn attach_win32_message_source<F: FnMut() -> Continue + 'static>(
+        &self,
+        priority: Priority,
+        func: F,
+    ) -> Source {
+        assert!(self.is_owner());
+
+        unsafe {
+            let source_funcs = Box::new(glib_sys::GSourceFuncs {
+                prepare: Some(prepare),
+                check: Some(check::<F>),
+                dispatch: Some(dispatch::<F>),
+                finalize: Some(finalize::<F>),
+                closure_callback: None,
+                closure_marshal: None,
+            });
+
+            let source = glib_sys::g_source_new(
+                mut_override(&*source_funcs),
+                mem::size_of::<Win32MessageSource<F>>() as u32,
+            ) as *mut Win32MessageSource<F>;
+            assert!(!source.is_null());
+
+            // Store all our data inside our part of the GSource
+            {
+                let source = &mut *source;
+                source.thread_id = get_thread_id();
+                source.poll_fd = glib_sys::GPollFD {
+                    fd: WIN32_MSG_HANDLE as _,
+                    events: glib_sys::G_IO_IN as u16,
+                    revents: 0,
+                };
+                ptr::write(&mut source.callback, Some(RefCell::new(func)));
+                ptr::write(&mut source.source_funcs, Some(source_funcs));
+
+                glib_sys::g_source_set_priority(&mut source.source, priority.to_glib());
+                glib_sys::g_source_set_name(&mut source.source, b"glib::Win32MessageSource\0".as_ptr() as *const _);
+                glib_sys::g_source_add_poll(&mut source.source, &mut source.poll_fd);
+            }
+
+            let source: Source = from_glib_full(mut_override(&(*source).source));
+            source.attach(Some(self));
+            source
+        }
+    }
+}
@@ -205,6 +205,32 @@
 /// }
 /// ```
 ///
+/// The interfaces given behind `@implements` work the same way for final classes.
+///
+/// #### Derivable classes
+///
+/// Only wrappers that opt in with `@derivable` implement [`IsDerivable`], which is required for
+/// using a type as the parent type of a subclass. Trying to subclass any other class with
+/// `ObjectSubclass` therefore fails to compile instead of registering a subclass of a class that
+/// is not meant to be subclassed, or with a wrong class struct layout. `@derivable` is only
+/// accepted together with a FFI class name and comes before `@extends` and `@implements`:
+///
+/// ```ignore
+/// glib_wrapper! {
+///     pub struct Widget(Object<ffi::GtkWidget, ffi::GtkWidgetClass, WidgetClass>)
+///         @derivable,
+///         @extends InitiallyUnowned,
+///         @implements Buildable;
+///
+///     match fn {
+///         get_type => || ffi::gtk_widget_get_type(),
+///     }
+/// }
+/// ```
+///
+/// This is a breaking change for wrappers that specify a FFI class name: they were derivable
+/// implicitly before and now have to add `@derivable` to allow subclassing them.
+///
 /// #### Interfaces
 ///
 /// Interfaces are passed in the same way to the macro but instead of specifying
@@ -233,6 +259,8 @@
 /// [#shared]: #shared
 /// [#object]: #object
 /// [#non-derivable-classes]: #non-derivable-classes
+/// [#derivable-classes]: #derivable-classes
+/// [`IsDerivable`]: trait.IsDerivable.html
 
 #[macro_export]
 macro_rules! glib_wrapper {
@@ -318,6 +346,19 @@ macro_rules! glib_wrapper {
         glib_object_wrapper!(@object [$($attr)*] $name, $ffi_name, $ffi_class_name, $rust_class_name, @get_type $get_type_expr, @extends [], @implements []);
     };
 
+    // Derivable object, class struct, no parents or interfaces
+    (
+        $(#[$attr:meta])*
+        pub struct $name:ident(Object<$ffi_name:path, $ffi_class_name:path, $rust_class_name:ident>) @derivable;
+
+        match fn {
+            get_type => || $get_type_expr:expr,
+        }
+    ) => {
+        glib_object_wrapper!(@object [$($attr)*] $name, $ffi_name, $ffi_class_name, $rust_class_name, @get_type $get_type_expr, @extends [], @implements []);
+        glib_object_wrapper!(@derivable $name);
+    };
+
     // Object, no class struct, parents, no interfaces
     (
         $(#[$attr:meta])*
@@ -344,6 +385,20 @@ macro_rules! glib_wrapper {
             @get_type $get_type_expr, @extends [$($extends),+], @implements []);
     };
 
+    // Derivable object, class struct, parents, no interfaces
+    (
+        $(#[$attr:meta])*
+        pub struct $name:ident(Object<$ffi_name:path, $ffi_class_name:path, $rust_class_name:ident>) @derivable, @extends $($extends:path),+;
+
+        match fn {
+            get_type => || $get_type_expr:expr,
+        }
+    ) => {
+        glib_object_wrapper!(@object [$($attr)*] $name, $ffi_name, $ffi_class_name, $rust_class_name,
+            @get_type $get_type_expr, @extends [$($extends),+], @implements []);
+        glib_object_wrapper!(@derivable $name);
+    };
+
     // Object, no class struct, no parents, interfaces
     (
         $(#[$attr:meta])*
@@ -370,6 +425,20 @@ macro_rules! glib_wrapper {
             @get_type $get_type_expr, @extends [], @implements [$($implements),+]);
     };
 
+    // Derivable object, class struct, no parents, interfaces
+    (
+        $(#[$attr:meta])*
+        pub struct $name:ident(Object<$ffi_name:path, $ffi_class_name:path, $rust_class_name:ident>) @derivable, @implements $($implements:path),+;
+
+        match fn {
+            get_type => || $get_type_expr:expr,
+        }
+    ) => {
+        glib_object_wrapper!(@object [$($attr)*] $name, $ffi_name, $ffi_class_name, $rust_class_name,
+            @get_type $get_type_expr, @extends [], @implements [$($implements),+]);
+        glib_object_wrapper!(@derivable $name);
+    };
+
     // Object, no class struct, parents and interfaces
     (
         $(#[$attr:meta])*
@@ -396,6 +465,20 @@ macro_rules! glib_wrapper {
             @get_type $get_type_expr, @extends [$($extends),+], @implements [$($implements),+]);
     };
 
+    // Derivable object, class struct, parents and interfaces
+    (
+        $(#[$attr:meta])*
+        pub struct $name:ident(Object<$ffi_name:path, $ffi_class_name:path, $rust_class_name:ident>) @derivable, @extends $($extends:path),+, @implements $($implements:path),+;
+
+        match fn {
+            get_type => || $get_type_expr:expr,
+        }
+    ) => {
+        glib_object_wrapper!(@object [$($attr)*] $name, $ffi_name, $ffi_class_name, $rust_class_name,
+            @get_type $get_type_expr, @extends [$($extends),+], @implements [$($implements),+]);
+        glib_object_wrapper!(@derivable $name);
+    };
+
     // Interface, no prerequisites
     (
         $(#[$attr:meta])*